@@ -15,8 +15,8 @@ fn format_errors(errors: Vec<Error>, source: Handle<JsString>) -> Vec<String> {
     .into_iter()
     .map(|err| {
         match err {
-            Error { start, end, .. } => {
-               ParseError::UnexpectedToken { start, end, source: source.value() }
+            Error { span, .. } => {
+               ParseError::UnexpectedToken { span, source: source.value() }
             }
         }
     })
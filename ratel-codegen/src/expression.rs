@@ -1,4 +1,4 @@
-use ratel::ast::{Expression, Literal, OperatorKind, OperatorCategory, Property, PropertyKey, Pattern};
+use ratel::ast::{Expression, ExpressionNode, Literal, OperatorKind, OperatorCategory, Property, PropertyKey, Pattern, MethodKind};
 use ratel::ast::expression::*;
 
 use {ToCode, Generator};
@@ -12,6 +12,7 @@ impl<'ast, G: Generator> ToCode<G> for Expression<'ast> {
         match *self {
             Void                         => {},
             This(_)                      => gen.write_bytes(b"this"),
+            Super(_)                     => gen.write_bytes(b"super"),
             Identifier(ref ident)        => gen.write(ident),
             Literal(ref value)           => gen.write(value),
             Sequence(ref sequence)       => gen.write(sequence),
@@ -20,8 +21,11 @@ impl<'ast, G: Generator> ToCode<G> for Expression<'ast> {
             ComputedMember(ref computed) => gen.write(computed),
             MetaProperty(ref property)   => gen.write(property),
             Call(ref call)               => gen.write(call),
+            Optional(ref optional)       => gen.write(optional),
             Binary(ref binary)           => gen.write(binary),
             Prefix(ref prefix)           => gen.write(prefix),
+            Yield(ref expression)        => gen.write(expression),
+            Await(ref expression)        => gen.write(expression),
             Postfix(ref postfix)         => gen.write(postfix),
             Conditional(ref conditional) => gen.write(conditional),
             Template(ref template)       => gen.write(template),
@@ -31,6 +35,11 @@ impl<'ast, G: Generator> ToCode<G> for Expression<'ast> {
             Object(ref object)           => gen.write(object),
             Function(ref function)       => gen.write(function),
             Class(ref class)             => gen.write(class),
+            JSXElement(ref element)      => gen.write(element),
+            JSXFragment(ref fragment)    => gen.write(fragment),
+            TypeCast(ref cast)           => gen.write(cast),
+            Instantiation(ref inst)      => gen.write(inst),
+            Parenthesized(ref paren)     => gen.write(paren),
         }
     }
 }
@@ -95,8 +104,20 @@ impl<'ast, G: Generator> ToCode<G> for Property<'ast> {
             },
             Method {
                 ref key,
+                kind,
                 ref value,
             } => {
+                if value.is_async {
+                    gen.write_bytes(b"async ");
+                }
+                if value.generator {
+                    gen.write_byte(b'*');
+                }
+                match kind {
+                    MethodKind::Get => gen.write_bytes(b"get "),
+                    MethodKind::Set => gen.write_bytes(b"set "),
+                    _               => {},
+                }
                 gen.write(key);
                 gen.write(value);
             },
@@ -126,10 +147,25 @@ impl<'ast, G: Generator> ToCode<G> for ArrayExpression<'ast> {
     }
 }
 
+/// Members can be chained without parentheses, which also keeps `a?.b.c`
+/// from turning into `(a?.b).c`, which would change its meaning.
+#[inline]
+fn write_member_object<'ast, G: Generator>(gen: &mut G, object: &ExpressionNode<'ast>) {
+    match object.item {
+        Expression::Member(_)         |
+        Expression::ComputedMember(_) |
+        Expression::MetaProperty(_)   |
+        Expression::Optional(_)       |
+        Expression::Instantiation(_)  => gen.write(object),
+        _ if object.is_optional_chain() => gen.write(object),
+        _                             => gen.write_expression(object, 19),
+    }
+}
+
 impl<'ast, G: Generator> ToCode<G> for MemberExpression<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
-        gen.write_expression(&self.object, 19);
+        write_member_object(gen, &self.object);
         gen.write_byte(b'.');
         gen.write(&self.property);
     }
@@ -138,7 +174,7 @@ impl<'ast, G: Generator> ToCode<G> for MemberExpression<'ast> {
 impl<'ast, G: Generator> ToCode<G> for ComputedMemberExpression<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
-        gen.write_expression(&self.object, 19);
+        write_member_object(gen, &self.object);
         gen.write_byte(b'[');
         gen.write(&self.property);
         gen.write_byte(b']');
@@ -157,13 +193,51 @@ impl<'ast, G: Generator> ToCode<G> for MetaPropertyExpression<'ast> {
 impl<'ast, G: Generator> ToCode<G> for CallExpression<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
-        gen.write(&self.callee);
+        match self.callee.item {
+            Expression::Call(_) => gen.write(&self.callee),
+            _                   => write_member_object(gen, &self.callee),
+        }
         gen.write_byte(b'(');
         gen.write_list(&self.arguments);
         gen.write_byte(b')');
     }
 }
 
+impl<'ast, G: Generator> ToCode<G> for OptionalExpression<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        write_member_object(gen, &self.object);
+        gen.write_bytes(b"?.");
+
+        match self.link {
+            OptionalLink::Member(ref property) => gen.write(property),
+            OptionalLink::ComputedMember(ref property) => {
+                gen.write_byte(b'[');
+                gen.write(property);
+                gen.write_byte(b']');
+            },
+            OptionalLink::Call(ref arguments) => {
+                gen.write_byte(b'(');
+                gen.write_list(arguments);
+                gen.write_byte(b')');
+            },
+        }
+    }
+}
+
+#[inline]
+fn mixes_nullish(operator: OperatorKind, operand: &Expression) -> bool {
+    match *operand {
+        Expression::Binary(BinaryExpression { operator: nested, .. }) => matches!((operator, nested),
+            (OperatorKind::Nullish, OperatorKind::LogicalOr)  |
+            (OperatorKind::Nullish, OperatorKind::LogicalAnd) |
+            (OperatorKind::LogicalOr, OperatorKind::Nullish)  |
+            (OperatorKind::LogicalAnd, OperatorKind::Nullish)
+        ),
+        _ => false,
+    }
+}
+
 impl<'ast, G: Generator> ToCode<G> for BinaryExpression<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
@@ -192,7 +266,37 @@ impl<'ast, G: Generator> ToCode<G> for BinaryExpression<'ast> {
             }
         };
 
-        gen.write_expression(&self.left, bp);
+        // `a ** b ** c` is `a ** (b ** c)`, so right associative operators
+        // need the parenthesis on the left side instead.
+        let (mut bp_left, mut bp_right) = match self.operator.is_right_associative() {
+            true  => (bp + 1, bp),
+            false => (bp, bp + 1),
+        };
+
+        // `-a ** b` is a syntax error, unary operations have to be
+        // parenthesized to be raised.
+        if self.operator == OperatorKind::Exponent {
+            match self.left.item {
+                Expression::Prefix(PrefixExpression { operator: OperatorKind::Increment, .. }) |
+                Expression::Prefix(PrefixExpression { operator: OperatorKind::Decrement, .. }) |
+                Expression::Prefix(PrefixExpression { operator: OperatorKind::New, .. })       => {},
+
+                Expression::Prefix(_) | Expression::Await(_) => bp_left += 1,
+                _ => {},
+            }
+        }
+
+        // `??` can't be mixed with `||` or `&&` without parenthesis,
+        // regardless of precedence.
+        if mixes_nullish(self.operator, &self.left) {
+            bp_left = self.left.binding_power() + 1;
+        }
+
+        if mixes_nullish(self.operator, &self.right) {
+            bp_right = self.right.binding_power() + 1;
+        }
+
+        gen.write_expression(&self.left, bp_left);
 
         if spacing_left {
             gen.write_byte(b' ');
@@ -211,7 +315,7 @@ impl<'ast, G: Generator> ToCode<G> for BinaryExpression<'ast> {
         // `2 / 2 * 2` and `2 / (2 * 2)` are different expressions,
         // hence the need for parenthesis in a right-balanced tree
         // even if binding power of operators is exactly the same.
-        gen.write_expression(&self.right, bp + 1);
+        gen.write_expression(&self.right, bp_right);
     }
 }
 
@@ -219,10 +323,45 @@ impl<'ast, G: Generator> ToCode<G> for PrefixExpression<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
         gen.write(&self.operator);
-        if self.operator.category() == OperatorCategory::Word {
+
+        let spacing = match self.operand.item {
+            _ if self.operator.category() == OperatorCategory::Word => true,
+
+            // `- -a` and `+ ++a` must not be glued into `--a` and `+++a`
+            Expression::Prefix(PrefixExpression { operator, .. }) => {
+                operator.as_str().as_bytes()[0] == self.operator.as_str().as_bytes()[0]
+            },
+            _ => false
+        };
+
+        if spacing {
             gen.write_byte(b' ');
         }
-        gen.write(&self.operand);
+        gen.write_expression(&self.operand, 15);
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for YieldExpression<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_bytes(b"yield");
+
+        if self.delegate {
+            gen.write_byte(b'*');
+        }
+
+        if let Some(ref argument) = self.argument {
+            gen.write_byte(b' ');
+            gen.write_expression(argument, 2);
+        }
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for AwaitExpression<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_bytes(b"await ");
+        gen.write_expression(&self.argument, 15);
     }
 }
 
@@ -237,15 +376,15 @@ impl<'ast, G: Generator> ToCode<G> for PostfixExpression<'ast> {
 impl<'ast, G: Generator> ToCode<G> for ConditionalExpression<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
-        gen.write(&self.test);
+        gen.write_expression(&self.test, 5);
         gen.write_pretty(b' ');
         gen.write_byte(b'?');
         gen.write_pretty(b' ');
-        gen.write(&self.consequent);
+        gen.write_expression(&self.consequent, 1);
         gen.write_pretty(b' ');
         gen.write_byte(b':');
         gen.write_pretty(b' ');
-        gen.write(&self.alternate);
+        gen.write_expression(&self.alternate, 1);
     }
 }
 
@@ -298,7 +437,15 @@ impl<'ast, G: Generator> ToCode<G> for ArrowBody<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
         match *self {
-            ArrowBody::Expression(ref expression) => gen.write(expression),
+            ArrowBody::Expression(ref expression) => match expression.item {
+                // `{` would start a block
+                Expression::Object(_) => {
+                    gen.write_byte(b'(');
+                    gen.write(expression);
+                    gen.write_byte(b')');
+                },
+                _ => gen.write_expression(expression, 1),
+            },
             ArrowBody::Block(ref block)           => gen.write(block),
         }
     }
@@ -351,6 +498,118 @@ impl<'ast, G: Generator> ToCode<G> for ObjectExpression<'ast> {
     }
 }
 
+impl<'ast, G: Generator> ToCode<G> for JSXAttribute<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        match *self {
+            JSXAttribute::Attribute { ref name, value } => {
+                gen.write(name);
+
+                if let Some(ref value) = value {
+                    gen.write_byte(b'=');
+
+                    match value.item {
+                        Expression::Literal(_)     |
+                        Expression::JSXElement(_)  |
+                        Expression::JSXFragment(_) => gen.write(value),
+                        _ => {
+                            gen.write_byte(b'{');
+                            gen.write(value);
+                            gen.write_byte(b'}');
+                        }
+                    }
+                }
+            },
+            JSXAttribute::Spread { ref argument } => {
+                gen.write_bytes(b"{...");
+                gen.write(argument);
+                gen.write_byte(b'}');
+            },
+        }
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for JSXChild<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        match *self {
+            JSXChild::Text(text) => gen.write_bytes(text.as_bytes()),
+            JSXChild::Expression(ref expression) => {
+                gen.write_byte(b'{');
+                gen.write(expression);
+                gen.write_byte(b'}');
+            },
+            JSXChild::Element(ref element) => gen.write(element),
+        }
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for JSXElement<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_byte(b'<');
+        gen.write(&self.name);
+
+        for attribute in &self.attributes {
+            gen.write_byte(b' ');
+            gen.write(attribute);
+        }
+
+        if self.self_closing {
+            gen.write_pretty(b' ');
+            gen.write_bytes(b"/>");
+            return;
+        }
+
+        gen.write_byte(b'>');
+
+        for child in &self.children {
+            gen.write(child);
+        }
+
+        gen.write_bytes(b"</");
+        gen.write(&self.name);
+        gen.write_byte(b'>');
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for JSXFragment<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_bytes(b"<>");
+
+        for child in &self.children {
+            gen.write(child);
+        }
+
+        gen.write_bytes(b"</>");
+    }
+}
+
+/// Types are stripped from the output, leaving only the expression.
+impl<'ast, G: Generator> ToCode<G> for TypeCastExpression<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_expression(&self.expression, 11);
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for InstantiationExpression<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        write_member_object(gen, &self.expression);
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for ParenthesizedExpression<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_byte(b'(');
+        gen.write(&self.expression);
+        gen.write_byte(b')');
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {assert_min, assert_pretty};
@@ -363,6 +622,10 @@ mod test {
         assert_min("false", "false;");
         assert_min("42", "42;");
         assert_min("3.14", "3.14;");
+        assert_min("9007199254740993", "9007199254740993;");
+        assert_min("0.1", "0.1;");
+        assert_min("123456789012345678901234567890", "123456789012345678901234567890;");
+        assert_min("1.000000000000000000001", "1.000000000000000000001;");
         assert_min(r#" "foobar" "#, r#""foobar";"#);
         assert_min(r#" 'foobar' "#, r#"'foobar';"#);
     }
@@ -420,6 +683,14 @@ mod test {
         assert_min("2 + 2 * 2", "2+2*2;");
         assert_min("2 + (2 * 2)", "2+2*2;");
         assert_min("(2 + 2) * 2", "(2+2)*2;");
+        assert_min("(-a) ** b", "(-a)**b;");
+        assert_min("(typeof a) ** b", "(typeof a)**b;");
+        assert_min("++a ** b", "++a**b;");
+        assert_min("-(a ** b)", "-(a**b);");
+        assert_min("(a || b) ?? c", "(a||b)??c;");
+        assert_min("a ?? (b && c)", "a??(b&&c);");
+        assert_min("(a ?? b) && c", "(a??b)&&c;");
+        assert_min("a || (b ?? c)", "a||(b??c);");
     }
 
     #[test]
@@ -433,6 +704,10 @@ mod test {
         assert_min("new foo", "new foo;");
         assert_min("void foo", "void foo;");
         assert_min("typeof foo", "typeof foo;");
+        assert_min("- -foo", "- -foo;");
+        assert_min("-(-foo)", "- -foo;");
+        assert_min("+ ++foo", "+ ++foo;");
+        assert_min("-(a + b)", "-(a+b);");
     }
 
     #[test]
@@ -443,7 +718,8 @@ mod test {
 
     #[test]
     fn conditional_expression() {
-        assert_min("true ? foo : bar", "true?foo:bar;")
+        assert_min("true ? foo : bar", "true?foo:bar;");
+        assert_min("(a, b) ? (c, d) : (e, f)", "(a,b)?(c,d):(e,f);");
     }
 
     #[test]
@@ -465,6 +741,8 @@ mod test {
     fn call_expression() {
         assert_min("foobar();", "foobar();");
         assert_min("foobar(1, 2, 3);", "foobar(1,2,3);");
+        assert_min("(foo || bar)();", "(foo||bar)();");
+        assert_min("foo()();", "foo()();");
     }
 
     #[test]
@@ -474,6 +752,24 @@ mod test {
         assert_min("10..fooz", "10..fooz;");
         assert_min("foo[10]", "foo[10];");
         assert_min(r#"foo["bar"]"#, r#"foo["bar"];"#);
+        assert_min("foo.bar.baz", "foo.bar.baz;");
+        assert_min("(x => x).baz", "(x=>x).baz;");
+        assert_min("function f() { new.target.baz }", "function f(){new.target.baz;}");
+    }
+
+    #[test]
+    fn yield_and_await_expressions() {
+        assert_min("function* f() { yield a + b; yield* c; yield }", "function* f(){yield a+b;yield* c;yield;}");
+        assert_min("function* f() { x = yield; (yield a) + b }", "function* f(){x=(yield);(yield a)+b;}");
+        assert_min("async function f() { await (a + b); await a.b }", "async function f(){await (a+b);await a.b;}");
+    }
+
+    #[test]
+    fn optional_expression() {
+        assert_min("foo?.bar", "foo?.bar;");
+        assert_min("foo?.[bar]", "foo?.[bar];");
+        assert_min("foo?.(1, 2)", "foo?.(1,2);");
+        assert_min("foo?.bar.baz()", "foo?.bar.baz();");
     }
 
     #[test]
@@ -540,4 +836,47 @@ mod test {
     fn regression_increments() {
         assert_min("x++ + ++y", "x++ + ++y;");
     }
+
+    #[test]
+    fn jsx() {
+        use ratel::{parse_with, ParseOptions};
+        use codegen;
+
+        let source = r#"<div  a="b" c={ d } {...e} f>text {g}<br /><></></div>"#;
+        let module = parse_with(source, ParseOptions { jsx: true, ..ParseOptions::default() }).unwrap();
+
+        assert_eq!(codegen(&module, true), r#"<div a="b" c={d} {...e} f>text {g}<br/><></></div>;"#);
+        assert_eq!(codegen(&module, false), r#"<div a="b" c={d} {...e} f>text {g}<br /><></></div>;"#);
+    }
+
+    #[test]
+    fn typescript_types_are_stripped() {
+        use ratel::{parse_with, ParseOptions};
+        use codegen;
+
+        let source = "function f<T>(a: T, b?: number, ...c: T[]): T { return a as T; }\n\
+                      const g = (x: string): number => x.length;\n\
+                      type A = { a?: number };\n\
+                      let h: [A, string] = f<number>(1) satisfies [A, string];";
+        let module = parse_with(source, ParseOptions { typescript: true, ..ParseOptions::default() }).unwrap();
+
+        assert_eq!(
+            codegen(&module, true),
+            "function f(a,b,...c){return a;}const g=(x)=>x.length;let h=f(1);"
+        );
+    }
+
+    #[test]
+    fn preserved_parens() {
+        use ratel::{parse_with, ParseOptions};
+        use codegen;
+
+        let source = "((a)); (a + b) * c; (a * b) + c; x = (y); (f)(1); ((a, b) => a);";
+        let module = parse_with(source, ParseOptions { preserve_parens: true, ..ParseOptions::default() }).unwrap();
+
+        assert_eq!(
+            codegen(&module, true),
+            "((a));(a+b)*c;(a*b)+c;x=(y);(f)(1);((a,b)=>a);"
+        );
+    }
 }
@@ -39,6 +39,16 @@ pub trait ClassFunctionDeclaration<G: Generator> {
     fn write_function(gen: &mut G) {
         gen.write_bytes(b"function");
     }
+
+    #[inline]
+    fn write_async(gen: &mut G) {
+        gen.write_bytes(b"async ");
+    }
+
+    #[inline]
+    fn write_generator(gen: &mut G) {
+        gen.write_byte(b'*');
+    }
 }
 
 impl<G: Generator> ClassFunctionDeclaration<G> for EmptyName {
@@ -47,6 +57,14 @@ impl<G: Generator> ClassFunctionDeclaration<G> for EmptyName {
 
     #[inline]
     fn write_function(_: &mut G) {}
+
+    /// Methods put `async` ahead of their key
+    #[inline]
+    fn write_async(_: &mut G) {}
+
+    /// Methods put `*` ahead of their key
+    #[inline]
+    fn write_generator(_: &mut G) {}
 }
 
 impl<'ast, G: Generator> ClassFunctionDeclaration<G> for OptionalName<'ast> {}
@@ -58,7 +76,13 @@ impl<'ast, G, N> ToCode<G> for Function<'ast, N> where
 {
     #[inline]
     fn to_code(&self, gen: &mut G) {
+        if self.is_async {
+            N::write_async(gen);
+        }
         N::write_function(gen);
+        if self.generator {
+            N::write_generator(gen);
+        }
         gen.write(&self.name);
         gen.write_byte(b'(');
         gen.write_list(&self.params);
@@ -84,6 +108,12 @@ impl<'ast, G: Generator> ToCode<G> for ClassMember<'ast> {
                 if is_static {
                     gen.write_bytes(b"static ");
                 }
+                if value.is_async {
+                    gen.write_bytes(b"async ");
+                }
+                if value.generator {
+                    gen.write_byte(b'*');
+                }
                 match kind {
                     MethodKind::Get => gen.write_bytes(b"get "),
                     MethodKind::Set => gen.write_bytes(b"set "),
@@ -121,7 +151,7 @@ impl<'ast, G, N> ToCode<G> for Class<'ast, N> where
         gen.write(&self.name);
         if let Some(ref super_class) = self.extends {
             gen.write_bytes(b" extends ");
-            gen.write(super_class);
+            gen.write_expression(super_class, 17);
         }
         gen.write_pretty(b' ');
         gen.write(&self.body);
@@ -155,5 +185,6 @@ mod test {
         assert_min("class Foo { static method(a, b) { debug; } }", "class Foo{static method(a,b){debug;}}");
         assert_min("class Foo { a = 10; b = 20; }", "class Foo{a=10;b=20;}");
         assert_min("class Foo { static a = 10; b = 20; }", "class Foo{static a=10;b=20;}");
+        assert_min("class Foo { 'foo bar'(a) { debug; } }", "class Foo{'foo bar'(a){debug;}}");
     }
 }
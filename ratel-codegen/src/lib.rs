@@ -10,6 +10,9 @@ mod expression;
 mod statement;
 mod function;
 
+#[cfg(test)]
+mod roundtrip;
+
 pub trait Generator: Sized {
     type Output;
 
@@ -298,6 +301,13 @@ impl<'ast, G: Generator> ToCode<G> for Pattern<'ast> {
             } => {
                 gen.write_byte(b'[');
                 gen.write_list(elements);
+
+                // A hole at the end needs a comma of its own, `[a,]` would
+                // parse back as a single element
+                if let Some(&Void) = elements.iter().last().map(|element| &element.item) {
+                    gen.write_byte(b',');
+                }
+
                 gen.write_byte(b']');
             },
             RestElement {
@@ -315,7 +325,11 @@ impl<'ast, G: Generator> ToCode<G> for Pattern<'ast> {
                 gen.write_byte(b'=');
                 gen.write_pretty(b' ');
                 gen.write(right);
-            }
+            },
+            Annotated {
+                ref pattern,
+                ..
+            } => gen.write(pattern),
         }
     }
 }
@@ -359,3 +373,23 @@ fn assert_pretty(source: &str, expected: &str) {
 
     assert_eq!(codegen(&module, false).as_str(), expected);
 }
+
+/// Parse the source, generate code from it both minified and pretty, and
+/// check that parsing the output again produces the same AST.
+#[cfg(test)]
+fn assert_roundtrip(source: &str) {
+    use ratel::parse;
+
+    let module = parse(source).unwrap();
+
+    for &minify in &[true, false] {
+        let output = codegen(&module, minify);
+        let reparsed = match parse(&output) {
+            Ok(reparsed) => reparsed,
+            Err(errors) => panic!("Generated code for {:?} doesn't parse: {:?}\n{}", source, errors, output),
+        };
+
+        assert_eq!(module.body(), reparsed.body(), "AST changed for {:?}, generated:\n{}", source, output);
+        assert_eq!(codegen(&reparsed, minify), output, "Output is not stable for {:?}", source);
+    }
+}
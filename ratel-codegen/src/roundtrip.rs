@@ -0,0 +1,139 @@
+//! Round-trip tests: every snippet is parsed, generated and parsed again,
+//! the AST must come out the same. New snippets can be added to any of the
+//! lists below, or as a new list for a new feature.
+
+use assert_roundtrip;
+
+fn assert_all(corpus: &[&str]) {
+    for source in corpus {
+        assert_roundtrip(source);
+    }
+}
+
+#[test]
+fn roundtrip_literals() {
+    assert_all(&[
+        "null; undefined; true; false;",
+        "0; 42; 3.14; .5; 1e10; 0xff; 0b101;",
+        r#""double"; 'single'; "escaped \" quote";"#,
+        r#"/^[A-Z]+\/[\d]+/g;"#,
+        "[]; [1, 2, 3]; [, , 1, , ];",
+        "({}); ({ a: 1, 'b': 2, 3: 4, [c]: 5, d, e() {} });",
+        "({ get a() { return 1; }, set a(v) {}, get: 1, set() {} });",
+    ]);
+}
+
+#[test]
+fn roundtrip_operators() {
+    assert_all(&[
+        "a + b * c; (a + b) * c; a - (b - c); a - b - c;",
+        "a = b = c; a += 1; a -= 1; a **= 2; a >>>= 1;",
+        "a ** b ** c; (a ** b) ** c; (-a) ** b; (void a) ** b; a ** -b; ++a ** b;",
+        "a && b || c; a && (b || c); -(a + b); typeof (a, b);",
+        "a ?? b ?? c; (a || b) ?? c; a ?? (b && c); (a ?? b) || c; a && (b ?? c);",
+        "!a; -a; +a; ~a; typeof a.b; void 0; delete a.b; - -a; + +a; -(-a);",
+        "a++; a--; ++a; --a; a++ + ++b; a-- - --b;",
+        "a ? b : c; a ? b ? c : d : e; a ? b : c ? d : e; (a, b) ? c : d;",
+        "x = a ? b = 1 : c = 2; (a ? b : c) ? d : e;",
+        "a, b, c; (a, b);",
+        "a in b; a instanceof b;",
+        "new Foo; new Foo(); new Foo.bar(); new (foo())(); new (foo().bar)();",
+    ]);
+}
+
+#[test]
+fn roundtrip_members_and_calls() {
+    assert_all(&[
+        "a.b.c; a[b][c]; a.b[c].d;",
+        "foo(); foo(a, b); foo.bar(a)(b); (foo || bar)();",
+        "a?.b; a?.[b]; a?.(b); a?.b.c(d)?.[e];",
+        "(a?.b).c; function f() { new.target; }",
+        "(() => {}).call(this); (() => {})(); (a, b)();",
+    ]);
+}
+
+#[test]
+fn roundtrip_destructuring() {
+    assert_all(&[
+        "var [a, b] = c; let { d, e } = f; const { g: h } = i;",
+        "var [e = 1, [f]] = g;",
+        "function foo([a, b], { c, d }, e = 1, ...f) {}",
+        "[a, b] = [b, a];",
+        "var [a, , b] = c; let [d, ,] = e; const [f,] = g; let { h, i, } = j;",
+        "([a, , b,]) => a; ([, c, ,]) => c;",
+    ]);
+}
+
+#[test]
+fn roundtrip_templates() {
+    assert_all(&[
+        "``; `foo`; `foo${bar}baz`; `${a}${b}`;",
+        "foo`bar`; foo.bar`baz${qux}`; foo()`bar`;",
+        "`outer${`inner${deep}`}`;",
+    ]);
+}
+
+#[test]
+fn roundtrip_functions_and_arrows() {
+    assert_all(&[
+        "function foo() {} function bar(a, b) { return a + b; }",
+        "(function () {}); (function named() {});",
+        "async function foo() {}",
+        "() => {}; a => a; (a, b) => a + b; () => ({}); () => ({ a }); () => (a, b); (a = 1) => a;",
+        "a => b => c; foo(a => a, () => {});",
+        "function* g() { yield; yield a; yield* b; x = yield a + b; foo(yield, yield a); } (function* () {});",
+        "async function f() { await a; await a.b(); (await a) ** b; -(await a); await (a, b); }",
+        "async function* f() { await (yield x); yield await x; }",
+    ]);
+}
+
+#[test]
+fn roundtrip_classes() {
+    assert_all(&[
+        "class Foo {} class Bar extends Foo {} (class {}); (class Baz extends (a, b) {});",
+        "class Foo { constructor(a) { this.a = a; } bar() {} static baz() {} }",
+        "class Foo { get a() {} set a(v) {} static get b() {} }",
+        "class Foo { a = 1; static b = 2; 'c'() {} 1() {} [d]() {} }",
+        "class Foo extends Bar { constructor() { this.baz(); } } class Baz extends foo() {}",
+        "class Foo { async a() {} static async b() {} static() {} async() {} get() {} set() {} }",
+        "class Foo extends Bar { constructor() { super(a); super.b(); super[c]; } static d() { return super.d; } }",
+        "({ async a() {}, async: 1, get: 1, set: 2, static: 3 });",
+    ]);
+}
+
+#[test]
+fn roundtrip_statements() {
+    assert_all(&[
+        "var a; let b = 1; const c = 2, d = 3;",
+        "({}).x; ({}.x = 1); (function () {})(); (class {}).name; ({}) ? a : b; ({}), a; {}",
+        "(() => {})(); ({ a } = b); ({}).x++; ({})`x`;",
+        "using a = b(); async function f() { await using c = d(), e = f; }",
+        "if (a) b; if (a) { b; } else { c; } if (a) b; else if (c) d; else e;",
+        "for (;;) {} for (var i = 0; i < 10; i++) {} for (a in b) {} for (let a of b) {}",
+        "for ((a in b);;) {} for (var a = (b in c);;) {} for (a = (b in c), d => (d in e);;) {} for ((a).b in c) {}",
+        "while (a) { b; } do { a; } while (b);",
+        "label: for (;;) { break label; continue label; }",
+        "switch (a) { case 1: b; break; default: c; }",
+        "try { a; } catch (e) { b; } finally { c; }",
+        "throw new Error('foo');",
+        "function foo() { return; } function bar() { return a, b; }",
+        "debugger",
+        "export var a = 1; export function foo() {} export class Bar {}",
+        "export { a, b as c, d as default }; export {}; export { default, default as e } from 'm';",
+        "export { a as 'a b' }; export { 'c d' as e } from 'm'; import { 'f g' as h } from 'n';",
+    ]);
+}
+
+#[test]
+fn roundtrip_automatic_semicolons() {
+    use ratel::parse;
+    use codegen;
+
+    let source = "let a = 1\nfoo()\nfunction f() { return\nb }\nthrow c\n";
+
+    assert_all(&[source, "a\n++b", "if (a) b\nelse c"]);
+
+    let output = codegen(&parse(source).unwrap(), true);
+
+    assert_eq!(output, "let a=1;foo();function f(){return;b;}throw c;");
+}
@@ -1,5 +1,7 @@
-use ratel::ast::{Statement, Declarator, DeclarationKind};
+use ratel::ast::{Statement, Expression, Declarator, DeclarationKind, OperatorKind};
 use ratel::ast::statement::*;
+use ratel::ast::expression::{BinaryExpression, SequenceExpression, ConditionalExpression};
+use ratel::ast::expression::{ArrowExpression, ArrowBody, YieldExpression};
 
 use {ToCode, Generator};
 use ratel::ast::Node;
@@ -45,7 +47,12 @@ impl<'ast, G: Generator> ToCode<G> for Statement<'ast> {
             Class(ref class)             => gen.write(class),
             Continue(ref cont)           => gen.write(cont),
             Switch(ref switch)           => gen.write(switch),
-            Import(ref import)           => gen.write(import)
+            Import(ref import)           => gen.write(import),
+            Export(ref export)           => gen.write(export),
+            ExportNamed(ref export)      => gen.write(export),
+            ExportDefault(ref export)    => gen.write(export),
+            TypeAlias(_)                 |
+            Interface(_)                 => {},
         }
     }
 }
@@ -59,6 +66,8 @@ impl<G: Generator> ToCode<G> for DeclarationKind {
             Var   => gen.write_bytes(b"var "),
             Let   => gen.write_bytes(b"let "),
             Const => gen.write_bytes(b"const "),
+            Using => gen.write_bytes(b"using "),
+            AwaitUsing => gen.write_bytes(b"await using "),
         }
     }
 }
@@ -81,7 +90,7 @@ impl<'ast, G: Generator> ToCode<G> for DeclarationStatement<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
         gen.write(&self.kind);
-        gen.write_list(&self.declarators);
+        gen.write_list(self.declarators);
     }
 }
 
@@ -191,13 +200,70 @@ impl<'ast, G: Generator> ToCode<G> for ForInit<'ast> {
     }
 }
 
+/// Check whether an `in` operator could be written outside of parenthesis,
+/// where the init of a `for` statement would be read as a `for-in` instead.
+fn exposes_in(expression: &Expression) -> bool {
+    match *expression {
+        Expression::Binary(BinaryExpression { operator: OperatorKind::In, .. }) => true,
+        Expression::Binary(BinaryExpression { operator, left, right }) => {
+            operator.binding_power() <= OperatorKind::In.binding_power() &&
+            (exposes_in(&left) || exposes_in(&right))
+        },
+        Expression::Sequence(SequenceExpression { body }) => {
+            body.iter().any(|expression| exposes_in(expression))
+        },
+        Expression::Conditional(ConditionalExpression { test, alternate, .. }) => {
+            exposes_in(&test) || exposes_in(&alternate)
+        },
+        Expression::Arrow(ArrowExpression { body: ArrowBody::Expression(body), .. }) => exposes_in(&body),
+        Expression::Yield(YieldExpression { argument: Some(argument), .. }) => exposes_in(&argument),
+        _ => false,
+    }
+}
+
+/// Write an expression in the init of a `for` statement, parenthesized if
+/// it would otherwise turn it into a `for-in`.
+fn write_for_init_expression<'ast, G: Generator>(expression: &Node<'ast, Expression<'ast>>, gen: &mut G) {
+    if exposes_in(expression) {
+        gen.write_byte(b'(');
+        gen.write(expression);
+        gen.write_byte(b')');
+    } else {
+        gen.write(expression);
+    }
+}
+
 impl<'ast, G: Generator> ToCode<G> for ForStatement<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
         gen.write_bytes(b"for");
         gen.write_pretty(b' ');
         gen.write_byte(b'(');
-        gen.write(&self.init);
+
+        match self.init.map(|init| init.item) {
+            Some(ForInit::Expression(ref expression)) => write_for_init_expression(expression, gen),
+            Some(ForInit::Declaration(ref declaration)) => {
+                gen.write(&declaration.kind);
+
+                for (index, declarator) in declaration.declarators.iter().enumerate() {
+                    if index != 0 {
+                        gen.write_byte(b',');
+                        gen.write_pretty(b' ');
+                    }
+
+                    gen.write(&declarator.id);
+
+                    if let Some(ref init) = declarator.init {
+                        gen.write_pretty(b' ');
+                        gen.write_byte(b'=');
+                        gen.write_pretty(b' ');
+                        write_for_init_expression(init, gen);
+                    }
+                }
+            },
+            None => {},
+        }
+
         gen.write_byte(b';');
         gen.write_pretty(b' ');
         gen.write(&self.test);
@@ -257,6 +323,8 @@ impl<'ast, G: Generator> ToCode<G> for TryStatement<'ast> {
             gen.write(&handler.body);
         }
         if let Some(ref finalizer) = self.finalizer {
+            gen.write_pretty(b' ');
+            gen.write_bytes(b"finally");
             gen.write_pretty(b' ');
             gen.write(finalizer);
         }
@@ -310,7 +378,7 @@ impl<'ast, G: Generator> ToCode<G> for SwitchCase<'ast> {
             }
             None => gen.write_bytes(b"default:")
         }
-        gen.write_block(&self.consequent);
+        gen.write_block(self.consequent);
     }
 }
 
@@ -331,7 +399,7 @@ impl<'ast, G: Generator> ToCode<G> for ImportDeclaration<'ast> {
                 ForImportSpecifier::ImportNamespaceSpecifier(_) => false,
                 ForImportSpecifier::ImportSpecifier(_) => true
             }
-        };
+        }
 
         let default_ns_specs =
             self.specifiers.iter().filter(|spec| !is_import_specifier(spec)).collect::<Vec<_>>();
@@ -353,6 +421,70 @@ impl<'ast, G: Generator> ToCode<G> for ImportDeclaration<'ast> {
         gen.write_byte(b'\'');
     }
 }
+
+impl<'ast, G: Generator> ToCode<G> for ExportDeclaration<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_bytes(b"export ");
+        gen.write(&self.declaration);
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for ExportNamedDeclaration<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_bytes(b"export {");
+        gen.write_list(self.specifiers);
+        gen.write_byte(b'}');
+
+        if let Some(source) = self.source {
+            gen.write_bytes(b" from '");
+            gen.write_bytes(source.as_bytes());
+            gen.write_byte(b'\'');
+        }
+
+        gen.write_byte(b';');
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for ExportSpecifier<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write(&self.local);
+
+        if self.exported.item != self.local.item {
+            gen.write_bytes(b" as ");
+            gen.write(&self.exported);
+        }
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for ExportDefaultDeclaration<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_bytes(b"export default ");
+
+        match *self {
+            ExportDefaultDeclaration::Function(ref function) => gen.write(function),
+            ExportDefaultDeclaration::Class(ref class)       => gen.write(class),
+            ExportDefaultDeclaration::Expression(ref value)  => {
+                // Function and class expressions would be read back as declarations
+                match value.item {
+                    Expression::Function(_) |
+                    Expression::Class(_)    => {
+                        gen.write_byte(b'(');
+                        gen.write(value);
+                        gen.write_byte(b')');
+                    },
+                    _ => gen.write_expression(value, 1),
+                }
+
+                gen.write_byte(b';');
+            },
+        }
+    }
+}
+
 impl<'ast, G: Generator> ToCode<G> for ForImportSpecifier<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
@@ -448,12 +580,22 @@ mod test {
         assert_min("for (var i = 0; i < 10; i++) {}", "for(var i=0;i<10;i++){}");
         assert_min("for (i = 0; i < 10; i++) {}", "for(i=0;i<10;i++){}");
         assert_min("for (;;) {}", "for(;;){}");
+        assert_min("for ((a in b);;) {}", "for((a in b);;){}");
+        assert_min("for (var a = (b in c), d = e;;) {}", "for(var a=(b in c),d=e;;){}");
+        assert_min("for (a = (b in c), d;;) {}", "for((a=b in c,d);;){}");
+        assert_min("for (f(a in b), (a in b) + c;;) {}", "for(f(a in b),(a in b)+c;;){}");
         assert_min("for (foo in bar){}", "for(foo in bar){}");
         assert_min("for (let foo in bar){}", "for(let foo in bar){}");
         assert_min("for (foo of bar){}", "for(foo of bar){}");
         assert_min("for (let foo of bar){}", "for(let foo of bar){}");
     }
 
+    #[test]
+    fn try_statement() {
+        assert_min("try { foo(); } catch (err) { bar(); }", "try{foo();}catch(err){bar();}");
+        assert_min("try { foo(); } finally { bar(); }", "try{foo();}finally{bar();}");
+    }
+
     #[test]
     fn import_statement() {
         assert_min("import 'fuga'", "import 'fuga'");
@@ -461,4 +603,18 @@ mod test {
         assert_min("import foo,{hoge as HOGE} from 'fuga'", "import foo,{hoge as HOGE} from 'fuga'");
         assert_min("import {hoge as HOGE,fuga} from 'fuga'", "import {hoge as HOGE,fuga} from 'fuga'");
     }
+
+    #[test]
+    fn export_statement() {
+        assert_min("export const foo = 1;", "export const foo=1;");
+        assert_min("export class Foo {}", "export class Foo{}");
+        assert_min("export async function foo() {}", "export async function foo(){}");
+        assert_min("export default (a, b);", "export default (a,b);");
+        assert_min("export default a = 1", "export default a=1;");
+        assert_min("export default function () {}", "export default function(){}");
+        assert_min("export default async function foo() {}", "export default async function foo(){}");
+        assert_min("export default class extends Foo {}", "export default class extends Foo{}");
+        assert_min("export default (function () {});", "export default (function(){});");
+        assert_min("export default (class {});", "export default (class{});");
+    }
 }
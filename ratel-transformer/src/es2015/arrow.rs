@@ -1,25 +1,71 @@
-use ratel::ast::{NodeList, ExpressionNode, Function, Name, OptionalName, Block};
-use ratel::ast::expression::{ArrowExpression, ArrowBody};
-use ratel::ast::statement::ReturnStatement;
-use ratel_visitor::Visitor;
+use toolshed::list::{ListBuilder, GrowableList};
+use ratel::ast::{NodeList, Expression, ExpressionNode, Statement, StatementNode, BlockNode};
+use ratel::ast::{Function, Name, OptionalName, Block, Pattern, Declarator, DeclarationKind};
+use ratel::ast::expression::{ArrowExpression, ArrowBody, ThisExpression, MemberExpression, CallExpression};
+use ratel::ast::expression::MetaPropertyExpression;
+use ratel::ast::statement::{ReturnStatement, DeclarationStatement, FunctionStatement, ExportDefaultDeclaration};
+use ratel::ast::{Node, EmptyName, PropertyKey, Identifier};
+use ratel_visitor::{Visitor, ParentNode, ScopeKind};
 
 use TransformerCtxt;
 
+/// Variable declared at the top of a function for its arrow functions to
+/// refer to its `this` through.
+const THIS_BINDING: &str = "_this";
+
+/// Same as `THIS_BINDING`, for `arguments` of the function.
+const ARGUMENTS_BINDING: &str = "_arguments";
+
+enum Frame<'ast> {
+    /// Arrow function, converted once all of its body has been visited
+    Arrow {
+        node: &'ast ExpressionNode<'ast>,
+        bind_this: bool,
+        /// Left as an arrow, as it refers to something no function can
+        /// capture, such as `super` or `new.target`
+        keep: bool,
+    },
+    /// Function that needs `this` or `arguments` declared as variables
+    Function {
+        body: Option<&'ast BlockNode<'ast, Statement<'ast>>>,
+        this: bool,
+        arguments: bool,
+    },
+    /// Class, `this` within its fields can't be captured in a variable
+    Class,
+    Other,
+}
+
+/// Convert arrow functions into function expressions. Arrows referring to
+/// `this` of the function they are in get it through a `var _this = this;`
+/// declared at the top of that function, and likewise for `arguments`.
+/// Where there is no function to declare it in, such as at the top level
+/// of a module or within class fields, the function is bound to `this` with
+/// `.bind(this)` instead. Arrows referring to `super` or `new.target`, or
+/// to `arguments` outside of any function, are left as they are.
 pub struct TransformArrow<'ast> {
-    ctx: TransformerCtxt<'ast>
+    ctx: TransformerCtxt<'ast>,
+    frames: Vec<Frame<'ast>>,
+    /// Body of the function whose scope is about to be entered
+    pending: Option<&'ast BlockNode<'ast, Statement<'ast>>>,
 }
 
 impl<'ast> TransformArrow<'ast> {
     pub fn new(ctx: TransformerCtxt<'ast>) -> TransformArrow<'ast> {
         TransformArrow {
-            ctx
+            ctx,
+            frames: Vec::new(),
+            pending: None,
         }
     }
-}
 
-impl<'ast> Visitor<'ast> for TransformArrow<'ast> {
-    fn on_arrow_expression(&mut self, node: &ArrowExpression<'ast>, ptr: &'ast ExpressionNode<'ast>) {
-        let body = match node.body {
+    fn convert(&self, node: &'ast ExpressionNode<'ast>, bind_this: bool) {
+        let arrow: ArrowExpression<'ast> = match node.item {
+            Expression::Arrow(arrow) => arrow,
+            _ => return,
+        };
+
+        let body = match arrow.body {
             ArrowBody::Block(block)     => block,
             ArrowBody::Expression(expr) => {
                 let ret = self.ctx.alloc_as_loc(&expr, ReturnStatement {
@@ -32,11 +78,314 @@ impl<'ast> Visitor<'ast> for TransformArrow<'ast> {
             }
         };
 
-        self.ctx.swap(*ptr, Function {
+        let function = Function {
             name: OptionalName::empty(),
             generator: false,
-            params: node.params,
+            is_async: false,
+            type_parameters: NodeList::empty(),
+            params: arrow.params,
+            return_type: arrow.return_type,
             body,
+        };
+
+        if !bind_this {
+            return self.ctx.swap(node, function);
+        }
+
+        let object = self.ctx.alloc_as_loc(node, function);
+        let callee = self.ctx.alloc_as_loc(node, MemberExpression {
+            object,
+            property: self.ctx.alloc("bind"),
         });
+
+        self.ctx.swap(node, CallExpression {
+            callee,
+            arguments: NodeList::from(self.ctx.arena, self.ctx.alloc(ThisExpression)),
+        });
+    }
+
+    /// Find the function or class that `this` and alike belong to, and the
+    /// index of the first frame within it. `None` if there is no arrow in
+    /// between to convert.
+    fn enclosing(&self) -> Option<(Option<usize>, usize)> {
+        let end = self.frames.iter().rposition(|frame| matches!(*frame, Frame::Function { .. } | Frame::Class));
+        let start = end.map_or(0, |index| index + 1);
+
+        let lexical = self.frames[start..].iter().any(|frame| matches!(*frame, Frame::Arrow { .. }));
+
+        match lexical {
+            true  => Some((end, start)),
+            false => None,
+        }
+    }
+
+    /// Leave all arrows from the frame at `start` on as they are.
+    fn keep(&mut self, start: usize) {
+        for frame in &mut self.frames[start..] {
+            if let Frame::Arrow { ref mut keep, .. } = *frame {
+                *keep = true;
+            }
+        }
+    }
+
+    /// Declare `var _this = this, _arguments = arguments;` at the top of the
+    /// function body, leaving out whichever isn't captured.
+    fn declare(&self, body: &'ast BlockNode<'ast, Statement<'ast>>, this: bool, arguments: bool) {
+        let arena = self.ctx.arena;
+        let declarators = GrowableList::new();
+
+        if this {
+            declarators.push(arena, self.ctx.alloc(Declarator {
+                id: self.ctx.alloc(Pattern::Identifier(THIS_BINDING)),
+                init: Some(self.ctx.alloc(ThisExpression)),
+            }));
+        }
+
+        if arguments {
+            declarators.push(arena, self.ctx.alloc(Declarator {
+                id: self.ctx.alloc(Pattern::Identifier(ARGUMENTS_BINDING)),
+                init: Some(self.ctx.alloc(Expression::Identifier("arguments"))),
+            }));
+        }
+
+        let declaration: StatementNode<'ast> = self.ctx.alloc(DeclarationStatement {
+            kind: DeclarationKind::Var,
+            declarators: declarators.as_list(),
+        });
+
+        let builder = ListBuilder::new(arena, declaration);
+
+        for statement in body.body.iter() {
+            builder.push(arena, *statement);
+        }
+
+        self.ctx.swap(body, Block {
+            body: builder.as_list()
+        });
+    }
+}
+
+impl<'ast> Visitor<'ast> for TransformArrow<'ast> {
+    fn push_parent(&mut self, node: ParentNode<'ast>) {
+        let frame = match node {
+            ParentNode::Expression(expression) => match expression.item {
+                Expression::Arrow(_) => Frame::Arrow { node: expression, bind_this: false, keep: false },
+                Expression::Class(_) => Frame::Class,
+                _ => Frame::Other,
+            },
+            ParentNode::Statement(statement) => match statement.item {
+                Statement::Class(_) |
+                Statement::ExportDefault(ExportDefaultDeclaration::Class(_)) => Frame::Class,
+                _ => Frame::Other,
+            },
+        };
+
+        self.frames.push(frame);
+    }
+
+    fn pop_parent(&mut self) {
+        if let Some(Frame::Arrow { node, bind_this, keep: false }) = self.frames.pop() {
+            self.convert(node, bind_this);
+        }
+    }
+
+    fn on_enter_scope(&mut self, kind: ScopeKind) {
+        let frame = match kind {
+            ScopeKind::Function => Frame::Function { body: self.pending.take(), this: false, arguments: false },
+            ScopeKind::Block    => Frame::Other,
+        };
+
+        self.frames.push(frame);
+    }
+
+    fn on_leave_scope(&mut self) {
+        if let Some(Frame::Function { body: Some(body), this, arguments }) = self.frames.pop() {
+            if this || arguments {
+                self.declare(body, this, arguments);
+            }
+        }
+    }
+
+    fn on_function_statement(&mut self, _: &FunctionStatement<'ast>, node: &'ast StatementNode<'ast>) {
+        if let Statement::Function(ref function) = node.item {
+            self.pending = Some(&function.body);
+        }
+    }
+
+    fn on_function_expression(&mut self, _: &Function<'ast, OptionalName<'ast>>, node: &'ast ExpressionNode<'ast>) {
+        if let Expression::Function(ref function) = node.item {
+            self.pending = Some(&function.body);
+        }
+    }
+
+    fn on_export_default_declaration_statement(&mut self, _: &ExportDefaultDeclaration<'ast>, node: &'ast StatementNode<'ast>) {
+        if let Statement::ExportDefault(ExportDefaultDeclaration::Function(ref function)) = node.item {
+            self.pending = Some(&function.body);
+        }
+    }
+
+    fn on_method(&mut self, _: &'ast Node<'ast, PropertyKey<'ast>>, value: &'ast Node<'ast, Function<'ast, EmptyName>>) {
+        self.pending = Some(&value.body);
+    }
+
+    fn on_this_expression(&mut self, node: &'ast ExpressionNode<'ast>) {
+        let (end, start) = match self.enclosing() {
+            Some(enclosing) => enclosing,
+            None            => return,
+        };
+
+        if let Some(index) = end {
+            if let Frame::Function { body: Some(_), ref mut this, .. } = self.frames[index] {
+                *this = true;
+
+                return self.ctx.swap(node, Expression::Identifier(THIS_BINDING));
+            }
+        }
+
+        // Nowhere to declare the variable in, bind `this` of each arrow instead
+        for frame in &mut self.frames[start..] {
+            if let Frame::Arrow { ref mut bind_this, .. } = *frame {
+                *bind_this = true;
+            }
+        }
+    }
+
+    fn on_identifier_expression(&mut self, item: &Identifier<'ast>, node: &'ast ExpressionNode<'ast>) {
+        if *item != "arguments" {
+            return;
+        }
+
+        let (end, start) = match self.enclosing() {
+            Some(enclosing) => enclosing,
+            None            => return,
+        };
+
+        if let Some(index) = end {
+            if let Frame::Function { body: Some(_), ref mut arguments, .. } = self.frames[index] {
+                *arguments = true;
+
+                return self.ctx.swap(node, Expression::Identifier(ARGUMENTS_BINDING));
+            }
+        }
+
+        self.keep(start);
+    }
+
+    fn on_super_expression(&mut self, _: &'ast ExpressionNode<'ast>) {
+        if let Some((_, start)) = self.enclosing() {
+            self.keep(start);
+        }
+    }
+
+    fn on_meta_property(&mut self, item: &MetaPropertyExpression<'ast>, _: &ExpressionNode<'ast>) {
+        if item.meta.item != "new" || item.property.item != "target" {
+            return;
+        }
+
+        if let Some((_, start)) = self.enclosing() {
+            self.keep(start);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratel::{parse, Module};
+    use ratel_visitor::Visitable;
+    use scope::Scope;
+
+    fn transform_arrows<'ast>(module: &'ast Module<'ast>) {
+        let arena = module.arena();
+        let ctx = TransformerCtxt {
+            arena,
+            scope: arena.alloc(Scope::new(ScopeKind::Function, None)),
+        };
+
+        module.visit_with(&mut TransformArrow::new(ctx));
+    }
+
+    fn assert_transform(source: &str, expected: &str) {
+        let module = parse(source).unwrap();
+        let expected = parse(expected).unwrap();
+
+        transform_arrows(&module);
+
+        assert_eq!(module.body(), expected.body());
+    }
+
+    #[test]
+    fn expression_body() {
+        assert_transform("x = a => a + 1;", "x = function (a) { return a + 1; };");
+        assert_transform("foo((a, b) => a * b);", "foo(function (a, b) { return a * b; });");
+    }
+
+    #[test]
+    fn block_body() {
+        assert_transform("x = () => { foo(); };", "x = function () { foo(); };");
+        assert_transform("x = (a = 1, b) => { return b; };", "x = function (a = 1, b) { return b; };");
+    }
+
+    #[test]
+    fn nested_arrows() {
+        assert_transform("x = a => b => a + b;", "x = function (a) { return function (b) { return a + b; }; };");
+    }
+
+    #[test]
+    fn this_captured_by_function() {
+        assert_transform(
+            "function foo() { bar(); return () => this.baz; }",
+            "function foo() { var _this = this; bar(); return function () { return _this.baz; }; }",
+        );
+        assert_transform(
+            "x = { foo() { return () => () => this; } };",
+            "x = { foo() { var _this = this; return function () { return function () { return _this; }; }; } };",
+        );
+    }
+
+    #[test]
+    fn this_of_nested_functions() {
+        assert_transform(
+            "function foo() { return () => function () { return this; }; }",
+            "function foo() { return function () { return function () { return this; }; }; }",
+        );
+    }
+
+    #[test]
+    fn arguments_captured_by_function() {
+        assert_transform(
+            "function foo() { return () => arguments[0]; }",
+            "function foo() { var _arguments = arguments; return function () { return _arguments[0]; }; }",
+        );
+        assert_transform(
+            "function foo() { return () => this.bar(arguments); }",
+            "function foo() { var _this = this, _arguments = arguments; return function () { return _this.bar(_arguments); }; }",
+        );
+        assert_transform("x = () => arguments;", "x = () => arguments;");
+    }
+
+    #[test]
+    fn super_and_new_target_are_kept() {
+        assert_transform(
+            "x = { foo() { return () => () => super.foo(); } };",
+            "x = { foo() { return () => () => super.foo(); } };",
+        );
+        assert_transform(
+            "function foo() { return [() => new.target, () => bar]; }",
+            "function foo() { return [() => new.target, function () { return bar; }]; }",
+        );
+        assert_transform(
+            "class Foo extends Bar { a = () => () => super.a; }",
+            "class Foo extends Bar { a = () => () => super.a; }",
+        );
+    }
+
+    #[test]
+    fn this_bound_without_function() {
+        assert_transform("x = () => this;", "x = function () { return this; }.bind(this);");
+        assert_transform(
+            "class Foo { a = () => this; }",
+            "class Foo { a = function () { return this; }.bind(this); }",
+        );
     }
 }
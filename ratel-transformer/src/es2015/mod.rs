@@ -1,5 +1,17 @@
 mod arrow;
+mod object;
 
+use ratel::Module;
+use ratel_visitor::Visitable;
+
+use TransformerCtxt;
 use self::arrow::TransformArrow;
+use self::object::TransformObject;
 
-pub type PresetES2015<'ast> = TransformArrow<'ast>;
+/// Transform ES2015 syntax of the module into ES5.
+pub fn transform<'ast>(module: &'ast Module<'ast>, ctx: TransformerCtxt<'ast>) {
+    // Arrows are converted first, since capturing `this` replaces the
+    // bodies of methods that are moved into function expressions after.
+    module.visit_with(&mut TransformArrow::new(ctx));
+    module.visit_with(&mut TransformObject::new(ctx));
+}
@@ -0,0 +1,107 @@
+use ratel::ast::{Expression, ExpressionNode, Function, Name, OptionalName, Property, PropertyKey, MethodKind};
+use ratel::ast::expression::ObjectExpression;
+use ratel_visitor::Visitor;
+
+use TransformerCtxt;
+
+/// Expand shorthand properties and methods of object literals into
+/// `key: value` pairs. Methods with computed keys and accessors, which are
+/// already valid ES5, are left as they are.
+pub struct TransformObject<'ast> {
+    ctx: TransformerCtxt<'ast>
+}
+
+impl<'ast> TransformObject<'ast> {
+    pub fn new(ctx: TransformerCtxt<'ast>) -> TransformObject<'ast> {
+        TransformObject {
+            ctx
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for TransformObject<'ast> {
+    fn on_object_expression(&mut self, item: &ObjectExpression<'ast>, _: &'ast ExpressionNode<'ast>) {
+        for property in item.body.iter() {
+            match property.item {
+                Property::Shorthand(name) => {
+                    let key = self.ctx.alloc_as_loc(property, PropertyKey::Literal(name));
+                    let value = self.ctx.alloc_as_loc(property, Expression::Identifier(name));
+
+                    self.ctx.swap(property, Property::Literal { key, value });
+                },
+                Property::Method { key, kind, value } => {
+                    if let PropertyKey::Computed(_) = key.item {
+                        continue;
+                    }
+
+                    if kind != MethodKind::Method {
+                        continue;
+                    }
+
+                    let value = self.ctx.alloc_as_loc(&value, Function {
+                        name: OptionalName::empty(),
+                        generator: value.generator,
+                        is_async: value.is_async,
+                        type_parameters: value.type_parameters,
+                        params: value.params,
+                        return_type: value.return_type,
+                        body: value.body,
+                    });
+
+                    self.ctx.swap(property, Property::Literal { key, value });
+                },
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratel::parse;
+    use ratel_visitor::{Visitable, ScopeKind};
+    use scope::Scope;
+
+    fn assert_transform(source: &str, expected: &str) {
+        let module = parse(source).unwrap();
+        let expected = parse(expected).unwrap();
+        let arena = module.arena();
+        let ctx = TransformerCtxt {
+            arena,
+            scope: arena.alloc(Scope::new(ScopeKind::Function, None)),
+        };
+
+        module.visit_with(&mut TransformObject::new(ctx));
+
+        assert_eq!(module.body(), expected.body());
+    }
+
+    #[test]
+    fn shorthand_properties() {
+        assert_transform("x = { a, b: 1, c };", "x = { a: a, b: 1, c: c };");
+        assert_transform("x = { a: { b } };", "x = { a: { b: b } };");
+    }
+
+    #[test]
+    fn shorthand_methods() {
+        assert_transform(
+            "x = { foo() { return 1; }, bar(a, b = 2) {} };",
+            "x = { foo: function () { return 1; }, bar: function (a, b = 2) {} };",
+        );
+        assert_transform(
+            "x = { foo() { return { bar() {} }; } };",
+            "x = { foo: function () { return { bar: function () {} }; } };",
+        );
+    }
+
+    #[test]
+    fn computed_methods_unchanged() {
+        assert_transform("x = { [a]() {}, b };", "x = { [a]() {}, b: b };");
+    }
+
+    #[test]
+    fn accessors_unchanged() {
+        assert_transform("x = { get a() {}, set a(v) {}, b };", "x = { get a() {}, set a(v) {}, b: b };");
+    }
+}
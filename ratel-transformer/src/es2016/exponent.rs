@@ -0,0 +1,249 @@
+use ratel::ast::{Node, NodeList, Expression, ExpressionNode, OperatorKind};
+use ratel::ast::{Function, Name, OptionalName, Pattern, Block, StatementNode, Declarator, DeclarationKind};
+use ratel::ast::expression::{BinaryExpression, MemberExpression, ComputedMemberExpression};
+use ratel::ast::expression::CallExpression;
+use ratel::ast::statement::{ReturnStatement, DeclarationStatement};
+use ratel_visitor::{Visitor, ParentNode};
+
+use TransformerCtxt;
+
+/// Names used by the functions evaluating the target of `**=` only once
+const OBJECT: &str = "_ref";
+const KEY: &str = "_key";
+const BASE: &str = "_base";
+const VALUE: &str = "_value";
+
+/// Convert `a ** b` into `Math.pow(a, b)` and `a **= b` into
+/// `a = Math.pow(a, b)`. Member targets whose object or key can have side
+/// effects are read and assigned within functions called with them as
+/// arguments, so that they are evaluated only once, and in order.
+pub struct PresetES2016<'ast> {
+    ctx: TransformerCtxt<'ast>,
+    /// Exponentiations, converted once their operands have been visited
+    stack: Vec<Option<&'ast ExpressionNode<'ast>>>,
+}
+
+impl<'ast> PresetES2016<'ast> {
+    pub fn new(ctx: TransformerCtxt<'ast>) -> PresetES2016<'ast> {
+        PresetES2016 {
+            ctx,
+            stack: Vec::new(),
+        }
+    }
+
+    fn pow(&mut self, left: ExpressionNode<'ast>, right: ExpressionNode<'ast>) -> CallExpression<'ast> {
+        let callee = self.ctx.alloc(MemberExpression {
+            object: self.identifier("Math"),
+            property: self.ctx.alloc("pow"),
+        });
+        let arguments = self.ctx.list([left, right]);
+
+        CallExpression {
+            callee,
+            arguments
+        }
+    }
+
+    fn identifier(&self, name: &'ast str) -> ExpressionNode<'ast> {
+        self.ctx.alloc(Expression::Identifier(name))
+    }
+
+    fn convert(&mut self, node: &'ast ExpressionNode<'ast>) {
+        let binary = match node.item {
+            Expression::Binary(binary) => binary,
+            _ => return,
+        };
+
+        match binary.operator {
+            OperatorKind::Exponent => {
+                let pow = self.pow(binary.left, binary.right);
+
+                self.ctx.swap(node, pow);
+            },
+            OperatorKind::ExponentAssign => self.assign(node, binary.left, binary.right),
+            _ => {}
+        }
+    }
+
+    fn assign(&mut self, node: &'ast ExpressionNode<'ast>, left: ExpressionNode<'ast>, right: ExpressionNode<'ast>) {
+        match left.item {
+            Expression::Member(MemberExpression { object, property }) if !is_pure(object) => {
+                let target = self.ctx.alloc_as_loc(&left, MemberExpression {
+                    object: self.identifier(OBJECT),
+                    property,
+                });
+
+                self.assign_once(node, target, &[(OBJECT, object)], right);
+            },
+            Expression::ComputedMember(ComputedMemberExpression { object, property }) if !is_pure(object) || !is_pure(property) => {
+                let target = self.ctx.alloc_as_loc(&left, ComputedMemberExpression {
+                    object: self.identifier(OBJECT),
+                    property: self.identifier(KEY),
+                });
+
+                self.assign_once(node, target, &[(OBJECT, object), (KEY, property)], right);
+            },
+            _ => {
+                let pow = self.pow(left, right);
+                let right = self.ctx.alloc_as_loc(node, pow);
+
+                self.ctx.swap(node, BinaryExpression {
+                    operator: OperatorKind::Assign,
+                    left,
+                    right,
+                });
+            }
+        }
+    }
+
+    /// Replace the node with `(function (_ref, ...) { var _base = target;
+    /// return function (_value) { return target = Math.pow(_base, _value); };
+    /// })(object, ...)(value)`, reading the target before `value` is evaluated,
+    /// which in turn stays outside of any function to keep its `this`.
+    fn assign_once(&mut self, node: &'ast ExpressionNode<'ast>, target: ExpressionNode<'ast>, bindings: &[(&'ast str, ExpressionNode<'ast>)], value: ExpressionNode<'ast>) {
+        let params: Vec<Node<'ast, Pattern<'ast>>> = bindings
+            .iter()
+            .map(|&(name, _)| self.ctx.alloc(Pattern::Identifier(name)))
+            .collect();
+        let arguments: Vec<ExpressionNode<'ast>> = bindings
+            .iter()
+            .map(|&(_, value)| value)
+            .collect();
+
+        let declarator = self.ctx.alloc(Declarator {
+            id: self.ctx.alloc(Pattern::Identifier(BASE)),
+            init: Some(target),
+        });
+        let declaration: StatementNode<'ast> = self.ctx.alloc(DeclarationStatement {
+            kind: DeclarationKind::Var,
+            declarators: NodeList::from(self.ctx.arena, declarator),
+        });
+
+        let pow = self.pow(self.identifier(BASE), self.identifier(VALUE));
+        let assign = self.ctx.alloc(BinaryExpression {
+            operator: OperatorKind::Assign,
+            left: target,
+            right: self.ctx.alloc(pow),
+        });
+        let value_param = self.ctx.alloc(Pattern::Identifier(VALUE));
+        let assign = self.function(NodeList::from(self.ctx.arena, value_param), &[], assign);
+
+        let params = self.ctx.list(params);
+        let read = self.function(params, &[declaration], assign);
+        let arguments = self.ctx.list(arguments);
+        let callee = self.ctx.alloc(CallExpression {
+            callee: read,
+            arguments,
+        });
+
+        // Code generator wraps the function in parentheses where needed
+        self.ctx.swap(node, CallExpression {
+            callee,
+            arguments: NodeList::from(self.ctx.arena, value),
+        });
+    }
+
+    /// Function expression running `statements`, then returning `value`.
+    fn function(&mut self, params: NodeList<'ast, Pattern<'ast>>, statements: &[StatementNode<'ast>], value: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
+        let ret = self.ctx.alloc(ReturnStatement {
+            value: Some(value)
+        });
+        let mut body: Vec<StatementNode<'ast>> = statements.to_vec();
+
+        body.push(ret);
+
+        let body = self.ctx.list(body);
+
+        self.ctx.alloc(Function {
+            name: OptionalName::empty(),
+            generator: false,
+            is_async: false,
+            type_parameters: NodeList::empty(),
+            params,
+            return_type: None,
+            body: self.ctx.alloc(Block {
+                body
+            }),
+        })
+    }
+}
+
+/// Whether evaluating the expression twice has no side effects
+fn is_pure(expression: ExpressionNode) -> bool {
+    matches!(
+        expression.item,
+        Expression::Identifier(_) |
+        Expression::This(_)       |
+        Expression::Literal(_)
+    )
+}
+
+impl<'ast> Visitor<'ast> for PresetES2016<'ast> {
+    fn push_parent(&mut self, node: ParentNode<'ast>) {
+        let exponent = match node {
+            ParentNode::Expression(expression) => match expression.item {
+                Expression::Binary(BinaryExpression { operator: OperatorKind::Exponent, .. }) |
+                Expression::Binary(BinaryExpression { operator: OperatorKind::ExponentAssign, .. }) => Some(expression),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        self.stack.push(exponent);
+    }
+
+    fn pop_parent(&mut self) {
+        if let Some(Some(node)) = self.stack.pop() {
+            self.convert(node);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratel::parse;
+    use ratel_visitor::{Visitable, ScopeKind};
+    use scope::Scope;
+
+    fn assert_transform(source: &str, expected: &str) {
+        let module = parse(source).unwrap();
+        let expected = parse(expected).unwrap();
+        let arena = module.arena();
+        let ctx = TransformerCtxt {
+            arena,
+            scope: arena.alloc(Scope::new(ScopeKind::Function, None)),
+        };
+
+        module.visit_with(&mut PresetES2016::new(ctx));
+
+        assert_eq!(module.body(), expected.body());
+    }
+
+    #[test]
+    fn exponent() {
+        assert_transform("a ** b;", "Math.pow(a, b);");
+        assert_transform("a ** b ** c;", "Math.pow(a, Math.pow(b, c));");
+        assert_transform("x = (a + 1) ** 2 * 3;", "x = Math.pow((a + 1), 2) * 3;");
+    }
+
+    #[test]
+    fn exponent_assign() {
+        assert_transform("a **= b;", "a = Math.pow(a, b);");
+        assert_transform("a **= b ** c;", "a = Math.pow(a, Math.pow(b, c));");
+    }
+
+    #[test]
+    fn exponent_assign_member() {
+        assert_transform("a.b **= c;", "a.b = Math.pow(a.b, c);");
+        assert_transform("this[0] **= c;", "this[0] = Math.pow(this[0], c);");
+        assert_transform(
+            "foo().b **= c;",
+            "(function (_ref) { var _base = _ref.b; return function (_value) { return _ref.b = Math.pow(_base, _value); }; })(foo())(c);",
+        );
+        assert_transform(
+            "a[key()] **= this.c;",
+            "(function (_ref, _key) { var _base = _ref[_key]; return function (_value) { return _ref[_key] = Math.pow(_base, _value); }; })(a, key())(this.c);",
+        );
+    }
+}
@@ -1,59 +1,12 @@
-use ratel::ast::{Node, Loc, Expression, ExpressionNode, OperatorKind};
-use ratel::ast::expression::{BinaryExpression, MemberExpression, CallExpression};
-use ratel_visitor::Visitor;
+mod exponent;
 
-use TransformerCtxt;
-
-pub struct PresetES2016<'ast> {
-    ctx: TransformerCtxt<'ast>
-}
-
-static MATH: &Loc<Expression> = &Loc {
-    start: 0,
-    end: 0,
-    item: Expression::Identifier("Math")
-};
-static POW: &Loc<&str> = &Loc {
-    start: 0,
-    end: 0,
-    item: "pow"
-};
+use ratel::Module;
+use ratel_visitor::Visitable;
 
-impl<'ast> Visitor<'ast> for PresetES2016<'ast> {
-    fn on_binary_expression(&mut self, node: &BinaryExpression<'ast>, ptr: &ExpressionNode<'ast>) {
-        match node.operator {
-            OperatorKind::Exponent => {
-                let callee = self.ctx.alloc(MemberExpression {
-                    object: Node::new(MATH),
-                    property: Node::new(POW),
-                });
-                let arguments = self.ctx.list([node.left, node.right]);
-
-                self.ctx.swap(ptr, CallExpression {
-                    callee,
-                    arguments
-                });
-            },
-
-            OperatorKind::ExponentAssign => {
-                let callee = self.ctx.alloc(MemberExpression {
-                    object: Node::new(MATH),
-                    property: Node::new(POW),
-                });
-                let arguments = self.ctx.list([node.left, node.right]);
-                let right = self.ctx.alloc(CallExpression {
-                    callee,
-                    arguments
-                });
-
-                self.ctx.swap(ptr, BinaryExpression {
-                    operator: OperatorKind::Assign,
-                    left: node.left,
-                    right,
-                });
-            },
+use TransformerCtxt;
+pub use self::exponent::PresetES2016;
 
-            _ => {}
-        }
-    }
+/// Transform ES2016 syntax of the module into ES2015.
+pub fn transform<'ast>(module: &'ast Module<'ast>, ctx: TransformerCtxt<'ast>) {
+    module.visit_with(&mut PresetES2016::new(ctx));
 }
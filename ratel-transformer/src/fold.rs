@@ -0,0 +1,291 @@
+use toolshed::Arena;
+use ratel::ast::{Loc, Node, Expression, ExpressionNode, Literal, OperatorKind};
+use ratel::ast::expression::{BinaryExpression, PrefixExpression, ParenthesizedExpression};
+
+/// Fold arithmetic on numeric literals and concatenation of string literals,
+/// such as `1 + 2` into `3` or `"a" + "b"` into `"ab"`. Operations that
+/// can't be represented exactly by a literal, like `1 / 0`, are left as is.
+pub fn fold_constants<'ast>(arena: &'ast Arena, expression: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
+    match expression.item {
+        Expression::Binary(binary) => {
+            let left = fold_constants(arena, binary.left);
+            let right = fold_constants(arena, binary.right);
+
+            if let Some(folded) = fold_binary(arena, &expression, binary.operator, left, right) {
+                return folded;
+            }
+
+            if same(left, binary.left) && same(right, binary.right) {
+                return expression;
+            }
+
+            alloc_as_loc(arena, &expression, BinaryExpression {
+                operator: binary.operator,
+                left,
+                right,
+            })
+        },
+        Expression::Prefix(prefix) => {
+            let operand = fold_constants(arena, prefix.operand);
+
+            let folded = match (prefix.operator, number_value(operand)) {
+                (OperatorKind::Subtraction, Some(value)) => number(arena, &expression, -value),
+                (OperatorKind::Addition, Some(value))    => number(arena, &expression, value),
+                _                                        => None,
+            };
+
+            if let Some(folded) = folded {
+                return folded;
+            }
+
+            if same(operand, prefix.operand) {
+                return expression;
+            }
+
+            alloc_as_loc(arena, &expression, PrefixExpression {
+                operator: prefix.operator,
+                operand,
+            })
+        },
+        Expression::Parenthesized(ParenthesizedExpression { expression: inner }) => {
+            let folded = fold_constants(arena, inner);
+
+            match folded.item {
+                Expression::Literal(_) => folded,
+                _ if same(folded, inner) => expression,
+                _ => alloc_as_loc(arena, &expression, ParenthesizedExpression { expression: folded }),
+            }
+        },
+        _ => expression,
+    }
+}
+
+fn fold_binary<'ast>(
+    arena: &'ast Arena,
+    loc: &ExpressionNode<'ast>,
+    operator: OperatorKind,
+    left: ExpressionNode<'ast>,
+    right: ExpressionNode<'ast>,
+) -> Option<ExpressionNode<'ast>> {
+    if operator == OperatorKind::Addition {
+        // Concatenation, with numbers converted to strings
+        let contents = match (string_value(left), string_value(right)) {
+            (Some(left), Some(right)) => Some(format!("{}{}", left, right)),
+            (Some(left), None)        => number_value(right).and_then(number_to_string).map(|right| format!("{}{}", left, right)),
+            (None, Some(right))       => number_value(left).and_then(number_to_string).map(|left| format!("{}{}", left, right)),
+            (None, None)              => return fold_numbers(arena, loc, operator, left, right),
+        };
+
+        return contents.and_then(|contents| string(arena, loc, &contents));
+    }
+
+    fold_numbers(arena, loc, operator, left, right)
+}
+
+fn fold_numbers<'ast>(
+    arena: &'ast Arena,
+    loc: &ExpressionNode<'ast>,
+    operator: OperatorKind,
+    left: ExpressionNode<'ast>,
+    right: ExpressionNode<'ast>,
+) -> Option<ExpressionNode<'ast>> {
+    use self::OperatorKind::*;
+
+    let left = number_value(left)?;
+    let right = number_value(right)?;
+
+    let value = match operator {
+        Addition       => left + right,
+        Subtraction    => left - right,
+        Multiplication => left * right,
+        Division       => left / right,
+        Remainder      => left % right,
+
+        // `powf` isn't guaranteed to round like engines do, so only
+        // fold exponents that are exact
+        Exponent => {
+            let value = left.powf(right);
+
+            if left.fract() != 0.0 || right.fract() != 0.0 || right < 0.0 || value.abs() > MAX_SAFE_INTEGER {
+                return None;
+            }
+
+            value
+        },
+        _ => return None,
+    };
+
+    number(arena, loc, value)
+}
+
+/// Largest integer that a double represents exactly, along with all below it.
+const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+/// Value of a numeric literal, possibly negated.
+fn number_value(expression: ExpressionNode) -> Option<f64> {
+    match expression.item {
+        Expression::Literal(literal) => literal.number_value(),
+        Expression::Prefix(PrefixExpression { operator: OperatorKind::Subtraction, operand }) => {
+            match operand.item {
+                Expression::Literal(literal) => literal.number_value().map(|value| -value),
+                _ => None,
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Contents of a string literal without quotes. Strings with escapes
+/// are skipped, as their contents can't be moved between quotes as is.
+fn string_value<'ast>(expression: ExpressionNode<'ast>) -> Option<&'ast str> {
+    match expression.item {
+        Expression::Literal(Literal::String(string)) => {
+            let contents = &string[1..string.len() - 1];
+
+            if contents.contains('\\') {
+                None
+            } else {
+                Some(contents)
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Same as `Number.prototype.toString`, limited to values that Rust
+/// formats the same way, without exponents.
+fn number_to_string(value: f64) -> Option<String> {
+    if value == 0.0 {
+        return Some(String::from("0"));
+    }
+
+    if !value.is_finite() || value.abs() < 1e-6 || value.abs() >= 1e21 {
+        return None;
+    }
+
+    Some(format!("{}", value))
+}
+
+fn number<'ast>(arena: &'ast Arena, loc: &ExpressionNode<'ast>, value: f64) -> Option<ExpressionNode<'ast>> {
+    if !value.is_finite() {
+        return None;
+    }
+
+    let abs = value.abs();
+    let literal = if abs != 0.0 && !(1e-6..1e21).contains(&abs) {
+        format!("{:e}", abs)
+    } else {
+        format!("{}", abs)
+    };
+
+    let literal = alloc_as_loc(arena, loc, Literal::Number(arena.alloc_string(literal)));
+
+    // Numeric literals are never negative, `-0` included
+    if value.is_sign_negative() {
+        return Some(alloc_as_loc(arena, loc, PrefixExpression {
+            operator: OperatorKind::Subtraction,
+            operand: literal,
+        }));
+    }
+
+    Some(literal)
+}
+
+fn string<'ast>(arena: &'ast Arena, loc: &ExpressionNode<'ast>, contents: &str) -> Option<ExpressionNode<'ast>> {
+    let quote = if !contents.contains('"') {
+        '"'
+    } else if !contents.contains('\'') {
+        '\''
+    } else {
+        return None;
+    };
+
+    let literal = arena.alloc_string(format!("{}{}{}", quote, contents, quote));
+
+    Some(alloc_as_loc(arena, loc, Literal::String(literal)))
+}
+
+/// Check whether both nodes point to the very same expression.
+#[inline]
+fn same<'ast>(a: ExpressionNode<'ast>, b: ExpressionNode<'ast>) -> bool {
+    ::std::ptr::eq(&*a, &*b)
+}
+
+#[inline]
+fn alloc_as_loc<'ast, I>(arena: &'ast Arena, loc: &ExpressionNode<'ast>, item: I) -> ExpressionNode<'ast> where
+    I: Into<Expression<'ast>>,
+{
+    Node::new(arena.alloc(Loc::new(loc.start, loc.end, item.into())))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratel::ast::Statement;
+    use ratel::{parse, Module};
+
+    fn expression<'ast>(module: &'ast Module<'ast>) -> ExpressionNode<'ast> {
+        match module.body().only_element().map(|statement| statement.item) {
+            Some(Statement::Expression(expression)) => expression,
+            body => panic!("Unexpected body {:?}", body),
+        }
+    }
+
+    fn assert_fold(source: &str, expected: &str) {
+        let module = parse(source).unwrap();
+        let expected = parse(expected).unwrap();
+
+        let folded = fold_constants(module.arena(), expression(&module));
+
+        assert_eq!(folded, expression(&expected));
+    }
+
+    #[test]
+    fn fold_numbers() {
+        assert_fold("1 + 2", "3");
+        assert_fold("1 + 2 * 3", "7");
+        assert_fold("0.1 + 0.2", "0.30000000000000004");
+        assert_fold("10 / 4", "2.5");
+        assert_fold("7 % -3", "1");
+        assert_fold("0xff - 0b1", "254");
+        assert_fold("2 ** 10", "1024");
+        assert_fold("1 - 2", "-1");
+        assert_fold("1e21 * 10", "1e22");
+    }
+
+    #[test]
+    fn fold_unary() {
+        assert_fold("-(-5)", "5");
+        assert_fold("+5", "5");
+        assert_fold("-(2 - 5)", "3");
+    }
+
+    #[test]
+    fn fold_strings() {
+        assert_fold("'a' + 'b'", "\"ab\"");
+        assert_fold("'a' + 1 + 2", "\"a12\"");
+        assert_fold("1 + 2 + 'a'", "\"3a\"");
+        assert_fold("'\"' + 'a'", "'\"a'");
+        assert_fold("'a' + 0.5", "\"a0.5\"");
+    }
+
+    #[test]
+    fn fold_within_expressions() {
+        assert_fold("a + (1 + 2)", "a + 3");
+        assert_fold("a * 2 * 3", "a * 2 * 3");
+    }
+
+    #[test]
+    fn no_fold() {
+        assert_fold("1 / 0", "1 / 0");
+        assert_fold("0 / 0", "0 / 0");
+        assert_fold("2 ** 0.5", "2 ** 0.5");
+        assert_fold("'a' + b", "'a' + b");
+        assert_fold("'\\n' + 'a'", "'\\n' + 'a'");
+        assert_fold("'\"' + \"'\"", "'\"' + \"'\"");
+        assert_fold("'a' + 1e-7", "'a' + 1e-7");
+        assert_fold("'a' - 1", "'a' - 1");
+        assert_fold("1 < 2", "1 < 2");
+        assert_fold("-'a'", "-'a'");
+    }
+}
@@ -9,10 +9,12 @@ use toolshed::Arena;
 use toolshed::list::ListBuilder;
 use ratel::ast::{Loc, Node, NodeList};
 use ratel::Module;
-use ratel_visitor::{ScopeKind, Visitable};
+use ratel_visitor::ScopeKind;
 
 pub mod es2015;
-// pub mod es2016;
+pub mod es2016;
+
+pub mod fold;
 
 pub mod scope;
 
@@ -61,7 +63,7 @@ impl<'ast> TransformerCtxt<'ast> {
     }
 
     #[inline]
-    pub fn swap<T, I>(&self, ptr: Node<'ast, T>, item: I) where
+    pub fn swap<T, I>(&self, ptr: &Node<'ast, T>, item: I) where
         T: Copy + 'ast,
         I: Into<T>,
     {
@@ -83,5 +85,6 @@ pub fn transform<'ast>(module: &'ast mut Module<'ast>) {
         scope: arena.alloc(Scope::new(ScopeKind::Function, None)),
     };
 
-    module.visit_with(&mut es2015::PresetES2015::new(ctx));
+    es2016::transform(module, ctx);
+    es2015::transform(module, ctx);
 }
@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+
+use ratel::ast::{Expression, ExpressionNode, StatementNode, Node, Function, EmptyName, OperatorKind};
+use ratel::ast::expression::{BinaryExpression, ConditionalExpression, FunctionExpression, PropertyKey};
+use ratel::ast::statement::*;
+use ratel::{Module, Span};
+
+use {Visitor, Visitable, ParentNode, ScopeKind};
+
+/// Cyclomatic complexity of a single function.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FunctionComplexity<'ast> {
+    /// Name of the function or method, if it has one
+    pub name: Option<&'ast str>,
+    /// One plus the number of decision points within the function body
+    pub complexity: u32,
+}
+
+/// Compute the cyclomatic complexity of every function in the module,
+/// keyed by the span of the function.
+///
+/// Decision points are `if`, `for`, `while` and `do` statements, `case`
+/// clauses, `catch` clauses, the `&&` and `||` operators and the ternary.
+/// Nested functions, arrow functions included, are counted independently
+/// and don't add to the complexity of the enclosing function.
+pub fn cyclomatic_complexity<'ast>(module: &'ast Module<'ast>) -> HashMap<Span, FunctionComplexity<'ast>> {
+    let mut visitor = ComplexityCounter::default();
+
+    module.visit_with(&mut visitor);
+
+    visitor.functions.into_iter().collect()
+}
+
+#[derive(Default)]
+struct ComplexityCounter<'ast> {
+    functions: Vec<(Span, FunctionComplexity<'ast>)>,
+    /// Index into `functions` for frames that start a function
+    frames: Vec<Option<usize>>,
+    /// Function whose scope is about to be entered
+    pending: Option<(Span, Option<&'ast str>)>,
+}
+
+impl<'ast> ComplexityCounter<'ast> {
+    fn open(&mut self, span: Span, name: Option<&'ast str>) {
+        self.frames.push(Some(self.functions.len()));
+        self.functions.push((span, FunctionComplexity {
+            name,
+            complexity: 1,
+        }));
+    }
+
+    fn add(&mut self, count: u32) {
+        // Decision points outside of any function aren't counted
+        if let Some(index) = self.frames.iter().rev().filter_map(|frame| *frame).next() {
+            self.functions[index].1.complexity += count;
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for ComplexityCounter<'ast> {
+    fn push_parent(&mut self, node: ParentNode<'ast>) {
+        match node {
+            ParentNode::Expression(expression) => match expression.item {
+                Expression::Arrow(_) => self.open(expression.span(), None),
+                _ => self.frames.push(None),
+            },
+            ParentNode::Statement(_) => self.frames.push(None),
+        }
+    }
+
+    fn pop_parent(&mut self) {
+        self.frames.pop();
+    }
+
+    fn on_enter_scope(&mut self, kind: ScopeKind) {
+        match (kind, self.pending.take()) {
+            (ScopeKind::Function, Some((span, name))) => self.open(span, name),
+            _ => self.frames.push(None),
+        }
+    }
+
+    fn on_leave_scope(&mut self) {
+        self.frames.pop();
+    }
+
+    fn on_method(&mut self, key: &'ast Node<'ast, PropertyKey<'ast>>, value: &'ast Node<'ast, Function<'ast, EmptyName>>) {
+        let name = match key.item {
+            PropertyKey::Literal(name) => Some(name),
+            _ => None,
+        };
+
+        self.pending = Some((value.span(), name));
+    }
+
+    fn on_function_statement(&mut self, item: &FunctionStatement<'ast>, node: &'ast StatementNode<'ast>) {
+        self.pending = Some((node.span(), Some(item.name.0.item)));
+    }
+
+    fn on_function_expression(&mut self, item: &FunctionExpression<'ast>, node: &'ast ExpressionNode<'ast>) {
+        self.pending = Some((node.span(), item.name.0.map(|name| name.item)));
+    }
+
+    fn on_export_default_declaration_statement(&mut self, item: &ExportDefaultDeclaration<'ast>, _: &'ast StatementNode<'ast>) {
+        if let ExportDefaultDeclaration::Function(ref function) = *item {
+            self.pending = Some((function.span(), function.name.0.map(|name| name.item)));
+        }
+    }
+
+    fn on_if_statement(&mut self, _: &IfStatement, _: &'ast StatementNode<'ast>) {
+        self.add(1);
+    }
+
+    fn on_while_statement(&mut self, _: &WhileStatement, _: &'ast StatementNode<'ast>) {
+        self.add(1);
+    }
+
+    fn on_do_statement(&mut self, _: &DoStatement, _: &'ast StatementNode<'ast>) {
+        self.add(1);
+    }
+
+    fn on_for_statement(&mut self, _: &ForStatement, _: &'ast StatementNode<'ast>) {
+        self.add(1);
+    }
+
+    fn on_for_in_statement(&mut self, _: &ForInStatement, _: &'ast StatementNode<'ast>) {
+        self.add(1);
+    }
+
+    fn on_for_of_statement(&mut self, _: &ForOfStatement, _: &'ast StatementNode<'ast>) {
+        self.add(1);
+    }
+
+    fn on_switch_statement(&mut self, item: &SwitchStatement, _: &'ast StatementNode<'ast>) {
+        // `default` is taken when no other case is, so it doesn't count
+        let cases = item.cases.body.iter().filter(|case| case.test.is_some()).count();
+
+        self.add(cases as u32);
+    }
+
+    fn on_try_statement(&mut self, item: &TryStatement, _: &'ast StatementNode<'ast>) {
+        if item.handler.is_some() {
+            self.add(1);
+        }
+    }
+
+    fn on_binary_expression(&mut self, item: &BinaryExpression<'ast>, _: &'ast ExpressionNode<'ast>) {
+        match item.operator {
+            OperatorKind::LogicalAnd |
+            OperatorKind::LogicalOr  => self.add(1),
+            _ => {},
+        }
+    }
+
+    fn on_conditional_expression(&mut self, _: &ConditionalExpression<'ast>, _: &'ast ExpressionNode<'ast>) {
+        self.add(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratel::parse;
+
+    fn complexities(src: &str) -> Vec<(Option<String>, u32)> {
+        let module = parse(src).unwrap();
+        let mut functions: Vec<_> = cyclomatic_complexity(&module).into_iter().collect();
+
+        functions.sort_by_key(|&(span, _)| span.start);
+        functions.into_iter().map(|(_, function)| (function.name.map(String::from), function.complexity)).collect()
+    }
+
+    #[test]
+    fn counts_decision_points() {
+        let src = r#"
+            function check(a, b) {
+                if (a && b) {
+                    for (const x of a) {
+                        while (x) x--;
+                    }
+                } else if (a || b) {
+                    return a ? 1 : 2;
+                }
+
+                switch (b) {
+                    case 1:
+                    case 2:
+                        break;
+                    default:
+                        break;
+                }
+
+                try {
+                    b();
+                } catch (e) {}
+            }
+        "#;
+
+        // if, &&, for, while, if, ||, ?:, case, case, catch
+        assert_eq!(complexities(src), vec![(Some(String::from("check")), 11)]);
+    }
+
+    #[test]
+    fn counts_nested_functions_independently() {
+        let src = r#"
+            if (top) {}
+            function outer(a) {
+                if (a) {}
+                const inner = (b) => b ? 1 : 2;
+                return {
+                    method() { return a || b; },
+                    other: function named() {},
+                };
+            }
+            class Foo {
+                bar(x) { if (x) {} if (!x) {} }
+            }
+        "#;
+
+        assert_eq!(complexities(src), vec![
+            (Some(String::from("outer")), 2),
+            (None, 2),
+            (Some(String::from("method")), 2),
+            (Some(String::from("named")), 1),
+            (Some(String::from("bar")), 3),
+        ]);
+    }
+}
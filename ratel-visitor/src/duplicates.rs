@@ -0,0 +1,204 @@
+use std::borrow::Cow;
+
+use ratel::ast::expression::{PropertyKey, ObjectExpression, BinaryExpression};
+use ratel::ast::{ExpressionNode, Expression, Literal, Property, MethodKind, OperatorKind};
+use ratel::{Module, Span};
+
+use strings::{cook, CowStr};
+use {Visitor, Visitable};
+
+/// List keys repeated within object literals of the module, in source
+/// order, along with the span of each repeated key. The first occurrence
+/// of a key isn't reported. A getter and a setter for the same key are a
+/// pair rather than duplicates, computed keys and spread are ignored.
+///
+/// Keys are compared by their value, so `a`, `'a'` and `"a"` are all the
+/// same key, as are `1`, `1.0` and `0x1`. Objects being destructured into,
+/// as in `({ a: b, a: c } = d)`, are patterns and are not checked.
+pub fn duplicate_keys<'ast>(module: &'ast Module<'ast>) -> Vec<(CowStr<'ast>, Span)> {
+    let mut visitor = DuplicateKeys::default();
+
+    module.visit_with(&mut visitor);
+
+    visitor.duplicates.sort_by_key(|&(_, span)| span.start);
+    visitor.duplicates
+}
+
+#[derive(Default)]
+struct DuplicateKeys<'ast> {
+    duplicates: Vec<(CowStr<'ast>, Span)>,
+    /// Objects on the left hand side of an assignment
+    patterns: Vec<Span>,
+}
+
+/// How a key is defined, getters and setters may share a key.
+#[derive(Clone, Copy, PartialEq)]
+enum Definition {
+    Value,
+    Get,
+    Set,
+}
+
+impl<'ast> Visitor<'ast> for DuplicateKeys<'ast> {
+    fn on_binary_expression(&mut self, item: &BinaryExpression<'ast>, _: &'ast ExpressionNode<'ast>) {
+        if let BinaryExpression { operator: OperatorKind::Assign, left, .. } = *item {
+            if let Expression::Object(_) = left.item {
+                self.patterns.push(left.span());
+            }
+        }
+    }
+
+    fn on_object_expression(&mut self, item: &ObjectExpression<'ast>, node: &'ast ExpressionNode<'ast>) {
+        if self.patterns.contains(&node.span()) {
+            return;
+        }
+
+        let mut keys: Vec<(CowStr<'ast>, Definition)> = Vec::new();
+
+        for property in item.body.iter() {
+            let (key, span, definition) = match property.item {
+                Property::Shorthand(name) => (Cow::Borrowed(name), property.span(), Definition::Value),
+                Property::Literal { key, .. } => match key_value(&key.item) {
+                    Some(value) => (value, key.span(), Definition::Value),
+                    None        => continue,
+                },
+                Property::Method { key, kind, .. } => {
+                    let definition = match kind {
+                        MethodKind::Get => Definition::Get,
+                        MethodKind::Set => Definition::Set,
+                        _               => Definition::Value,
+                    };
+
+                    match key_value(&key.item) {
+                        Some(value) => (value, key.span(), definition),
+                        None        => continue,
+                    }
+                },
+                Property::Spread { .. } => continue,
+            };
+
+            let pairs = |other: Definition| matches!(
+                (definition, other),
+                (Definition::Get, Definition::Set) |
+                (Definition::Set, Definition::Get)
+            );
+
+            let earlier: Vec<Definition> = keys.iter()
+                .filter(|&(other, _)| *other == key)
+                .map(|&(_, other)| other)
+                .collect();
+
+            // Only a single getter and a single setter make a pair
+            let duplicate = match earlier.len() {
+                0 => false,
+                1 => !pairs(earlier[0]),
+                _ => true,
+            };
+
+            if duplicate {
+                self.duplicates.push((key.clone(), span));
+            }
+
+            keys.push((key, definition));
+        }
+    }
+}
+
+/// Value of a non-computed key, as a property name.
+fn key_value<'ast>(key: &PropertyKey<'ast>) -> Option<CowStr<'ast>> {
+    match *key {
+        PropertyKey::Computed(_) => None,
+        PropertyKey::Literal(raw) => Some(match raw.as_bytes()[0] {
+            b'"' | b'\'' => cook(&raw[1..raw.len() - 1]),
+            b'0'..=b'9' | b'.' => Cow::Owned(number_key(Literal::Number(raw))),
+            _ => Cow::Borrowed(raw),
+        }),
+        PropertyKey::Binary(raw) => Some(Cow::Owned(number_key(Literal::Binary(raw)))),
+    }
+}
+
+/// Numeric keys are converted to strings the way `Number.prototype.toString`
+/// does it, so that `1`, `1.0`, `0x1` and `1e0` are all `"1"`, and `1e21`
+/// is `"1e+21"`.
+fn number_key(literal: Literal) -> String {
+    let value = literal.number_value().unwrap_or(0.0);
+
+    if value == 0.0 {
+        return String::from("0");
+    }
+
+    if value.is_infinite() {
+        return String::from("Infinity");
+    }
+
+    // Shortest digits that round trip, along with the decimal exponent
+    let scientific = format!("{:e}", value);
+    let (mantissa, exponent) = scientific.split_at(scientific.find('e').unwrap());
+    let digits: String = mantissa.chars().filter(|ch| *ch != '.').collect();
+    let exponent: i32 = exponent[1..].parse().unwrap();
+
+    let k = digits.len() as i32;
+    let n = exponent + 1;
+
+    if k <= n && n <= 21 {
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat(-n as usize), digits)
+    } else {
+        let sign = if n - 1 < 0 { '-' } else { '+' };
+        let fraction = if k > 1 { format!(".{}", &digits[1..]) } else { String::new() };
+
+        format!("{}{}e{}{}", &digits[..1], fraction, sign, (n - 1).abs())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratel::parse;
+
+    fn duplicates(src: &str) -> Vec<(String, Span)> {
+        let module = parse(src).unwrap();
+
+        duplicate_keys(&module).into_iter().map(|(key, span)| (key.into_owned(), span)).collect()
+    }
+
+    fn keys(src: &str) -> Vec<String> {
+        duplicates(src).into_iter().map(|(key, _)| key).collect()
+    }
+
+    #[test]
+    fn repeated_keys() {
+        assert_eq!(duplicates("({ a: 1, b: 2, a: 3 });"), vec![
+            (String::from("a"), Span::new(15, 16)),
+        ]);
+        assert_eq!(keys("({ a, 'a': 1, \"a\"() {}, b: { c, c } });"), ["a", "a", "c"]);
+        assert_eq!(keys("({ 1: a, 1.0: b, 0x1: c, '1': d });"), ["1", "1", "1"]);
+    }
+
+    #[test]
+    fn numeric_keys_as_strings() {
+        assert_eq!(keys("({ 0b11: a, 3: b, 0o3: c });"), ["3", "3"]);
+        assert_eq!(keys("({ .5: a, '0.5': b, 5e-1: c });"), ["0.5", "0.5"]);
+        assert_eq!(keys("({ 1e21: a, '1e+21': b, 0.0000001: c, '1e-7': d });"), ["1e+21", "1e-7"]);
+        assert_eq!(keys("({ 1.50: a, '1.5': b, 100: c, 1e2: d });"), ["1.5", "100"]);
+        assert!(keys("({ 1: a, '1.0': b });").is_empty());
+    }
+
+    #[test]
+    fn getter_and_setter_pair() {
+        assert!(keys("({ get a() {}, set a(v) {} });").is_empty());
+        assert_eq!(keys("({ get a() {}, get a() {} });"), ["a"]);
+        assert_eq!(keys("({ a: 1, get a() {} });"), ["a"]);
+        assert_eq!(keys("({ get a() {}, set a(v) {}, set a(v) {} });"), ["a"]);
+    }
+
+    #[test]
+    fn computed_keys_ignored() {
+        assert!(keys("({ [a]: 1, [a]: 2, a: 3, ['a']: 4 });").is_empty());
+        assert!(keys("({ ...a, ...a });").is_empty());
+        assert!(keys("({ a: b, a: c } = d);").is_empty());
+    }
+}
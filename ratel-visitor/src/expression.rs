@@ -1,4 +1,4 @@
-use ratel::ast::{Identifier, Expression, ExpressionNode, StatementNode, Literal};
+use ratel::ast::{Identifier, Expression, ExpressionNode, StatementNode, Literal, Node};
 use ratel::ast::expression::*;
 
 use {Visitor, Visitable, ParentNode};
@@ -20,7 +20,11 @@ impl<'ast> Visitable<'ast> for ExpressionNode<'ast> {
                 return;
             },
             This(_) => {
-                visitor.on_this_expression(&self);
+                visitor.on_this_expression(self);
+                return;
+            },
+            Super(_) => {
+                visitor.on_super_expression(self);
                 return;
             },
             Identifier(ref ident) => {
@@ -62,6 +66,11 @@ impl<'ast> Visitable<'ast> for ExpressionNode<'ast> {
                 visitor.push_parent(ParentNode::from(self));
                 call.visit_with(visitor);
             },
+            Optional(ref optional) => {
+                visitor.on_optional_expression(optional, self);
+                visitor.push_parent(ParentNode::from(self));
+                optional.visit_with(visitor);
+            },
             Binary(ref binary) => {
                 visitor.on_binary_expression(binary, self);
                 visitor.push_parent(ParentNode::from(self));
@@ -72,6 +81,16 @@ impl<'ast> Visitable<'ast> for ExpressionNode<'ast> {
                 visitor.push_parent(ParentNode::from(self));
                 prefix.visit_with(visitor);
             },
+            Yield(ref expression) => {
+                visitor.on_yield_expression(expression, self);
+                visitor.push_parent(ParentNode::from(self));
+                expression.visit_with(visitor);
+            },
+            Await(ref expression) => {
+                visitor.on_await_expression(expression, self);
+                visitor.push_parent(ParentNode::from(self));
+                expression.visit_with(visitor);
+            },
             Postfix(ref postfix) => {
                 visitor.on_postfix_expression(postfix, self);
                 visitor.push_parent(ParentNode::from(self));
@@ -116,6 +135,31 @@ impl<'ast> Visitable<'ast> for ExpressionNode<'ast> {
                 visitor.on_class_expression(class, self);
                 visitor.push_parent(ParentNode::from(self));
                 class.visit_with(visitor);
+            },
+            JSXElement(ref element) => {
+                visitor.on_jsx_element_expression(element, self);
+                visitor.push_parent(ParentNode::from(self));
+                element.visit_with(visitor);
+            },
+            JSXFragment(ref fragment) => {
+                visitor.on_jsx_fragment_expression(fragment, self);
+                visitor.push_parent(ParentNode::from(self));
+                fragment.visit_with(visitor);
+            },
+            TypeCast(ref cast) => {
+                visitor.on_type_cast_expression(cast, self);
+                visitor.push_parent(ParentNode::from(self));
+                cast.visit_with(visitor);
+            },
+            Instantiation(ref instantiation) => {
+                visitor.on_instantiation_expression(instantiation, self);
+                visitor.push_parent(ParentNode::from(self));
+                instantiation.visit_with(visitor);
+            },
+            Parenthesized(ref parenthesized) => {
+                visitor.on_parenthesized_expression(parenthesized, self);
+                visitor.push_parent(ParentNode::from(self));
+                parenthesized.visit_with(visitor);
             }
         }
         visitor.pop_parent();
@@ -129,6 +173,13 @@ impl<'ast> Visitable<'ast> for ThisExpression {
     fn visit_with<V: Visitor<'ast>>(&self, _: &mut V) {}
 }
 
+impl<'ast> Visitable<'ast> for SuperExpression {
+    type Parent = ExpressionNode<'ast>;
+
+    #[inline]
+    fn visit_with<V: Visitor<'ast>>(&self, _: &mut V) {}
+}
+
 impl<'ast> Visitable<'ast> for Identifier<'ast> {
     type Parent = ExpressionNode<'ast>;
 
@@ -220,6 +271,24 @@ impl<'ast> Visitable<'ast> for CallExpression<'ast> {
     }
 }
 
+impl<'ast> Visitable<'ast> for OptionalExpression<'ast> {
+    type Parent = ExpressionNode<'ast>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        self.object.visit_with(visitor);
+
+        match self.link {
+            OptionalLink::Member(_) => {},
+            OptionalLink::ComputedMember(ref property) => property.visit_with(visitor),
+            OptionalLink::Call(ref arguments) => arguments.visit_with(visitor),
+        }
+    }
+}
+
 impl<'ast> Visitable<'ast> for BinaryExpression<'ast> {
     type Parent = ExpressionNode<'ast>;
 
@@ -245,6 +314,30 @@ impl<'ast> Visitable<'ast> for PrefixExpression<'ast> {
     }
 }
 
+impl<'ast> Visitable<'ast> for YieldExpression<'ast> {
+    type Parent = ExpressionNode<'ast>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        self.argument.visit_with(visitor);
+    }
+}
+
+impl<'ast> Visitable<'ast> for AwaitExpression<'ast> {
+    type Parent = ExpressionNode<'ast>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        self.argument.visit_with(visitor);
+    }
+}
+
 impl<'ast> Visitable<'ast> for PostfixExpression<'ast> {
     type Parent = ExpressionNode<'ast>;
 
@@ -347,3 +440,102 @@ impl<'ast> Visitable<'ast> for ObjectExpression<'ast> {
         self.body.visit_with(visitor);
     }
 }
+
+impl<'ast> Visitable<'ast> for JSXAttribute<'ast> {
+    type Parent = Node<'ast, Self>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        match *self {
+            JSXAttribute::Attribute { ref value, .. } => value.visit_with(visitor),
+            JSXAttribute::Spread { ref argument }     => argument.visit_with(visitor),
+        }
+    }
+}
+
+impl<'ast> Visitable<'ast> for JSXChild<'ast> {
+    type Parent = Node<'ast, Self>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        match *self {
+            JSXChild::Text(_) => {},
+            JSXChild::Expression(ref expression) => expression.visit_with(visitor),
+            JSXChild::Element(ref element)       => element.visit_with(visitor),
+        }
+    }
+}
+
+impl<'ast> Visitable<'ast> for JSXElement<'ast> {
+    type Parent = ExpressionNode<'ast>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        // Lowercase tag names such as `div` are intrinsic elements, not references
+        match self.name.item {
+            Expression::Identifier(name) if name.starts_with(|ch: char| ch.is_lowercase()) => {},
+            _ => self.name.visit_with(visitor),
+        }
+
+        self.attributes.visit_with(visitor);
+        self.children.visit_with(visitor);
+    }
+}
+
+impl<'ast> Visitable<'ast> for JSXFragment<'ast> {
+    type Parent = ExpressionNode<'ast>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        self.children.visit_with(visitor);
+    }
+}
+
+impl<'ast> Visitable<'ast> for TypeCastExpression<'ast> {
+    type Parent = ExpressionNode<'ast>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        self.expression.visit_with(visitor);
+    }
+}
+
+impl<'ast> Visitable<'ast> for InstantiationExpression<'ast> {
+    type Parent = ExpressionNode<'ast>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        self.expression.visit_with(visitor);
+    }
+}
+
+impl<'ast> Visitable<'ast> for ParenthesizedExpression<'ast> {
+    type Parent = ExpressionNode<'ast>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        self.expression.visit_with(visitor);
+    }
+}
+
@@ -0,0 +1,360 @@
+use ratel::ast::{Expression, ExpressionNode, Statement, StatementNode, Pattern, PatternList, Property, NodeList, Identifier};
+use ratel::ast::{Node, Function, EmptyName, OperatorKind, DeclarationKind};
+use ratel::ast::expression::{PropertyKey, ObjectExpression, ArrayExpression, BinaryExpression, SpreadExpression};
+use ratel::ast::statement::{DeclarationStatement, ForInit, ForStatement, ForInStatement, ForOfStatement};
+use ratel::ast::statement::{ImportDeclaration, ForImportSpecifier};
+use ratel::{Module, Span};
+
+use globals::GlobalEnv;
+use {Visitor, Visitable, ParentNode, ScopeKind};
+
+/// List references to identifiers within the expression that aren't bound
+/// within it, in source order. Parameters of functions and arrows, as well
+/// as declarations within their bodies, bind names for everything nested
+/// in them. What remains refers to the enclosing scope or to globals.
+pub fn free_identifiers<'ast>(expression: &'ast ExpressionNode<'ast>) -> Vec<(&'ast str, Span)> {
+    let mut visitor = FreeIdentifiers::default();
+
+    expression.visit_with(&mut visitor);
+
+    visitor.free.sort_by_key(|&(_, span)| span.start);
+    visitor.free
+}
+
+/// List references within the module to names that are neither declared
+/// in it nor known to `env`, in source order. Declarations at the top level
+/// of the module, imports included, bind names for the whole of it.
+pub fn undeclared<'ast>(module: &'ast Module<'ast>, env: &GlobalEnv) -> Vec<(&'ast str, Span)> {
+    let mut visitor = FreeIdentifiers::default();
+
+    visitor.frames.push(Frame::new(Kind::Function));
+    module.visit_with(&mut visitor);
+    visitor.pop();
+
+    visitor.free.retain(|&(name, _)| !env.contains(name));
+    visitor.free.sort_by_key(|&(_, span)| span.start);
+    visitor.free
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Function,
+    Block,
+    /// `try` statement, counting the blocks entered within it so far
+    Try { blocks: u8 },
+    Other,
+}
+
+struct Frame<'ast> {
+    kind: Kind,
+    bound: Vec<&'ast str>,
+    uses: Vec<(&'ast str, Span)>,
+    /// Names bound by the parameter of a catch clause
+    catch: Vec<&'ast str>,
+}
+
+impl<'ast> Frame<'ast> {
+    fn new(kind: Kind) -> Self {
+        Frame {
+            kind,
+            bound: Vec::new(),
+            uses: Vec::new(),
+            catch: Vec::new(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct FreeIdentifiers<'ast> {
+    frames: Vec<Frame<'ast>>,
+    free: Vec<(&'ast str, Span)>,
+    /// Parameters and name of the function whose scope is about to be entered
+    pending: Option<(PatternList<'ast>, Option<&'ast str>)>,
+}
+
+impl<'ast> FreeIdentifiers<'ast> {
+    fn refer(&mut self, name: &'ast str, span: Span) {
+        match self.frames.last_mut() {
+            Some(frame) => frame.uses.push((name, span)),
+            None        => self.free.push((name, span)),
+        }
+    }
+
+    /// Names bound within the frame are only known once all of it has been
+    /// visited, references that remain unresolved move on to the parent.
+    fn pop(&mut self) {
+        let Frame { bound, uses, .. } = match self.frames.pop() {
+            Some(frame) => frame,
+            None        => return,
+        };
+
+        let unresolved = uses.into_iter().filter(|&(name, _)| !bound.contains(&name));
+
+        match self.frames.last_mut() {
+            Some(parent) => parent.uses.extend(unresolved),
+            None         => self.free.extend(unresolved),
+        }
+    }
+
+    /// Frame of the scope declarations of `kind` bind their names in
+    fn scope(&mut self, kind: DeclarationKind) -> Option<&mut Frame<'ast>> {
+        self.frames.iter_mut().rev().find(|frame| match frame.kind {
+            Kind::Function => true,
+            Kind::Block    => kind != DeclarationKind::Var,
+            _              => false,
+        })
+    }
+
+    fn bind(&mut self, kind: DeclarationKind, name: &'ast str) {
+        if let Some(frame) = self.scope(kind) {
+            frame.bound.push(name);
+        }
+    }
+
+    fn declare(&mut self, declaration: &DeclarationStatement<'ast>) {
+        let mut names = Vec::new();
+
+        for declarator in declaration.declarators.iter() {
+            bound_names(&declarator.id, &mut names);
+        }
+
+        if let Some(frame) = self.scope(declaration.kind) {
+            frame.bound.extend(names);
+        }
+    }
+
+    fn loop_frame(&mut self, init: Option<Node<'ast, ForInit<'ast>>>) {
+        // Bindings of `for (let ...)` belong to the loop
+        self.frames.push(Frame::new(Kind::Block));
+
+        if let Some(ForInit::Declaration(declaration)) = init.map(|init| init.item) {
+            self.declare(&declaration);
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for FreeIdentifiers<'ast> {
+    fn push_parent(&mut self, node: ParentNode<'ast>) {
+        let kind = match node {
+            ParentNode::Statement(statement) => match statement.item {
+                Statement::Declaration(ref declaration) => {
+                    self.declare(declaration);
+                    Kind::Other
+                },
+                Statement::Function(ref function) => {
+                    self.bind(DeclarationKind::Let, function.name.0.item);
+                    self.pending = Some((function.params, None));
+                    Kind::Other
+                },
+                Statement::Class(ref class) => {
+                    self.bind(DeclarationKind::Let, class.name.0.item);
+                    Kind::Other
+                },
+                Statement::For(ForStatement { init, .. }) => return self.loop_frame(init),
+                Statement::ForIn(ForInStatement { left, .. }) |
+                Statement::ForOf(ForOfStatement { left, .. }) => return self.loop_frame(Some(left)),
+                Statement::Try(ref try_statement) => {
+                    let mut frame = Frame::new(Kind::Try { blocks: 0 });
+
+                    if let Some(handler) = try_statement.handler {
+                        bound_names(&handler.param, &mut frame.catch);
+                    }
+
+                    return self.frames.push(frame);
+                },
+                _ => Kind::Other,
+            },
+            ParentNode::Expression(expression) => match expression.item {
+                Expression::Arrow(ref arrow) => {
+                    let mut frame = Frame::new(Kind::Function);
+
+                    for param in arrow.params.iter() {
+                        bound_names(param, &mut frame.bound);
+                    }
+
+                    return self.frames.push(frame);
+                },
+                Expression::Function(ref function) => {
+                    self.pending = Some((function.params, function.name.0.map(|name| name.item)));
+                    Kind::Other
+                },
+                // Name of a class expression is only bound within the class
+                Expression::Class(ref class) => {
+                    let mut frame = Frame::new(Kind::Block);
+
+                    frame.bound.extend(class.name.0.map(|name| name.item));
+
+                    return self.frames.push(frame);
+                },
+                _ => Kind::Other,
+            },
+        };
+
+        self.frames.push(Frame::new(kind));
+    }
+
+    fn pop_parent(&mut self) {
+        self.pop();
+    }
+
+    fn on_enter_scope(&mut self, kind: ScopeKind) {
+        let mut frame = match kind {
+            ScopeKind::Function => Frame::new(Kind::Function),
+            ScopeKind::Block    => Frame::new(Kind::Block),
+        };
+
+        match kind {
+            ScopeKind::Function => {
+                frame.bound.push("arguments");
+
+                if let Some((params, name)) = self.pending.take() {
+                    frame.bound.extend(name);
+
+                    for param in params.iter() {
+                        bound_names(param, &mut frame.bound);
+                    }
+                }
+            },
+            ScopeKind::Block => {
+                if let Some(&mut Frame { kind: Kind::Try { ref mut blocks }, ref catch, .. }) = self.frames.last_mut() {
+                    *blocks += 1;
+
+                    // The try block comes first, followed by the body of the catch clause
+                    if *blocks == 2 {
+                        frame.bound.extend(catch.iter().cloned());
+                    }
+                }
+            },
+        }
+
+        self.frames.push(frame);
+    }
+
+    fn on_leave_scope(&mut self) {
+        self.pop();
+    }
+
+    fn on_method(&mut self, _: &'ast Node<'ast, PropertyKey<'ast>>, value: &'ast Node<'ast, Function<'ast, EmptyName>>) {
+        self.pending = Some((value.params, None));
+    }
+
+    fn on_import_declaration_statement(&mut self, item: &ImportDeclaration<'ast>, _: &'ast StatementNode<'ast>) {
+        for specifier in item.specifiers.iter() {
+            let local = match specifier.item {
+                ForImportSpecifier::ImportSpecifier(ref spec)          => spec.local,
+                ForImportSpecifier::ImportDefaultSpecifier(ref spec)   => spec.local,
+                ForImportSpecifier::ImportNamespaceSpecifier(ref spec) => spec.local,
+            };
+
+            self.bind(DeclarationKind::Const, local.item);
+        }
+    }
+
+    fn on_identifier_expression(&mut self, item: &Identifier<'ast>, node: &'ast ExpressionNode<'ast>) {
+        self.refer(item, node.span());
+    }
+
+    fn on_object_expression(&mut self, item: &ObjectExpression<'ast>, _: &'ast ExpressionNode<'ast>) {
+        for property in item.body.iter() {
+            if let Property::Shorthand(name) = property.item {
+                self.refer(name, property.span());
+            }
+        }
+    }
+}
+
+/// Collect the names bound by a pattern.
+fn bound_names<'ast>(pattern: &Pattern<'ast>, names: &mut Vec<&'ast str>) {
+    match *pattern {
+        Pattern::Void => {},
+        Pattern::Identifier(name) => names.push(name),
+        Pattern::ObjectPattern { properties } => property_names(properties, names),
+        Pattern::ArrayPattern { elements } => {
+            for element in elements.iter() {
+                bound_names(element, names);
+            }
+        },
+        Pattern::RestElement { argument } => names.push(argument.item),
+        Pattern::AssignmentPattern { left, .. } => bound_names(&left, names),
+        Pattern::Annotated { pattern, .. } => bound_names(&pattern, names),
+    }
+}
+
+fn property_names<'ast>(properties: NodeList<'ast, Property<'ast>>, names: &mut Vec<&'ast str>) {
+    for property in properties.iter() {
+        match property.item {
+            Property::Shorthand(name) => names.push(name),
+            Property::Literal { value, .. } => expression_names(&value, names),
+            Property::Spread { argument } => expression_names(&argument, names),
+            Property::Method { .. } => {},
+        }
+    }
+}
+
+/// Values within object patterns are kept as expressions, such as `b = 1`
+/// in `{ a: b = 1 }`.
+fn expression_names<'ast>(expression: &Expression<'ast>, names: &mut Vec<&'ast str>) {
+    match *expression {
+        Expression::Identifier(name) => names.push(name),
+        Expression::Binary(BinaryExpression { operator: OperatorKind::Assign, left, .. }) => expression_names(&left, names),
+        Expression::Spread(SpreadExpression { argument }) => expression_names(&argument, names),
+        Expression::Object(ObjectExpression { body }) => property_names(body, names),
+        Expression::Array(ArrayExpression { body }) => {
+            for element in body.iter() {
+                expression_names(element, names);
+            }
+        },
+        _ => {},
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratel::parse;
+
+    fn free(src: &str) -> Vec<(String, Span)> {
+        let module = parse(src).unwrap();
+
+        let expression = match module.body().iter().next().map(|statement| &statement.item) {
+            Some(Statement::Expression(expression)) => expression,
+            _ => panic!("Expected an expression statement"),
+        };
+
+        free_identifiers(expression).into_iter().map(|(name, span)| (name.to_owned(), span)).collect()
+    }
+
+    fn names(src: &str) -> Vec<String> {
+        free(src).into_iter().map(|(name, _)| name).collect()
+    }
+
+    #[test]
+    fn arrow_referencing_outer_variable() {
+        assert_eq!(free("(x) => x + y * z;"), vec![
+            (String::from("y"), Span::new(11, 12)),
+            (String::from("z"), Span::new(15, 16)),
+        ]);
+        assert_eq!(names("(a = b) => ({ a, b, c: d.e });"), ["b", "b", "d"]);
+    }
+
+    #[test]
+    fn all_references_bound() {
+        let src = "(function f(a, { b, c: [d] }) {
+            var e = a + b;
+            let g = () => { try {} catch (h) { return h; } };
+            class K { m(n) { return n + K; } }
+            for (let i of d) { e += i + arguments.length; }
+            return f(e, g, K, x);
+            var x;
+        });";
+
+        assert_eq!(names(src), Vec::<String>::new());
+    }
+
+    #[test]
+    fn bindings_are_scoped() {
+        assert_eq!(names("() => { { let a; } a; var b; { b; } };"), ["a"]);
+        assert_eq!(names("() => { try { e; } catch (e) {} };"), ["e"]);
+        assert_eq!(names("() => (class A {}, A);"), ["A"]);
+    }
+}
@@ -76,6 +76,7 @@ impl<'ast> Visitable<'ast> for ClassMember<'ast> {
                 ..
             } => {
                 key.visit_with(visitor);
+                visitor.on_method(key, value);
                 value.visit_with(visitor);
             },
             Literal {
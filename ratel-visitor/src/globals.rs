@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+
+/// Globals of the language itself, available in every environment.
+const BUILTINS: &[&str] = &[
+    "undefined", "NaN", "Infinity", "globalThis",
+    "Object", "Function", "Array", "Number", "Boolean", "String", "Symbol", "BigInt",
+    "Date", "RegExp", "Math", "JSON", "Reflect", "Proxy", "Promise",
+    "Map", "Set", "WeakMap", "WeakSet", "WeakRef", "FinalizationRegistry",
+    "ArrayBuffer", "SharedArrayBuffer", "DataView", "Atomics",
+    "Int8Array", "Uint8Array", "Uint8ClampedArray", "Int16Array", "Uint16Array",
+    "Int32Array", "Uint32Array", "Float32Array", "Float64Array",
+    "BigInt64Array", "BigUint64Array",
+    "Error", "EvalError", "RangeError", "ReferenceError", "SyntaxError",
+    "TypeError", "URIError", "AggregateError",
+    "eval", "isFinite", "isNaN", "parseFloat", "parseInt",
+    "decodeURI", "decodeURIComponent", "encodeURI", "encodeURIComponent",
+    "escape", "unescape", "Intl",
+];
+
+/// Provided by browsers, workers and node alike.
+const COMMON: &[&str] = &[
+    "console", "setTimeout", "clearTimeout", "setInterval", "clearInterval",
+    "queueMicrotask", "structuredClone", "atob", "btoa", "fetch",
+    "URL", "URLSearchParams", "TextEncoder", "TextDecoder",
+    "AbortController", "AbortSignal", "Event", "EventTarget",
+    "Headers", "Request", "Response", "FormData", "Blob",
+    "MessageChannel", "MessagePort", "WebAssembly", "crypto", "performance",
+];
+
+const BROWSER: &[&str] = &[
+    "window", "self", "document", "navigator", "location", "history", "screen",
+    "localStorage", "sessionStorage", "alert", "confirm", "prompt",
+    "requestAnimationFrame", "cancelAnimationFrame", "getComputedStyle",
+    "matchMedia", "open", "close", "postMessage", "frames", "parent", "top",
+    "XMLHttpRequest", "WebSocket", "Worker", "Image", "File", "FileReader",
+    "HTMLElement", "Element", "Node", "CustomEvent", "MutationObserver",
+    "IntersectionObserver", "ResizeObserver", "indexedDB", "caches",
+];
+
+const NODE: &[&str] = &[
+    "global", "process", "Buffer", "require", "module", "exports",
+    "__dirname", "__filename", "setImmediate", "clearImmediate",
+];
+
+const WORKER: &[&str] = &[
+    "self", "importScripts", "postMessage", "close", "location", "navigator",
+    "XMLHttpRequest", "WebSocket", "FileReader", "indexedDB", "caches",
+    "onmessage", "onerror",
+];
+
+/// Set of names an analysis pass treats as declared by the environment the
+/// code runs in, rather than by the code itself. Every preset includes the
+/// builtins of the language, extra names can be added with `insert`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GlobalEnv {
+    names: HashSet<String>,
+}
+
+impl GlobalEnv {
+    /// Only the builtins of the language, such as `Object` or `Math`.
+    pub fn builtins() -> Self {
+        GlobalEnv::from_lists(&[BUILTINS])
+    }
+
+    pub fn browser() -> Self {
+        GlobalEnv::from_lists(&[BUILTINS, COMMON, BROWSER])
+    }
+
+    pub fn node() -> Self {
+        GlobalEnv::from_lists(&[BUILTINS, COMMON, NODE])
+    }
+
+    pub fn worker() -> Self {
+        GlobalEnv::from_lists(&[BUILTINS, COMMON, WORKER])
+    }
+
+    fn from_lists(lists: &[&[&str]]) -> Self {
+        GlobalEnv {
+            names: lists.iter().flat_map(|list| list.iter()).map(|name| name.to_string()).collect(),
+        }
+    }
+
+    /// Add a name, returns `false` if it was already known.
+    pub fn insert<N: Into<String>>(&mut self, name: N) -> bool {
+        self.names.insert(name.into())
+    }
+
+    /// Add a name, consuming and returning the environment.
+    pub fn with<N: Into<String>>(mut self, name: N) -> Self {
+        self.insert(name);
+        self
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.names.remove(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.contains(name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use free::undeclared;
+    use ratel::{parse, Span};
+
+    fn names(src: &str, env: &GlobalEnv) -> Vec<String> {
+        let module = parse(src).unwrap();
+
+        undeclared(&module, env).into_iter().map(|(name, _)| name.to_owned()).collect()
+    }
+
+    #[test]
+    fn presets() {
+        assert!(GlobalEnv::browser().contains("window"));
+        assert!(GlobalEnv::browser().contains("Math"));
+        assert!(!GlobalEnv::browser().contains("process"));
+        assert!(GlobalEnv::node().contains("process"));
+        assert!(!GlobalEnv::node().contains("document"));
+        assert!(GlobalEnv::worker().contains("importScripts"));
+        assert!(!GlobalEnv::worker().contains("window"));
+        assert!(!GlobalEnv::builtins().contains("console"));
+    }
+
+    #[test]
+    fn undeclared_in_browser() {
+        let module = parse("window.foo = undeclaredThing + 1;").unwrap();
+
+        assert_eq!(undeclared(&module, &GlobalEnv::browser()), vec![
+            ("undeclaredThing", Span::new(13, 28)),
+        ]);
+    }
+
+    #[test]
+    fn declarations_and_imports() {
+        let src = "import a, { b as c } from 'm';
+            function f(x) { return x + a + c + g + missing; }
+            const g = document.body;
+            f(y);";
+
+        assert_eq!(names(src, &GlobalEnv::browser()), ["missing", "y"]);
+        assert_eq!(names(src, &GlobalEnv::node()), ["missing", "document", "y"]);
+    }
+
+    #[test]
+    fn custom_names() {
+        let env = GlobalEnv::browser().with("undeclaredThing");
+
+        assert!(names("undeclaredThing(window);", &env).is_empty());
+        assert_eq!(names("jQuery(window);", &env), ["jQuery"]);
+    }
+}
@@ -6,13 +6,24 @@ extern crate ratel;
 use ratel::ast::expression::*;
 use ratel::ast::statement::*;
 use ratel::ast::{ExpressionList, ExpressionNode, StatementList, StatementNode};
-use ratel::ast::{Identifier, Literal, Node, NodeList, Pattern};
+use ratel::ast::{Identifier, Literal, Node, NodeList, Pattern, Function, EmptyName};
 
 use ratel::Module;
 
 mod expression;
 mod function;
 mod statement;
+pub mod complexity;
+pub mod duplicates;
+pub mod free;
+pub mod globals;
+pub mod locate;
+pub mod operators;
+pub mod reassigned;
+pub mod strings;
+pub mod symbols;
+pub mod this;
+pub mod throws;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ScopeKind {
@@ -45,8 +56,13 @@ pub trait Visitor<'ast> {
     /// A reference has been declared within the current scope
     fn on_reference_declaration(&mut self, ident: &Identifier<'ast>) {}
 
+    /// A method of a class or an object literal is about to be visited,
+    /// called after its key
+    fn on_method(&mut self, key: &'ast Node<'ast, PropertyKey<'ast>>, value: &'ast Node<'ast, Function<'ast, EmptyName>>) {}
+
     // expressions
     fn on_this_expression(&mut self, node: &'ast ExpressionNode<'ast>) {}
+    fn on_super_expression(&mut self, node: &'ast ExpressionNode<'ast>) {}
     fn on_identifier_expression(&mut self, item: &Identifier<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_literal_expression(&mut self, item: &Literal<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_sequence_expression(&mut self, item: &SequenceExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
@@ -55,8 +71,11 @@ pub trait Visitor<'ast> {
     fn on_computed_member_expression(&mut self, item: &ComputedMemberExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_meta_property(&mut self, item: &MetaPropertyExpression<'ast>, node: &ExpressionNode<'ast>) {}
     fn on_call_expression(&mut self, item: &CallExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
+    fn on_optional_expression(&mut self, item: &OptionalExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_binary_expression(&mut self, item: &BinaryExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_prefix_expression(&mut self, item: &PrefixExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
+    fn on_yield_expression(&mut self, item: &YieldExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
+    fn on_await_expression(&mut self, item: &AwaitExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_postfix_expression(&mut self, item: &PostfixExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_conditional_expression(&mut self, item: &ConditionalExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_template_literal(&mut self, item: &TemplateLiteral<'ast>, node: &'ast ExpressionNode<'ast>) {}
@@ -66,6 +85,11 @@ pub trait Visitor<'ast> {
     fn on_object_expression(&mut self, item: &ObjectExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_function_expression(&mut self, item: &FunctionExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_class_expression(&mut self, item: &ClassExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
+    fn on_jsx_element_expression(&mut self, item: &JSXElement<'ast>, node: &'ast ExpressionNode<'ast>) {}
+    fn on_jsx_fragment_expression(&mut self, item: &JSXFragment<'ast>, node: &'ast ExpressionNode<'ast>) {}
+    fn on_type_cast_expression(&mut self, item: &TypeCastExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
+    fn on_instantiation_expression(&mut self, item: &InstantiationExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
+    fn on_parenthesized_expression(&mut self, item: &ParenthesizedExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
 
     // statements
     fn on_expression_statement(&mut self, item: &'ast ExpressionNode<'ast>, node: &'ast StatementNode<'ast>) {}
@@ -87,6 +111,11 @@ pub trait Visitor<'ast> {
     fn on_function_statement(&mut self, item: &FunctionStatement<'ast>, node: &'ast StatementNode<'ast>) {}
     fn on_class_statement(&mut self, item:&ClassStatement<'ast>, node: &'ast StatementNode<'ast>) {}
     fn on_import_declaration_statement(&mut self, item:&ImportDeclaration<'ast>, node: &'ast StatementNode<'ast>) {}
+    fn on_export_declaration_statement(&mut self, item:&ExportDeclaration<'ast>, node: &'ast StatementNode<'ast>) {}
+    fn on_export_named_declaration_statement(&mut self, item: &ExportNamedDeclaration<'ast>, node: &'ast StatementNode<'ast>) {}
+    fn on_export_default_declaration_statement(&mut self, item: &ExportDefaultDeclaration<'ast>, node: &'ast StatementNode<'ast>) {}
+    fn on_type_alias_declaration_statement(&mut self, item: &TypeAliasDeclaration<'ast>, node: &'ast StatementNode<'ast>) {}
+    fn on_interface_declaration_statement(&mut self, item: &InterfaceDeclaration<'ast>, node: &'ast StatementNode<'ast>) {}
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -159,6 +188,9 @@ impl<'ast> Visitable<'ast> for Pattern<'ast> {
                 left.visit_with(visitor);
                 right.visit_with(visitor);
             }
+            Pattern::Annotated { ref pattern, .. } => {
+                pattern.visit_with(visitor);
+            }
         }
     }
 }
@@ -192,8 +224,9 @@ impl<'ast> Visitable<'ast> for Property<'ast> {
                 key.visit_with(visitor);
                 value.visit_with(visitor);
             }
-            Property::Method { ref key, ref value } => {
+            Property::Method { ref key, ref value, .. } => {
                 key.visit_with(visitor);
+                visitor.on_method(key, value);
                 value.visit_with(visitor);
             },
             Property::Spread { ref argument } => {
@@ -488,6 +521,23 @@ mod test {
         assert_eq!(visitor.declared_vars, &[("doge", 0)]);
     }
 
+    #[test]
+    fn jsx_components_and_expressions_are_uses() {
+        use ratel::{parse_with, ParseOptions};
+
+        let src = "<Doge.Wow to={the} {...moon}><div>{much}</div><Such /></Doge.Wow>";
+        let module = parse_with(src, ParseOptions { jsx: true, ..ParseOptions::default() }).unwrap();
+        let mut visitor = ScopeTest::new();
+
+        module.visit_with(&mut visitor);
+
+        assert_eq!(
+            visitor.used_vars,
+            &[("Doge", 0), ("the", 0), ("moon", 0), ("much", 0), ("Such", 0)]
+        );
+        assert_eq!(visitor.declared_vars, &[]);
+    }
+
     #[test]
     fn function_params_are_declarations() {
         let module = parse("function doge(to, the) { const moon; }").unwrap();
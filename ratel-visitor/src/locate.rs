@@ -0,0 +1,168 @@
+use ratel::ast::ExpressionNode;
+use ratel::ast::Literal;
+use ratel::{Module, Span};
+
+use {Visitor, Visitable, ParentNode};
+
+/// Statements and expressions from the root of a module down to one
+/// of its nodes, outermost first.
+#[derive(Clone, Debug, Default)]
+pub struct NodePath<'ast> {
+    pub nodes: Vec<ParentNode<'ast>>,
+}
+
+impl<'ast> NodePath<'ast> {
+    /// The node at the end of the path, `None` if the path is empty.
+    #[inline]
+    pub fn innermost(&self) -> Option<ParentNode<'ast>> {
+        self.nodes.last().cloned()
+    }
+}
+
+/// Find the path to the innermost statement or expression whose span
+/// contains the byte at `offset`. When the offset sits on a boundary
+/// shared by several nodes, the most specific one wins. The path is
+/// empty if the offset isn't within any statement of the module.
+pub fn node_at<'ast>(module: &'ast Module<'ast>, offset: u32) -> NodePath<'ast> {
+    let mut visitor = Locate {
+        offset,
+        stack: Vec::new(),
+        inside: 0,
+        found: NodePath::default(),
+    };
+
+    module.visit_with(&mut visitor);
+
+    visitor.found
+}
+
+#[inline]
+fn span_of(node: ParentNode) -> Span {
+    match node {
+        ParentNode::Statement(statement) => statement.span(),
+        ParentNode::Expression(expression) => expression.span(),
+    }
+}
+
+struct Locate<'ast> {
+    offset: u32,
+    stack: Vec<ParentNode<'ast>>,
+    /// Number of nodes at the bottom of the stack containing the offset
+    inside: usize,
+    found: NodePath<'ast>,
+}
+
+impl<'ast> Locate<'ast> {
+    /// Leaf expressions never become parents, so they are entered and
+    /// left right away.
+    fn leaf(&mut self, node: &'ast ExpressionNode<'ast>) {
+        self.push_parent(ParentNode::Expression(node));
+        self.pop_parent();
+    }
+}
+
+impl<'ast> Visitor<'ast> for Locate<'ast> {
+    fn push_parent(&mut self, node: ParentNode<'ast>) {
+        if self.inside == self.stack.len() && span_of(node).contains(self.offset) {
+            self.inside += 1;
+        }
+
+        self.stack.push(node);
+
+        // Strictly deeper only, so that of two siblings sharing a
+        // boundary the first one to contain the offset is kept
+        if self.inside == self.stack.len() && self.inside > self.found.nodes.len() {
+            self.found.nodes.clear();
+            self.found.nodes.extend_from_slice(&self.stack);
+        }
+    }
+
+    fn pop_parent(&mut self) {
+        if self.inside == self.stack.len() && self.inside > 0 {
+            self.inside -= 1;
+        }
+
+        self.stack.pop();
+    }
+
+    fn on_this_expression(&mut self, node: &'ast ExpressionNode<'ast>) {
+        self.leaf(node);
+    }
+
+    fn on_super_expression(&mut self, node: &'ast ExpressionNode<'ast>) {
+        self.leaf(node);
+    }
+
+    fn on_literal_expression(&mut self, _: &Literal<'ast>, node: &'ast ExpressionNode<'ast>) {
+        self.leaf(node);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratel::parse;
+    use ratel::ast::{Expression, Statement};
+
+    fn kinds(path: &NodePath) -> Vec<&'static str> {
+        path.nodes.iter().map(|node| match *node {
+            ParentNode::Statement(statement) => match statement.item {
+                Statement::Expression(_) => "ExpressionStatement",
+                Statement::Declaration(_) => "Declaration",
+                Statement::Function(_) => "Function",
+                Statement::Return(_) => "Return",
+                _ => "Statement",
+            },
+            ParentNode::Expression(expression) => match expression.item {
+                Expression::Identifier(_) => "Identifier",
+                Expression::Literal(_) => "Literal",
+                Expression::Call(_) => "Call",
+                Expression::Member(_) => "Member",
+                Expression::Binary(_) => "Binary",
+                _ => "Expression",
+            },
+        }).collect()
+    }
+
+    #[test]
+    fn finds_identifier() {
+        let module = parse("let a = 1; foo + bar;").unwrap();
+        let path = node_at(&module, 18);
+
+        assert_eq!(kinds(&path), vec!["ExpressionStatement", "Binary", "Identifier"]);
+        assert_eq!(path.innermost().map(span_of), Some(Span::new(17, 20)));
+    }
+
+    #[test]
+    fn finds_call_argument() {
+        let module = parse("foo.bar(1, baz);").unwrap();
+
+        assert_eq!(kinds(&node_at(&module, 8)), vec!["ExpressionStatement", "Call", "Literal"]);
+        assert_eq!(kinds(&node_at(&module, 12)), vec!["ExpressionStatement", "Call", "Identifier"]);
+        assert_eq!(kinds(&node_at(&module, 9)), vec!["ExpressionStatement", "Call"]);
+    }
+
+    #[test]
+    fn prefers_more_specific_child_on_boundaries() {
+        let module = parse("foo.bar(1, baz);").unwrap();
+
+        // `foo` starts where the statement, call and member do
+        assert_eq!(kinds(&node_at(&module, 0)), vec!["ExpressionStatement", "Call", "Member", "Identifier"]);
+    }
+
+    #[test]
+    fn finds_nothing_between_statements() {
+        let module = parse("a;   b;").unwrap();
+
+        assert_eq!(kinds(&node_at(&module, 3)), Vec::<&str>::new());
+        assert!(node_at(&module, 3).innermost().is_none());
+        assert_eq!(kinds(&node_at(&module, 5)), vec!["ExpressionStatement", "Identifier"]);
+    }
+
+    #[test]
+    fn finds_this_within_function() {
+        let module = parse("function f() { return this; }").unwrap();
+
+        assert_eq!(kinds(&node_at(&module, 23)), vec!["Function", "Return", "Expression"]);
+    }
+}
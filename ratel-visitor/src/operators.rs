@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use ratel::ast::{ExpressionNode, OperatorKind};
+use ratel::ast::expression::{BinaryExpression, PrefixExpression, PostfixExpression};
+use ratel::Module;
+
+use {Visitor, Visitable};
+
+/// Count how many times each `OperatorKind` is used in binary, assignment,
+/// prefix (unary) and postfix (update) positions within the module.
+pub fn operators_used<'ast>(module: &'ast Module<'ast>) -> HashMap<OperatorKind, usize> {
+    let mut visitor = OperatorCounter::default();
+
+    module.visit_with(&mut visitor);
+
+    visitor.counts
+}
+
+#[derive(Default)]
+struct OperatorCounter {
+    counts: HashMap<OperatorKind, usize>,
+}
+
+impl OperatorCounter {
+    #[inline]
+    fn count(&mut self, operator: OperatorKind) {
+        *self.counts.entry(operator).or_insert(0) += 1;
+    }
+}
+
+impl<'ast> Visitor<'ast> for OperatorCounter {
+    fn on_binary_expression(&mut self, item: &BinaryExpression<'ast>, _: &'ast ExpressionNode<'ast>) {
+        self.count(item.operator);
+    }
+
+    fn on_prefix_expression(&mut self, item: &PrefixExpression<'ast>, _: &'ast ExpressionNode<'ast>) {
+        self.count(item.operator);
+    }
+
+    fn on_postfix_expression(&mut self, item: &PostfixExpression<'ast>, _: &'ast ExpressionNode<'ast>) {
+        self.count(item.operator);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratel::parse;
+
+    #[test]
+    fn counts_operators_in_colors() {
+        let module = parse(include_str!("../benches/colors.js")).unwrap();
+        let counts = operators_used(&module);
+
+        assert_eq!(counts.get(&OperatorKind::Addition), Some(&1));
+        assert_eq!(counts.get(&OperatorKind::BitShiftRight), Some(&2));
+        assert_eq!(counts.get(&OperatorKind::BitwiseAnd), Some(&2));
+        assert_eq!(counts.get(&OperatorKind::Subtraction), Some(&1));
+        assert_eq!(counts.get(&OperatorKind::Multiplication), None);
+    }
+
+    #[test]
+    fn counts_prefix_postfix_and_assignment() {
+        let module = parse("a += -b; c++; c++; new Foo(!d);").unwrap();
+        let counts = operators_used(&module);
+
+        assert_eq!(counts.get(&OperatorKind::AddAssign), Some(&1));
+        assert_eq!(counts.get(&OperatorKind::Subtraction), Some(&1));
+        assert_eq!(counts.get(&OperatorKind::Increment), Some(&2));
+        assert_eq!(counts.get(&OperatorKind::New), Some(&1));
+        assert_eq!(counts.get(&OperatorKind::LogicalNot), Some(&1));
+    }
+}
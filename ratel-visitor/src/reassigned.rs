@@ -0,0 +1,109 @@
+use ratel::ast::{Expression, ExpressionNode, Literal, Property, OperatorKind};
+use ratel::ast::expression::{BinaryExpression, PrefixExpression, PostfixExpression};
+use ratel::ast::expression::{ArrayExpression, ObjectExpression, SpreadExpression, ParenthesizedExpression};
+use ratel::{Module, Span};
+
+use {Visitor, Visitable};
+
+/// Globals that hold a value no code should ever replace.
+const READONLY: &[&str] = &["Infinity", "NaN", "undefined"];
+
+/// List assignments to `Infinity`, `NaN` and `undefined` within the module,
+/// in source order. All three are plain globals rather than literals, so
+/// assigning to them is valid syntax, but it is silently ignored outside
+/// strict mode. Plain and compound assignments, destructuring, as well as
+/// `++` and `--` are reported, with the span of the assigned name.
+pub fn reassigned_globals<'ast>(module: &'ast Module<'ast>) -> Vec<(&'ast str, Span)> {
+    let mut visitor = ReassignedGlobals::default();
+
+    module.visit_with(&mut visitor);
+
+    visitor.found.sort_by_key(|&(_, span)| span.start);
+    visitor.found
+}
+
+#[derive(Default)]
+struct ReassignedGlobals<'ast> {
+    found: Vec<(&'ast str, Span)>,
+}
+
+impl<'ast> ReassignedGlobals<'ast> {
+    /// Defaults within patterns, as in `[a = 1] = b`, are assignments of
+    /// their own the visitor reaches separately, so they aren't descended into.
+    fn target(&mut self, target: ExpressionNode<'ast>) {
+        match target.item {
+            Expression::Identifier(name) if READONLY.contains(&name) => self.found.push((name, target.span())),
+            Expression::Literal(Literal::Undefined) => self.found.push(("undefined", target.span())),
+            Expression::Parenthesized(ParenthesizedExpression { expression }) => self.target(expression),
+            Expression::Spread(SpreadExpression { argument }) => self.target(argument),
+            Expression::Array(ArrayExpression { body }) => {
+                for &element in body.iter() {
+                    self.target(element);
+                }
+            },
+            Expression::Object(ObjectExpression { body }) => {
+                for property in body.iter() {
+                    match property.item {
+                        Property::Shorthand(name) => if READONLY.contains(&name) {
+                            self.found.push((name, property.span()));
+                        },
+                        Property::Literal { value, .. } => self.target(value),
+                        Property::Spread { argument } => self.target(argument),
+                        Property::Method { .. } => {},
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for ReassignedGlobals<'ast> {
+    fn on_binary_expression(&mut self, item: &BinaryExpression<'ast>, _: &'ast ExpressionNode<'ast>) {
+        if item.operator.assignment() {
+            self.target(item.left);
+        }
+    }
+
+    fn on_prefix_expression(&mut self, item: &PrefixExpression<'ast>, _: &'ast ExpressionNode<'ast>) {
+        if let OperatorKind::Increment | OperatorKind::Decrement = item.operator {
+            self.target(item.operand);
+        }
+    }
+
+    fn on_postfix_expression(&mut self, item: &PostfixExpression<'ast>, _: &'ast ExpressionNode<'ast>) {
+        self.target(item.operand);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratel::parse;
+
+    fn names(src: &str) -> Vec<String> {
+        let module = parse(src).unwrap();
+
+        reassigned_globals(&module).into_iter().map(|(name, _)| name.to_owned()).collect()
+    }
+
+    #[test]
+    fn assignment_to_undefined() {
+        let module = parse("var a; undefined = 1;").unwrap();
+
+        assert_eq!(reassigned_globals(&module), vec![("undefined", Span::new(7, 16))]);
+    }
+
+    #[test]
+    fn assignments_of_every_kind() {
+        assert_eq!(names("NaN += 1; Infinity++; --NaN; (undefined) = a;"), ["NaN", "Infinity", "NaN", "undefined"]);
+        assert_eq!(names("[NaN, { a: Infinity = 1, ...undefined }] = b;"), ["NaN", "Infinity", "undefined"]);
+        assert_eq!(names("function f() { a = () => { NaN = 0; }; }"), ["NaN"]);
+    }
+
+    #[test]
+    fn normal_use() {
+        assert!(names("a = undefined; b = NaN + Infinity; c(undefined); -NaN; d.NaN = 1;").is_empty());
+        assert!(names("if (a === undefined) { a = isNaN(b) ? Infinity : b; }").is_empty());
+    }
+}
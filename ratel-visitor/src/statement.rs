@@ -114,7 +114,32 @@ impl<'ast> Visitable<'ast> for StatementNode<'ast> {
             },
             Import(ref import) => {
                 visitor.on_import_declaration_statement(import, self);
+                visitor.push_parent(ParentNode::from(self));
                 import.visit_with(visitor);
+            },
+            Export(ref export) => {
+                visitor.on_export_declaration_statement(export, self);
+                visitor.push_parent(ParentNode::from(self));
+                export.visit_with(visitor);
+            },
+            ExportNamed(ref export) => {
+                visitor.on_export_named_declaration_statement(export, self);
+                visitor.push_parent(ParentNode::from(self));
+                export.visit_with(visitor);
+            },
+            ExportDefault(ref export) => {
+                visitor.on_export_default_declaration_statement(export, self);
+                visitor.push_parent(ParentNode::from(self));
+                export.visit_with(visitor);
+            },
+            TypeAlias(ref alias) => {
+                // Types don't exist at runtime, so declarations have no children to visit
+                visitor.on_type_alias_declaration_statement(alias, self);
+                return;
+            },
+            Interface(ref interface) => {
+                visitor.on_interface_declaration_statement(interface, self);
+                return;
             }
         }
         visitor.pop_parent();
@@ -397,6 +422,65 @@ impl<'ast> Visitable<'ast> for ImportDeclaration<'ast> {
     }
 }
 
+impl<'ast> Visitable<'ast> for ExportDeclaration<'ast> {
+    type Parent = StatementNode<'ast>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        self.declaration.visit_with(visitor);
+    }
+}
+
+impl<'ast> Visitable<'ast> for ExportNamedDeclaration<'ast> {
+    type Parent = StatementNode<'ast>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        // Names re-exported from another module don't refer to local bindings
+        if self.source.is_some() {
+            return;
+        }
+
+        for specifier in &self.specifiers {
+            specifier.local.visit_with(visitor);
+        }
+    }
+}
+
+impl<'ast> Visitable<'ast> for ExportDefaultDeclaration<'ast> {
+    type Parent = StatementNode<'ast>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        match *self {
+            ExportDefaultDeclaration::Function(ref function) => {
+                if let Some(ref name) = function.name.0 {
+                    visitor.on_reference_declaration(&name.item);
+                }
+
+                function.visit_with(visitor);
+            },
+            ExportDefaultDeclaration::Class(ref class) => {
+                if let Some(ref name) = class.name.0 {
+                    visitor.on_reference_declaration(&name.item);
+                }
+
+                class.visit_with(visitor);
+            },
+            ExportDefaultDeclaration::Expression(ref value) => value.visit_with(visitor),
+        }
+    }
+}
+
 impl<'ast> Visitable<'ast> for ForImportSpecifier<'ast> {
     type Parent = Node<'ast, ForImportSpecifier<'ast>>;
 
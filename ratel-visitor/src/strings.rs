@@ -0,0 +1,205 @@
+use std::borrow::Cow;
+use std::char;
+
+use ratel::ast::expression::{TemplateLiteral, TaggedTemplateExpression};
+use ratel::ast::{ExpressionNode, Literal};
+use ratel::{Module, Span};
+
+use Visitor;
+use Visitable;
+
+pub type CowStr<'ast> = Cow<'ast, str>;
+
+/// How the contents of collected strings are reported.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StringForm {
+    /// As written in the source, escape sequences included
+    Raw,
+    /// With escape sequences replaced by the characters they stand for,
+    /// and line breaks within templates normalized to `\n`
+    Cooked,
+}
+
+/// Collect the contents of all string literals and template chunks in
+/// the module, in source order, along with the span of each token.
+pub fn collect_strings<'ast>(module: &'ast Module<'ast>, form: StringForm) -> Vec<(CowStr<'ast>, Span)> {
+    let mut visitor = StringCollector {
+        form,
+        strings: Vec::new(),
+    };
+
+    module.visit_with(&mut visitor);
+
+    // Template chunks are collected ahead of their expressions
+    visitor.strings.sort_by_key(|&(_, span)| span.start);
+    visitor.strings
+}
+
+struct StringCollector<'ast> {
+    form: StringForm,
+    strings: Vec<(CowStr<'ast>, Span)>,
+}
+
+impl<'ast> StringCollector<'ast> {
+    fn push(&mut self, raw: &'ast str, span: Span) {
+        let value = match self.form {
+            StringForm::Raw    => Cow::Borrowed(raw),
+            StringForm::Cooked => cook(raw),
+        };
+
+        self.strings.push((value, span));
+    }
+
+    fn push_template(&mut self, template: &TemplateLiteral<'ast>) {
+        for quasi in template.quasis.iter() {
+            self.push(quasi.item, quasi.span());
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for StringCollector<'ast> {
+    fn on_literal_expression(&mut self, item: &Literal<'ast>, node: &'ast ExpressionNode<'ast>) {
+        if let Literal::String(string) = *item {
+            self.push(&string[1..string.len() - 1], node.span());
+        }
+    }
+
+    fn on_template_literal(&mut self, item: &TemplateLiteral<'ast>, _: &'ast ExpressionNode<'ast>) {
+        self.push_template(item);
+    }
+
+    fn on_tagged_template_expression(&mut self, item: &TaggedTemplateExpression<'ast>, _: &'ast ExpressionNode<'ast>) {
+        self.push_template(&item.quasi);
+    }
+}
+
+/// Replace escape sequences in `raw` with the characters they stand for,
+/// and `\r\n` or `\r` line breaks with `\n`. Malformed escapes are kept as written.
+pub(crate) fn cook<'ast>(raw: &'ast str) -> CowStr<'ast> {
+    if !raw.contains(&['\\', '\r'][..]) {
+        return Cow::Borrowed(raw);
+    }
+
+    let mut cooked = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch == '\r' {
+            if chars.peek() == Some(&'\n') {
+                chars.next();
+            }
+
+            cooked.push('\n');
+            continue;
+        }
+
+        if ch != '\\' {
+            cooked.push(ch);
+            continue;
+        }
+
+        let escaped = match chars.next() {
+            Some(escaped) => escaped,
+            None => {
+                cooked.push('\\');
+                break;
+            }
+        };
+
+        match escaped {
+            'n' => cooked.push('\n'),
+            't' => cooked.push('\t'),
+            'r' => cooked.push('\r'),
+            'b' => cooked.push('\u{8}'),
+            'f' => cooked.push('\u{c}'),
+            'v' => cooked.push('\u{b}'),
+            '0' => cooked.push('\0'),
+
+            // Line continuation
+            '\n' | '\u{2028}' | '\u{2029}' => {},
+            '\r' => {
+                if chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+            },
+            'x' | 'u' => {
+                let digits: String = if escaped == 'u' && chars.peek() == Some(&'{') {
+                    chars.next();
+                    chars.by_ref().take_while(|&ch| ch != '}').collect()
+                } else {
+                    let len = if escaped == 'x' { 2 } else { 4 };
+                    chars.by_ref().take(len).collect()
+                };
+
+                match u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32) {
+                    Some(ch) => cooked.push(ch),
+                    None => {
+                        cooked.push('\\');
+                        cooked.push(escaped);
+                        cooked.push_str(&digits);
+                    }
+                }
+            },
+            _ => cooked.push(escaped),
+        }
+    }
+
+    Cow::Owned(cooked)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratel::parse;
+
+    fn strings(src: &str, form: StringForm) -> Vec<(String, Span)> {
+        let module = parse(src).unwrap();
+
+        collect_strings(&module, form).into_iter().map(|(string, span)| (string.into_owned(), span)).collect()
+    }
+
+    #[test]
+    fn collects_strings_and_templates() {
+        let src = r#"const a = "foo", b = `x${ 'bar' }y`; call('baz');"#;
+
+        assert_eq!(strings(src, StringForm::Raw), vec![
+            (String::from("foo"), Span::new(10, 15)),
+            (String::from("x"), Span::new(21, 25)),
+            (String::from("bar"), Span::new(26, 31)),
+            (String::from("y"), Span::new(32, 35)),
+            (String::from("baz"), Span::new(42, 47)),
+        ]);
+    }
+
+    #[test]
+    fn collects_tagged_templates() {
+        let src = "tag`a${b}c`";
+
+        let strings: Vec<String> = strings(src, StringForm::Raw).into_iter().map(|(string, _)| string).collect();
+
+        assert_eq!(strings, ["a", "c"]);
+    }
+
+    #[test]
+    fn raw_and_cooked() {
+        let src = r#"'a\nb'; "\x41\u0042\u{43}"; `\t${x}\\`; 'line\
+continued'"#;
+
+        let raw: Vec<String> = strings(src, StringForm::Raw).into_iter().map(|(string, _)| string).collect();
+        let cooked: Vec<String> = strings(src, StringForm::Cooked).into_iter().map(|(string, _)| string).collect();
+
+        assert_eq!(raw, ["a\\nb", "\\x41\\u0042\\u{43}", "\\t", "\\\\", "line\\\ncontinued"]);
+        assert_eq!(cooked, ["a\nb", "ABC", "\t", "\\", "linecontinued"]);
+    }
+
+    #[test]
+    fn crlf_line_breaks() {
+        let src = "`a\r\nb${c}\r\n`;\r\n'line\\\r\ncontinued'";
+
+        let raw: Vec<String> = strings(src, StringForm::Raw).into_iter().map(|(string, _)| string).collect();
+        let cooked: Vec<String> = strings(src, StringForm::Cooked).into_iter().map(|(string, _)| string).collect();
+
+        assert_eq!(raw, ["a\r\nb", "\r\n", "line\\\r\ncontinued"]);
+        assert_eq!(cooked, ["a\nb", "\n", "linecontinued"]);
+    }
+}
@@ -0,0 +1,175 @@
+use ratel::ast::{Expression, Statement, Node, Function, EmptyName};
+use ratel::ast::expression::PropertyKey;
+use ratel::ast::statement::ExportDefaultDeclaration;
+use ratel::{Module, Span};
+
+use {Visitor, Visitable, ParentNode, ScopeKind};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SymbolKind {
+    Function,
+    Class,
+    Method,
+    Arrow,
+}
+
+/// A function or class declared anywhere in a module.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SymbolInfo<'ast> {
+    /// Name of the declaration, `None` for anonymous functions and classes
+    /// as well as methods with computed keys
+    pub name: Option<&'ast str>,
+    pub kind: SymbolKind,
+    pub span: Span,
+    /// Index of the enclosing symbol, such as the class of a method
+    pub parent: Option<usize>,
+}
+
+/// List all functions, classes, methods and arrow functions of the module,
+/// nested ones included, in the order they appear in the source.
+pub fn symbols<'ast>(module: &'ast Module<'ast>) -> Vec<SymbolInfo<'ast>> {
+    let mut visitor = SymbolCollector::default();
+
+    module.visit_with(&mut visitor);
+
+    visitor.symbols
+}
+
+#[derive(Default)]
+struct SymbolCollector<'ast> {
+    symbols: Vec<SymbolInfo<'ast>>,
+    /// Index into `symbols` for frames that start a symbol
+    frames: Vec<Option<usize>>,
+    /// Method whose scope is about to be entered
+    pending: Option<(Span, Option<&'ast str>)>,
+}
+
+impl<'ast> SymbolCollector<'ast> {
+    fn open(&mut self, kind: SymbolKind, span: Span, name: Option<&'ast str>) {
+        let parent = self.frames.iter().rev().filter_map(|frame| *frame).next();
+
+        self.frames.push(Some(self.symbols.len()));
+        self.symbols.push(SymbolInfo {
+            name,
+            kind,
+            span,
+            parent,
+        });
+    }
+}
+
+impl<'ast> Visitor<'ast> for SymbolCollector<'ast> {
+    fn push_parent(&mut self, node: ParentNode<'ast>) {
+        match node {
+            ParentNode::Statement(statement) => match statement.item {
+                Statement::Function(ref function) => {
+                    self.open(SymbolKind::Function, statement.span(), Some(function.name.0.item))
+                },
+                Statement::Class(ref class) => {
+                    self.open(SymbolKind::Class, statement.span(), Some(class.name.0.item))
+                },
+                Statement::ExportDefault(ExportDefaultDeclaration::Function(function)) => {
+                    self.open(SymbolKind::Function, function.span(), function.name.0.map(|name| name.item))
+                },
+                Statement::ExportDefault(ExportDefaultDeclaration::Class(class)) => {
+                    self.open(SymbolKind::Class, class.span(), class.name.0.map(|name| name.item))
+                },
+                _ => self.frames.push(None),
+            },
+            ParentNode::Expression(expression) => match expression.item {
+                Expression::Function(ref function) => {
+                    self.open(SymbolKind::Function, expression.span(), function.name.0.map(|name| name.item))
+                },
+                Expression::Class(ref class) => {
+                    self.open(SymbolKind::Class, expression.span(), class.name.0.map(|name| name.item))
+                },
+                Expression::Arrow(_) => self.open(SymbolKind::Arrow, expression.span(), None),
+                _ => self.frames.push(None),
+            },
+        }
+    }
+
+    fn pop_parent(&mut self) {
+        self.frames.pop();
+    }
+
+    fn on_enter_scope(&mut self, kind: ScopeKind) {
+        match (kind, self.pending.take()) {
+            (ScopeKind::Function, Some((span, name))) => self.open(SymbolKind::Method, span, name),
+            _ => self.frames.push(None),
+        }
+    }
+
+    fn on_leave_scope(&mut self) {
+        self.frames.pop();
+    }
+
+    fn on_method(&mut self, key: &'ast Node<'ast, PropertyKey<'ast>>, value: &'ast Node<'ast, Function<'ast, EmptyName>>) {
+        let name = match key.item {
+            PropertyKey::Literal(name) => Some(name),
+            _ => None,
+        };
+
+        self.pending = Some((value.span(), name));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratel::parse;
+    use super::SymbolKind::*;
+
+    fn outline(src: &str) -> Vec<(Option<String>, SymbolKind, Option<usize>)> {
+        let module = parse(src).unwrap();
+
+        symbols(&module)
+            .into_iter()
+            .map(|symbol| (symbol.name.map(String::from), symbol.kind, symbol.parent))
+            .collect()
+    }
+
+    #[test]
+    fn lists_classes_methods_and_functions() {
+        let src = r#"
+            class Doge extends Animal {
+                constructor(name) {
+                    super(name);
+                }
+                bark() {
+                    return [1, 2].map((n) => n * 2);
+                }
+                static [key]() {}
+            }
+            function wow(a) {
+                function inner() {}
+                return { method() {}, other: function named() {} };
+            }
+        "#;
+
+        assert_eq!(outline(src), vec![
+            (Some(String::from("Doge")), Class, None),
+            (Some(String::from("constructor")), Method, Some(0)),
+            (Some(String::from("bark")), Method, Some(0)),
+            (None, Arrow, Some(2)),
+            (None, Method, Some(0)),
+            (Some(String::from("wow")), Function, None),
+            (Some(String::from("inner")), Function, Some(5)),
+            (Some(String::from("method")), Method, Some(5)),
+            (Some(String::from("named")), Function, Some(5)),
+        ]);
+    }
+
+    #[test]
+    fn records_spans() {
+        let src = "function foo() {} export default class {}";
+        let module = parse(src).unwrap();
+        let symbols = symbols(&module);
+
+        assert_eq!(symbols.len(), 2);
+        assert_eq!(symbols[0].span, Span::new(0, 17));
+        assert_eq!(symbols[1].name, None);
+        assert_eq!(symbols[1].kind, Class);
+        assert_eq!(&src[symbols[1].span.start as usize..symbols[1].span.end as usize], "class {}");
+    }
+}
@@ -0,0 +1,105 @@
+use ratel::ast::{Expression, ExpressionNode, Function, Name};
+
+use {Visitor, Visitable, ParentNode, ScopeKind};
+
+/// Check whether the body of the function references its `this` from
+/// within an arrow function, which captures `this` lexically. References
+/// made by nested regular functions belong to those functions instead.
+pub fn uses_lexical_this<'ast, N>(function: &'ast Function<'ast, N>) -> bool
+where
+    N: Name<'ast>,
+{
+    let mut visitor = LexicalThis::default();
+
+    function.params.visit_with(&mut visitor);
+    function.body.body.visit_with(&mut visitor);
+
+    visitor.found
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Frame {
+    Arrow,
+    Function,
+    Other,
+}
+
+#[derive(Default)]
+struct LexicalThis {
+    frames: Vec<Frame>,
+    found: bool,
+}
+
+impl<'ast> Visitor<'ast> for LexicalThis {
+    fn push_parent(&mut self, node: ParentNode<'ast>) {
+        self.frames.push(if is_arrow(node) { Frame::Arrow } else { Frame::Other });
+    }
+
+    fn pop_parent(&mut self) {
+        self.frames.pop();
+    }
+
+    fn on_enter_scope(&mut self, kind: ScopeKind) {
+        self.frames.push(match kind {
+            ScopeKind::Function => Frame::Function,
+            ScopeKind::Block    => Frame::Other,
+        });
+    }
+
+    fn on_leave_scope(&mut self) {
+        self.frames.pop();
+    }
+
+    fn on_this_expression(&mut self, _: &'ast ExpressionNode<'ast>) {
+        // `this` within a nested function belongs to that function, even if
+        // there is an arrow in between
+        if self.frames.contains(&Frame::Function) {
+            return;
+        }
+
+        if self.frames.contains(&Frame::Arrow) {
+            self.found = true;
+        }
+    }
+}
+
+#[inline]
+fn is_arrow(node: ParentNode) -> bool {
+    if let ParentNode::Expression(expression) = node {
+        if let Expression::Arrow(_) = expression.item {
+            return true;
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratel::parse;
+    use ratel::ast::Statement;
+
+    fn check(src: &str) -> bool {
+        let module = parse(src).unwrap();
+
+        match module.body().iter().next().map(|statement| &statement.item) {
+            Some(Statement::Function(function)) => uses_lexical_this(function),
+            _ => panic!("Expected a function declaration"),
+        }
+    }
+
+    #[test]
+    fn arrow_uses_outer_this() {
+        assert!(check("function f() { return () => this }"));
+        assert!(check("function f() { return () => () => this.x }"));
+        assert!(check("function f(a = () => this) {}"));
+    }
+
+    #[test]
+    fn nested_function_has_own_this() {
+        assert!(!check("function f() { return function () { return this } }"));
+        assert!(!check("function f() { return function () { return () => this } }"));
+        assert!(!check("function f() { return this }"));
+    }
+}
@@ -0,0 +1,96 @@
+use ratel::ast::{Expression, ExpressionNode, OperatorKind, StatementNode};
+use ratel::ast::expression::{PrefixExpression, ParenthesizedExpression};
+use ratel::ast::statement::ThrowStatement;
+use ratel::Module;
+
+use {Visitor, Visitable};
+
+/// Shape of the value thrown by a `throw` statement.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ThrowArgument {
+    /// `throw new Error()`
+    New,
+    /// `throw err`
+    Identifier,
+    /// `throw "message"`
+    Literal,
+    /// Anything else, such as `throw foo()`
+    Other,
+}
+
+impl ThrowArgument {
+    /// Classify the thrown expression, looking past any parentheses.
+    pub fn of(expression: ExpressionNode) -> Self {
+        match expression.item {
+            Expression::Prefix(PrefixExpression { operator: OperatorKind::New, .. }) => ThrowArgument::New,
+            Expression::Identifier(_) => ThrowArgument::Identifier,
+            Expression::Literal(_)    => ThrowArgument::Literal,
+            Expression::Parenthesized(ParenthesizedExpression { expression }) => ThrowArgument::of(expression),
+            _ => ThrowArgument::Other,
+        }
+    }
+}
+
+/// Collect all `throw` statements in the module, nested ones included,
+/// in source order, along with the shape of the value each one throws.
+pub fn throw_sites<'ast>(module: &'ast Module<'ast>) -> Vec<(&'ast StatementNode<'ast>, ThrowArgument)> {
+    let mut visitor = ThrowCollector::default();
+
+    module.visit_with(&mut visitor);
+
+    visitor.sites
+}
+
+#[derive(Default)]
+struct ThrowCollector<'ast> {
+    sites: Vec<(&'ast StatementNode<'ast>, ThrowArgument)>,
+}
+
+impl<'ast> Visitor<'ast> for ThrowCollector<'ast> {
+    fn on_throw_statement(&mut self, item: &ThrowStatement, node: &'ast StatementNode<'ast>) {
+        self.sites.push((node, ThrowArgument::of(item.value)));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratel::parse;
+
+    #[test]
+    fn classifies_throw_arguments() {
+        let src = r#"
+            function check(e) {
+                if (!e) throw new Error();
+                throw e;
+            }
+            try {
+                throw "msg";
+            } catch (_) {
+                throw (err);
+            }
+            throw e.cause;
+        "#;
+        let module = parse(src).unwrap();
+
+        let kinds: Vec<ThrowArgument> = throw_sites(&module).into_iter().map(|(_, kind)| kind).collect();
+
+        assert_eq!(kinds, [
+            ThrowArgument::New,
+            ThrowArgument::Identifier,
+            ThrowArgument::Literal,
+            ThrowArgument::Identifier,
+            ThrowArgument::Other,
+        ]);
+    }
+
+    #[test]
+    fn reports_throw_statements() {
+        let module = parse("foo(); throw 'a';").unwrap();
+
+        let sites = throw_sites(&module);
+
+        assert_eq!(sites.len(), 1);
+        assert_eq!(sites[0].0.span(), module.body().iter().nth(1).unwrap().span());
+    }
+}
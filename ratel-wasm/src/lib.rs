@@ -12,8 +12,8 @@ fn format_errors(errors: Vec<Error>, source: &str) -> String {
     .into_iter()
     .map(|err| {
         match err {
-            Error { start, end, .. } => {
-               ParseError::UnexpectedToken { start, end, source: source.to_string() }
+            Error { span, .. } => {
+               ParseError::UnexpectedToken { span, source: source.to_string() }
             }
         }
     })
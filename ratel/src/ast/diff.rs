@@ -0,0 +1,536 @@
+//! Structural comparison of two ASTs, ignoring source locations.
+
+use std::fmt;
+
+use module::Module;
+use ast::{Node, NodeList, Block, Pattern, Literal, OperatorKind, DeclarationKind};
+use ast::{Function, Class, ClassMember, MethodKind, Name, EmptyName, OptionalName, MandatoryName};
+use ast::{Type, Primitive, TypeMember, TypeParameter, Statement, Declarator};
+use ast::expression::*;
+use ast::statement::*;
+
+/// Single step on the way from the root of a module to one of its nodes.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PathSegment {
+    /// Named field of a node, `params`
+    Field(&'static str),
+
+    /// Kind of a node, `Function`
+    Variant(&'static str),
+
+    /// Position within a list, `[1]`
+    Index(usize),
+}
+
+/// Path to a node, such as `body[2].Function.params[1]`.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct DiffPath {
+    pub segments: Vec<PathSegment>,
+}
+
+impl fmt::Display for DiffPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, segment) in self.segments.iter().enumerate() {
+            match *segment {
+                PathSegment::Index(position) => write!(f, "[{}]", position)?,
+                PathSegment::Field(name) | PathSegment::Variant(name) => {
+                    if index != 0 {
+                        f.write_str(".")?;
+                    }
+
+                    f.write_str(name)?;
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Compare the bodies of two modules, ignoring spans. Returns the path
+/// to the first node that differs, or `None` if both are the same.
+pub fn ast_diff<'ast>(a: &Module<'ast>, b: &Module<'ast>) -> Option<DiffPath> {
+    let mut segments = Vec::new();
+
+    if field(&mut segments, PathSegment::Field("body"), &a.body(), &b.body()) {
+        Some(DiffPath { segments })
+    } else {
+        None
+    }
+}
+
+trait Diff {
+    /// Check whether `self` differs from `other`. If it does, segments
+    /// leading to the difference are left on the `path`.
+    fn diff(&self, other: &Self, path: &mut Vec<PathSegment>) -> bool;
+}
+
+#[inline]
+fn field<T: Diff>(path: &mut Vec<PathSegment>, segment: PathSegment, a: &T, b: &T) -> bool {
+    path.push(segment);
+
+    if a.diff(b, path) {
+        return true;
+    }
+
+    path.pop();
+
+    false
+}
+
+/// Nodes without any children, compared as a whole
+macro_rules! impl_leaf {
+    ($( $type:ty ),*) => ($(
+        impl<'ast> Diff for $type {
+            #[inline]
+            fn diff(&self, other: &Self, _: &mut Vec<PathSegment>) -> bool {
+                self != other
+            }
+        }
+    )*)
+}
+
+macro_rules! impl_struct {
+    ($( $type:ident { $( $field:ident ),* } )*) => ($(
+        impl<'ast> Diff for $type<'ast> {
+            fn diff(&self, other: &Self, path: &mut Vec<PathSegment>) -> bool {
+                $(
+                    field(path, PathSegment::Field(stringify!($field)), &self.$field, &other.$field) ||
+                )* false
+            }
+        }
+    )*)
+}
+
+/// Enums with only unit and single value variants
+macro_rules! impl_enum {
+    ($( $type:ident { $( $unit:ident ),* ; $( $variant:ident ),* } )*) => ($(
+        impl<'ast> Diff for $type<'ast> {
+            fn diff(&self, other: &Self, path: &mut Vec<PathSegment>) -> bool {
+                match (*self, *other) {
+                    $(
+                        ($type::$unit, $type::$unit) => false,
+                    )*
+                    $(
+                        ($type::$variant(ref a), $type::$variant(ref b)) => {
+                            field(path, PathSegment::Variant(stringify!($variant)), a, b)
+                        },
+                    )*
+                    _ => true,
+                }
+            }
+        }
+    )*)
+}
+
+impl_leaf! {
+    bool, &'ast str, Literal<'ast>, OperatorKind, DeclarationKind, MethodKind,
+    Primitive, TypeCastKind, ThisExpression, SuperExpression, EmptyName
+}
+
+impl<'ast, T: 'ast + Diff> Diff for Node<'ast, T> {
+    #[inline]
+    fn diff(&self, other: &Self, path: &mut Vec<PathSegment>) -> bool {
+        self.item.diff(&other.item, path)
+    }
+}
+
+impl<T: Diff> Diff for Option<T> {
+    #[inline]
+    fn diff(&self, other: &Self, path: &mut Vec<PathSegment>) -> bool {
+        match (self.as_ref(), other.as_ref()) {
+            (Some(a), Some(b)) => a.diff(b, path),
+            (None, None)       => false,
+            _                  => true,
+        }
+    }
+}
+
+impl<'ast, T: 'ast + Diff> Diff for NodeList<'ast, T> {
+    fn diff(&self, other: &Self, path: &mut Vec<PathSegment>) -> bool {
+        let mut a = self.iter();
+        let mut b = other.iter();
+        let mut index = 0;
+
+        loop {
+            match (a.next(), b.next()) {
+                (Some(a), Some(b)) => {
+                    if field(path, PathSegment::Index(index), a, b) {
+                        return true;
+                    }
+                },
+                (None, None) => return false,
+
+                // Point at the first element missing from the shorter list
+                _ => {
+                    path.push(PathSegment::Index(index));
+
+                    return true;
+                }
+            }
+
+            index += 1;
+        }
+    }
+}
+
+impl<'ast, T: 'ast + Diff> Diff for Block<'ast, T> {
+    #[inline]
+    fn diff(&self, other: &Self, path: &mut Vec<PathSegment>) -> bool {
+        field(path, PathSegment::Field("body"), &self.body, &other.body)
+    }
+}
+
+impl<'ast> Diff for MandatoryName<'ast> {
+    #[inline]
+    fn diff(&self, other: &Self, path: &mut Vec<PathSegment>) -> bool {
+        self.0.diff(&other.0, path)
+    }
+}
+
+impl<'ast> Diff for OptionalName<'ast> {
+    #[inline]
+    fn diff(&self, other: &Self, path: &mut Vec<PathSegment>) -> bool {
+        self.0.diff(&other.0, path)
+    }
+}
+
+impl<'ast, N: Name<'ast> + Diff> Diff for Function<'ast, N> {
+    fn diff(&self, other: &Self, path: &mut Vec<PathSegment>) -> bool {
+        field(path, PathSegment::Field("name"), &self.name, &other.name) ||
+        field(path, PathSegment::Field("generator"), &self.generator, &other.generator) ||
+        field(path, PathSegment::Field("is_async"), &self.is_async, &other.is_async) ||
+        field(path, PathSegment::Field("type_parameters"), &self.type_parameters, &other.type_parameters) ||
+        field(path, PathSegment::Field("params"), &self.params, &other.params) ||
+        field(path, PathSegment::Field("return_type"), &self.return_type, &other.return_type) ||
+        field(path, PathSegment::Field("body"), &self.body, &other.body)
+    }
+}
+
+impl<'ast, N: Name<'ast> + Diff> Diff for Class<'ast, N> {
+    fn diff(&self, other: &Self, path: &mut Vec<PathSegment>) -> bool {
+        field(path, PathSegment::Field("name"), &self.name, &other.name) ||
+        field(path, PathSegment::Field("extends"), &self.extends, &other.extends) ||
+        field(path, PathSegment::Field("body"), &self.body, &other.body)
+    }
+}
+
+impl_struct! {
+    SequenceExpression { body }
+    ArrayExpression { body }
+    MemberExpression { object, property }
+    MetaPropertyExpression { meta, property }
+    ComputedMemberExpression { object, property }
+    CallExpression { callee, arguments }
+    OptionalExpression { object, link }
+    BinaryExpression { operator, left, right }
+    PrefixExpression { operator, operand }
+    YieldExpression { delegate, argument }
+    AwaitExpression { argument }
+    PostfixExpression { operator, operand }
+    ConditionalExpression { test, consequent, alternate }
+    TemplateLiteral { expressions, quasis }
+    TaggedTemplateExpression { tag, quasi }
+    ParenthesizedExpression { expression }
+    SpreadExpression { argument }
+    ArrowExpression { params, return_type, body }
+    ObjectExpression { body }
+    JSXElement { name, attributes, children, self_closing }
+    JSXFragment { children }
+    TypeCastExpression { expression, kind, annotation }
+    InstantiationExpression { expression, arguments }
+
+    Declarator { id, init }
+    DeclarationStatement { kind, declarators }
+    ReturnStatement { value }
+    BreakStatement { label }
+    ContinueStatement { label }
+    ThrowStatement { value }
+    IfStatement { test, consequent, alternate }
+    WhileStatement { test, body }
+    DoStatement { body, test }
+    ForStatement { init, test, update, body }
+    ForInStatement { left, right, body }
+    ForOfStatement { left, right, body }
+    CatchClause { param, body }
+    TryStatement { block, handler, finalizer }
+    LabeledStatement { label, body }
+    SwitchStatement { discriminant, cases }
+    SwitchCase { test, consequent }
+    ImportDeclaration { specifiers, source }
+    ImportSpecifier { imported, local }
+    ImportDefaultSpecifier { local }
+    ImportNamespaceSpecifier { local }
+    ExportDeclaration { declaration }
+    ExportNamedDeclaration { specifiers, source }
+    ExportSpecifier { local, exported }
+    TypeAliasDeclaration { name, type_parameters, value }
+    InterfaceDeclaration { name, type_parameters, extends, body }
+
+    TypeMember { key, optional, value }
+    TypeParameter { name, constraint, default }
+}
+
+impl_enum! {
+    Expression {
+        Void;
+        This, Super, Identifier, Literal, Sequence, Array, Member, ComputedMember,
+        MetaProperty, Call, Optional, Binary, Prefix, Yield, Await, Postfix, Conditional,
+        Template, TaggedTemplate, Spread, Arrow, Object, Function, Class,
+        JSXElement, JSXFragment, TypeCast, Instantiation, Parenthesized
+    }
+    Statement {
+        Empty, Debugger;
+        Expression, Declaration, Return, Break, Continue, Throw, If, While,
+        Do, For, ForIn, ForOf, Try, Block, Labeled, Function, Class, Switch,
+        Import, Export, ExportNamed, ExportDefault, TypeAlias, Interface
+    }
+    PropertyKey {
+        ;
+        Computed, Literal, Binary
+    }
+    ExportDefaultDeclaration {
+        ;
+        Function, Class, Expression
+    }
+    OptionalLink {
+        ;
+        Member, ComputedMember, Call
+    }
+    ArrowBody {
+        ;
+        Expression, Block
+    }
+    JSXChild {
+        ;
+        Text, Expression, Element
+    }
+    ForInit {
+        ;
+        Declaration, Expression
+    }
+    ForImportSpecifier {
+        ;
+        ImportSpecifier, ImportDefaultSpecifier, ImportNamespaceSpecifier
+    }
+}
+
+impl<'ast> Diff for Pattern<'ast> {
+    fn diff(&self, other: &Self, path: &mut Vec<PathSegment>) -> bool {
+        use self::Pattern::*;
+
+        match (*self, *other) {
+            (Void, Void) => false,
+            (Identifier(ref a), Identifier(ref b)) => {
+                field(path, PathSegment::Variant("Identifier"), a, b)
+            },
+            (ObjectPattern { properties: ref a }, ObjectPattern { properties: ref b }) => {
+                path.push(PathSegment::Variant("ObjectPattern"));
+                field(path, PathSegment::Field("properties"), a, b) || { path.pop(); false }
+            },
+            (ArrayPattern { elements: ref a }, ArrayPattern { elements: ref b }) => {
+                path.push(PathSegment::Variant("ArrayPattern"));
+                field(path, PathSegment::Field("elements"), a, b) || { path.pop(); false }
+            },
+            (RestElement { argument: ref a }, RestElement { argument: ref b }) => {
+                path.push(PathSegment::Variant("RestElement"));
+                field(path, PathSegment::Field("argument"), a, b) || { path.pop(); false }
+            },
+            (AssignmentPattern { left: ref a_left, right: ref a_right },
+             AssignmentPattern { left: ref b_left, right: ref b_right }) => {
+                path.push(PathSegment::Variant("AssignmentPattern"));
+                field(path, PathSegment::Field("left"), a_left, b_left) ||
+                field(path, PathSegment::Field("right"), a_right, b_right) ||
+                { path.pop(); false }
+            },
+            (Annotated { pattern: ref a_pattern, optional: ref a_optional, annotation: ref a_annotation },
+             Annotated { pattern: ref b_pattern, optional: ref b_optional, annotation: ref b_annotation }) => {
+                path.push(PathSegment::Variant("Annotated"));
+                field(path, PathSegment::Field("pattern"), a_pattern, b_pattern) ||
+                field(path, PathSegment::Field("optional"), a_optional, b_optional) ||
+                field(path, PathSegment::Field("annotation"), a_annotation, b_annotation) ||
+                { path.pop(); false }
+            },
+            _ => true,
+        }
+    }
+}
+
+impl<'ast> Diff for Property<'ast> {
+    fn diff(&self, other: &Self, path: &mut Vec<PathSegment>) -> bool {
+        use self::Property::*;
+
+        match (*self, *other) {
+            (Shorthand(ref a), Shorthand(ref b)) => {
+                field(path, PathSegment::Variant("Shorthand"), a, b)
+            },
+            (Literal { key: ref a_key, value: ref a_value }, Literal { key: ref b_key, value: ref b_value }) => {
+                path.push(PathSegment::Variant("Literal"));
+                field(path, PathSegment::Field("key"), a_key, b_key) ||
+                field(path, PathSegment::Field("value"), a_value, b_value) ||
+                { path.pop(); false }
+            },
+            (Method { key: ref a_key, kind: ref a_kind, value: ref a_value }, Method { key: ref b_key, kind: ref b_kind, value: ref b_value }) => {
+                path.push(PathSegment::Variant("Method"));
+                field(path, PathSegment::Field("key"), a_key, b_key) ||
+                field(path, PathSegment::Field("kind"), a_kind, b_kind) ||
+                field(path, PathSegment::Field("value"), a_value, b_value) ||
+                { path.pop(); false }
+            },
+            (Spread { argument: ref a }, Spread { argument: ref b }) => {
+                path.push(PathSegment::Variant("Spread"));
+                field(path, PathSegment::Field("argument"), a, b) || { path.pop(); false }
+            },
+            _ => true,
+        }
+    }
+}
+
+impl<'ast> Diff for ClassMember<'ast> {
+    fn diff(&self, other: &Self, path: &mut Vec<PathSegment>) -> bool {
+        use self::ClassMember::*;
+
+        match (*self, *other) {
+            (Error, Error) => false,
+            (Method { is_static: ref a_static, key: ref a_key, kind: ref a_kind, value: ref a_value },
+             Method { is_static: ref b_static, key: ref b_key, kind: ref b_kind, value: ref b_value }) => {
+                path.push(PathSegment::Variant("Method"));
+                field(path, PathSegment::Field("is_static"), a_static, b_static) ||
+                field(path, PathSegment::Field("key"), a_key, b_key) ||
+                field(path, PathSegment::Field("kind"), a_kind, b_kind) ||
+                field(path, PathSegment::Field("value"), a_value, b_value) ||
+                { path.pop(); false }
+            },
+            (Literal { is_static: ref a_static, key: ref a_key, value: ref a_value },
+             Literal { is_static: ref b_static, key: ref b_key, value: ref b_value }) => {
+                path.push(PathSegment::Variant("Literal"));
+                field(path, PathSegment::Field("is_static"), a_static, b_static) ||
+                field(path, PathSegment::Field("key"), a_key, b_key) ||
+                field(path, PathSegment::Field("value"), a_value, b_value) ||
+                { path.pop(); false }
+            },
+            _ => true,
+        }
+    }
+}
+
+impl<'ast> Diff for JSXAttribute<'ast> {
+    fn diff(&self, other: &Self, path: &mut Vec<PathSegment>) -> bool {
+        use self::JSXAttribute::*;
+
+        match (*self, *other) {
+            (Attribute { name: ref a_name, value: ref a_value }, Attribute { name: ref b_name, value: ref b_value }) => {
+                path.push(PathSegment::Variant("Attribute"));
+                field(path, PathSegment::Field("name"), a_name, b_name) ||
+                field(path, PathSegment::Field("value"), a_value, b_value) ||
+                { path.pop(); false }
+            },
+            (Spread { argument: ref a }, Spread { argument: ref b }) => {
+                path.push(PathSegment::Variant("Spread"));
+                field(path, PathSegment::Field("argument"), a, b) || { path.pop(); false }
+            },
+            _ => true,
+        }
+    }
+}
+
+impl<'ast> Diff for Type<'ast> {
+    fn diff(&self, other: &Self, path: &mut Vec<PathSegment>) -> bool {
+        use self::Type::*;
+
+        match (*self, *other) {
+            (Any, Any) => false,
+            (Primitive(ref a), Primitive(ref b)) => {
+                field(path, PathSegment::Variant("Primitive"), a, b)
+            },
+            (Identifier(ref a), Identifier(ref b)) => {
+                field(path, PathSegment::Variant("Identifier"), a, b)
+            },
+            (Literal(ref a), Literal(ref b)) => {
+                field(path, PathSegment::Variant("Literal"), a, b)
+            },
+            (Union { variants: ref a }, Union { variants: ref b }) => {
+                path.push(PathSegment::Variant("Union"));
+                field(path, PathSegment::Field("variants"), a, b) || { path.pop(); false }
+            },
+            (Intersection { variants: ref a }, Intersection { variants: ref b }) => {
+                path.push(PathSegment::Variant("Intersection"));
+                field(path, PathSegment::Field("variants"), a, b) || { path.pop(); false }
+            },
+            (Generic { ident: ref a_ident, subtypes: ref a_subtypes }, Generic { ident: ref b_ident, subtypes: ref b_subtypes }) => {
+                path.push(PathSegment::Variant("Generic"));
+                field(path, PathSegment::Field("ident"), a_ident, b_ident) ||
+                field(path, PathSegment::Field("subtypes"), a_subtypes, b_subtypes) ||
+                { path.pop(); false }
+            },
+            (Array { element: ref a }, Array { element: ref b }) => {
+                path.push(PathSegment::Variant("Array"));
+                field(path, PathSegment::Field("element"), a, b) || { path.pop(); false }
+            },
+            (Tuple { elements: ref a }, Tuple { elements: ref b }) => {
+                path.push(PathSegment::Variant("Tuple"));
+                field(path, PathSegment::Field("elements"), a, b) || { path.pop(); false }
+            },
+            (Function { params: ref a_params, returns: ref a_returns }, Function { params: ref b_params, returns: ref b_returns }) => {
+                path.push(PathSegment::Variant("Function"));
+                field(path, PathSegment::Field("params"), a_params, b_params) ||
+                field(path, PathSegment::Field("returns"), a_returns, b_returns) ||
+                { path.pop(); false }
+            },
+            (Object { members: ref a }, Object { members: ref b }) => {
+                path.push(PathSegment::Variant("Object"));
+                field(path, PathSegment::Field("members"), a, b) || { path.pop(); false }
+            },
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use parser::parse;
+
+    fn diff(a: &str, b: &str) -> Option<String> {
+        ast_diff(&parse(a).unwrap(), &parse(b).unwrap()).map(|path| path.to_string())
+    }
+
+    #[test]
+    fn identical_modules() {
+        assert_eq!(diff("", ""), None);
+        assert_eq!(diff("function f(a, b) { return a + b }", "function f(a, b) { return a + b }"), None);
+    }
+
+    #[test]
+    fn spans_are_ignored() {
+        assert_eq!(diff("let  a=1 ;\nfoo( a )", "let a = 1; foo(a);"), None);
+    }
+
+    #[test]
+    fn nested_difference() {
+        assert_eq!(
+            diff("a; b; function f(a, b) {}", "a; b; function f(a, c) {}"),
+            Some(String::from("body[2].Function.params[1].Identifier"))
+        );
+        assert_eq!(
+            diff("let { a: [x, y] } = b", "let { a: [x, z] } = b"),
+            Some(String::from("body[0].Declaration.declarators[0].id.ObjectPattern.properties[0].Literal.value.Array.body[1].Identifier"))
+        );
+        assert_eq!(
+            diff("class A { m() { return x } }", "class A { m() { return y } }"),
+            Some(String::from("body[0].Class.body.body[0].Method.value.body.body[0].Return.value.Identifier"))
+        );
+    }
+
+    #[test]
+    fn different_kinds() {
+        assert_eq!(diff("a + b", "a - b"), Some(String::from("body[0].Expression.Binary.operator")));
+        assert_eq!(diff("a; b", "a; 1"), Some(String::from("body[1].Expression")));
+    }
+
+    #[test]
+    fn different_lengths() {
+        assert_eq!(diff("a; b", "a"), Some(String::from("body[1]")));
+        assert_eq!(diff("f(a)", "f(a, b)"), Some(String::from("body[0].Expression.Call.arguments[1]")));
+    }
+}
@@ -1,5 +1,6 @@
-use ast::{Node, NodeList, Literal, OperatorKind, Function, Class, EmptyName, OptionalName};
+use ast::{Node, NodeList, Literal, OperatorKind, Function, Class, EmptyName, OptionalName, MethodKind};
 use ast::{Identifier, IdentifierNode, BlockNode, ExpressionNode, Statement, ExpressionList, Pattern};
+use ast::{TypeNode, TypeList};
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum PropertyKey<'ast> {
@@ -8,6 +9,17 @@ pub enum PropertyKey<'ast> {
     Binary(&'ast str),
 }
 
+impl<'ast> PropertyKey<'ast> {
+    /// Whether the key is an expression in brackets, `[a]`.
+    #[inline]
+    pub fn is_computed(&self) -> bool {
+        match *self {
+            PropertyKey::Computed(_) => true,
+            _                        => false,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Property<'ast> {
     Shorthand(&'ast str),
@@ -15,8 +27,10 @@ pub enum Property<'ast> {
         key: Node<'ast, PropertyKey<'ast>>,
         value: ExpressionNode<'ast>,
     },
+    /// Plain methods as well as `get` and `set` accessors, never `Constructor`
     Method {
         key: Node<'ast, PropertyKey<'ast>>,
+        kind: MethodKind,
         value: Node<'ast, Function<'ast, EmptyName>>,
     },
     Spread {
@@ -29,16 +43,43 @@ pub enum Property<'ast> {
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct ThisExpression;
 
+/// `super` as the callee of a call or the object of a member expression,
+/// the parser rejects it anywhere else.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SuperExpression;
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct SequenceExpression<'ast> {
     pub body: ExpressionList<'ast>
 }
 
+/// Holes are kept in `body` as `Expression::Void`, along with one more
+/// following a trailing comma, so `[a,]` has two entries. Use `elements`
+/// to get the elements as written.
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct ArrayExpression<'ast> {
     pub body: ExpressionList<'ast>
 }
 
+impl<'ast> ArrayExpression<'ast> {
+    /// Iterate over the elements of the array, `None` being a hole, as in
+    /// `[, a]`. Unlike `void 0` or `undefined`, holes don't set an element
+    /// at all. A trailing comma doesn't add a hole, `[a,]` has one element
+    /// while `[a,,]` has two.
+    pub fn elements(&self) -> impl Iterator<Item = Option<&'ast ExpressionNode<'ast>>> {
+        let trailing = match self.body.iter().last() {
+            Some(node) if node.item == Expression::Void => 1,
+            _                                           => 0,
+        };
+        let len = self.body.iter().count() - trailing;
+
+        self.body.iter().take(len).map(|node| match node.item {
+            Expression::Void => None,
+            _                => Some(node),
+        })
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct MemberExpression<'ast> {
     pub object: ExpressionNode<'ast>,
@@ -63,6 +104,39 @@ pub struct CallExpression<'ast> {
     pub arguments: ExpressionList<'ast>,
 }
 
+/// The part of an optional chain link following the `?.` operator.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum OptionalLink<'ast> {
+    /// `a?.b`
+    Member(IdentifierNode<'ast>),
+    /// `a?.[b]`
+    ComputedMember(ExpressionNode<'ast>),
+    /// `a?.(b)`
+    Call(ExpressionList<'ast>),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct OptionalExpression<'ast> {
+    pub object: ExpressionNode<'ast>,
+    pub link: OptionalLink<'ast>,
+}
+
+/// Optional chain flattened into its links, see `Expression::optional_chain`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct OptionalChain<'ast> {
+    /// Expression the chain starts from, `a` in `a.b?.c`
+    pub object: ExpressionNode<'ast>,
+    pub links: Vec<ChainLink<'ast>>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ChainLink<'ast> {
+    pub link: OptionalLink<'ast>,
+    /// Link follows `?.`, with everything after it up to the end of the
+    /// chain skipped when the value before it is `null` or `undefined`
+    pub optional: bool,
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct BinaryExpression<'ast> {
     pub operator: OperatorKind,
@@ -76,6 +150,20 @@ pub struct PrefixExpression<'ast> {
     pub operand: ExpressionNode<'ast>,
 }
 
+/// `yield a` and `yield* a` within generators, the argument being optional
+/// unless delegating.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct YieldExpression<'ast> {
+    pub delegate: bool,
+    pub argument: Option<ExpressionNode<'ast>>,
+}
+
+/// `await a` within async functions.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct AwaitExpression<'ast> {
+    pub argument: ExpressionNode<'ast>,
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct PostfixExpression<'ast> {
     pub operator: OperatorKind,
@@ -101,6 +189,12 @@ pub struct TaggedTemplateExpression<'ast> {
     pub quasi: Node<'ast, TemplateLiteral<'ast>>
 }
 
+/// Only produced with `ParseOptions::preserve_parens`, `(expression)`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ParenthesizedExpression<'ast> {
+    pub expression: ExpressionNode<'ast>,
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct SpreadExpression<'ast> {
     pub argument: ExpressionNode<'ast>
@@ -115,14 +209,86 @@ pub enum ArrowBody<'ast> {
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct ArrowExpression<'ast> {
     pub params: NodeList<'ast, Pattern<'ast>>,
+    /// TypeScript only, `(): T => ...`
+    pub return_type: Option<TypeNode<'ast>>,
     pub body: ArrowBody<'ast>,
 }
 
+impl<'ast> ArrowBody<'ast> {
+    /// Offset where the body, and so the whole arrow, ends.
+    #[inline]
+    pub fn end(&self) -> u32 {
+        match *self {
+            ArrowBody::Expression(expression) => expression.end,
+            ArrowBody::Block(block)           => block.end,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct ObjectExpression<'ast> {
     pub body: NodeList<'ast, Property<'ast>>,
 }
 
+/// Attribute of a JSX element.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum JSXAttribute<'ast> {
+    /// `name`, `name="value"`, `name={value}` or `name=<element />`
+    Attribute {
+        name: IdentifierNode<'ast>,
+        value: Option<ExpressionNode<'ast>>,
+    },
+    /// `{...argument}`
+    Spread {
+        argument: ExpressionNode<'ast>,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum JSXChild<'ast> {
+    /// Raw text between tags, whitespace included
+    Text(&'ast str),
+    /// `{expression}`, `Expression::Void` for an empty `{}`
+    Expression(ExpressionNode<'ast>),
+    /// Nested `JSXElement` or `JSXFragment`
+    Element(ExpressionNode<'ast>),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct JSXElement<'ast> {
+    /// Either an `Identifier` or a `MemberExpression`, as in `<a.b />`
+    pub name: ExpressionNode<'ast>,
+    pub attributes: NodeList<'ast, JSXAttribute<'ast>>,
+    pub children: NodeList<'ast, JSXChild<'ast>>,
+    pub self_closing: bool,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct JSXFragment<'ast> {
+    pub children: NodeList<'ast, JSXChild<'ast>>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TypeCastKind {
+    As,
+    Satisfies,
+}
+
+/// TypeScript only, `expression as T` or `expression satisfies T`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TypeCastExpression<'ast> {
+    pub expression: ExpressionNode<'ast>,
+    pub kind: TypeCastKind,
+    pub annotation: TypeNode<'ast>,
+}
+
+/// TypeScript only, explicit type arguments such as `foo<T>` in `foo<T>(x)`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct InstantiationExpression<'ast> {
+    pub expression: ExpressionNode<'ast>,
+    pub arguments: TypeList<'ast>,
+}
+
 pub type FunctionExpression<'ast> = Function<'ast, OptionalName<'ast>>;
 pub type ClassExpression<'ast> = Class<'ast, OptionalName<'ast>>;
 
@@ -130,6 +296,7 @@ pub type ClassExpression<'ast> = Class<'ast, OptionalName<'ast>>;
 pub enum Expression<'ast> {
     Void,
     This(ThisExpression),
+    Super(SuperExpression),
     Identifier(Identifier<'ast>),
     Literal(Literal<'ast>),
     Sequence(SequenceExpression<'ast>),
@@ -138,8 +305,11 @@ pub enum Expression<'ast> {
     ComputedMember(ComputedMemberExpression<'ast>),
     MetaProperty(MetaPropertyExpression<'ast>),
     Call(CallExpression<'ast>),
+    Optional(OptionalExpression<'ast>),
     Binary(BinaryExpression<'ast>),
     Prefix(PrefixExpression<'ast>),
+    Yield(YieldExpression<'ast>),
+    Await(AwaitExpression<'ast>),
     Postfix(PostfixExpression<'ast>),
     Conditional(ConditionalExpression<'ast>),
     Template(TemplateLiteral<'ast>),
@@ -149,6 +319,11 @@ pub enum Expression<'ast> {
     Object(ObjectExpression<'ast>),
     Function(FunctionExpression<'ast>),
     Class(ClassExpression<'ast>),
+    JSXElement(JSXElement<'ast>),
+    JSXFragment(JSXFragment<'ast>),
+    TypeCast(TypeCastExpression<'ast>),
+    Instantiation(InstantiationExpression<'ast>),
+    Parenthesized(ParenthesizedExpression<'ast>),
 }
 
 macro_rules! impl_from {
@@ -164,6 +339,7 @@ macro_rules! impl_from {
 
 impl_from! {
     ThisExpression => This,
+    SuperExpression => Super,
     Identifier<'ast> => Identifier,
     Literal<'ast> => Literal,
     SequenceExpression<'ast> => Sequence,
@@ -172,8 +348,11 @@ impl_from! {
     ComputedMemberExpression<'ast> => ComputedMember,
     MetaPropertyExpression<'ast> => MetaProperty,
     CallExpression<'ast> => Call,
+    OptionalExpression<'ast> => Optional,
     BinaryExpression<'ast> => Binary,
     PrefixExpression<'ast> => Prefix,
+    YieldExpression<'ast> => Yield,
+    AwaitExpression<'ast> => Await,
     PostfixExpression<'ast> => Postfix,
     ConditionalExpression<'ast> => Conditional,
     TemplateLiteral<'ast> => Template,
@@ -182,7 +361,12 @@ impl_from! {
     ArrowExpression<'ast> => Arrow,
     ObjectExpression<'ast> => Object,
     FunctionExpression<'ast> => Function,
-    ClassExpression<'ast> => Class
+    ClassExpression<'ast> => Class,
+    JSXElement<'ast> => JSXElement,
+    JSXFragment<'ast> => JSXFragment,
+    TypeCastExpression<'ast> => TypeCast,
+    InstantiationExpression<'ast> => Instantiation,
+    ParenthesizedExpression<'ast> => Parenthesized
 }
 
 impl<'ast> Expression<'ast> {
@@ -191,24 +375,92 @@ impl<'ast> Expression<'ast> {
         use self::Expression::*;
 
         match *self {
-            Member(_) | MetaProperty(_) | Arrow(_) => 18,
+            Member(_) | Optional(_) | MetaProperty(_) | Arrow(_) | Instantiation(_) => 18,
 
             Call(_) => 17,
 
-            Prefix(_) => 15,
+            Prefix(_) | Await(_) => 15,
 
             Binary(BinaryExpression { ref operator, .. })   |
             Postfix(PostfixExpression { ref operator, .. }) => operator.binding_power(),
 
+            TypeCast(_) => 11,
+
             Conditional(_) => 4,
 
+            Yield(_) => 2,
+
             Sequence(_) => 0,
 
             _  => 100,
         }
     }
 
+    /// Check whether the expression is a link of an optional chain, such as `a?.b.c`.
     #[inline]
+    pub fn is_optional_chain(&self) -> bool {
+        use self::Expression::*;
+
+        match *self {
+            Optional(_) => true,
+
+            Member(MemberExpression { ref object, .. })                 |
+            ComputedMember(ComputedMemberExpression { ref object, .. }) |
+            Call(CallExpression { callee: ref object, .. })             => object.is_optional_chain(),
+
+            _ => false,
+        }
+    }
+
+    /// Flatten an optional chain into the object it starts from and its
+    /// links in source order, `None` if the expression isn't a chain. The
+    /// chain ends with this expression, so calling this on the outermost
+    /// link gives the whole chain. Links up to the first optional one are
+    /// always evaluated, each optional link short-circuits all that follow
+    /// it, as `.b.c` in `a?.b.c`. Parentheses end a chain, `(a?.b).c` isn't
+    /// one, only `a?.b` within it.
+    pub fn optional_chain(&self) -> Option<OptionalChain<'ast>> {
+        use self::Expression::*;
+
+        if !self.is_optional_chain() {
+            return None;
+        }
+
+        let mut links = Vec::new();
+        let mut expression = *self;
+
+        loop {
+            let (object, link, optional) = match expression {
+                Member(MemberExpression { object, property })                 => (object, OptionalLink::Member(property), false),
+                ComputedMember(ComputedMemberExpression { object, property }) => (object, OptionalLink::ComputedMember(property), false),
+                Call(CallExpression { callee, arguments })                    => (callee, OptionalLink::Call(arguments), false),
+                Optional(OptionalExpression { object, link })                 => (object, link, true),
+                _ => unreachable!("Links of an optional chain are members or calls"),
+            };
+
+            links.push(ChainLink {
+                link,
+                optional,
+            });
+
+            match object.item {
+                Member(_) | ComputedMember(_) | Call(_) | Optional(_) => expression = object.item,
+                _ => {
+                    links.reverse();
+
+                    return Some(OptionalChain {
+                        object,
+                        links,
+                    });
+                },
+            }
+        }
+    }
+
+    /// Check whether the expression can start a statement without being
+    /// wrapped in parentheses. Objects, functions and classes can't, as `{`,
+    /// `function` and `class` would start a block or a declaration instead,
+    /// and neither can expressions starting with one, such as `({}).x`.
     pub fn is_allowed_as_bare_statement(&self) -> bool {
         use self::Expression::*;
 
@@ -216,7 +468,24 @@ impl<'ast> Expression<'ast> {
             Object(_)   |
             Function(_) |
             Class(_)    => false,
-            _           => true,
+
+            Member(MemberExpression { ref object, .. })                 |
+            ComputedMember(ComputedMemberExpression { ref object, .. }) |
+            Optional(OptionalExpression { ref object, .. })             |
+            Call(CallExpression { callee: ref object, .. })             |
+            TaggedTemplate(TaggedTemplateExpression { tag: ref object, .. }) |
+            Binary(BinaryExpression { left: ref object, .. })           |
+            Postfix(PostfixExpression { operand: ref object, .. })      |
+            Conditional(ConditionalExpression { test: ref object, .. }) |
+            TypeCast(TypeCastExpression { expression: ref object, .. }) |
+            Instantiation(InstantiationExpression { expression: ref object, .. }) => object.is_allowed_as_bare_statement(),
+
+            Sequence(SequenceExpression { ref body }) => match body.iter().next() {
+                Some(first) => first.is_allowed_as_bare_statement(),
+                None        => true,
+            },
+
+            _ => true,
         }
     }
 
@@ -225,12 +494,27 @@ impl<'ast> Expression<'ast> {
         use self::Expression::*;
 
         match *self {
-            Identifier(_)     |
+            // `a?.b = c` and `a?.b.c = d` are not allowed
             Member(_)         |
-            ComputedMember(_) |
+            ComputedMember(_) => !self.is_optional_chain(),
+
+            Identifier(_)     |
             Object(_)         |
             Array(_)          |
             Spread(_)         => true,
+
+            // Kept as a literal, but `undefined` is a global like `NaN`,
+            // so `undefined = a` is syntactically fine
+            Literal(::ast::Literal::Undefined) => true,
+
+            // `(a) = b` is fine, but `({ a }) = b` is not
+            Parenthesized(ParenthesizedExpression { ref expression }) => matches!(
+                expression.item,
+                Identifier(_)     |
+                Member(_)         |
+                ComputedMember(_) |
+                Literal(::ast::Literal::Undefined)
+            ),
             _ => false,
         }
     }
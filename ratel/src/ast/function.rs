@@ -1,5 +1,7 @@
 use ast::{Node, Loc, IdentifierNode, ExpressionNode};
-use ast::{BlockNode, Statement, PatternList, PropertyKey};
+use ast::{BlockNode, Statement, PatternList, PropertyKey, TypeNode, TypeParameterList};
+use ast::{Pattern, Property, Expression, NodeList, OperatorKind};
+use ast::expression::{BinaryExpression, ObjectExpression, ArrayExpression, SpreadExpression};
 
 pub trait Name<'ast>: Copy {
     fn empty() -> Self;
@@ -66,10 +68,99 @@ impl<'ast> From<Option<IdentifierNode<'ast>>> for OptionalName<'ast> {
 pub struct Function<'ast, N: Name<'ast>> {
     pub name: N,
     pub generator: bool,
+    pub is_async: bool,
+    /// TypeScript only, `<T>` following the name
+    pub type_parameters: TypeParameterList<'ast>,
     pub params: PatternList<'ast>,
+    /// TypeScript only, `: T` following the parameters
+    pub return_type: Option<TypeNode<'ast>>,
     pub body: BlockNode<'ast, Statement<'ast>>,
 }
 
+/// Name bound by a parameter, see `Function::parameter_names`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ParameterName<'ast> {
+    pub name: &'ast str,
+    /// Position of the parameter the name belongs to
+    pub index: usize,
+    /// The parameter has a default value, `a = 1` or `{ a } = {}`
+    pub default: bool,
+    /// The parameter is the rest parameter, `...a`
+    pub rest: bool,
+}
+
+impl<'ast, N: Name<'ast>> Function<'ast, N> {
+    /// Names bound by the parameters, in order, with destructuring flattened.
+    /// For `function f({ a, b: c } = {}, ...rest)` these are `a` and `c`,
+    /// both with a default, followed by the rest parameter `rest`.
+    pub fn parameter_names(&self) -> Vec<ParameterName<'ast>> {
+        let mut result = Vec::new();
+
+        for (index, param) in self.params.iter().enumerate() {
+            let mut names = Vec::new();
+
+            pattern_names(&param.item, &mut names);
+
+            let (default, rest) = match param.item {
+                Pattern::AssignmentPattern { .. } => (true, false),
+                Pattern::RestElement { .. }       => (false, true),
+                Pattern::Annotated { pattern, .. } => match pattern.item {
+                    Pattern::AssignmentPattern { .. } => (true, false),
+                    _                                 => (false, false),
+                },
+                _ => (false, false),
+            };
+
+            result.extend(names.into_iter().map(|name| ParameterName { name, index, default, rest }));
+        }
+
+        result
+    }
+}
+
+fn pattern_names<'ast>(pattern: &Pattern<'ast>, names: &mut Vec<&'ast str>) {
+    match *pattern {
+        Pattern::Void => {},
+        Pattern::Identifier(name) => names.push(name),
+        Pattern::ObjectPattern { properties } => property_names(properties, names),
+        Pattern::ArrayPattern { elements } => {
+            for element in elements.iter() {
+                pattern_names(element, names);
+            }
+        },
+        Pattern::RestElement { argument } => names.push(argument.item),
+        Pattern::AssignmentPattern { left, .. } => pattern_names(&left, names),
+        Pattern::Annotated { pattern, .. } => pattern_names(&pattern, names),
+    }
+}
+
+fn property_names<'ast>(properties: NodeList<'ast, Property<'ast>>, names: &mut Vec<&'ast str>) {
+    for property in properties.iter() {
+        match property.item {
+            Property::Shorthand(name) => names.push(name),
+            Property::Literal { value, .. } => expression_names(&value, names),
+            Property::Spread { argument } => expression_names(&argument, names),
+            Property::Method { .. } => {},
+        }
+    }
+}
+
+/// Nested patterns within object patterns are kept as expressions.
+fn expression_names<'ast>(expression: &Expression<'ast>, names: &mut Vec<&'ast str>) {
+    match *expression {
+        Expression::Identifier(name) => names.push(name),
+        Expression::Binary(BinaryExpression { operator: OperatorKind::Assign, left, .. }) => expression_names(&left, names),
+        Expression::Spread(SpreadExpression { argument }) => expression_names(&argument, names),
+        Expression::Object(ObjectExpression { body }) => property_names(body, names),
+        Expression::Array(ArrayExpression { body }) => {
+            for element in body.iter() {
+                expression_names(element, names);
+            }
+        },
+        _ => {},
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum MethodKind {
     Constructor,
@@ -9,3 +9,145 @@ pub enum Literal<'ast> {
     String(&'ast str),
     RegEx(&'ast str),
 }
+
+/// Flags of a regular expression literal, such as `gi` in `/x/gi`.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct RegexFlags {
+    /// `d`
+    pub has_indices: bool,
+    /// `g`
+    pub global: bool,
+    /// `i`
+    pub ignore_case: bool,
+    /// `m`
+    pub multiline: bool,
+    /// `s`
+    pub dot_all: bool,
+    /// `u`
+    pub unicode: bool,
+    /// `v`
+    pub unicode_sets: bool,
+    /// `y`
+    pub sticky: bool,
+}
+
+impl<'ast> Literal<'ast> {
+    /// Flags of a regular expression literal. Returns `None` for other
+    /// literals, or if the flags are unknown or repeated.
+    pub fn regex_flags(&self) -> Option<RegexFlags> {
+        let regex = match *self {
+            Literal::RegEx(regex) => regex,
+            _ => return None,
+        };
+
+        // Pattern can contain slashes, flags never do
+        let flags = &regex[regex.rfind('/')? + 1..];
+        let mut result = RegexFlags::default();
+
+        for flag in flags.chars() {
+            let slot = match flag {
+                'd' => &mut result.has_indices,
+                'g' => &mut result.global,
+                'i' => &mut result.ignore_case,
+                'm' => &mut result.multiline,
+                's' => &mut result.dot_all,
+                'u' => &mut result.unicode,
+                'v' => &mut result.unicode_sets,
+                'y' => &mut result.sticky,
+                _   => return None,
+            };
+
+            if *slot {
+                return None;
+            }
+
+            *slot = true;
+        }
+
+        Some(result)
+    }
+
+    /// Value of a numeric literal as a double, `None` for other literals.
+    pub fn number_value(&self) -> Option<f64> {
+        match *self {
+            Literal::Number(number) => Some(if number.starts_with("0x") || number.starts_with("0X") {
+                radix_value(&number[2..], 16)
+            } else if number.starts_with("0o") || number.starts_with("0O") {
+                radix_value(&number[2..], 8)
+            } else if number.len() > 1 && number.starts_with('0') && number.bytes().all(|byte| (b'0'..=b'7').contains(&byte)) {
+                // Legacy octal, such as `017`
+                radix_value(&number[1..], 8)
+            } else {
+                number.parse().unwrap_or(0.0)
+            }),
+            Literal::Binary(number) => Some(radix_value(&number[2..], 2)),
+            _ => None,
+        }
+    }
+
+    /// Check whether this is a numeric literal that evaluates to `Infinity`
+    /// once parsed to a double, such as `1e1000`. Can be used by optimizers
+    /// that need to know the value without interpreting the source slice.
+    pub fn is_infinite_when_parsed(&self) -> bool {
+        match self.number_value() {
+            Some(value) => value.is_infinite(),
+            None        => false,
+        }
+    }
+}
+
+/// Value of the digits of an integer literal in the given radix.
+fn radix_value(digits: &str, radix: u32) -> f64 {
+    digits.chars().fold(0.0, |value, digit| {
+        value * radix as f64 + digit.to_digit(radix).unwrap_or(0) as f64
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn number_value() {
+        assert_eq!(Literal::Number("42").number_value(), Some(42.0));
+        assert_eq!(Literal::Number(".5e1").number_value(), Some(5.0));
+        assert_eq!(Literal::Number("0xff").number_value(), Some(255.0));
+        assert_eq!(Literal::Number("0o17").number_value(), Some(15.0));
+        assert_eq!(Literal::Number("017").number_value(), Some(15.0));
+        assert_eq!(Literal::Number("019").number_value(), Some(19.0));
+        assert_eq!(Literal::Binary("0b101").number_value(), Some(5.0));
+        assert_eq!(Literal::String("'42'").number_value(), None);
+    }
+
+    #[test]
+    fn regex_flags() {
+        assert_eq!(Literal::RegEx("/x/").regex_flags(), Some(RegexFlags::default()));
+        assert_eq!(Literal::RegEx("/a\\/b/gy").regex_flags(), Some(RegexFlags {
+            global: true,
+            sticky: true,
+            ..RegexFlags::default()
+        }));
+        assert_eq!(Literal::RegEx("/x/gg").regex_flags(), None);
+        assert_eq!(Literal::RegEx("/x/gq").regex_flags(), None);
+        assert_eq!(Literal::String("'/x/g'").regex_flags(), None);
+    }
+
+    #[test]
+    fn infinite_when_parsed() {
+        assert!(Literal::Number("1e1000").is_infinite_when_parsed());
+        assert!(Literal::Number(".5e400").is_infinite_when_parsed());
+        assert!(Literal::Number(&format!("0x{}", "f".repeat(260))).is_infinite_when_parsed());
+        assert!(Literal::Binary(&format!("0b{}", "1".repeat(1030))).is_infinite_when_parsed());
+    }
+
+    #[test]
+    fn finite_when_parsed() {
+        assert!(!Literal::Number("1e10").is_infinite_when_parsed());
+        assert!(!Literal::Number("1.7976931348623157e308").is_infinite_when_parsed());
+        assert!(!Literal::Number("1e-1000").is_infinite_when_parsed());
+        assert!(!Literal::Number("0xff").is_infinite_when_parsed());
+        assert!(!Literal::Number("017").is_infinite_when_parsed());
+        assert!(!Literal::Binary("0b101").is_infinite_when_parsed());
+        assert!(!Literal::String("'1e1000'").is_infinite_when_parsed());
+    }
+}
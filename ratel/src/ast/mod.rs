@@ -1,25 +1,27 @@
 #[macro_use]
 mod variable;
 mod operator;
-// mod types;
+mod types;
 mod function;
 mod literal;
 pub mod node;
 pub mod expression;
 pub mod statement;
+pub mod diff;
 
 use toolshed::list::List;
 use std::ops::Deref;
+use span::Span;
 
 pub use ast::variable::*;
 pub use ast::operator::*;
 pub use ast::node::Node;
-// pub use ast::types::{Type, Primitive};
+pub use ast::types::{Type, Primitive, TypeMember, TypeParameter, TypeParameterList};
 pub use ast::expression::{Expression, Property, PropertyKey};
 pub use ast::statement::{Statement, Declarator, BlockStatement};
-pub use ast::function::{Function, Class, ClassMember, Method, MethodKind};
+pub use ast::function::{Function, Class, ClassMember, Method, MethodKind, ParameterName};
 pub use ast::function::{Name, EmptyName, OptionalName, MandatoryName};
-pub use ast::literal::Literal;
+pub use ast::literal::{Literal, RegexFlags};
 
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -44,6 +46,19 @@ pub enum Pattern<'ast> {
     AssignmentPattern {
         left: Node<'ast, Pattern<'ast>>,
         right: ExpressionNode<'ast>,
+    },
+    /// TypeScript only, `x: number` in declarations, or `x?: number` in parameters
+    Annotated {
+        pattern: Node<'ast, Pattern<'ast>>,
+        optional: bool,
+        annotation: Option<TypeNode<'ast>>,
+    }
+}
+
+impl<'ast> Pattern<'ast> {
+    #[inline]
+    pub fn is_identifier(&self) -> bool {
+        matches!(*self, Pattern::Identifier(_))
     }
 }
 
@@ -59,8 +74,8 @@ pub type StatementNode<'ast> = Node<'ast, Statement<'ast>>;
 pub type StatementList<'ast> = NodeList<'ast, Statement<'ast>>;
 pub type IdentifierNode<'ast> = Node<'ast, &'ast str>;
 pub type IdentifierList<'ast> = NodeList<'ast, &'ast str>;
-// pub type TypeNode<'ast> = NodeList<'ast, Type<'ast>>;
-// pub type TypeList<'ast> = NodeList<'ast, Type<'ast>>;
+pub type TypeNode<'ast> = Node<'ast, Type<'ast>>;
+pub type TypeList<'ast> = NodeList<'ast, Type<'ast>>;
 
 #[derive(Debug, Clone)]
 pub struct Loc<T> {
@@ -95,6 +110,20 @@ impl<T> Loc<T> {
             item,
         }
     }
+
+    /// Get the `Span` of source this `Loc` covers.
+    #[inline]
+    pub fn span(&self) -> Span {
+        Span::new(self.start, self.end)
+    }
+
+    /// Slice the original text of this `Loc` out of the `source` it was
+    /// parsed from. Empty if the span doesn't fit within the source, as is
+    /// the case for nodes that were created rather than parsed.
+    #[inline]
+    pub fn source_text<'src>(&self, source: &'src str) -> &'src str {
+        source.get(self.start as usize..self.end as usize).unwrap_or("")
+    }
 }
 
 impl<T: PartialEq> PartialEq for Loc<T> {
@@ -55,6 +55,9 @@ impl<'ast, T: 'ast + Debug> Debug for Node<'ast, T> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use ast::{Statement, Expression};
+    use ast::expression::BinaryExpression;
+    use parser::parse;
 
     #[test]
     fn ptr() {
@@ -72,4 +75,31 @@ mod test {
         assert_eq!(*one_ptr, Loc::new(0, 0, "one"));
         assert_eq!(*two_ptr, Loc::new(0, 0, "two"));
     }
+
+    #[test]
+    fn source_text() {
+        let src = "function f(a) { return a * (b + c); }\nx = 1 +  2;";
+        let module = parse(src).unwrap();
+        let mut body = module.body().iter();
+
+        match body.next().unwrap().item {
+            Statement::Function(ref function) => {
+                assert_eq!(function.body.source_text(src), "{ return a * (b + c); }");
+            },
+            ref statement => panic!("Expected a function, got {:?}", statement),
+        }
+
+        match body.next().unwrap().item {
+            Statement::Expression(ref expression) => match expression.item {
+                Expression::Binary(BinaryExpression { right, .. }) => {
+                    assert_eq!(expression.source_text(src), "x = 1 +  2");
+                    assert_eq!(right.source_text(src), "1 +  2");
+                },
+                ref expression => panic!("Expected an assignment, got {:?}", expression),
+            },
+            ref statement => panic!("Expected an expression statement, got {:?}", statement),
+        }
+
+        assert_eq!(Loc::new(4, 100, "out of bounds").source_text(src), "");
+    }
 }
@@ -1,7 +1,7 @@
 use lexer::Token;
 use lexer::Token::*;
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum OperatorKind {
     FatArrow,         //   …  => …
     New,              //     new …
@@ -36,6 +36,7 @@ pub enum OperatorKind {
     BitwiseOr,        //   …  |  …
     LogicalAnd,       //   …  && …
     LogicalOr,        //   …  || …
+    Nullish,          //   …  ?? …
     Conditional,      //   …  ?  …  :  …
     Assign,           //   …  =  …
     AddAssign,        //   …  += …
@@ -50,6 +51,9 @@ pub enum OperatorKind {
     BitAndAssign,     //   …  &= …
     BitXorAssign,     //   …  ^= …
     BitOrAssign,      //   …  |= …
+    NullishAssign,    //   … ??= …
+    LogicalOrAssign,  //   … ||= …
+    LogicalAndAssign, //   … &&= …
     Spread,           //     ... …
 }
 
@@ -101,6 +105,7 @@ impl OperatorKind {
             OperatorBitwiseOr        => Some(BitwiseOr),
             OperatorLogicalAnd       => Some(LogicalAnd),
             OperatorLogicalOr        => Some(LogicalOr),
+            OperatorNullish          => Some(Nullish),
             OperatorConditional      => Some(Conditional),
             OperatorAssign           => Some(Assign),
             OperatorAddAssign        => Some(AddAssign),
@@ -115,6 +120,9 @@ impl OperatorKind {
             OperatorBitAndAssign     => Some(BitAndAssign),
             OperatorBitXorAssign     => Some(BitXorAssign),
             OperatorBitOrAssign      => Some(BitOrAssign),
+            OperatorNullishAssign    => Some(NullishAssign),
+            OperatorLogicalOrAssign  => Some(LogicalOrAssign),
+            OperatorLogicalAndAssign => Some(LogicalAndAssign),
             OperatorSpread           => Some(Spread),
             _                        => None
         }
@@ -156,6 +164,7 @@ impl OperatorKind {
             BitwiseOr        => "|",
             LogicalAnd       => "&&",
             LogicalOr        => "||",
+            Nullish          => "??",
             Conditional      => "?",
             Assign           => "=",
             AddAssign        => "+=",
@@ -170,11 +179,17 @@ impl OperatorKind {
             BitAndAssign     => "&=",
             BitXorAssign     => "^=",
             BitOrAssign      => "|=",
+            NullishAssign    => "??=",
+            LogicalOrAssign  => "||=",
+            LogicalAndAssign => "&&=",
             Spread           => "...",
         }
     }
 
-    /// According to the Operator Precedence Table
+    /// How tightly the operator binds its operands, according to the Operator
+    /// Precedence Table. An expression has to be wrapped in parentheses when
+    /// used as an operand of an operator with a higher binding power.
+    ///
     /// Note: Unary operators default to 15!
     #[inline]
     pub fn binding_power(&self) -> u8 {
@@ -220,7 +235,8 @@ impl OperatorKind {
             BitwiseXor       => 8,
             BitwiseOr        => 7,
             LogicalAnd       => 6,
-            LogicalOr        => 5,
+            LogicalOr        |
+            Nullish          => 5,
             Conditional      => 4,
 
             Assign           |
@@ -235,12 +251,30 @@ impl OperatorKind {
             UBSRAssign       |
             BitAndAssign     |
             BitXorAssign     |
-            BitOrAssign      => 3,
+            BitOrAssign      |
+            NullishAssign    |
+            LogicalOrAssign  |
+            LogicalAndAssign => 3,
 
             Spread           => 1,
         }
     }
 
+    /// Check whether operands of equal binding power group to the right, so
+    /// that `a = b = c` is `a = (b = c)`. This is the case for assignments,
+    /// the conditional and the exponent operator, other infix operators group
+    /// to the left.
+    #[inline]
+    pub fn is_right_associative(&self) -> bool {
+        match self {
+            Exponent         |
+            Conditional      => true,
+
+            _                => self.assignment()
+        }
+    }
+
+    /// Check whether the operator can precede its operand, such as `!` or `-`.
     #[inline]
     pub fn prefix(&self) -> bool {
         match self {
@@ -260,6 +294,8 @@ impl OperatorKind {
         }
     }
 
+    /// Check whether the operator can stand between two operands, such as
+    /// `-` or `instanceof`. Assignments are included.
     #[inline]
     pub fn infix(&self) -> bool {
         match self {
@@ -286,6 +322,7 @@ impl OperatorKind {
             BitwiseOr        |
             LogicalAnd       |
             LogicalOr        |
+            Nullish          |
             Conditional      |
             Addition         |
             Subtraction      |
@@ -301,12 +338,17 @@ impl OperatorKind {
             UBSRAssign       |
             BitAndAssign     |
             BitXorAssign     |
-            BitOrAssign      => true,
+            BitOrAssign      |
+            NullishAssign    |
+            LogicalOrAssign  |
+            LogicalAndAssign => true,
 
             _                => false
         }
     }
 
+    /// Check whether the operator assigns to its left operand, such as `=`
+    /// or `+=`.
     #[inline]
     pub fn assignment(&self) -> bool {
         match self {
@@ -322,7 +364,10 @@ impl OperatorKind {
             UBSRAssign       |
             BitAndAssign     |
             BitXorAssign     |
-            BitOrAssign      => true,
+            BitOrAssign      |
+            NullishAssign    |
+            LogicalOrAssign  |
+            LogicalAndAssign => true,
 
             _                => false
         }
@@ -346,3 +391,62 @@ impl OperatorKind {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn binding_power_ordering() {
+        let ordered = [
+            New,
+            Increment,
+            LogicalNot,
+            Multiplication,
+            Addition,
+            BitShiftLeft,
+            Lesser,
+            StrictEquality,
+            BitwiseAnd,
+            BitwiseXor,
+            BitwiseOr,
+            LogicalAnd,
+            LogicalOr,
+            Conditional,
+            Assign,
+            Spread,
+        ];
+
+        for pair in ordered.windows(2) {
+            assert!(pair[0].binding_power() > pair[1].binding_power(), "{:?} should bind tighter than {:?}", pair[0], pair[1]);
+        }
+
+        assert_eq!(Multiplication.binding_power(), Remainder.binding_power());
+        assert_eq!(Addition.binding_power(), Subtraction.binding_power());
+        assert_eq!(LogicalOr.binding_power(), Nullish.binding_power());
+        assert_eq!(Assign.binding_power(), LogicalAndAssign.binding_power());
+    }
+
+    #[test]
+    fn associativity() {
+        assert!(Assign.is_right_associative());
+        assert!(AddAssign.is_right_associative());
+        assert!(NullishAssign.is_right_associative());
+        assert!(Exponent.is_right_associative());
+        assert!(Conditional.is_right_associative());
+
+        assert!(!Addition.is_right_associative());
+        assert!(!Subtraction.is_right_associative());
+        assert!(!LogicalOr.is_right_associative());
+        assert!(!Nullish.is_right_associative());
+    }
+
+    #[test]
+    fn kinds() {
+        assert!(Subtraction.prefix() && Subtraction.infix());
+        assert!(Typeof.prefix() && !Typeof.infix());
+        assert!(Instanceof.infix() && !Instanceof.prefix());
+        assert!(BitOrAssign.assignment() && BitOrAssign.infix());
+        assert!(!Equality.assignment());
+    }
+}
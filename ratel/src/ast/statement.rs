@@ -1,5 +1,6 @@
-use ast::{Node, NodeList, DeclarationKind, Function, Class, MandatoryName, IdentifierNode};
+use ast::{Node, NodeList, DeclarationKind, Function, Class, MandatoryName, OptionalName, IdentifierNode};
 use ast::{ExpressionNode, StatementNode, StatementList, Block, BlockNode, Pattern};
+use ast::{TypeNode, TypeList, TypeMember, TypeParameterList, Expression, Literal};
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct Declarator<'ast> {
@@ -104,6 +105,7 @@ pub struct LabeledStatement<'ast> {
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct SwitchStatement<'ast> {
     pub discriminant: ExpressionNode<'ast>,
+    /// All cases share a single block scope, see `lexical_declarations`.
     pub cases: BlockNode<'ast, SwitchCase<'ast>>,
 }
 
@@ -119,6 +121,8 @@ pub struct ImportDeclaration<'ast> {
     pub source: &'ast str,
 }
 
+/// `imported as local`, both being the same name without `as`. The imported
+/// name can also be a string, kept with its quotes, as in `{ "a b" as local }`.
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct ImportSpecifier<'ast> {
     pub imported: IdentifierNode<'ast>,
@@ -142,6 +146,56 @@ pub enum ForImportSpecifier<'ast> {
     ImportNamespaceSpecifier(ImportNamespaceSpecifier<'ast>),
 }
 
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ExportDeclaration<'ast> {
+    pub declaration: StatementNode<'ast>,
+}
+
+/// `export { a, b as c }`, or `export { a } from 'm'` re-exporting names
+/// of another module
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ExportNamedDeclaration<'ast> {
+    pub specifiers: NodeList<'ast, ExportSpecifier<'ast>>,
+    pub source: Option<&'ast str>,
+}
+
+/// `local as exported`, both being the same name without `as`. Either can
+/// be `default`, as in `export { a as default }` or `export { default } from 'm'`,
+/// or a string kept with its quotes, as in `export { a as "a b" }`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ExportSpecifier<'ast> {
+    pub local: IdentifierNode<'ast>,
+    pub exported: IdentifierNode<'ast>,
+}
+
+/// `export default ...`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ExportDefaultDeclaration<'ast> {
+    /// `export default function name() {}`, the name being optional
+    Function(Node<'ast, Function<'ast, OptionalName<'ast>>>),
+    /// `export default class Name {}`, the name being optional
+    Class(Node<'ast, Class<'ast, OptionalName<'ast>>>),
+    /// `export default value;`
+    Expression(ExpressionNode<'ast>),
+}
+
+/// TypeScript only, `type Name<T> = ...;`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TypeAliasDeclaration<'ast> {
+    pub name: IdentifierNode<'ast>,
+    pub type_parameters: TypeParameterList<'ast>,
+    pub value: TypeNode<'ast>,
+}
+
+/// TypeScript only, `interface Name<T> extends Base { ... }`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct InterfaceDeclaration<'ast> {
+    pub name: IdentifierNode<'ast>,
+    pub type_parameters: TypeParameterList<'ast>,
+    pub extends: TypeList<'ast>,
+    pub body: NodeList<'ast, TypeMember<'ast>>,
+}
+
 
 
 pub type BlockStatement<'ast> = Block<'ast, Statement<'ast>>;
@@ -170,7 +224,12 @@ pub enum Statement<'ast> {
     Function(FunctionStatement<'ast>),
     Class(ClassStatement<'ast>),
     Switch(SwitchStatement<'ast>),
-    Import(ImportDeclaration<'ast>)
+    Import(ImportDeclaration<'ast>),
+    Export(ExportDeclaration<'ast>),
+    ExportNamed(ExportNamedDeclaration<'ast>),
+    ExportDefault(ExportDefaultDeclaration<'ast>),
+    TypeAlias(TypeAliasDeclaration<'ast>),
+    Interface(InterfaceDeclaration<'ast>),
 }
 
 macro_rules! impl_from {
@@ -203,7 +262,12 @@ impl_from! {
     FunctionStatement => Function,
     ClassStatement => Class,
     SwitchStatement => Switch,
-    ImportDeclaration => Import
+    ImportDeclaration => Import,
+    ExportDeclaration => Export,
+    ExportNamedDeclaration => ExportNamed,
+    ExportDefaultDeclaration => ExportDefault,
+    TypeAliasDeclaration => TypeAlias,
+    InterfaceDeclaration => Interface
 }
 
 impl<'ast> From<DeclarationStatement<'ast>> for ForInit<'ast> {
@@ -247,4 +311,55 @@ impl<'ast> Statement<'ast> {
             _                   => false,
         }
     }
+
+    /// Raw text of the string literal, quotes included, if this statement
+    /// consists of nothing else and so can be a directive such as
+    /// `"use strict";`. See `directives`.
+    #[inline]
+    pub fn directive(&self) -> Option<&'ast str> {
+        match *self {
+            Statement::Expression(expression) => match expression.item {
+                Expression::Literal(Literal::String(raw)) => Some(raw),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Iterate over the raw directives of the prologue opening `body`, which is
+/// either a program or a function body. The prologue ends with the first
+/// statement that isn't a lone string literal.
+pub fn directives<'ast>(body: StatementList<'ast>) -> impl Iterator<Item = &'ast str> {
+    body.iter()
+        .map(|statement| match statement.item {
+            // Parentheses aren't kept in the tree, but `('use strict');`
+            // still isn't a directive, its literal starts past the statement
+            Statement::Expression(expression) if expression.start != statement.start => None,
+            ref item => item.directive(),
+        })
+        .take_while(Option::is_some)
+        .flatten()
+}
+
+/// Check whether the prologue of `body` contains a `"use strict"` directive.
+/// Directives are compared by their raw text, so ones containing escapes or
+/// line continuations, such as `"use\x20strict"`, never count.
+pub fn is_strict(body: StatementList) -> bool {
+    directives(body).any(|raw| &raw[1..raw.len() - 1] == "use strict")
+}
+
+impl<'ast> SwitchStatement<'ast> {
+    /// Iterate over `let`, `const` and `class` declarations in all of the cases.
+    /// Unlike blocks, cases don't introduce a scope of their own, so these all
+    /// share one scope and `case 1: let a; case 2: let a;` is a redeclaration.
+    pub fn lexical_declarations(&self) -> impl Iterator<Item = &'ast StatementNode<'ast>> {
+        self.cases.body.iter()
+            .flat_map(|case| case.consequent.iter())
+            .filter(|statement| match statement.item {
+                Statement::Declaration(DeclarationStatement { kind, .. }) => kind != DeclarationKind::Var,
+                Statement::Class(_) => true,
+                _ => false,
+            })
+    }
 }
@@ -1,10 +1,4 @@
-use ast::{Loc, TypeNode, TypeList};
-
-static TYPE_ANY: &Loc<Type<'static>> = &Loc {
-    start: 0,
-    end: 0,
-    item: Type::Any
-};
+use ast::{NodeList, TypeNode, TypeList, PatternList, IdentifierNode, Literal};
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Primitive {
@@ -13,6 +7,9 @@ pub enum Primitive {
     Boolean,
     Null,
     Undefined,
+    Void,
+    Never,
+    Unknown,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -20,11 +17,75 @@ pub enum Type<'ast> {
     Any,
     Primitive(Primitive),
     Identifier(&'ast str),
+    /// `"foo"`, `42` or `true` used as a type
+    Literal(Literal<'ast>),
     Union {
         variants: TypeList<'ast>,
     },
+    Intersection {
+        variants: TypeList<'ast>,
+    },
     Generic {
         ident: &'ast str,
         subtypes: TypeList<'ast>,
+    },
+    /// `T[]`
+    Array {
+        element: TypeNode<'ast>,
+    },
+    /// `[T, U]`
+    Tuple {
+        elements: TypeList<'ast>,
+    },
+    /// `(a: T) => U`
+    Function {
+        params: PatternList<'ast>,
+        returns: TypeNode<'ast>,
+    },
+    /// `{ a: T; b?: U }`
+    Object {
+        members: NodeList<'ast, TypeMember<'ast>>,
+    },
+}
+
+/// Property of an object type or an interface, methods are represented
+/// as properties with a function type.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TypeMember<'ast> {
+    pub key: IdentifierNode<'ast>,
+    pub optional: bool,
+    pub value: TypeNode<'ast>,
+}
+
+/// `T`, `T extends U` or `T = U` in `<...>` following the name of a
+/// generic function, type alias or interface.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TypeParameter<'ast> {
+    pub name: &'ast str,
+    pub constraint: Option<TypeNode<'ast>>,
+    pub default: Option<TypeNode<'ast>>,
+}
+
+pub type TypeParameterList<'ast> = NodeList<'ast, TypeParameter<'ast>>;
+
+impl<'ast> From<Primitive> for Type<'ast> {
+    #[inline]
+    fn from(primitive: Primitive) -> Self {
+        Type::Primitive(primitive)
+    }
+}
+
+impl<'ast> From<Literal<'ast>> for Type<'ast> {
+    #[inline]
+    fn from(literal: Literal<'ast>) -> Self {
+        Type::Literal(literal)
+    }
+}
+
+#[cfg(test)]
+impl<'ast> From<&'ast str> for Type<'ast> {
+    #[inline]
+    fn from(ident: &'ast str) -> Self {
+        Type::Identifier(ident)
     }
 }
@@ -3,4 +3,8 @@ pub enum DeclarationKind {
     Var,
     Let,
     Const,
+    /// `using`, disposing of the value at the end of the block
+    Using,
+    /// `await using`, same as `using` with an async dispose
+    AwaitUsing,
 }
@@ -1,7 +1,8 @@
 use serde::ser::{Serialize, Serializer, SerializeStruct};
-use ast::{Expression, Loc, OperatorKind};
+use ast::{Expression, ExpressionNode, Loc, OperatorKind, Literal};
 use ast::expression::*;
 use astgen::SerializeInLoc;
+use astgen::types::TypeAnnotation;
 
 #[inline]
 fn expression_type(operator: OperatorKind, prefix: bool) -> &'static str {
@@ -20,9 +21,13 @@ fn expression_type(operator: OperatorKind, prefix: bool) -> &'static str {
         BitOrAssign         |
         BitXorAssign        |
         SubtractAssign      |
-        BitAndAssign        => "AssignmentExpression",
+        BitAndAssign        |
+        NullishAssign       |
+        LogicalOrAssign     |
+        LogicalAndAssign    => "AssignmentExpression",
         LogicalAnd          |
-        LogicalOr           => "LogicalExpression",
+        LogicalOr           |
+        Nullish             => "LogicalExpression",
         Increment           |
         Decrement           => "UpdateExpression",
         Typeof              |
@@ -48,6 +53,40 @@ impl<'ast> SerializeInLoc for SpreadExpression<'ast> {
     }
 }
 
+impl<'ast> SerializeInLoc for YieldExpression<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "YieldExpression", 2, |state| {
+            state.serialize_field("argument", &self.argument)?;
+            state.serialize_field("delegate", &self.delegate)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for AwaitExpression<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "AwaitExpression", 1, |state| {
+            state.serialize_field("argument", &self.argument)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for ParenthesizedExpression<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "ParenthesizedExpression", 1, |state| {
+            state.serialize_field("expression", &self.expression)
+        })
+    }
+}
+
 impl<'ast> SerializeInLoc for MemberExpression<'ast> {
     fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
     where
@@ -98,6 +137,39 @@ impl<'ast> SerializeInLoc for CallExpression<'ast> {
     }
 }
 
+impl<'ast> SerializeInLoc for OptionalExpression<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.link {
+            OptionalLink::Member(ref property) => {
+                self.in_loc(serializer, "MemberExpression", 4, |state| {
+                    state.serialize_field("object", &self.object)?;
+                    state.serialize_field("property", property)?;
+                    state.serialize_field("computed", &false)?;
+                    state.serialize_field("optional", &true)
+                })
+            },
+            OptionalLink::ComputedMember(ref property) => {
+                self.in_loc(serializer, "MemberExpression", 4, |state| {
+                    state.serialize_field("object", &self.object)?;
+                    state.serialize_field("property", property)?;
+                    state.serialize_field("computed", &true)?;
+                    state.serialize_field("optional", &true)
+                })
+            },
+            OptionalLink::Call(ref arguments) => {
+                self.in_loc(serializer, "CallExpression", 3, |state| {
+                    state.serialize_field("callee", &self.object)?;
+                    state.serialize_field("arguments", arguments)?;
+                    state.serialize_field("optional", &true)
+                })
+            },
+        }
+    }
+}
+
 impl<'ast> SerializeInLoc for ConditionalExpression<'ast> {
     fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
     where
@@ -131,6 +203,9 @@ impl<'ast> SerializeInLoc for ArrowExpression<'ast> {
     {
         self.in_loc(serializer, "ArrowFunctionExpression", 2, |state| {
             state.serialize_field("params", &self.params)?;
+            if let Some(return_type) = self.return_type {
+                state.serialize_field("returnType", &TypeAnnotation(return_type))?;
+            }
             state.serialize_field("body", &Loc::new(0, 0, self.body))
         })
     }
@@ -225,23 +300,9 @@ impl<'ast> SerializeInLoc for ArrayExpression<'ast> {
         S: Serializer,
     {
         self.in_loc(serializer, "ArrayExpression", 1, |state| {
+            let elements: Vec<_> = self.elements().collect();
 
-            let mut elems = self.body.iter()
-                                     .map(|loc| if loc.item != Expression::Void { Some(loc) } else { None } )
-                                     .collect::<Vec<_>>();
-            loop {
-                let should_pop = match elems.last() {
-                    Some(&None) => true,
-                    Some(&Some(_)) => break,
-                    None => break,
-                };
-
-                if should_pop {
-                    elems.pop();
-                }
-            }
-
-            state.serialize_field("elements", &elems)
+            state.serialize_field("elements", &elements)
         })
     }
 }
@@ -260,6 +321,186 @@ impl<'ast> SerializeInLoc for BinaryExpression<'ast> {
     }
 }
 
+/// JSX names are serialized with their own node types, `JSXIdentifier`
+/// and `JSXMemberExpression`, rather than as regular expressions.
+struct JSXName<'ast>(ExpressionNode<'ast>);
+
+struct JSXIdentifier<'ast>(&'ast str, u32, u32);
+
+impl<'ast> Serialize for JSXIdentifier<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("JSXIdentifier", 4)?;
+        state.serialize_field("type", "JSXIdentifier")?;
+        state.serialize_field("name", self.0)?;
+        state.serialize_field("start", &self.1)?;
+        state.serialize_field("end", &self.2)?;
+        state.end()
+    }
+}
+
+impl<'ast> Serialize for JSXName<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0.item {
+            Expression::Member(ref member) => {
+                let property = member.property;
+                let mut state = serializer.serialize_struct("JSXMemberExpression", 5)?;
+                state.serialize_field("type", "JSXMemberExpression")?;
+                state.serialize_field("object", &JSXName(member.object))?;
+                state.serialize_field("property", &JSXIdentifier(property.item, property.start, property.end))?;
+                state.serialize_field("start", &self.0.start)?;
+                state.serialize_field("end", &self.0.end)?;
+                state.end()
+            },
+            Expression::Identifier(name) => JSXIdentifier(name, self.0.start, self.0.end).serialize(serializer),
+            // Recovered from an invalid name, such as in `<1></1>`
+            _ => JSXIdentifier("", self.0.start, self.0.end).serialize(serializer),
+        }
+    }
+}
+
+/// Attribute values other than strings and elements are wrapped in a container.
+struct JSXAttributeValue<'ast>(ExpressionNode<'ast>);
+
+impl<'ast> Serialize for JSXAttributeValue<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0.item {
+            Expression::Literal(Literal::String(_)) |
+            Expression::JSXElement(_)               |
+            Expression::JSXFragment(_)              => self.0.serialize(serializer),
+            _ => {
+                let mut state = serializer.serialize_struct("JSXExpressionContainer", 2)?;
+                state.serialize_field("type", "JSXExpressionContainer")?;
+                state.serialize_field("expression", &self.0)?;
+                state.end()
+            }
+        }
+    }
+}
+
+impl<'ast> SerializeInLoc for JSXAttribute<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            JSXAttribute::Attribute { name, value } => {
+                self.in_loc(serializer, "JSXAttribute", 2, |state| {
+                    state.serialize_field("name", &JSXIdentifier(name.item, name.start, name.end))?;
+                    state.serialize_field("value", &value.map(JSXAttributeValue))
+                })
+            },
+            JSXAttribute::Spread { argument } => {
+                self.in_loc(serializer, "JSXSpreadAttribute", 1, |state| {
+                    state.serialize_field("argument", &argument)
+                })
+            },
+        }
+    }
+}
+
+struct JSXEmptyExpression;
+
+impl Serialize for JSXEmptyExpression {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("JSXEmptyExpression", 1)?;
+        state.serialize_field("type", "JSXEmptyExpression")?;
+        state.end()
+    }
+}
+
+impl<'ast> SerializeInLoc for JSXChild<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            JSXChild::Text(text) => {
+                self.in_loc(serializer, "JSXText", 2, |state| {
+                    state.serialize_field("value", text)?;
+                    state.serialize_field("raw", text)
+                })
+            },
+            JSXChild::Expression(expression) => {
+                self.in_loc(serializer, "JSXExpressionContainer", 1, |state| {
+                    match expression.item {
+                        Expression::Void => state.serialize_field("expression", &JSXEmptyExpression),
+                        _                => state.serialize_field("expression", &expression),
+                    }
+                })
+            },
+            JSXChild::Element(element) => element.item.serialize(serializer),
+        }
+    }
+}
+
+struct JSXOpeningElement<'a, 'ast: 'a>(&'a JSXElement<'ast>);
+
+impl<'a, 'ast> Serialize for JSXOpeningElement<'a, 'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("JSXOpeningElement", 4)?;
+        state.serialize_field("type", "JSXOpeningElement")?;
+        state.serialize_field("name", &JSXName(self.0.name))?;
+        state.serialize_field("attributes", &self.0.attributes)?;
+        state.serialize_field("selfClosing", &self.0.self_closing)?;
+        state.end()
+    }
+}
+
+struct JSXClosingElement<'ast>(ExpressionNode<'ast>);
+
+impl<'ast> Serialize for JSXClosingElement<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("JSXClosingElement", 2)?;
+        state.serialize_field("type", "JSXClosingElement")?;
+        state.serialize_field("name", &JSXName(self.0))?;
+        state.end()
+    }
+}
+
+impl<'ast> SerializeInLoc for JSXElement<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "JSXElement", 3, |state| {
+            let closing = if self.self_closing { None } else { Some(JSXClosingElement(self.name)) };
+
+            state.serialize_field("openingElement", &JSXOpeningElement(self))?;
+            state.serialize_field("closingElement", &closing)?;
+            state.serialize_field("children", &self.children)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for JSXFragment<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "JSXFragment", 1, |state| {
+            state.serialize_field("children", &self.children)
+        })
+    }
+}
+
 impl<'ast> SerializeInLoc for Expression<'ast> {
     fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
     where
@@ -270,6 +511,7 @@ impl<'ast> SerializeInLoc for Expression<'ast> {
         match *self {
             Void => unreachable!(),
             This(_) => self.in_loc(serializer, "ThisExpression", 0, |_| Ok(())),
+            Super(_) => self.in_loc(serializer, "Super", 0, |_| Ok(())),
             Identifier(ref ident) => {
                 self.in_loc(serializer, "Identifier", 1, |state| {
                     state.serialize_field("name", ident)
@@ -280,6 +522,8 @@ impl<'ast> SerializeInLoc for Expression<'ast> {
             Sequence(ref expression)       => expression.serialize(serializer),
             Binary(ref expression)         => expression.serialize(serializer),
             Prefix(ref expression)         => expression.serialize(serializer),
+            Yield(ref expression)          => expression.serialize(serializer),
+            Await(ref expression)          => expression.serialize(serializer),
             Postfix(ref expression)        => expression.serialize(serializer),
             Object(ref expression)         => expression.serialize(serializer),
             Template(ref expression)       => expression.serialize(serializer),
@@ -289,10 +533,16 @@ impl<'ast> SerializeInLoc for Expression<'ast> {
             ComputedMember(ref expression) => expression.serialize(serializer),
             MetaProperty(ref expression)   => expression.serialize(serializer),
             Call(ref expression)           => expression.serialize(serializer),
+            Optional(ref expression)       => expression.serialize(serializer),
             Conditional(ref expression)    => expression.serialize(serializer),
             Arrow(ref expression)          => expression.serialize(serializer),
             Function(ref expression)       => expression.serialize(serializer),
             Class(ref expression)          => expression.serialize(serializer),
+            JSXElement(ref expression)     => expression.serialize(serializer),
+            JSXFragment(ref expression)    => expression.serialize(serializer),
+            TypeCast(ref expression)       => expression.serialize(serializer),
+            Instantiation(ref expression)  => expression.serialize(serializer),
+            Parenthesized(ref expression)  => expression.serialize(serializer),
         }
     }
 }
@@ -343,6 +593,7 @@ mod test {
                                 "start": 1,
                                 "end": 2
                             },
+                            null,
                         ],
                         "start": 0,
                         "end": 5
@@ -384,6 +635,36 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_array_hole_and_undefined() {
+        expect_parse!("[, undefined]", {
+            "type": "Program",
+            "body": [
+                {
+                    "type": "ExpressionStatement",
+                    "expression": {
+                        "type": "ArrayExpression",
+                        "elements": [
+                            null,
+                            {
+                                "type": "Literal",
+                                "raw": "undefined",
+                                "start": 3,
+                                "end": 12
+                            },
+                        ],
+                        "start": 0,
+                        "end": 13
+                    },
+                    "start": 0,
+                    "end": 13
+                }
+            ],
+            "start": 0,
+            "end": 13
+        });
+    }
+
     #[test]
     fn test_this_expression() {
         expect_parse!("this;", {
@@ -663,6 +944,52 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_logical_assignment_expression() {
+        expect_parse!("a[x] ||= y;", {
+            "type": "Program",
+            "body": [
+                {
+                    "type": "ExpressionStatement",
+                    "expression": {
+                        "type": "AssignmentExpression",
+                        "operator": "||=",
+                        "left": {
+                            "type": "MemberExpression",
+                            "object": {
+                                "type": "Identifier",
+                                "name": "a",
+                                "start": 0,
+                                "end": 1
+                            },
+                            "property": {
+                                "type": "Identifier",
+                                "name": "x",
+                                "start": 2,
+                                "end": 3
+                            },
+                            "computed": true,
+                            "start": 0,
+                            "end": 4
+                        },
+                        "right": {
+                            "type": "Identifier",
+                            "name": "y",
+                            "start": 9,
+                            "end": 10
+                        },
+                        "start": 0,
+                        "end": 10
+                    },
+                    "start": 0,
+                    "end": 10
+                }
+            ],
+            "start": 0,
+            "end": 10
+        });
+    }
+
     #[test]
     fn test_prefix_expression () {
         expect_parse!("++i", {
@@ -927,6 +1254,7 @@ mod test {
                                         "value": {
                                             "type": "FunctionExpression",
                                             "generator": false,
+                                            "async": false,
                                             "id": null,
                                             "params": [
                                                 {
@@ -974,6 +1302,7 @@ mod test {
             {
               "type": "FunctionDeclaration",
               "generator": false,
+              "async": false,
               "id": {
                 "type": "Identifier",
                 "name": "Handler",
@@ -1037,7 +1366,7 @@ mod test {
                         "property": {
                             "type": "Identifier",
                             "name": "bar",
-                            "start": 7,
+                            "start": 4,
                             "end": 7
                         },
                         "computed": false,
@@ -1095,6 +1424,7 @@ mod test {
                 {
                     "type": "FunctionDeclaration",
                     "generator": false,
+                    "async": false,
                     "id": {
                         "type": "Identifier",
                         "name": "foo",
@@ -1194,7 +1524,7 @@ mod test {
                             "end": 3
                         },
                         "arguments": [],
-                        "start": 3,
+                        "start": 0,
                         "end": 5
                     },
                     "start": 0,
@@ -1273,11 +1603,11 @@ mod test {
                         "start": 1,
                         "end": 8
                     },
-                    "start": 1,
+                    "start": 0,
                     "end": 8
                 }
               ],
-              "start": 1,
+              "start": 0,
               "end": 8,
         });
         expect_parse!("(b) => {}", {
@@ -1304,11 +1634,11 @@ mod test {
                         "start": 1,
                         "end": 9
                     },
-                    "start": 1,
+                    "start": 0,
                     "end": 9
                 }
               ],
-              "start": 1,
+              "start": 0,
               "end": 9,
         });
         expect_parse!("() => {}", {
@@ -1376,6 +1706,7 @@ mod test {
                 {
                     "type": "FunctionDeclaration",
                     "generator": false,
+                    "async": false,
                     "id": {
                         "type": "Identifier",
                         "name": "foo",
@@ -1403,6 +1734,7 @@ mod test {
                 {
                     "type": "FunctionDeclaration",
                     "generator": false,
+                    "async": false,
                     "id": {
                         "type": "Identifier",
                         "name": "foo",
@@ -1538,6 +1870,7 @@ mod test {
                             "value": {
                                 "type": "FunctionExpression",
                                 "generator": false,
+                                "async": false,
                                 "id": null,
                                 "params": [],
                                 "body": {
@@ -1593,6 +1926,7 @@ mod test {
                             "value": {
                                 "type": "FunctionExpression",
                                 "generator": false,
+                                "async": false,
                                 "id": null,
                                 "params": [],
                                 "body": {
@@ -1619,4 +1953,177 @@ mod test {
             "end": 29
         });
     }
+
+    #[test]
+    fn test_method_kinds() {
+        use parser::parse;
+        use serde_json::{to_value, Value};
+
+        // `kind`, `method` or `static`, and `computed` of every member at `pointer`
+        fn kinds(src: &str, pointer: &str) -> Vec<Value> {
+            let value = to_value(&parse(src).unwrap()).unwrap();
+
+            value.pointer(pointer).unwrap().as_array().unwrap().iter().map(|member| json!([
+                member["kind"],
+                member.get("method").or_else(|| member.get("static")),
+                member["computed"],
+            ])).collect()
+        }
+
+        assert_eq!(kinds("({ a: 1, b() {}, get c() {}, set c(v) {}, [d]() {} })", "/body/0/expression/properties"), [
+            json!(["init", false, false]),
+            json!(["init", true, false]),
+            json!(["get", false, false]),
+            json!(["set", false, false]),
+            json!(["init", true, true]),
+        ]);
+
+        assert_eq!(kinds("(class { constructor() {} a() {} get b() {} set b(v) {} static get c() {} [d]() {} })", "/body/0/expression/body/body"), [
+            json!(["constructor", false, false]),
+            json!(["method", false, false]),
+            json!(["get", false, false]),
+            json!(["set", false, false]),
+            json!(["get", true, false]),
+            json!(["method", false, true]),
+        ]);
+    }
+
+    #[test]
+    fn test_jsx_element() {
+        use parser::ParseOptions;
+
+        expect_parse!(r#"<a.b c="d" {...e}>{f}x{}</a.b>"#, ParseOptions { jsx: true, ..ParseOptions::default() }, {
+            "type": "Program",
+            "body": [
+                {
+                    "type": "ExpressionStatement",
+                    "expression": {
+                        "type": "JSXElement",
+                        "openingElement": {
+                            "type": "JSXOpeningElement",
+                            "name": {
+                                "type": "JSXMemberExpression",
+                                "object": {
+                                    "type": "JSXIdentifier",
+                                    "name": "a",
+                                    "start": 1,
+                                    "end": 2,
+                                },
+                                "property": {
+                                    "type": "JSXIdentifier",
+                                    "name": "b",
+                                    "start": 2,
+                                    "end": 4,
+                                },
+                                "start": 1,
+                                "end": 4,
+                            },
+                            "attributes": [
+                                {
+                                    "type": "JSXAttribute",
+                                    "name": {
+                                        "type": "JSXIdentifier",
+                                        "name": "c",
+                                        "start": 5,
+                                        "end": 6,
+                                    },
+                                    "value": {
+                                        "type": "Literal",
+                                        "value": "d",
+                                        "raw": "\"d\"",
+                                        "start": 7,
+                                        "end": 10,
+                                    },
+                                    "start": 5,
+                                    "end": 10,
+                                },
+                                {
+                                    "type": "JSXSpreadAttribute",
+                                    "argument": {
+                                        "type": "Identifier",
+                                        "name": "e",
+                                        "start": 15,
+                                        "end": 16,
+                                    },
+                                    "start": 11,
+                                    "end": 17,
+                                }
+                            ],
+                            "selfClosing": false,
+                        },
+                        "closingElement": {
+                            "type": "JSXClosingElement",
+                            "name": {
+                                "type": "JSXMemberExpression",
+                                "object": {
+                                    "type": "JSXIdentifier",
+                                    "name": "a",
+                                    "start": 1,
+                                    "end": 2,
+                                },
+                                "property": {
+                                    "type": "JSXIdentifier",
+                                    "name": "b",
+                                    "start": 2,
+                                    "end": 4,
+                                },
+                                "start": 1,
+                                "end": 4,
+                            },
+                        },
+                        "children": [
+                            {
+                                "type": "JSXExpressionContainer",
+                                "expression": {
+                                    "type": "Identifier",
+                                    "name": "f",
+                                    "start": 19,
+                                    "end": 20,
+                                },
+                                "start": 18,
+                                "end": 21,
+                            },
+                            {
+                                "type": "JSXText",
+                                "value": "x",
+                                "raw": "x",
+                                "start": 21,
+                                "end": 22,
+                            },
+                            {
+                                "type": "JSXExpressionContainer",
+                                "expression": {
+                                    "type": "JSXEmptyExpression",
+                                },
+                                "start": 22,
+                                "end": 24,
+                            }
+                        ],
+                        "start": 0,
+                        "end": 30,
+                    },
+                    "start": 0,
+                    "end": 30,
+                }
+            ],
+            "start": 0,
+            "end": 30
+        });
+    }
+
+    #[test]
+    fn test_jsx_invalid_name() {
+        use parser::{parse_recovering, ParseOptions};
+        use serde_json::{to_string, to_value};
+
+        let (module, errors) = parse_recovering("<1></1>;", ParseOptions { jsx: true, ..ParseOptions::default() });
+
+        assert!(!errors.is_empty());
+
+        let json = to_value(&module).unwrap();
+
+        assert_eq!(json["body"][0]["expression"]["openingElement"]["name"]["type"], "JSXIdentifier");
+        assert_eq!(json["body"][0]["expression"]["openingElement"]["name"]["name"], "");
+        assert!(to_string(&module).is_ok());
+    }
 }
@@ -1,5 +1,6 @@
 use serde::ser::{Serialize, Serializer, SerializeStruct};
 use astgen::SerializeInLoc;
+use astgen::types::{TypeAnnotation, TypeParameters};
 use ast::{Function, Class, Name, MandatoryName, OptionalName, EmptyName, ClassMember, Block};
 use ast::MethodKind;
 
@@ -31,7 +32,7 @@ impl<'ast> SerializeInLoc for ClassMember<'ast> {
                 self.in_loc(serializer, "MethodDefinition", 5, |state| {
                     state.serialize_field("kind", &kind)?;
                     state.serialize_field("static", &is_static)?;
-                    state.serialize_field("computed", &false)?;
+                    state.serialize_field("computed", &key.is_computed())?;
                     state.serialize_field("key", &*key)?;
                     state.serialize_field("value", &value)
                 })
@@ -104,14 +105,22 @@ where
     where
         S: Serializer,
     {
-        self.in_loc(serializer, N::IN_CLASS, 3, |state| {
-            state.serialize_field("id", &self.name)?;
-            state.serialize_field("superClass", &self.extends)?;
-            state.serialize_field("body", &self.body)
-        })
+        serialize_class(self, serializer, N::IN_CLASS)
     }
 }
 
+fn serialize_class<'ast, N, S>(class: &Class<'ast, N>, serializer: S, name: &'static str) -> Result<S::SerializeStruct, S::Error>
+where
+    N: Serialize + NameType<'ast>,
+    S: Serializer,
+{
+    class.in_loc(serializer, name, 3, |state| {
+        state.serialize_field("id", &class.name)?;
+        state.serialize_field("superClass", &class.extends)?;
+        state.serialize_field("body", &class.body)
+    })
+}
+
 impl<'ast, N> SerializeInLoc for Function<'ast, N>
 where
     N: Serialize + NameType<'ast>,
@@ -120,11 +129,48 @@ where
     where
         S: Serializer,
     {
-        self.in_loc(serializer, N::IN_FUNCTION, 3, |state| {
-            state.serialize_field("generator", &self.generator)?;
-            state.serialize_field("id", &self.name)?;
-            state.serialize_field("params", &self.params)?;
-            state.serialize_field("body", &self.body)
-        })
+        serialize_function(self, serializer, N::IN_FUNCTION)
+    }
+}
+
+fn serialize_function<'ast, N, S>(function: &Function<'ast, N>, serializer: S, name: &'static str) -> Result<S::SerializeStruct, S::Error>
+where
+    N: Serialize + NameType<'ast>,
+    S: Serializer,
+{
+    function.in_loc(serializer, name, 3, |state| {
+        state.serialize_field("generator", &function.generator)?;
+        state.serialize_field("async", &function.is_async)?;
+        state.serialize_field("id", &function.name)?;
+        if !function.type_parameters.is_empty() {
+            state.serialize_field("typeParameters", &TypeParameters(function.type_parameters))?;
+        }
+        state.serialize_field("params", &function.params)?;
+        if let Some(return_type) = function.return_type {
+            state.serialize_field("returnType", &TypeAnnotation(return_type))?;
+        }
+        state.serialize_field("body", &function.body)
+    })
+}
+
+/// Function or class with an optional name, serialized as a declaration
+/// rather than an expression, as in `export default function () {}`.
+pub struct AsDeclaration<T>(pub T);
+
+impl<'ast> SerializeInLoc for AsDeclaration<Function<'ast, OptionalName<'ast>>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_function(&self.0, serializer, MandatoryName::IN_FUNCTION)
+    }
+}
+
+impl<'ast> SerializeInLoc for AsDeclaration<Class<'ast, OptionalName<'ast>>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_class(&self.0, serializer, MandatoryName::IN_CLASS)
     }
 }
\ No newline at end of file
@@ -1,14 +1,18 @@
 /// Parses the given input string into an AST and compares it
-/// with the given JSON input.
+/// with the given JSON input. Takes optional `ParseOptions` as the
+/// second argument.
 
 #[cfg(test)]
 #[macro_export]
 macro_rules! expect_parse {
-    ($expr:expr, $expected:tt) => {{
-        use $crate::parser::parse;
+    ($expr:expr, $expected:tt) => {
+        expect_parse!($expr, Default::default(), $expected)
+    };
+    ($expr:expr, $options:expr, $expected:tt) => {{
+        use $crate::parser::parse_with;
         use $crate::serde_json::to_value;
 
-        let module = parse($expr).unwrap();
+        let module = parse_with($expr, $options).unwrap();
         let result = to_value(&module).unwrap();
         let expected = json!($expected);
         assert_eq!(result, expected);
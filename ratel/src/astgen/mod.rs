@@ -4,6 +4,7 @@ mod statement;
 mod expression;
 mod function;
 mod value;
+mod types;
 
 use serde::ser::{Serialize, Serializer, SerializeStruct};
 use ast::{Loc, Node};
@@ -78,6 +79,29 @@ impl<'ast> Serialize for Module<'ast> {
 
 #[cfg(test)]
 mod test {
+    use parser::parse;
+    use serde_json::to_string;
+
+    #[test]
+    fn test_generate_ast_independent_of_arena() {
+        let src = r#"
+            import foo, { bar as baz } from "qux";
+            const [a, { b, c }] = `x${ foo }y`;
+            class Doge extends baz { static much() { return /wow/gi.test(a || 0.5); } }
+            function gen(...args) { return args; }
+        "#;
+
+        let first = parse(src).unwrap();
+        let first_json = to_string(&first).unwrap();
+
+        // Keep the first module and its arena alive, so that the second
+        // one is allocated in a different place in memory
+        let second = parse(src).unwrap();
+        let second_json = to_string(&second).unwrap();
+
+        assert_eq!(first_json, second_json);
+    }
+
     #[test]
     fn test_generate_ast_empty() {
         expect_parse!("", {
@@ -1,6 +1,7 @@
 use ast::statement::*;
 use ast::{Block, DeclarationKind, Declarator, Loc, Statement};
 use astgen::SerializeInLoc;
+use astgen::function::AsDeclaration;
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
 // TODO: DRY with BlockStatement
@@ -47,6 +48,8 @@ impl<'ast> Serialize for DeclarationKind {
             Const => serializer.serialize_str("const"),
             Let => serializer.serialize_str("let"),
             Var => serializer.serialize_str("var"),
+            Using => serializer.serialize_str("using"),
+            AwaitUsing => serializer.serialize_str("await using"),
         }
     }
 }
@@ -240,6 +243,64 @@ impl<'ast> SerializeInLoc for ForImportSpecifier<'ast> {
     }
 }
 
+impl<'ast> SerializeInLoc for ExportDeclaration<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "ExportNamedDeclaration", 3, |state| {
+            state.serialize_field("declaration", &self.declaration)?;
+            state.serialize_field("specifiers", &[(); 0])?;
+            state.serialize_field("source", &())
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for ExportNamedDeclaration<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "ExportNamedDeclaration", 3, |state| {
+            state.serialize_field("declaration", &())?;
+            state.serialize_field("specifiers", &self.specifiers)?;
+            state.serialize_field("source", &self.source)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for ExportSpecifier<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "ExportSpecifier", 2, |state| {
+            state.serialize_field("local", &self.local)?;
+            state.serialize_field("exported", &self.exported)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for ExportDefaultDeclaration<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "ExportDefaultDeclaration", 1, |state| {
+            match *self {
+                ExportDefaultDeclaration::Function(function) => {
+                    state.serialize_field("declaration", &Loc::new(function.start, function.end, AsDeclaration(function.item)))
+                },
+                ExportDefaultDeclaration::Class(class) => {
+                    state.serialize_field("declaration", &Loc::new(class.start, class.end, AsDeclaration(class.item)))
+                },
+                ExportDefaultDeclaration::Expression(value) => {
+                    state.serialize_field("declaration", &value)
+                },
+            }
+        })
+    }
+}
 
 impl<'ast> SerializeInLoc for Statement<'ast> {
     fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
@@ -299,7 +360,12 @@ impl<'ast> SerializeInLoc for Statement<'ast> {
             Function(statement) => statement.serialize(serializer),
             Class(statement) => statement.serialize(serializer),
             Switch(statement) => statement.serialize(serializer),
-            Import(statement) => statement.serialize(serializer)
+            Import(statement) => statement.serialize(serializer),
+            Export(statement) => statement.serialize(serializer),
+            ExportNamed(statement) => statement.serialize(serializer),
+            ExportDefault(statement) => statement.serialize(serializer),
+            TypeAlias(statement) => statement.serialize(serializer),
+            Interface(statement) => statement.serialize(serializer),
         }
     }
 }
@@ -516,7 +582,11 @@ mod test {
 
     #[test]
     fn test_statement_return_statement() {
-        expect_parse!("return;", {
+        use parser::ParseOptions;
+
+        let options = ParseOptions { allow_return_outside_function: true, ..ParseOptions::default() };
+
+        expect_parse!("return;", options, {
             "type": "Program",
             "body": [
                 {
@@ -530,7 +600,7 @@ mod test {
               "end": 6,
         });
 
-        expect_parse!("return foo;", {
+        expect_parse!("return foo;", options, {
             "type": "Program",
             "body": [
                 {
@@ -602,23 +672,40 @@ mod test {
               "end": 8
         });
 
-        expect_parse!("continue foo;", {
+        expect_parse!("foo: while (x) continue foo;", {
             "type": "Program",
             "body": [
                 {
-                    "type": "ContinueStatement",
-                    "label": {
-                        "type": "Identifier",
-                        "name": "foo",
-                        "start": 9,
-                        "end": 12
+                    "type": "LabeledStatement",
+                    "label": "foo",
+                    "body": {
+                        "type": "WhileStatement",
+                        "test": {
+                            "type": "Identifier",
+                            "name": "x",
+                            "start": 12,
+                            "end": 13
+                        },
+                        "body": {
+                            "type": "ContinueStatement",
+                            "label": {
+                                "type": "Identifier",
+                                "name": "foo",
+                                "start": 24,
+                                "end": 27
+                            },
+                            "start": 15,
+                            "end": 27,
+                        },
+                        "start": 5,
+                        "end": 27,
                     },
                     "start": 0,
-                    "end": 12,
+                    "end": 27,
                 }
               ],
               "start": 0,
-              "end": 12,
+              "end": 27,
         });
     }
 
@@ -978,6 +1065,181 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_export_declaration() {
+        expect_parse!("export async function foo () {}", {
+            "type": "Program",
+            "body": [
+                {
+                    "type": "ExportNamedDeclaration",
+                    "declaration": {
+                        "type": "FunctionDeclaration",
+                        "generator": false,
+                        "async": true,
+                        "id": {
+                            "type": "Identifier",
+                            "name": "foo",
+                            "start": 22,
+                            "end": 25
+                        },
+                        "params": [],
+                        "body": {
+                            "type": "BlockStatement",
+                            "body": [],
+                            "start": 29,
+                            "end": 31,
+                        },
+                        "start": 7,
+                        "end": 31,
+                    },
+                    "specifiers": [],
+                    "source": null,
+                    "start": 0,
+                    "end": 31,
+                }
+            ],
+            "start": 0,
+            "end": 31,
+        });
+    }
+
+    #[test]
+    fn test_export_named_declaration() {
+        expect_parse!("export { x as default } from 'm'", {
+            "type": "Program",
+            "body": [
+                {
+                    "type": "ExportNamedDeclaration",
+                    "declaration": null,
+                    "specifiers": [
+                        {
+                            "type": "ExportSpecifier",
+                            "local": {
+                                "type": "Identifier",
+                                "name": "x",
+                                "start": 9,
+                                "end": 10,
+                            },
+                            "exported": {
+                                "type": "Identifier",
+                                "name": "default",
+                                "start": 14,
+                                "end": 21,
+                            },
+                            "start": 9,
+                            "end": 21,
+                        },
+                    ],
+                    "source": "m",
+                    "start": 0,
+                    "end": 32,
+                }
+            ],
+            "start": 0,
+            "end": 32,
+        });
+        expect_parse!("export { a as \"b c\" }", {
+            "type": "Program",
+            "body": [
+                {
+                    "type": "ExportNamedDeclaration",
+                    "declaration": null,
+                    "specifiers": [
+                        {
+                            "type": "ExportSpecifier",
+                            "local": {
+                                "type": "Identifier",
+                                "name": "a",
+                                "start": 9,
+                                "end": 10,
+                            },
+                            "exported": {
+                                "type": "Literal",
+                                "value": "b c",
+                                "raw": "\"b c\"",
+                                "start": 14,
+                                "end": 19,
+                            },
+                            "start": 9,
+                            "end": 19,
+                        },
+                    ],
+                    "source": null,
+                    "start": 0,
+                    "end": 21,
+                }
+            ],
+            "start": 0,
+            "end": 21,
+        });
+    }
+
+    #[test]
+    fn test_export_default_declaration() {
+        expect_parse!("export default (a, b);", {
+            "type": "Program",
+            "body": [
+                {
+                    "type": "ExportDefaultDeclaration",
+                    "declaration": {
+                        "type": "SequenceExpression",
+                        "expressions": [
+                            {
+                                "type": "Identifier",
+                                "name": "a",
+                                "start": 16,
+                                "end": 17,
+                            },
+                            {
+                                "type": "Identifier",
+                                "name": "b",
+                                "start": 19,
+                                "end": 20,
+                            }
+                        ],
+                        "start": 16,
+                        "end": 21,
+                    },
+                    "start": 0,
+                    "end": 21,
+                }
+            ],
+            "start": 0,
+            "end": 21,
+        });
+    }
+
+    #[test]
+    fn test_export_default_anonymous_function() {
+        expect_parse!("export default function () {}", {
+            "type": "Program",
+            "body": [
+                {
+                    "type": "ExportDefaultDeclaration",
+                    "declaration": {
+                        "type": "FunctionDeclaration",
+                        "generator": false,
+                        "async": false,
+                        "id": null,
+                        "params": [],
+                        "body": {
+                            "type": "BlockStatement",
+                            "body": [],
+                            "start": 27,
+                            "end": 29,
+                        },
+                        "start": 15,
+                        "end": 29,
+                    },
+                    "start": 0,
+                    "end": 29,
+                }
+            ],
+            "start": 0,
+            "end": 29,
+        });
+    }
+
     #[test]
     fn test_function_statement() {
         expect_parse!("function foo () {}", {
@@ -986,6 +1248,7 @@ mod test {
                 {
                     "type": "FunctionDeclaration",
                     "generator": false,
+                    "async": false,
                     "id": {
                         "type": "Identifier",
                         "name": "foo",
@@ -1013,6 +1276,7 @@ mod test {
                 {
                     "type": "FunctionDeclaration",
                     "generator": true,
+                    "async": false,
                     "id": {
                         "type": "Identifier",
                         "name": "foo",
@@ -1040,6 +1304,7 @@ mod test {
                 {
                     "type": "FunctionDeclaration",
                     "generator": false,
+                    "async": false,
                     "id": {
                         "type": "Identifier",
                         "name": "foo",
@@ -0,0 +1,346 @@
+use serde::ser::{Serialize, Serializer, SerializeStruct};
+use astgen::SerializeInLoc;
+use ast::{Literal, Type, Primitive, TypeNode, TypeList, TypeMember, TypeParameter, TypeParameterList};
+use ast::expression::{TypeCastKind, TypeCastExpression, InstantiationExpression};
+use ast::statement::{TypeAliasDeclaration, InterfaceDeclaration};
+
+/// Annotations are wrapped in a `TSTypeAnnotation` node spanning the type.
+pub struct TypeAnnotation<'ast>(pub TypeNode<'ast>);
+
+impl<'ast> Serialize for TypeAnnotation<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("TSTypeAnnotation", 4)?;
+        state.serialize_field("type", "TSTypeAnnotation")?;
+        state.serialize_field("typeAnnotation", &self.0)?;
+        state.serialize_field("start", &self.0.start)?;
+        state.serialize_field("end", &self.0.end)?;
+        state.end()
+    }
+}
+
+pub struct TypeParameters<'ast>(pub TypeParameterList<'ast>);
+
+impl<'ast> Serialize for TypeParameters<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("TSTypeParameterDeclaration", 2)?;
+        state.serialize_field("type", "TSTypeParameterDeclaration")?;
+        state.serialize_field("params", &self.0)?;
+        state.end()
+    }
+}
+
+struct TypeArguments<'ast>(TypeList<'ast>);
+
+impl<'ast> Serialize for TypeArguments<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("TSTypeParameterInstantiation", 2)?;
+        state.serialize_field("type", "TSTypeParameterInstantiation")?;
+        state.serialize_field("params", &self.0)?;
+        state.end()
+    }
+}
+
+/// Names of referenced types don't carry a location of their own.
+struct TypeName<'ast>(&'ast str);
+
+impl<'ast> Serialize for TypeName<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Identifier", 2)?;
+        state.serialize_field("type", "Identifier")?;
+        state.serialize_field("name", self.0)?;
+        state.end()
+    }
+}
+
+struct LiteralType<'ast>(Literal<'ast>);
+
+impl<'ast> Serialize for LiteralType<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        SerializeInLoc::serialize(&self.0, serializer)?.end()
+    }
+}
+
+impl<'ast> SerializeInLoc for Type<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        use self::Type::*;
+
+        match *self {
+            Any => self.in_loc(serializer, "TSAnyKeyword", 0, |_| Ok(())),
+            Primitive(primitive) => {
+                let name = match primitive {
+                    self::Primitive::Number    => "TSNumberKeyword",
+                    self::Primitive::String    => "TSStringKeyword",
+                    self::Primitive::Boolean   => "TSBooleanKeyword",
+                    self::Primitive::Null      => "TSNullKeyword",
+                    self::Primitive::Undefined => "TSUndefinedKeyword",
+                    self::Primitive::Void      => "TSVoidKeyword",
+                    self::Primitive::Never     => "TSNeverKeyword",
+                    self::Primitive::Unknown   => "TSUnknownKeyword",
+                };
+
+                self.in_loc(serializer, name, 0, |_| Ok(()))
+            },
+            Identifier(name) => self.in_loc(serializer, "TSTypeReference", 1, |state| {
+                state.serialize_field("typeName", &TypeName(name))
+            }),
+            Literal(literal) => self.in_loc(serializer, "TSLiteralType", 1, |state| {
+                state.serialize_field("literal", &LiteralType(literal))
+            }),
+            Union { variants } => self.in_loc(serializer, "TSUnionType", 1, |state| {
+                state.serialize_field("types", &variants)
+            }),
+            Intersection { variants } => self.in_loc(serializer, "TSIntersectionType", 1, |state| {
+                state.serialize_field("types", &variants)
+            }),
+            Generic { ident, subtypes } => self.in_loc(serializer, "TSTypeReference", 2, |state| {
+                state.serialize_field("typeName", &TypeName(ident))?;
+                state.serialize_field("typeArguments", &TypeArguments(subtypes))
+            }),
+            Array { element } => self.in_loc(serializer, "TSArrayType", 1, |state| {
+                state.serialize_field("elementType", &element)
+            }),
+            Tuple { elements } => self.in_loc(serializer, "TSTupleType", 1, |state| {
+                state.serialize_field("elementTypes", &elements)
+            }),
+            Function { params, returns } => self.in_loc(serializer, "TSFunctionType", 2, |state| {
+                state.serialize_field("params", &params)?;
+                state.serialize_field("returnType", &TypeAnnotation(returns))
+            }),
+            Object { members } => self.in_loc(serializer, "TSTypeLiteral", 1, |state| {
+                state.serialize_field("members", &members)
+            }),
+        }
+    }
+}
+
+impl<'ast> SerializeInLoc for TypeMember<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSPropertySignature", 3, |state| {
+            state.serialize_field("key", &self.key)?;
+            state.serialize_field("optional", &self.optional)?;
+            state.serialize_field("typeAnnotation", &TypeAnnotation(self.value))
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for TypeParameter<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSTypeParameter", 3, |state| {
+            state.serialize_field("name", self.name)?;
+            state.serialize_field("constraint", &self.constraint)?;
+            state.serialize_field("default", &self.default)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for TypeCastExpression<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        let name = match self.kind {
+            TypeCastKind::As        => "TSAsExpression",
+            TypeCastKind::Satisfies => "TSSatisfiesExpression",
+        };
+
+        self.in_loc(serializer, name, 2, |state| {
+            state.serialize_field("expression", &self.expression)?;
+            state.serialize_field("typeAnnotation", &self.annotation)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for InstantiationExpression<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSInstantiationExpression", 2, |state| {
+            state.serialize_field("expression", &self.expression)?;
+            state.serialize_field("typeArguments", &TypeArguments(self.arguments))
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for TypeAliasDeclaration<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSTypeAliasDeclaration", 3, |state| {
+            state.serialize_field("id", &self.name)?;
+            if !self.type_parameters.is_empty() {
+                state.serialize_field("typeParameters", &TypeParameters(self.type_parameters))?;
+            }
+            state.serialize_field("typeAnnotation", &self.value)
+        })
+    }
+}
+
+struct InterfaceBody<'a, 'ast: 'a>(&'a InterfaceDeclaration<'ast>);
+
+impl<'a, 'ast> Serialize for InterfaceBody<'a, 'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("TSInterfaceBody", 2)?;
+        state.serialize_field("type", "TSInterfaceBody")?;
+        state.serialize_field("body", &self.0.body)?;
+        state.end()
+    }
+}
+
+impl<'ast> SerializeInLoc for InterfaceDeclaration<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSInterfaceDeclaration", 4, |state| {
+            state.serialize_field("id", &self.name)?;
+            if !self.type_parameters.is_empty() {
+                state.serialize_field("typeParameters", &TypeParameters(self.type_parameters))?;
+            }
+            state.serialize_field("extends", &self.extends)?;
+            state.serialize_field("body", &InterfaceBody(self))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use parser::ParseOptions;
+
+    fn ts() -> ParseOptions {
+        ParseOptions { typescript: true, ..ParseOptions::default() }
+    }
+
+    #[test]
+    fn test_type_alias() {
+        expect_parse!("type Id = string | number[];", ts(), {
+            "type": "Program",
+            "body": [
+                {
+                    "type": "TSTypeAliasDeclaration",
+                    "id": {
+                        "type": "Identifier",
+                        "name": "Id",
+                        "start": 5,
+                        "end": 7,
+                    },
+                    "typeAnnotation": {
+                        "type": "TSUnionType",
+                        "types": [
+                            {
+                                "type": "TSStringKeyword",
+                                "start": 10,
+                                "end": 16,
+                            },
+                            {
+                                "type": "TSArrayType",
+                                "elementType": {
+                                    "type": "TSNumberKeyword",
+                                    "start": 19,
+                                    "end": 25,
+                                },
+                                "start": 19,
+                                "end": 27,
+                            }
+                        ],
+                        "start": 10,
+                        "end": 27,
+                    },
+                    "start": 0,
+                    "end": 27,
+                }
+            ],
+            "start": 0,
+            "end": 27,
+        });
+    }
+
+    #[test]
+    fn test_annotated_identifier() {
+        expect_parse!("let a: T = b as any;", ts(), {
+            "type": "Program",
+            "body": [
+                {
+                    "type": "VariableDeclaration",
+                    "kind": "let",
+                    "declarations": [
+                        {
+                            "type": "VariableDeclarator",
+                            "id": {
+                                "type": "Identifier",
+                                "name": "a",
+                                "optional": false,
+                                "typeAnnotation": {
+                                    "type": "TSTypeAnnotation",
+                                    "typeAnnotation": {
+                                        "type": "TSTypeReference",
+                                        "typeName": {
+                                            "type": "Identifier",
+                                            "name": "T",
+                                        },
+                                        "start": 7,
+                                        "end": 8,
+                                    },
+                                    "start": 7,
+                                    "end": 8,
+                                },
+                                "start": 4,
+                                "end": 8,
+                            },
+                            "init": {
+                                "type": "TSAsExpression",
+                                "expression": {
+                                    "type": "Identifier",
+                                    "name": "b",
+                                    "start": 11,
+                                    "end": 12,
+                                },
+                                "typeAnnotation": {
+                                    "type": "TSAnyKeyword",
+                                    "start": 16,
+                                    "end": 19,
+                                },
+                                "start": 11,
+                                "end": 19,
+                            },
+                            "start": 4,
+                            "end": 19,
+                        }
+                    ],
+                    "start": 0,
+                    "end": 20,
+                }
+            ],
+            "start": 0,
+            "end": 20,
+        });
+    }
+}
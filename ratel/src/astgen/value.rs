@@ -1,6 +1,7 @@
 use ast::expression::{PropertyKey, TaggedTemplateExpression, TemplateLiteral};
-use ast::{Expression, Literal, Loc, NodeList, Pattern, Property};
+use ast::{Expression, Literal, Loc, NodeList, Pattern, Property, MethodKind};
 use astgen::SerializeInLoc;
+use astgen::types::TypeAnnotation;
 use serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
 
 #[derive(Debug, Serialize, PartialEq)]
@@ -10,7 +11,7 @@ pub struct RegExLiteral<'ast> {
 }
 
 #[inline]
-pub fn parse_regex(value: &str) -> RegExLiteral {
+pub fn parse_regex(value: &str) -> RegExLiteral<'_> {
     let mut end = value.len() - 1;
     for index in (0..value.len()).rev() {
         if "/" == &value[index..=index] {
@@ -30,6 +31,48 @@ pub fn is_float(value: &str) -> bool {
     value.bytes().any(|c| c == b'.')
 }
 
+/// Value of a numeric literal. Integers are kept exact as long as they fit
+/// in `u64`, anything else is rounded to a double. The literal as written
+/// is always available in `raw`, this is only ever used for `value`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum NumberValue {
+    Integer(u64),
+    Float(f64),
+}
+
+impl NumberValue {
+    pub fn of(literal: &Literal) -> NumberValue {
+        let integer = match *literal {
+            Literal::Number(number) if !is_float(number) => match number.get(..2) {
+                Some("0x") | Some("0X") => u64::from_str_radix(&number[2..], 16).ok(),
+                Some("0o") | Some("0O") => u64::from_str_radix(&number[2..], 8).ok(),
+                // Legacy octal, such as `017`
+                _ if number.len() > 1 && number.starts_with('0') => None,
+                _ => number.parse().ok(),
+            },
+            Literal::Binary(number) => u64::from_str_radix(&number[2..], 2).ok(),
+            _ => None,
+        };
+
+        match integer {
+            Some(integer) => NumberValue::Integer(integer),
+            None => NumberValue::Float(literal.number_value().unwrap_or(0.0)),
+        }
+    }
+}
+
+impl Serialize for NumberValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            NumberValue::Integer(integer) => serializer.serialize_u64(integer),
+            NumberValue::Float(float) => serializer.serialize_f64(float),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct TemplateElement<'ast> {
     pub tail: bool,
@@ -68,11 +111,16 @@ impl<'ast> Serialize for TemplateQuasis<'ast> {
     }
 }
 
-impl<'ast> SerializeInLoc for &'ast str {
+impl SerializeInLoc for &str {
     fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
     where
         S: Serializer,
     {
+        // Names of imports and exports can be strings, `export { a as "a b" }`
+        if self.starts_with('"') || self.starts_with('\'') {
+            return Literal::String(self).serialize(serializer);
+        }
+
         self.in_loc(serializer, "Identifier", 1, |state| {
             state.serialize_field("name", *self)
         })
@@ -114,28 +162,27 @@ impl<'ast> SerializeInLoc for Property<'ast> {
                 Expression::Identifier(value).serialize(serializer)
             }
             Literal { key, value } => {
-                let computed = if let PropertyKey::Computed(_) = key.item {
-                    true
-                } else {
-                    false
-                };
-
                 self.in_loc(serializer, "Property", 6, |state| {
                     state.serialize_field("key", &*key)?;
                     state.serialize_field("method", &false)?;
                     state.serialize_field("shorthand", &false)?;
-                    state.serialize_field("computed", &computed)?;
+                    state.serialize_field("computed", &key.is_computed())?;
                     state.serialize_field("value", &value)?;
                     state.serialize_field("kind", &"init")
                 })
             }
-            Method { key, value } => self.in_loc(serializer, "Property", 6, |state| {
+            // Accessors aren't methods, and have their own `kind`
+            Method { key, kind, value } => self.in_loc(serializer, "Property", 6, |state| {
                 state.serialize_field("key", &*key)?;
-                state.serialize_field("method", &true)?;
+                state.serialize_field("method", &(kind == MethodKind::Method))?;
                 state.serialize_field("shorthand", &false)?;
-                state.serialize_field("computed", &false)?;
+                state.serialize_field("computed", &key.is_computed())?;
                 state.serialize_field("value", &value)?;
-                state.serialize_field("kind", &"init")
+                match kind {
+                    MethodKind::Get => state.serialize_field("kind", &"get"),
+                    MethodKind::Set => state.serialize_field("kind", &"set"),
+                    _               => state.serialize_field("kind", &"init"),
+                }
             }),
             Spread { argument } => self.in_loc(serializer, "SpreadProperty", 1, |state| {
                 state.serialize_field("argument", &argument)
@@ -197,38 +244,9 @@ impl<'ast> SerializeInLoc for Literal<'ast> {
                     state.serialize_field("value", &false)?;
                     state.serialize_field("raw", &"false")
                 },
-                Number(number) => {
-                    if number.len() > 2 {
-                        let prefix = &number[0..2];
-                        let is_hexdecimal = prefix == "0x" || prefix == "0X";
-                        let is_octal = prefix == "0o" || prefix == "0O";
-                        if is_hexdecimal || is_octal {
-                            let value = unsafe { number.get_unchecked(2..number.len()) };
-                            let radix = if is_hexdecimal { 16 } else { 8 };
-                            state.serialize_field(
-                                "value",
-                                &i32::from_str_radix(value, radix).expect("Invalid number"),
-                            )?;
-                            return state.serialize_field("raw", &number)
-                        }
-                    }
-
-                    if is_float(number) {
-                        let value: f64 = number.parse().expect("Invalid number");
-                        state.serialize_field("value", &value)?;
-                        return state.serialize_field("raw", &number)
-                    }
-
-                    let value: i64 = number.parse().expect("Invalid number");
-                    state.serialize_field("value", &value)?;
-                    state.serialize_field("raw", &number)
-                }
+                Number(number) |
                 Binary(number) => {
-                    let value = unsafe { number.get_unchecked(2..number.len()) };
-                    state.serialize_field(
-                        "value",
-                        &i32::from_str_radix(value, 2).expect("Invalid number"),
-                    )?;
+                    state.serialize_field("value", &NumberValue::of(self))?;
                     state.serialize_field("raw", &number)
                 }
                 String(value) => {
@@ -270,6 +288,12 @@ impl<'ast> SerializeInLoc for Pattern<'ast> {
             RestElement { argument } => self.in_loc(serializer, "RestElement", 1, |state| {
                 state.serialize_field("argument", &argument)
             }),
+            Annotated { pattern, optional, annotation } => {
+                let mut state = pattern.item.serialize(serializer)?;
+                state.serialize_field("optional", &optional)?;
+                state.serialize_field("typeAnnotation", &annotation.map(TypeAnnotation))?;
+                Ok(state)
+            },
         }
     }
 }
@@ -321,6 +345,45 @@ mod test {
         assert_eq!(is_float(".1"), true);
     }
 
+    #[test]
+    fn test_number_value() {
+        assert_eq!(NumberValue::of(&Literal::Number("9007199254740993")), NumberValue::Integer(9007199254740993));
+        assert_eq!(NumberValue::of(&Literal::Number("0xffffffffff")), NumberValue::Integer(0xffffffffff));
+        assert_eq!(NumberValue::of(&Literal::Number("017")), NumberValue::Float(15.0));
+        assert_eq!(NumberValue::of(&Literal::Number("0.1")), NumberValue::Float(0.1));
+        assert_eq!(NumberValue::of(&Literal::Number("1e10")), NumberValue::Float(1e10));
+        assert_eq!(NumberValue::of(&Literal::Number("123456789012345678901234567890")), NumberValue::Float(1.2345678901234568e29));
+        assert_eq!(NumberValue::of(&Literal::Binary("0b1111")), NumberValue::Integer(15));
+    }
+
+    #[test]
+    fn test_value_number_exact_text() {
+        use parser::parse;
+        use serde_json::to_string;
+
+        let literals = [
+            "9007199254740993",
+            "0.1",
+            "0.30000000000000004",
+            "123456789012345678901234567890",
+            "1.000000000000000000001",
+            "1e400",
+            "0xffffffffffffffffffff",
+        ];
+
+        for literal in literals.iter() {
+            let module = parse(literal).unwrap();
+            let json = to_string(&module).unwrap();
+            let raw = format!(r#""raw":"{}""#, literal);
+
+            assert!(json.contains(&raw), "{} is missing from {}", raw, json);
+        }
+
+        let json = to_string(&parse("9007199254740993").unwrap()).unwrap();
+
+        assert!(json.contains(r#""value":9007199254740993,"#), "{}", json);
+    }
+
     #[test]
     fn test_value_undefined() {
         expect_parse!("undefined", {
@@ -0,0 +1,131 @@
+use lexer::{Comment, CommentKind};
+use span::{is_line_terminator, Span};
+
+/// Markers looked for by default, see `find_markers`.
+pub const DEFAULT_MARKERS: &[&str] = &["TODO", "FIXME", "HACK"];
+
+/// A marker such as `TODO` found in a comment, along with the text that
+/// follows it.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Marker<'src> {
+    pub marker: &'src str,
+    /// Rest of the line following the marker, without a leading `:`
+    pub text: &'src str,
+    /// Span from the start of the marker to the end of `text`
+    pub span: Span,
+}
+
+/// Find all `markers` within `comments` recorded for `source`, such as
+/// `// TODO: ...`. Markers have to be whole words, and are looked for
+/// once on each line, so a block comment may hold more than one.
+pub fn find_markers<'src>(source: &'src str, comments: &[Comment], markers: &[&str]) -> Vec<Marker<'src>> {
+    let mut found = Vec::new();
+
+    for comment in comments {
+        // Leave out the delimiters
+        let start = comment.span.start as usize + 2;
+        let end = match comment.kind {
+            CommentKind::Line  => comment.span.end as usize,
+            CommentKind::Block => comment.span.end as usize - 2,
+        };
+
+        for line in source[start..end].split(is_line_terminator) {
+            let offset = line.as_ptr() as usize - source.as_ptr() as usize;
+
+            let (index, marker) = match find_marker(line, markers) {
+                Some(found) => found,
+                None        => continue,
+            };
+
+            let text_start = index + marker.len();
+            let text = line[text_start..].trim_start_matches(':').trim();
+            let text_end = match text.len() {
+                0 => text_start,
+                _ => text.as_ptr() as usize - line.as_ptr() as usize + text.len(),
+            };
+
+            found.push(Marker {
+                marker: &line[index..text_start],
+                text,
+                span: Span::new((offset + index) as u32, (offset + text_end) as u32),
+            });
+        }
+    }
+
+    found
+}
+
+/// Find the first of `markers` in `line` that is a whole word.
+fn find_marker<'a>(line: &str, markers: &[&'a str]) -> Option<(usize, &'a str)> {
+    markers
+        .iter()
+        .filter_map(|&marker| {
+            line.match_indices(marker)
+                .map(|(index, _)| index)
+                .find(|&index| {
+                    let before = line[..index].chars().next_back();
+                    let after = line[index + marker.len()..].chars().next();
+
+                    !before.into_iter().chain(after).any(is_word_char)
+                })
+                .map(|index| (index, marker))
+        })
+        .min_by_key(|&(index, _)| index)
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_' || ch == '$'
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use parser::{parse_collecting, ParseOptions};
+
+    fn comments(source: &str) -> Vec<Comment> {
+        parse_collecting(source, ParseOptions { comments: true, ..ParseOptions::default() }).unwrap().comments
+    }
+
+    #[test]
+    fn collects_comments() {
+        let source = "/* a */ foo(); // b\n/** c\n */ bar; // d";
+        let comments = comments(source);
+
+        assert_eq!(comments, [
+            Comment { kind: CommentKind::Block, span: Span::new(0, 7) },
+            Comment { kind: CommentKind::Line, span: Span::new(15, 19) },
+            Comment { kind: CommentKind::Block, span: Span::new(20, 29) },
+            Comment { kind: CommentKind::Line, span: Span::new(35, 39) },
+        ]);
+    }
+
+    #[test]
+    fn finds_markers() {
+        let source = "// TODO: first\nfoo(); // not a TODOS list\n/*\n * FIXME fix this\n * HACK */\nbar(/* just a comment */);";
+        let comments = comments(source);
+        let markers = find_markers(source, &comments, DEFAULT_MARKERS);
+
+        assert_eq!(markers, [
+            Marker { marker: "TODO", text: "first", span: Span::new(3, 14) },
+            Marker { marker: "FIXME", text: "fix this", span: Span::new(48, 62) },
+            Marker { marker: "HACK", text: "", span: Span::new(66, 70) },
+        ]);
+
+        for marker in markers {
+            let slice = &source[marker.span.start as usize..marker.span.end as usize];
+
+            assert!(slice.starts_with(marker.marker));
+            assert!(slice.ends_with(marker.text));
+        }
+    }
+
+    #[test]
+    fn custom_markers() {
+        let source = "// XXX: later\n// TODO: never";
+        let comments = comments(source);
+
+        assert_eq!(find_markers(source, &comments, &["XXX"]), [
+            Marker { marker: "XXX", text: "later", span: Span::new(3, 13) },
+        ]);
+    }
+}
@@ -1,19 +1,245 @@
 use std::fmt::{self, Debug, Display};
+use serde::ser::{Serialize, Serializer, SerializeStruct};
 use lexer::Token;
+use span::{line_column, is_line_terminator, Span};
+
+/// Machine-readable category of an `Error`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize)]
+pub enum ErrorCode {
+    /// A token that is not valid in its position.
+    UnexpectedToken,
+
+    /// Source ended before the construct was complete.
+    UnexpectedEndOfProgram,
+
+    /// Left hand side of an assignment or update is not assignable.
+    InvalidAssignmentTarget,
+
+    /// The same name is bound twice in a parameter list that requires unique names.
+    DuplicateParam,
+
+    /// A template literal directly following an optional chain, `a?.b`x``.
+    TaggedTemplateInOptionalChain,
+
+    /// An optional chain as the constructor of `new`, `new a?.b()`.
+    OptionalChainInNew,
+
+    /// A class body with more than one `constructor` method.
+    DuplicateConstructor,
+
+    /// A class `constructor` that is not a plain method, such as `get constructor() {}`.
+    InvalidConstructor,
+
+    /// A JSX closing tag that doesn't match its opening tag, `<a></b>`.
+    MismatchedClosingTag,
+
+    /// A required parameter following an optional one, `(a?: T, b: T)`.
+    RequiredParamAfterOptional,
+
+    /// A `)`, `]` or `}` at the start of a statement, with no bracket to close.
+    UnmatchedClosingBracket,
+
+    /// Expressions, statements, patterns or types nested deeper than the parser supports.
+    NestingTooDeep,
+
+    /// `new.target` outside of any function.
+    InvalidNewTarget,
+
+    /// `super()` outside of the constructor of a derived class.
+    InvalidSuperCall,
+
+    /// `super.a` or `super[a]` outside of a method.
+    InvalidSuperProperty,
+
+    /// `continue` to a label that doesn't enclose it within the same function.
+    UndefinedLabel,
+
+    /// `continue` to a label of a statement that isn't a loop, `a: { continue a; }`.
+    InvalidContinueLabel,
+
+    /// An arrow function marked as a generator, `*() => {}`.
+    GeneratorArrow,
+
+    /// A rest element followed by more properties, `{ ...a, b } = c`.
+    RestElementNotLast,
+
+    /// A `using` declaration without an initializer, `using a;`.
+    MissingInitializer,
+
+    /// A destructuring pattern in a `using` declaration, `using { a } = b;`.
+    InvalidUsingBinding,
+
+    /// `await using` outside of an async function.
+    InvalidAwaitUsing,
+
+    /// `return` outside of any function, unless `allow_return_outside_function` is set.
+    InvalidReturn,
+
+    /// `??` mixed with `||` or `&&` without parentheses, `a ?? b || c`.
+    MixedNullish,
+}
 
 /// Error type used by the tokenizer and the parser internally.
 #[derive(PartialEq, Clone)]
 pub struct Error {
+    pub code: ErrorCode,
     pub token: Token,
+    /// Token the parser was expecting instead, if known.
+    pub expected: Option<Token>,
     pub raw: Box<str>,
-    pub start: usize,
-    pub end: usize,
+    pub span: Span,
+}
+
+impl Error {
+    /// Human readable description of the error, without any positional information.
+    pub fn message(&self) -> String {
+        let mut message = match self.code {
+            ErrorCode::UnexpectedToken => {
+                format!("Unexpected token `{}`", &*self.raw)
+            },
+            ErrorCode::UnexpectedEndOfProgram => {
+                String::from("Unexpected end of program")
+            },
+            ErrorCode::InvalidAssignmentTarget => {
+                format!("Invalid assignment target `{}`", &*self.raw)
+            },
+            ErrorCode::DuplicateParam => {
+                format!("Duplicate parameter name `{}`", &*self.raw)
+            },
+            ErrorCode::TaggedTemplateInOptionalChain => {
+                String::from("Tagged template cannot be used in an optional chain")
+            },
+            ErrorCode::OptionalChainInNew => {
+                String::from("Optional chain cannot be used as a constructor")
+            },
+            ErrorCode::DuplicateConstructor => {
+                String::from("A class may only have one constructor")
+            },
+            ErrorCode::InvalidConstructor => {
+                String::from("Class constructor may not be an accessor or a field")
+            },
+            ErrorCode::MismatchedClosingTag => {
+                format!("Closing tag `{}` doesn't match its opening tag", &*self.raw)
+            },
+            ErrorCode::RequiredParamAfterOptional => {
+                format!("Required parameter `{}` cannot follow an optional parameter", &*self.raw)
+            },
+            ErrorCode::UnmatchedClosingBracket => {
+                format!("Unmatched closing bracket `{}`", &*self.raw)
+            },
+            ErrorCode::NestingTooDeep => {
+                String::from("Nesting is too deep")
+            },
+            ErrorCode::InvalidNewTarget => {
+                String::from("`new.target` is only allowed within functions")
+            },
+            ErrorCode::InvalidSuperCall => {
+                String::from("`super` can only be called in the constructor of a derived class")
+            },
+            ErrorCode::InvalidSuperProperty => {
+                String::from("`super` properties are only allowed within methods")
+            },
+            ErrorCode::UndefinedLabel => {
+                format!("Undefined label `{}`", &*self.raw)
+            },
+            ErrorCode::InvalidContinueLabel => {
+                format!("Label `{}` of `continue` doesn't belong to a loop", &*self.raw)
+            },
+            ErrorCode::GeneratorArrow => {
+                String::from("Arrow functions cannot be generators")
+            },
+            ErrorCode::RestElementNotLast => {
+                String::from("Rest element must be last in a destructuring pattern")
+            },
+            ErrorCode::MissingInitializer => {
+                format!("Missing initializer for `{}`", &*self.raw)
+            },
+            ErrorCode::InvalidUsingBinding => {
+                String::from("`using` declarations can only bind identifiers")
+            },
+            ErrorCode::InvalidAwaitUsing => {
+                String::from("`await using` is only allowed within async functions")
+            },
+            ErrorCode::InvalidReturn => {
+                String::from("`return` is only allowed within functions")
+            },
+            ErrorCode::MixedNullish => {
+                String::from("`??` cannot be mixed with `||` or `&&` without parentheses")
+            },
+        };
+
+        if let Some(expected) = self.expected {
+            message.push_str(", expected ");
+            message.push_str(&describe(expected));
+        }
+
+        message
+    }
+
+    /// Pair the error with the `source` it occurred in, so that it can be
+    /// serialized along with its line and column.
+    #[inline]
+    pub fn diagnostic<'a>(&'a self, source: &'a str) -> Diagnostic<'a> {
+        Diagnostic {
+            source,
+            error: self,
+        }
+    }
+}
+
+/// An `Error` along with the source it occurred in. Serializes to
+/// `{ code, message, start, end, line, column }`, where `start` and `end`
+/// are byte offsets, lines count from 1 and columns from 0, as in the
+/// `loc` of ESTree nodes.
+#[derive(Clone, Copy)]
+pub struct Diagnostic<'a> {
+    source: &'a str,
+    error: &'a Error,
+}
+
+impl<'a> Serialize for Diagnostic<'a> {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (line, column) = line_column(self.source, self.error.span.start as usize);
+        let mut state = serializer.serialize_struct("Diagnostic", 6)?;
+
+        state.serialize_field("code", &self.error.code)?;
+        state.serialize_field("message", &self.error.message())?;
+        state.serialize_field("start", &self.error.span.start)?;
+        state.serialize_field("end", &self.error.span.end)?;
+        state.serialize_field("line", &line)?;
+        state.serialize_field("column", &(column - 1))?;
+        state.end()
+    }
+}
+
+/// Tokens with a fixed representation are quoted, others are described.
+fn describe(token: Token) -> String {
+    use lexer::Token::*;
+
+    match token {
+        EndOfProgram           |
+        Identifier             |
+        LiteralString          |
+        LiteralNumber          |
+        LiteralBinary          |
+        LiteralRegEx           |
+        Accessor               |
+        TemplateOpen           |
+        TemplateClosed         |
+        UnexpectedToken        |
+        UnexpectedEndOfProgram => token.as_str().to_owned(),
+
+        _                      => format!("`{}`", token.as_str()),
+    }
 }
 
 impl Debug for Error {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Unexpected {:?}({}) at {}:{}", &self.token, &*self.raw, self.start, self.end)
+        write!(f, "{:?}: Unexpected {:?}({}) at {}:{}", self.code, &self.token, &*self.raw, self.span.start, self.span.end)
     }
 }
 
@@ -24,8 +250,7 @@ pub enum ParseError {
     UnexpectedEndOfProgram,
     UnexpectedToken {
         source: String,
-        start: usize,
-        end: usize,
+        span: Span,
     },
 }
 
@@ -46,16 +271,11 @@ impl Display for ParseError {
 
             ParseError::UnexpectedToken {
                 ref source,
-                start,
-                end
+                span,
             } => {
-                let (lineno, line) = source[..start]
-                                       .lines()
-                                       .enumerate()
-                                       .last()
-                                       .unwrap_or((0, ""));
-
-                let colno = line.chars().count();
+                let (start, end) = (span.start as usize, span.end as usize);
+                let (lineno, colno) = line_column(source, start);
+                let (lineno, colno) = (lineno - 1, colno - 1);
                 let token_len = source[start..end].chars().count();
 
                 writeln!(f, "Unexpected token at {}:{}\n", lineno + 1, colno + 1)?;
@@ -99,6 +319,49 @@ impl Display for ParseError {
     }
 }
 
+/// Render `error` as a diagnostic showing the line of `source` it occurred
+/// on, with the span of the error underlined:
+///
+/// ```text
+/// error: Unexpected token `bar`, expected `)`
+///  --> 1:9
+///   |
+/// 1 | if (foo bar
+///   |         ^^^
+/// ```
+pub fn render_error(source: &str, error: &Error) -> String {
+    let start = (error.span.start as usize).min(source.len());
+    let end = (error.span.end as usize).min(source.len()).max(start);
+    let (lineno, colno) = line_column(source, start);
+
+    let line_start = source[..start]
+        .char_indices()
+        .rev()
+        .find(|&(_, ch)| is_line_terminator(ch))
+        .map_or(0, |(index, ch)| index + ch.len_utf8());
+    let line_end = source[start..]
+        .find(is_line_terminator)
+        .map_or(source.len(), |index| start + index);
+    let line = &source[line_start..line_end];
+
+    // Keep tabs so that the caret lines up with the text above it
+    let indent: String = source[line_start..start]
+        .chars()
+        .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+        .collect();
+    let carets = source[start..end.min(line_end)].chars().count().max(1);
+    let width = log10(lineno);
+
+    let mut rendered = format!("error: {}\n", error.message());
+
+    rendered.push_str(&format!("{:width$}--> {}:{}\n", "", lineno, colno, width = width));
+    rendered.push_str(&format!("{:1$} |\n", "", width));
+    rendered.push_str(&format!("{} | {}\n", lineno, line));
+    rendered.push_str(&format!("{:1$} | {2}{3}\n", "", width, indent, "^".repeat(carets)));
+
+    rendered
+}
+
 fn log10(mut num: usize) -> usize {
     let mut log = 0;
 
@@ -122,8 +385,7 @@ mod test {
     fn test_format_unexpected_token_error () {
         let err = ParseError::UnexpectedToken {
             source: "foo".to_string(),
-            start: 0,
-            end: 1
+            span: Span::new(0, 1),
         };
 
         let expected = "Unexpected token at 1:1\n\n> 1 | foo\n    | ^\n";
@@ -131,4 +393,99 @@ mod test {
         assert_eq!(format!("{}", err), expected);
     }
 
+    fn first_error(source: &str) -> Error {
+        ::parser::parse(source).unwrap_err().remove(0)
+    }
+
+    #[test]
+    fn unexpected_token_with_expected_hint() {
+        let err = first_error("if (foo bar");
+
+        assert_eq!(err.code, ErrorCode::UnexpectedToken);
+        assert_eq!(err.expected, Some(Token::ParenClose));
+        assert_eq!(err.message(), "Unexpected token `bar`, expected `)`");
+    }
+
+    #[test]
+    fn unexpected_end_of_program() {
+        let err = first_error("foo(");
+
+        assert_eq!(err.code, ErrorCode::UnexpectedEndOfProgram);
+        assert_eq!(err.message(), "Unexpected end of program");
+    }
+
+    #[test]
+    fn invalid_assignment_target() {
+        let err = first_error("a + b = 1");
+
+        assert_eq!(err.code, ErrorCode::InvalidAssignmentTarget);
+        assert_eq!(err.span, Span::new(0, 5));
+        assert_eq!(err.message(), "Invalid assignment target `a + b`");
+
+        let err = first_error("1++");
+
+        assert_eq!(err.code, ErrorCode::InvalidAssignmentTarget);
+        assert_eq!(err.message(), "Invalid assignment target `1`");
+    }
+
+    #[test]
+    fn duplicate_param() {
+        let err = first_error("(a, b, a) => a");
+
+        assert_eq!(err.code, ErrorCode::DuplicateParam);
+        assert_eq!(err.span, Span::new(7, 8));
+        assert_eq!(err.message(), "Duplicate parameter name `a`");
+
+        assert_eq!(first_error("function f(a, [a]) {}").code, ErrorCode::DuplicateParam);
+        assert_eq!(first_error("({ f(a, a) {} })").code, ErrorCode::DuplicateParam);
+        assert_eq!(first_error("class A { f(a, a) {} }").code, ErrorCode::DuplicateParam);
+    }
+
+    #[test]
+    fn duplicate_simple_params_are_allowed() {
+        assert!(::parser::parse("function f(a, a) {}").is_ok());
+    }
+
+    #[test]
+    fn diagnostic_to_json() {
+        let source = "foo();\nif (foo bar";
+        let err = first_error(source);
+
+        assert_eq!(::serde_json::to_value(err.diagnostic(source)).unwrap(), json!({
+            "code": "UnexpectedToken",
+            "message": "Unexpected token `bar`, expected `)`",
+            "start": 15,
+            "end": 18,
+            "line": 2,
+            "column": 8
+        }));
+    }
+
+    #[test]
+    fn render_error_mid_line() {
+        let source = "if (foo bar";
+        let err = first_error(source);
+
+        assert_eq!(render_error(source, &err), "\
+error: Unexpected token `bar`, expected `)`
+ --> 1:9
+  |
+1 | if (foo bar
+  |         ^^^
+");
+    }
+
+    #[test]
+    fn render_error_on_later_line() {
+        let source = "foo();\n\tlet x = (a + b = 1);\nbar();";
+        let err = first_error(source);
+
+        assert_eq!(render_error(source, &err), "\
+error: Invalid assignment target `a + b`
+ --> 2:11
+  |
+2 | \tlet x = (a + b = 1);
+  | \t         ^^^^^
+");
+    }
 }
@@ -0,0 +1,36 @@
+use std::io::Write;
+use serde_json::{to_writer, Error};
+use module::Module;
+
+/// Serialize the module to ESTree JSON, writing it out to `writer` as it
+/// goes rather than building up a `String` first. The output is the same
+/// as that of `serde_json::to_string`.
+///
+/// The writer isn't buffered here, wrap it in a `BufWriter` when writing
+/// to a file or stdout.
+pub fn serialize_to_writer<W: Write>(module: &Module, writer: W) -> Result<(), Error> {
+    to_writer(writer, module)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use parser::parse;
+    use serde_json::to_string;
+
+    #[test]
+    fn same_as_to_string() {
+        let src = r#"
+            import foo, { bar as baz } from "qux";
+            export default class Doge extends baz { static much(...args) { return `x${ args }y`; } }
+            let { a, b: [c = 1] } = d ?? e;
+        "#;
+
+        let module = parse(src).unwrap();
+        let mut buffer = Vec::new();
+
+        serialize_to_writer(&module, &mut buffer).unwrap();
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), to_string(&module).unwrap());
+    }
+}
@@ -1,11 +1,11 @@
-use lexer::{util, ByteHandler};
+use lexer::ByteHandler;
 use lexer::token::Token::*;
 
 macro_rules! match_label {
     ($lex:ident [$( $byte:expr )* => $token:expr]) => {
         if $(
             $lex.next_byte() == $byte &&
-        )* {$lex.bump(); !util::legal_in_label($lex.read_byte())} {
+        )* {$lex.bump(); !$lex.in_label()} {
             return $lex.token = $token;
         }
     };
@@ -15,7 +15,7 @@ macro_rules! match_label {
             $(
                 $match => match_label!($lex $cont),
             )*
-            ch if !util::legal_in_label(ch) => return $lex.token = $token,
+            _ if !$lex.in_label() => return $lex.token = $token,
             _ => {}
         }
     };
@@ -5,10 +5,15 @@ mod util;
 pub use lexer::token::*;
 
 use lexer::labels::*;
+use lexer::util::legal_in_label;
 use lexer::token::Token::*;
 
 use std::str;
-use error::Error;
+use std::mem;
+use std::rc::Rc;
+use std::cell::RefCell;
+use error::{Error, ErrorCode};
+use span::Span;
 use toolshed::Arena;
 
 macro_rules! expect_byte {
@@ -54,6 +59,30 @@ pub enum Asi {
     NoSemicolon,
 }
 
+/// A token along with its position in the source, as collected with
+/// `ParseOptions::tokens`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct LexToken {
+    pub token: Token,
+    pub span: Span,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CommentKind {
+    /// `// ...`, up to the end of the line
+    Line,
+    /// `/* ... */`
+    Block,
+}
+
+/// A comment along with its position in the source, delimiters included,
+/// as collected with `ParseOptions::comments`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Comment {
+    pub kind: CommentKind,
+    pub span: Span,
+}
+
 type ByteHandler = Option<for<'arena> fn(&mut Lexer<'arena>)>;
 
 /// Lookup table mapping any incoming byte to a handler function defined below.
@@ -268,9 +297,28 @@ const MOR: ByteHandler = Some(|lex| {
 
 // ?
 const QST: ByteHandler = Some(|lex| {
-    lex.bump();
+    lex.token = match lex.next_byte() {
+        // `?.5` is a conditional followed by a number
+        b'.' if !lex.peek_byte().is_ascii_digit() => {
+            lex.bump();
+
+            OperatorOptionalChaining
+        },
+
+        b'?' => {
+            match lex.next_byte() {
+                b'=' => {
+                    lex.bump();
+
+                    OperatorNullishAssign
+                },
 
-    lex.token = OperatorConditional;
+                _ => OperatorNullish
+            }
+        },
+
+        _ => OperatorConditional
+    };
 });
 
 // ~
@@ -297,9 +345,15 @@ const CRT: ByteHandler = Some(|lex| {
 const AMP: ByteHandler = Some(|lex| {
     lex.token = match lex.next_byte() {
         b'&' => {
-            lex.bump();
+            match lex.next_byte() {
+                b'=' => {
+                    lex.bump();
+
+                    OperatorLogicalAndAssign
+                },
 
-            OperatorLogicalAnd
+                _ => OperatorLogicalAnd
+            }
         },
 
         b'=' => {
@@ -316,9 +370,15 @@ const AMP: ByteHandler = Some(|lex| {
 const PIP: ByteHandler = Some(|lex| {
     lex.token = match lex.next_byte() {
         b'|' => {
-            lex.bump();
+            match lex.next_byte() {
+                b'=' => {
+                    lex.bump();
+
+                    OperatorLogicalOrAssign
+                },
 
-            OperatorLogicalOr
+                _ => OperatorLogicalOr
+            }
         },
 
         b'=' => {
@@ -402,7 +462,12 @@ const SLH: ByteHandler = Some(|lex| {
             // Keep consuming bytes until new line or end of source
             unwind_loop!({
                 match lex.next_byte() {
-                    0 | b'\n' => {
+                    0 | b'\n' | b'\r' => {
+                        lex.record_comment(CommentKind::Line);
+                        return lex.consume();
+                    }
+                    0xE2 if lex.at_line_separator() => {
+                        lex.record_comment(CommentKind::Line);
                         return lex.consume();
                     }
                     _ => {}
@@ -413,6 +478,10 @@ const SLH: ByteHandler = Some(|lex| {
         // block comment
         b'*' => {
             lex.bump();
+
+            // A comment spanning lines separates tokens same as a new line does
+            let mut new_line = lex.asi == Asi::ImplicitSemicolon;
+
             // Keep consuming bytes until */ happens in a row
             unwind_loop!({
                 match lex.read_byte() {
@@ -420,12 +489,27 @@ const SLH: ByteHandler = Some(|lex| {
                         match lex.next_byte() {
                             b'/' => {
                                 lex.bump();
-                                return lex.consume();
+                                lex.record_comment(CommentKind::Block);
+                                lex.consume();
+
+                                if new_line && lex.asi == Asi::NoSemicolon {
+                                    lex.asi = Asi::ImplicitSemicolon;
+                                }
+
+                                return;
                             },
                             0 => return lex.token = UnexpectedEndOfProgram,
                             _ => {}
                         }
                     },
+                    b'\n' | b'\r' => {
+                        new_line = true;
+                        lex.bump();
+                    },
+                    0xE2 if lex.at_line_separator() => {
+                        new_line = true;
+                        lex.index += 3;
+                    },
                     0 => return lex.token = UnexpectedEndOfProgram,
                     _ => lex.bump()
                 }
@@ -457,22 +541,23 @@ const PRC: ByteHandler = Some(|lex| {
 
 // Unicode character
 const UNI: ByteHandler = Some(|lex| {
-    let start = lex.index;
+    // U+2028 and U+2029 end lines just like `\n` does
+    if lex.at_line_separator() {
+        lex.index += 3;
+        lex.consume();
 
-    // TODO: unicodes with different lengths
-    let first = lex.slice_source(start, start + 4).chars().next().expect("Has to have one");
+        if lex.asi == Asi::NoSemicolon {
+            lex.asi = Asi::ImplicitSemicolon;
+        }
 
-    if !first.is_alphanumeric() {
-        return lex.token = UnexpectedToken;
+        return;
     }
 
-    // `read_label` bumps one at the beginning,
-    // so we subtract it here.
-    lex.index += first.len_utf8() - 1;
-
-    lex.read_label();
-
-    lex.token = Identifier;
+    lex.token = if lex.read_unicode_label() {
+        Identifier
+    } else {
+        UnexpectedToken
+    };
 });
 
 // 0
@@ -582,8 +667,19 @@ const QOT: ByteHandler = Some(|lex| {
             },
             b'\\' => {
                 lex.bump();
+
+                // Line continuation, `\r\n` counts as a single line terminator
+                if lex.read_byte() == b'\r' && lex.peek_byte() == b'\n' {
+                    lex.bump();
+                }
+
                 expect_byte!(lex);
             },
+            // Only templates can span lines, U+2028 and U+2029 on the other
+            // hand are allowed in strings since ES2019
+            b'\n' | b'\r' => {
+                return lex.token = UnexpectedToken;
+            },
             0 => {
                 return lex.token = UnexpectedEndOfProgram;
             },
@@ -598,6 +694,7 @@ const TPL: ByteHandler = Some(|lex| {
     lex.read_template_kind();
 });
 
+#[derive(Clone)]
 pub struct Lexer<'arena> {
     /// Current `Token` from the source.
     pub token: Token,
@@ -617,6 +714,17 @@ pub struct Lexer<'arena> {
     accessor_start: usize,
 
     pub quasi: &'arena str,
+
+    /// Tokens produced so far, if recording. Shared between clones, so that
+    /// tokens read by a clone the parser rewinds from are replaced once the
+    /// lexer reads past the same position again.
+    tokens: Option<Rc<RefCell<Vec<LexToken>>>>,
+
+    /// Comments read so far, if recording. Shared between clones same as `tokens`.
+    comments: Option<Rc<RefCell<Vec<Comment>>>>,
+
+    /// Tokens read from the same source earlier, if replaying. See `replay_tokens`.
+    replay: Option<Rc<[LexToken]>>,
 }
 
 
@@ -637,14 +745,26 @@ impl<'arena> Lexer<'arena> {
     /// to bugs or undefined behavior.
     #[inline]
     pub unsafe fn from_ptr(ptr: *const u8) -> Self {
+        Lexer::from_ptr_at(ptr, 0)
+    }
+
+    /// Same as `from_ptr`, but starts reading the source at byte `index`.
+    /// Positions of produced tokens are still relative to `ptr`.
+    ///
+    /// **The index must be within the source and on a token boundary!**
+    #[inline]
+    pub(crate) unsafe fn from_ptr_at(ptr: *const u8, index: usize) -> Self {
         let mut lexer = Lexer {
             token: UnexpectedToken,
             asi: Asi::NoSemicolon,
             ptr,
-            index: 0,
-            token_start: 0,
-            accessor_start: 0,
+            index,
+            token_start: index,
+            accessor_start: index,
             quasi: "",
+            tokens: None,
+            comments: None,
+            replay: None,
         };
 
         lexer.consume();
@@ -655,6 +775,10 @@ impl<'arena> Lexer<'arena> {
     /// Advances the lexer, produces a new `Token` and stores it on `self.token`.
     #[inline]
     pub fn consume(&mut self) {
+        if self.replay() {
+            return;
+        }
+
         self.asi = Asi::NoSemicolon;
 
         let mut ch;
@@ -664,17 +788,151 @@ impl<'arena> Lexer<'arena> {
 
             if let Some(handler) = self.handler_from_byte(ch) {
                 self.token_start = self.index;
-                return handler(self);
+                handler(self);
+                return self.record();
             }
 
             self.bump();
 
-            if ch == b'\n' {
+            if ch == b'\n' || ch == b'\r' {
                 self.asi = Asi::ImplicitSemicolon;
             }
         })
     }
 
+    /// Start recording all tokens produced from now on, the current one included.
+    pub(crate) fn record_tokens(&mut self) {
+        self.tokens = Some(Rc::new(RefCell::new(Vec::new())));
+        self.record();
+    }
+
+    /// Take all tokens recorded so far, see `record_tokens`.
+    pub(crate) fn take_tokens(&mut self) -> Vec<LexToken> {
+        match self.tokens {
+            Some(ref tokens) => mem::take(&mut *tokens.borrow_mut()),
+            None => Vec::new(),
+        }
+    }
+
+    /// Take tokens from `tokens` instead of reading them from the source,
+    /// starting over at the current token. The tokens must be the ones
+    /// recorded from the same source, sorted and not overlapping.
+    pub(crate) fn replay_tokens(&mut self, tokens: Rc<[LexToken]>) {
+        self.replay = Some(tokens);
+        self.index = self.token_start;
+        self.consume();
+    }
+
+    /// Produce the next token from the replayed ones, if any. Tokens the
+    /// parser has the lexer read in a particular way, such as accessors or
+    /// regular expressions, as well as any token the parser split, are
+    /// read from the source again. Returns `false` if the source is to be read.
+    #[inline]
+    fn replay(&mut self) -> bool {
+        let next = match self.replay {
+            Some(ref tokens) => {
+                let index = self.index as u32;
+                let position = match tokens.binary_search_by_key(&index, |token| token.span.start) {
+                    Ok(position) | Err(position) => position,
+                };
+
+                if position > 0 && tokens[position - 1].span.end > index {
+                    return false;
+                }
+
+                match tokens.get(position) {
+                    Some(&token) => token,
+                    None         => return false,
+                }
+            },
+            None => return false,
+        };
+
+        self.asi = match next.token {
+            Accessor | TemplateOpen | TemplateClosed | LiteralRegEx => return false,
+            Semicolon               => Asi::ExplicitSemicolon,
+            ParenClose | BraceClose => Asi::ImplicitSemicolon,
+            _ => {
+                let gap = self.slice_source(self.index, next.span.start as usize);
+
+                if gap.contains(['\n', '\r', '\u{2028}', '\u{2029}']) {
+                    Asi::ImplicitSemicolon
+                } else {
+                    Asi::NoSemicolon
+                }
+            },
+        };
+
+        self.token = next.token;
+        self.token_start = next.span.start as usize;
+        self.index = next.span.end as usize;
+
+        true
+    }
+
+    /// Start recording all comments, reading the source again from byte
+    /// `index`, so that comments preceding the current token are included.
+    ///
+    /// **The index must be on a token boundary!**
+    pub(crate) fn record_comments_from(&mut self, index: usize) {
+        self.comments = Some(Rc::new(RefCell::new(Vec::new())));
+        self.index = index;
+        self.consume();
+    }
+
+    /// Take all comments recorded so far, see `record_comments`.
+    pub(crate) fn take_comments(&mut self) -> Vec<Comment> {
+        match self.comments {
+            Some(ref comments) => mem::take(&mut *comments.borrow_mut()),
+            None => Vec::new(),
+        }
+    }
+
+    /// Record a comment starting at the current token and ending at the
+    /// current index. Same as with tokens, comments read again after the
+    /// parser rewinds replace the ones previously recorded.
+    #[inline]
+    fn record_comment(&mut self, kind: CommentKind) {
+        if let Some(ref comments) = self.comments {
+            let mut comments = comments.borrow_mut();
+            let start = self.token_start as u32;
+
+            while comments.last().map(|comment| comment.span.start >= start) == Some(true) {
+                comments.pop();
+            }
+
+            comments.push(Comment {
+                kind,
+                span: Span::new(start, self.index as u32),
+            });
+        }
+    }
+
+    /// Record the current token, replacing any tokens previously recorded
+    /// at or past its position. Tokens are read again at the same position
+    /// whenever the parser rewinds, or needs the current token read as
+    /// something else, like a regular expression instead of a division.
+    #[inline]
+    fn record(&mut self) {
+        if let Some(ref tokens) = self.tokens {
+            if self.token == EndOfProgram {
+                return;
+            }
+
+            let mut tokens = tokens.borrow_mut();
+            let start = self.token_start as u32;
+
+            while tokens.last().map(|token| token.span.start >= start) == Some(true) {
+                tokens.pop();
+            }
+
+            tokens.push(LexToken {
+                token: self.token,
+                span: Span::new(start, self.index as u32),
+            });
+        }
+    }
+
     /// Create an `&str` slice from source spanning current token.
     #[inline]
     pub fn token_as_str(&self) -> &'arena str {
@@ -701,6 +959,12 @@ impl<'arena> Lexer<'arena> {
         (self.start(), self.end())
     }
 
+    /// Get the `Span` of the current token.
+    #[inline]
+    pub fn span(&self) -> Span {
+        Span::new(self.start(), self.end())
+    }
+
     /// Get the start position of the current token.
     #[inline]
     pub fn start(&self) -> u32 {
@@ -748,7 +1012,7 @@ impl<'arena> Lexer<'arena> {
                     self.quasi = self.slice_source(start, end);
                     self.token = TemplateClosed;
 
-                    return;
+                    return self.record();
                 },
                 b'$' => {
                     let end = self.index;
@@ -762,7 +1026,8 @@ impl<'arena> Lexer<'arena> {
 
                     self.quasi = self.slice_source(start, end);
                     self.token = TemplateOpen;
-                    return;
+
+                    return self.record();
                 },
                 b'\\' => {
                     self.bump();
@@ -775,11 +1040,101 @@ impl<'arena> Lexer<'arena> {
                         _ => self.bump()
                     }
                 },
+                0 => {
+                    self.token = UnexpectedEndOfProgram;
+                    return;
+                },
                 _ => self.bump()
             }
         }
     }
 
+    /// Used by the parser in JSX mode to read a child of an element, starting
+    /// at `index`, past the `>` of the opening tag or the `}` of an expression.
+    /// Produces `OperatorLesser` for a `<` and `BraceOpen` for a `{`, any text
+    /// up to either is produced as a `LiteralString` token with its raw value.
+    pub fn read_jsx_child(&mut self, index: u32) {
+        self.index = index as usize;
+        self.token_start = self.index;
+        self.asi = Asi::NoSemicolon;
+
+        self.token = match self.read_byte() {
+            0    => EndOfProgram,
+            b'<' => {
+                self.bump();
+                OperatorLesser
+            },
+            b'{' => {
+                self.bump();
+                BraceOpen
+            },
+            _    => {
+                loop {
+                    match self.read_byte() {
+                        0 | b'<' | b'{' => break,
+                        _ => self.bump(),
+                    }
+                }
+
+                LiteralString
+            }
+        };
+
+        self.record();
+    }
+
+    /// Used by the parser in JSX mode to extend the current word token with
+    /// any dashes and label characters following it, as in `data-foo`.
+    pub fn extend_jsx_name(&mut self) {
+        while self.read_byte() == b'-' || self.in_label() {
+            self.bump();
+        }
+
+        self.token = Identifier;
+        self.record();
+    }
+
+    /// Used by the parser in JSX mode to read a string cut short by a line
+    /// break again, this time until its closing quote, as attribute values
+    /// may span lines. Any other token is left as it is.
+    pub fn extend_jsx_string(&mut self) {
+        let style = unsafe { *self.ptr.add(self.token_start) };
+
+        if self.token != UnexpectedToken || (style != b'"' && style != b'\'') {
+            return;
+        }
+
+        self.index = self.token_start + 1;
+
+        self.token = loop {
+            match self.read_byte() {
+                ch if ch == style => {
+                    self.bump();
+                    break LiteralString;
+                },
+                b'\\' => {
+                    self.bump();
+
+                    if self.read_byte() != 0 {
+                        self.bump();
+                    }
+                },
+                0 => break UnexpectedEndOfProgram,
+                _ => self.bump(),
+            }
+        };
+
+        self.record();
+    }
+
+    /// Discard the current token and produce a new one starting at `index`.
+    /// Used when a JSX tag closes with a `>` the lexer read as a part of a
+    /// longer operator, such as `>=`.
+    pub fn consume_at(&mut self, index: u32) {
+        self.index = index as usize;
+        self.consume();
+    }
+
     /// Get a definition of which ASI rules can be applied.
     #[inline]
     pub fn asi(&self) -> Asi {
@@ -795,14 +1150,36 @@ impl<'arena> Lexer<'arena> {
             self.consume();
         }
 
+        let code = match token {
+            EndOfProgram           |
+            UnexpectedEndOfProgram => ErrorCode::UnexpectedEndOfProgram,
+            _                      => ErrorCode::UnexpectedToken,
+        };
+
         Error {
+            code,
             token,
-            start,
-            end,
+            expected: None,
+            span: Span::new(start as u32, end as u32),
             raw: self.slice_source(start, end).to_owned().into_boxed_str()
         }
     }
 
+    /// Create an `Error` with a given code spanning an arbitrary part of the source,
+    /// without advancing the lexer.
+    pub fn error_at(&self, code: ErrorCode, span: Span) -> Error {
+        // Nodes recovered from earlier errors can end up with inverted spans
+        let end = span.end.max(span.start);
+
+        Error {
+            code,
+            token: self.token,
+            expected: None,
+            span,
+            raw: self.slice_source(span.start as usize, end as usize).to_owned().into_boxed_str()
+        }
+    }
+
     /// Read a byte from the source. Note that this does not increment
     /// the index. In few cases (all of them related to number parsing)
     /// we want to peek at the byte before doing anything. This will,
@@ -827,6 +1204,26 @@ impl<'arena> Lexer<'arena> {
         self.read_byte()
     }
 
+    /// Read the byte following the current one without advancing.
+    #[inline]
+    fn peek_byte(&self) -> u8 {
+        unsafe { *self.ptr.add(self.index + 1) }
+    }
+
+    /// Check whether the current byte starts U+2028 LINE SEPARATOR or
+    /// U+2029 PARAGRAPH SEPARATOR, both encoded in three bytes.
+    #[inline]
+    fn at_line_separator(&self) -> bool {
+        if self.read_byte() != 0xE2 || self.peek_byte() != 0x80 {
+            return false;
+        }
+
+        // Source ends with a 0 byte, so the third byte is always there
+        let third = unsafe { *self.ptr.add(self.index + 2) };
+
+        third == 0xA8 || third == 0xA9
+    }
+
     #[inline]
     fn read_binary(&mut self) {
         loop {
@@ -882,8 +1279,11 @@ impl<'arena> Lexer<'arena> {
                 self.accessor_start = self.index;
 
                 if ch > 127 {
-                    unimplemented!();
-                    // return unicode(self)
+                    return self.token = if self.read_unicode_label() {
+                        Accessor
+                    } else {
+                        UnexpectedToken
+                    };
                 } else if TABLE[ch as usize] {
                     self.read_label();
                     return self.token = Accessor;
@@ -892,13 +1292,60 @@ impl<'arena> Lexer<'arena> {
                 }
             }
 
+            // Source is terminated with a zero byte, don't read past it
+            if ch == 0 {
+                self.accessor_start = self.index;
+
+                return self.token = UnexpectedEndOfProgram;
+            }
+
             self.bump();
         })
     }
 
+    /// Read a label starting with a non-ASCII character. Returns `false` if
+    /// the character can't start an identifier, in which case it's skipped
+    /// over so that the lexer always makes progress.
+    #[inline]
+    fn read_unicode_label(&mut self) -> bool {
+        let start = self.index;
+
+        // Length of the UTF8 sequence follows from its leading byte
+        let len = match self.read_byte() {
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _           => {
+                self.bump();
+
+                return false;
+            }
+        };
+
+        let first = self.slice_source(start, start + len).chars().next();
+
+        self.index += len;
+
+        match first {
+            Some(first) if first.is_alphanumeric() => {
+                self.read_label();
+
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// Check whether the current byte can continue an identifier. All of
+    /// the non-ASCII bytes can, except for the line separators.
+    #[inline]
+    fn in_label(&self) -> bool {
+        legal_in_label(self.read_byte()) && !self.at_line_separator()
+    }
+
     #[inline]
     fn read_label(&mut self) {
-        while util::legal_in_label(self.read_byte()) {
+        while self.in_label() {
             self.bump();
         }
     }
@@ -923,10 +1370,7 @@ impl<'arena> Lexer<'arena> {
 
     #[inline]
     fn read_octal(&mut self) {
-        while match self.read_byte() {
-            b'0'..=b'7' => true,
-            _ => false,
-        } {
+        while matches!(self.read_byte(), b'0'..=b'7') {
             self.bump();
         }
 
@@ -935,12 +1379,7 @@ impl<'arena> Lexer<'arena> {
 
     #[inline]
     fn read_hexadec(&mut self) {
-        while match self.read_byte() {
-            b'0'..=b'9' |
-            b'a'..=b'f' |
-            b'A'..=b'F' => true,
-            _ => false,
-        } {
+        while self.read_byte().is_ascii_hexdigit() {
             self.bump();
         }
 
@@ -970,10 +1409,13 @@ impl<'arena> Lexer<'arena> {
             _           => {}
         }
 
-        while match self.read_byte() {
-            b'0'..=b'9' => true,
-            _ => false,
-        } {
+        // The exponent needs at least one digit, `1e` and `1e+` are invalid
+        match self.read_byte() {
+            b'0'..=b'9' => self.bump(),
+            _           => return self.token = UnexpectedToken,
+        }
+
+        while self.read_byte().is_ascii_digit() {
             self.bump();
         }
 
@@ -982,7 +1424,11 @@ impl<'arena> Lexer<'arena> {
 
     #[inline]
     pub fn read_regular_expression(&mut self) -> &'arena str {
-        let start = self.index - 1;
+        // Current token is either `/` or `/=`, both open the expression
+        let start = self.token_start;
+
+        // Within a class `[` is a plain character and `/` doesn't close
+        // the expression, only an unescaped `]` ends the class.
         let mut in_class = false;
         loop {
             match self.read_byte() {
@@ -1006,23 +1452,34 @@ impl<'arena> Lexer<'arena> {
                             self.token = UnexpectedEndOfProgram;
                             return "";
                         },
+                        // Line terminators can't be escaped
+                        b'\n' | b'\r' => {
+                            self.bump();
+                            self.token = UnexpectedToken;
+                            return "";
+                        },
                         _ => self.bump()
                     }
                 },
-                b'\n' => {
+                b'\n' | b'\r' => {
                     self.bump();
                     self.token = UnexpectedToken;
                     return "";
                 },
+                0     => {
+                    self.token = UnexpectedEndOfProgram;
+                    return "";
+                },
                 _     => self.bump()
             }
         }
 
-        while let b'g' | b'i' | b'm' | b'u' | b'y' = self.read_byte() {
+        while let b'd' | b'g' | b'i' | b'm' | b's' | b'u' | b'v' | b'y' = self.read_byte() {
             self.bump();
         }
 
         self.token = LiteralRegEx;
+        self.record();
         self.slice_from(start)
     }
 }
@@ -1075,6 +1532,54 @@ mod test {
         );
     }
 
+    #[test]
+    fn optional_chaining() {
+        assert_lex(
+            "foo?.bar?.[0]",
+            [
+                (Identifier, "foo"),
+                (OperatorOptionalChaining, "?."),
+                (Identifier, "bar"),
+                (OperatorOptionalChaining, "?."),
+                (BracketOpen, "["),
+                (LiteralNumber, "0"),
+                (BracketClose, "]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn logical_assignment() {
+        assert_lex(
+            "a ?? b ??= c ||= d &&= e",
+            [
+                (Identifier, "a"),
+                (OperatorNullish, "??"),
+                (Identifier, "b"),
+                (OperatorNullishAssign, "??="),
+                (Identifier, "c"),
+                (OperatorLogicalOrAssign, "||="),
+                (Identifier, "d"),
+                (OperatorLogicalAndAssign, "&&="),
+                (Identifier, "e"),
+            ]
+        );
+    }
+
+    #[test]
+    fn conditional_with_float() {
+        assert_lex(
+            "a?.5:1",
+            [
+                (Identifier, "a"),
+                (OperatorConditional, "?"),
+                (LiteralNumber, ".5"),
+                (Colon, ":"),
+                (LiteralNumber, "1"),
+            ]
+        );
+    }
+
     #[test]
     fn method_call_with_keyword() {
         assert_lex(
@@ -1141,6 +1646,48 @@ mod test {
         );
     }
 
+    #[test]
+    fn scientific_numbers() {
+        assert_lex(
+            "1e10 1.5e-3 1E+2 .5e1 0e0",
+            [
+                (LiteralNumber, "1e10"),
+                (LiteralNumber, "1.5e-3"),
+                (LiteralNumber, "1E+2"),
+                (LiteralNumber, ".5e1"),
+                (LiteralNumber, "0e0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_scientific_numbers() {
+        assert_lex("1e", [(UnexpectedToken, "1e")]);
+        assert_lex("1e+", [(UnexpectedToken, "1e+")]);
+        assert_lex("1.5E-", [(UnexpectedToken, "1.5E-")]);
+        assert_lex("1e;", [(UnexpectedToken, "1e"), (Semicolon, ";")]);
+    }
+
+    #[test]
+    fn dotted_floats() {
+        assert_lex(
+            ".5 5. 5.e3 .5e3 5.5",
+            [
+                (LiteralNumber, ".5"),
+                (LiteralNumber, "5."),
+                (LiteralNumber, "5.e3"),
+                (LiteralNumber, ".5e3"),
+                (LiteralNumber, "5.5"),
+            ]
+        );
+    }
+
+    #[test]
+    fn member_access_on_number() {
+        assert_lex("5..a", [(LiteralNumber, "5."), (Accessor, ".a")]);
+        assert_lex("a.5", [(Identifier, "a"), (LiteralNumber, ".5")]);
+    }
+
     #[test]
     fn unexpected_token() {
         assert_lex("..", [(UnexpectedToken, "..")]);
@@ -1151,6 +1698,62 @@ mod test {
         assert_lex("'foo", [(UnexpectedEndOfProgram, "'foo")]);
     }
 
+    #[test]
+    fn line_terminators_in_strings() {
+        assert_lex("'foo\nbar'", [(UnexpectedToken, "'foo"), (Identifier, "bar"), (UnexpectedEndOfProgram, "'")]);
+        assert_lex("'foo\u{2028}'", [(LiteralString, "'foo\u{2028}'")]);
+        assert_lex("\"foo\u{2029}bar\"", [(LiteralString, "\"foo\u{2029}bar\"")]);
+        assert_lex("`foo\u{2028}bar`", [(TemplateClosed, "`foo\u{2028}bar`")]);
+    }
+
+    #[test]
+    fn line_continuations_in_strings() {
+        assert_lex("'foo\\\nbar'", [(LiteralString, "'foo\\\nbar'")]);
+        assert_lex("'foo\\\r\nbar'", [(LiteralString, "'foo\\\r\nbar'")]);
+        assert_lex("'foo\\\u{2028}bar'", [(LiteralString, "'foo\\\u{2028}bar'")]);
+    }
+
+    #[test]
+    fn unicode_line_terminators() {
+        let arena = Arena::new();
+        let mut lex = Lexer::new(&arena, "a\u{2028}b\u{2029}c d");
+
+        for &(slice, new_line) in [("b", true), ("c", true), ("d", false)].iter() {
+            lex.consume();
+
+            assert_eq!(lex.token, Identifier);
+            assert_eq!(lex.token_as_str(), slice);
+            assert!((lex.asi() == Asi::ImplicitSemicolon) == new_line);
+        }
+
+        assert_lex("return\u{2028}a", [(Return, "return"), (Identifier, "a")]);
+    }
+
+    #[test]
+    fn unterminated_template() {
+        assert_lex("`foo", [(UnexpectedEndOfProgram, "`foo")]);
+    }
+
+    #[test]
+    fn unicode_identifiers() {
+        assert_lex(
+            "żółw 日本 ä.ö",
+            [
+                (Identifier, "żółw"),
+                (Identifier, "日本"),
+                (Identifier, "ä"),
+                (Accessor, ".ö"),
+            ]
+        );
+    }
+
+    #[test]
+    fn unicode_non_identifiers() {
+        assert_lex("— a", [(UnexpectedToken, "—"), (Identifier, "a")]);
+        assert_lex("🎉", [(UnexpectedToken, "🎉")]);
+        assert_lex("a.—", [(Identifier, "a"), (UnexpectedToken, ".—")]);
+    }
+
     #[test]
     fn keywords() {
         assert_lex(
@@ -10,6 +10,7 @@
 // SUPER WITH  CONT  FOR   SWTCH YIELD DBGGR FUNCT THIS  DEFLT IF    THROW
 // IMPRT TRY   STATI TRUE  FALSE NULL  UNDEF STR   NUM   BIN   REGEX ENUM
 // IMPL  PCKG  PROT  IFACE PRIV  PUBLI IDENT ACCSS TPL_O TPL_C ERR_T ERR_E
+// ?.    ??    ??=   ||=   &&=
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Token {
@@ -121,6 +122,11 @@ pub enum Token {
     TemplateClosed,
     UnexpectedToken,
     UnexpectedEndOfProgram,
+    OperatorOptionalChaining, //   … ?. …
+    OperatorNullish,          //   …  ?? …
+    OperatorNullishAssign,    //   … ??= …
+    OperatorLogicalOrAssign,  //   … ||= …
+    OperatorLogicalAndAssign, //   … &&= …
 }
 
 impl Token {
@@ -169,4 +175,127 @@ impl Token {
             _                  => false,
         }
     }
+
+    /// Source text of the token, or a short description of it for tokens
+    /// that don't have a fixed representation (such as identifiers or literals).
+    #[inline]
+    pub fn as_str(&self) -> &'static str {
+        use self::Token::*;
+
+        match self {
+            EndOfProgram             => "end of program",
+            Semicolon                => ";",
+            Colon                    => ":",
+            Comma                    => ",",
+            ParenOpen                => "(",
+            ParenClose               => ")",
+            BracketOpen              => "[",
+            BracketClose             => "]",
+            BraceOpen                => "{",
+            BraceClose               => "}",
+            OperatorFatArrow         => "=>",
+            OperatorNew              => "new",
+            OperatorIncrement        => "++",
+            OperatorDecrement        => "--",
+            OperatorLogicalNot       => "!",
+            OperatorBitwiseNot       => "~",
+            OperatorTypeof           => "typeof",
+            OperatorVoid             => "void",
+            OperatorDelete           => "delete",
+            OperatorMultiplication   => "*",
+            OperatorDivision         => "/",
+            OperatorRemainder        => "%",
+            OperatorExponent         => "**",
+            OperatorAddition         => "+",
+            OperatorSubtraction      => "-",
+            OperatorBitShiftLeft     => "<<",
+            OperatorBitShiftRight    => ">>",
+            OperatorUBitShiftRight   => ">>>",
+            OperatorLesser           => "<",
+            OperatorLesserEquals     => "<=",
+            OperatorGreater          => ">",
+            OperatorGreaterEquals    => ">=",
+            OperatorInstanceof       => "instanceof",
+            OperatorIn               => "in",
+            OperatorStrictEquality   => "===",
+            OperatorStrictInequality => "!==",
+            OperatorEquality         => "==",
+            OperatorInequality       => "!=",
+            OperatorBitwiseAnd       => "&",
+            OperatorBitwiseXor       => "^",
+            OperatorBitwiseOr        => "|",
+            OperatorLogicalAnd       => "&&",
+            OperatorLogicalOr        => "||",
+            OperatorConditional      => "?",
+            OperatorAssign           => "=",
+            OperatorAddAssign        => "+=",
+            OperatorSubtractAssign   => "-=",
+            OperatorExponentAssign   => "**=",
+            OperatorMultiplyAssign   => "*=",
+            OperatorDivideAssign     => "/=",
+            OperatorRemainderAssign  => "%=",
+            OperatorBSLAssign        => "<<=",
+            OperatorBSRAssign        => ">>=",
+            OperatorUBSRAssign       => ">>>=",
+            OperatorBitAndAssign     => "&=",
+            OperatorBitXorAssign     => "^=",
+            OperatorBitOrAssign      => "|=",
+            OperatorSpread           => "...",
+            DeclarationVar           => "var",
+            DeclarationLet           => "let",
+            DeclarationConst         => "const",
+            Break                    => "break",
+            Do                       => "do",
+            Case                     => "case",
+            Else                     => "else",
+            Catch                    => "catch",
+            Export                   => "export",
+            Class                    => "class",
+            Extends                  => "extends",
+            Return                   => "return",
+            While                    => "while",
+            Finally                  => "finally",
+            Super                    => "super",
+            With                     => "with",
+            Continue                 => "continue",
+            For                      => "for",
+            Switch                   => "switch",
+            Yield                    => "yield",
+            Debugger                 => "debugger",
+            Function                 => "function",
+            This                     => "this",
+            Default                  => "default",
+            If                       => "if",
+            Throw                    => "throw",
+            Import                   => "import",
+            Try                      => "try",
+            Static                   => "static",
+            LiteralTrue              => "true",
+            LiteralFalse             => "false",
+            LiteralNull              => "null",
+            LiteralUndefined         => "undefined",
+            LiteralString            => "string",
+            LiteralNumber            => "number",
+            LiteralBinary            => "binary number",
+            LiteralRegEx             => "regular expression",
+            ReservedEnum             => "enum",
+            ReservedImplements       => "implements",
+            ReservedPackage          => "package",
+            ReservedProtected        => "protected",
+            ReservedInterface        => "interface",
+            ReservedPrivate          => "private",
+            ReservedPublic           => "public",
+            Identifier               => "identifier",
+            Accessor                 => "accessor",
+            TemplateOpen             => "template",
+            TemplateClosed           => "template",
+            UnexpectedToken          => "unexpected token",
+            UnexpectedEndOfProgram   => "unexpected end of program",
+            OperatorOptionalChaining => "?.",
+            OperatorNullish          => "??",
+            OperatorNullishAssign    => "??=",
+            OperatorLogicalOrAssign  => "||=",
+            OperatorLogicalAndAssign => "&&=",
+        }
+    }
 }
@@ -7,8 +7,8 @@ extern crate toolshed;
 #[macro_use]
 extern crate serde_derive;
 
-#[cfg(test)]
-#[macro_use]
+#[cfg(any(test, feature = "json"))]
+#[cfg_attr(test, macro_use)]
 extern crate serde_json;
 
 #[cfg(test)]
@@ -16,12 +16,35 @@ extern crate serde_json;
 extern crate pretty_assertions;
 
 pub mod ast;
+pub mod comments;
 pub mod error;
 pub mod lexer;
 
 mod module;
 mod parser;
 mod astgen;
+mod span;
+
+#[cfg(feature = "owned")]
+pub mod owned;
+
+#[cfg(feature = "json")]
+mod json;
 
-pub use parser::parse;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+pub use parser::{parse, parse_with, parse_collecting, parse_recovering};
+pub use parser::{ParseOptions, Parsed, Parser, Statements, BlockFunction};
 pub use module::Module;
+pub use span::Span;
+pub use ast::diff::{ast_diff, DiffPath};
+
+#[cfg(feature = "owned")]
+pub use owned::{parse_owned, parse_owned_with};
+
+#[cfg(feature = "json")]
+pub use json::serialize_to_writer;
+
+#[cfg(feature = "wasm")]
+pub use wasm::parse_to_json;
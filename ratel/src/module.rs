@@ -1,24 +1,51 @@
 use toolshed::list::UnsafeList;
 use toolshed::Arena;
 use ast::StatementList;
+use error::Error;
+use parser::{self, ParseOptions, Statements};
+use span::{self, Span};
 
 use std::fmt;
 use std::marker::PhantomData;
 
+/// Once sources left behind by `Module::reparse` add up to this many times
+/// the size of the current source, the module is parsed again from scratch.
+const STALE_SOURCES: usize = 8;
 
 /// A JavaScript module parsed to an AST.
 pub struct Module<'ast> {
     body: UnsafeList,
+    source: *const str,
+    options: ParseOptions,
     arena: Arena,
+    /// Bytes of sources copied onto the arena by `reparse` and since replaced
+    stale: usize,
     _phantom: PhantomData<&'ast StatementList<'ast>>
 }
 
 impl<'ast> Module<'ast> {
+    /// Parse `source` lazily, producing one top-level statement at a time.
+    /// Parsing stops as soon as the iterator is no longer advanced, which
+    /// makes scanning the beginning of large sources cheap.
+    ///
+    /// The statements are allocated on `arena`, so they can be kept around
+    /// for as long as it lives, past the iterator itself.
+    ///
+    /// Unlike `parse`, errors don't stop the iteration, they are collected
+    /// in `Statements::errors` instead.
     #[inline]
-    pub(crate) fn new(body: UnsafeList, arena: Arena) -> Self {
+    pub fn statements(arena: &'ast Arena, source: &str, options: ParseOptions) -> Statements<'ast> {
+        Statements::new(arena, source, options)
+    }
+
+    #[inline]
+    pub(crate) fn new(body: UnsafeList, source: *const str, options: ParseOptions, arena: Arena) -> Self {
         Module {
             body,
+            source,
+            options,
             arena,
+            stale: 0,
             _phantom: PhantomData,
         }
     }
@@ -29,11 +56,72 @@ impl<'ast> Module<'ast> {
         unsafe { self.body.into_list() }
     }
 
+    /// Get the source the module was parsed from.
+    #[inline]
+    pub fn source(&self) -> &str {
+        unsafe { &*self.source }
+    }
+
+    /// Get the line and column of byte `offset` in the source, both counting
+    /// from 1, with columns counted in characters. Offsets past the end of
+    /// the source resolve to the end.
+    ///
+    /// **Panics** if `offset` doesn't fall on a UTF8 character boundary.
+    #[inline]
+    pub fn line_column(&self, offset: u32) -> (usize, usize) {
+        span::line_column(self.source(), offset as usize)
+    }
+
     /// Get a reference to the `Arena` on which the AST is allocated.
     #[inline]
     pub fn arena(&'ast self) -> &'ast Arena {
         &self.arena
     }
+
+    /// Replace the `edit` range of the source with `text` and update the AST
+    /// accordingly. Only the top-level statements touched by the edit are
+    /// parsed again, all other statements keep their nodes. Statements
+    /// following the edit can only be kept if `text` is as long as the range
+    /// it replaces, since their positions would change otherwise.
+    ///
+    /// Every edit copies the whole changed source onto the arena, which only
+    /// ever grows. To keep the memory of a module edited many times in check,
+    /// once the sources left behind add up to several times the size of the
+    /// current one, the module is parsed again from scratch on a new arena
+    /// instead. None of the nodes are kept then, and all of the old ones are
+    /// freed along with the old arena.
+    ///
+    /// On errors the module is left unchanged.
+    ///
+    /// **Panics** if `edit` is out of bounds, ends before it starts, or doesn't
+    /// fall on UTF8 character boundaries of the source.
+    pub fn reparse(&mut self, edit: Span, text: &str) -> Result<(), Vec<Error>> {
+        assert!(
+            edit.start <= edit.end && edit.end as usize <= self.source().len(),
+            "Edit {:?} is out of bounds of the source", edit
+        );
+
+        let length = self.source().len() - (edit.end - edit.start) as usize + text.len();
+
+        if self.stale + length + 1 >= STALE_SOURCES * (length + 1) {
+            let changed = parser::apply_edit(self.source(), edit, text);
+
+            *self = parser::parse_with(&changed, self.options)?;
+
+            return Ok(());
+        }
+
+        // The changed source is copied onto the arena before it's parsed,
+        // and stays there whether parsing it succeeds or not
+        self.stale += length + 1;
+
+        let (body, source) = parser::reparse(&self.arena, self.body, self.source(), self.options, edit, text)?;
+
+        self.body = body;
+        self.source = source;
+
+        Ok(())
+    }
 }
 
 impl<'ast> fmt::Debug for Module<'ast> {
@@ -44,4 +132,141 @@ impl<'ast> fmt::Debug for Module<'ast> {
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ast::{Loc, Statement};
+    use parser::parse;
+
+    const SOURCE: &str = "function a() { return 1; }\nfunction b() { return 2; }\nfunction c() { return 3; }\n";
+
+    fn pointers<'ast>(module: &Module<'ast>) -> Vec<*const Loc<Statement<'ast>>> {
+        module.body().iter().map(|statement| &**statement as *const _).collect()
+    }
+
+    fn edit(source: &str, needle: &str) -> Span {
+        let start = source.find(needle).unwrap() as u32;
+
+        Span::new(start, start + needle.len() as u32)
+    }
+
+    #[test]
+    fn reparse_reuses_untouched_statements() {
+        let mut module = parse(SOURCE).unwrap();
+        let before = pointers(&module);
+
+        module.reparse(edit(SOURCE, "return 2"), "return 5").unwrap();
+
+        let expected = SOURCE.replace("return 2", "return 5");
+        let after = pointers(&module);
+
+        assert_eq!(module.source(), expected);
+        assert_eq!(module.body(), parse(&expected).unwrap().body());
+        assert_eq!(after.len(), 3);
+        assert_eq!(after[0], before[0]);
+        assert_ne!(after[1], before[1]);
+        assert_eq!(after[2], before[2]);
+    }
+
+    #[test]
+    fn reparse_with_length_change() {
+        let mut module = parse(SOURCE).unwrap();
+        let before = pointers(&module);
+
+        module.reparse(edit(SOURCE, "return 2;"), "return 2 + 2;").unwrap();
+
+        let expected = SOURCE.replace("return 2;", "return 2 + 2;");
+        let expected_module = parse(&expected).unwrap();
+        let after = pointers(&module);
+
+        assert_eq!(module.source(), expected);
+        assert_eq!(module.body(), expected_module.body());
+        assert_eq!(after[0], before[0]);
+
+        let spans: Vec<Span> = module.body().iter().map(|statement| statement.span()).collect();
+        let expected_spans: Vec<Span> = expected_module.body().iter().map(|statement| statement.span()).collect();
+
+        assert_eq!(spans, expected_spans);
+    }
+
+    #[test]
+    fn reparse_continued_expression() {
+        let source = "foo\nbar;\nbaz;";
+        let mut module = parse(source).unwrap();
+
+        module.reparse(Span::new(4, 4), ".").unwrap();
+
+        assert_eq!(module.source(), "foo\n.bar;\nbaz;");
+        assert_eq!(module.body(), parse("foo.bar; baz;").unwrap().body());
+    }
+
+    #[test]
+    fn reparse_between_statements() {
+        let source = "foo;\nbar;";
+        let mut module = parse(source).unwrap();
+
+        module.reparse(Span::new(4, 4), " baz;").unwrap();
+
+        assert_eq!(module.body(), parse("foo; baz; bar;").unwrap().body());
+    }
+
+    #[test]
+    fn reparse_starts_over_on_a_new_arena() {
+        let mut module = parse(SOURCE).unwrap();
+        let before = pointers(&module);
+
+        for _ in 1..STALE_SOURCES {
+            module.reparse(edit(SOURCE, "return 2"), "return 2").unwrap();
+
+            assert_eq!(pointers(&module)[0], before[0]);
+        }
+
+        module.reparse(edit(SOURCE, "return 2"), "return 2").unwrap();
+
+        assert_eq!(module.source(), SOURCE);
+        assert_eq!(module.body(), parse(SOURCE).unwrap().body());
+        assert_ne!(pointers(&module)[0], before[0]);
+
+        // Kept again by the edit right after
+        let before = pointers(&module);
+
+        module.reparse(edit(SOURCE, "return 2"), "return 2").unwrap();
+
+        assert_eq!(pointers(&module)[0], before[0]);
+    }
+
+    #[test]
+    fn reparse_errors_count_towards_starting_over() {
+        let mut module = parse(SOURCE).unwrap();
+        let before = pointers(&module);
+
+        for _ in 1..STALE_SOURCES {
+            assert!(module.reparse(edit(SOURCE, "return 2;"), "return (;").is_err());
+        }
+
+        module.reparse(edit(SOURCE, "return 2"), "return 2").unwrap();
+
+        assert_eq!(module.body(), parse(SOURCE).unwrap().body());
+        assert_ne!(pointers(&module)[0], before[0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn reparse_inverted_edit() {
+        let mut module = parse(SOURCE).unwrap();
+
+        let _ = module.reparse(Span::new(5, 4), "");
+    }
+
+    #[test]
+    fn reparse_error_leaves_module_unchanged() {
+        let mut module = parse(SOURCE).unwrap();
+        let before = pointers(&module);
+
+        assert!(module.reparse(edit(SOURCE, "return 2;"), "return (;").is_err());
+        assert_eq!(module.source(), SOURCE);
+        assert_eq!(pointers(&module), before);
+    }
+}
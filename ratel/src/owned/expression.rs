@@ -0,0 +1,439 @@
+use ast;
+use owned::{ToOwnedAst, Node, NodeList, Literal, OperatorKind, TypeCastKind, Function, Class, EmptyName, OptionalName, MethodKind};
+use owned::{Identifier, IdentifierNode, BlockNode, ExpressionNode, Statement, ExpressionList, Pattern};
+use owned::{TypeNode, TypeList};
+
+pub use ast::expression::{ThisExpression, SuperExpression};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum PropertyKey {
+    Computed(ExpressionNode),
+    Literal(String),
+    Binary(String),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Property {
+    Shorthand(String),
+    Literal {
+        key: Node<PropertyKey>,
+        value: ExpressionNode,
+    },
+    Method {
+        key: Node<PropertyKey>,
+        kind: MethodKind,
+        value: Node<Function<EmptyName>>,
+    },
+    Spread {
+        argument: ExpressionNode,
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SequenceExpression {
+    pub body: ExpressionList
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArrayExpression {
+    pub body: ExpressionList
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MemberExpression {
+    pub object: ExpressionNode,
+    pub property: IdentifierNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MetaPropertyExpression {
+    pub meta: IdentifierNode,
+    pub property: IdentifierNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ComputedMemberExpression {
+    pub object: ExpressionNode,
+    pub property: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CallExpression {
+    pub callee: ExpressionNode,
+    pub arguments: ExpressionList,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum OptionalLink {
+    Member(IdentifierNode),
+    ComputedMember(ExpressionNode),
+    Call(ExpressionList),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct OptionalExpression {
+    pub object: ExpressionNode,
+    pub link: OptionalLink,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BinaryExpression {
+    pub operator: OperatorKind,
+    pub left: ExpressionNode,
+    pub right: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PrefixExpression {
+    pub operator: OperatorKind,
+    pub operand: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct YieldExpression {
+    pub delegate: bool,
+    pub argument: Option<ExpressionNode>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct AwaitExpression {
+    pub argument: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PostfixExpression {
+    pub operator: OperatorKind,
+    pub operand: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConditionalExpression {
+    pub test: ExpressionNode,
+    pub consequent: ExpressionNode,
+    pub alternate: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TemplateLiteral {
+    pub expressions: ExpressionList,
+    pub quasis: NodeList<String>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TaggedTemplateExpression {
+    pub tag: ExpressionNode,
+    pub quasi: Node<TemplateLiteral>
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParenthesizedExpression {
+    pub expression: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SpreadExpression {
+    pub argument: ExpressionNode
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ArrowBody {
+    Expression(ExpressionNode),
+    Block(BlockNode<Statement>)
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArrowExpression {
+    pub params: NodeList<Pattern>,
+    pub return_type: Option<TypeNode>,
+    pub body: ArrowBody,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ObjectExpression {
+    pub body: NodeList<Property>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum JSXAttribute {
+    Attribute {
+        name: IdentifierNode,
+        value: Option<ExpressionNode>,
+    },
+    Spread {
+        argument: ExpressionNode,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum JSXChild {
+    Text(String),
+    Expression(ExpressionNode),
+    Element(ExpressionNode),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct JSXElement {
+    pub name: ExpressionNode,
+    pub attributes: NodeList<JSXAttribute>,
+    pub children: NodeList<JSXChild>,
+    pub self_closing: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct JSXFragment {
+    pub children: NodeList<JSXChild>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypeCastExpression {
+    pub expression: ExpressionNode,
+    pub kind: TypeCastKind,
+    pub annotation: TypeNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InstantiationExpression {
+    pub expression: ExpressionNode,
+    pub arguments: TypeList,
+}
+
+pub type FunctionExpression = Function<OptionalName>;
+pub type ClassExpression = Class<OptionalName>;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Expression {
+    Void,
+    This(ThisExpression),
+    Super(SuperExpression),
+    Identifier(Identifier),
+    Literal(Literal),
+    Sequence(SequenceExpression),
+    Array(ArrayExpression),
+    Member(MemberExpression),
+    ComputedMember(ComputedMemberExpression),
+    MetaProperty(MetaPropertyExpression),
+    Call(CallExpression),
+    Optional(OptionalExpression),
+    Binary(BinaryExpression),
+    Prefix(PrefixExpression),
+    Yield(YieldExpression),
+    Await(AwaitExpression),
+    Postfix(PostfixExpression),
+    Conditional(ConditionalExpression),
+    Template(TemplateLiteral),
+    TaggedTemplate(TaggedTemplateExpression),
+    Spread(SpreadExpression),
+    Arrow(ArrowExpression),
+    Object(ObjectExpression),
+    Function(FunctionExpression),
+    Class(ClassExpression),
+    JSXElement(JSXElement),
+    JSXFragment(JSXFragment),
+    TypeCast(TypeCastExpression),
+    Instantiation(InstantiationExpression),
+    Parenthesized(ParenthesizedExpression),
+}
+
+impl<'ast> ToOwnedAst for ast::PropertyKey<'ast> {
+    type Owned = PropertyKey;
+
+    fn to_owned_ast(&self) -> PropertyKey {
+        match *self {
+            ast::PropertyKey::Computed(ref expression) => PropertyKey::Computed(expression.to_owned_ast()),
+            ast::PropertyKey::Literal(key)             => PropertyKey::Literal(key.to_owned()),
+            ast::PropertyKey::Binary(key)              => PropertyKey::Binary(key.to_owned()),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::Property<'ast> {
+    type Owned = Property;
+
+    fn to_owned_ast(&self) -> Property {
+        match *self {
+            ast::Property::Shorthand(name) => Property::Shorthand(name.to_owned()),
+            ast::Property::Literal { ref key, ref value } => Property::Literal {
+                key: key.to_owned_ast(),
+                value: value.to_owned_ast(),
+            },
+            ast::Property::Method { ref key, kind, ref value } => Property::Method {
+                key: key.to_owned_ast(),
+                kind,
+                value: value.to_owned_ast(),
+            },
+            ast::Property::Spread { ref argument } => Property::Spread {
+                argument: argument.to_owned_ast(),
+            },
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::expression::OptionalLink<'ast> {
+    type Owned = OptionalLink;
+
+    fn to_owned_ast(&self) -> OptionalLink {
+        use ast::expression::OptionalLink::*;
+
+        match *self {
+            Member(ref property)         => OptionalLink::Member(property.to_owned_ast()),
+            ComputedMember(ref property) => OptionalLink::ComputedMember(property.to_owned_ast()),
+            Call(ref arguments)          => OptionalLink::Call(arguments.to_owned_ast()),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::expression::TemplateLiteral<'ast> {
+    type Owned = TemplateLiteral;
+
+    fn to_owned_ast(&self) -> TemplateLiteral {
+        TemplateLiteral {
+            expressions: self.expressions.to_owned_ast(),
+            quasis: self.quasis.to_owned_ast(),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::expression::ArrowBody<'ast> {
+    type Owned = ArrowBody;
+
+    fn to_owned_ast(&self) -> ArrowBody {
+        match *self {
+            ast::expression::ArrowBody::Expression(ref expression) => ArrowBody::Expression(expression.to_owned_ast()),
+            ast::expression::ArrowBody::Block(ref block)           => ArrowBody::Block(block.to_owned_ast()),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::expression::JSXAttribute<'ast> {
+    type Owned = JSXAttribute;
+
+    fn to_owned_ast(&self) -> JSXAttribute {
+        match *self {
+            ast::expression::JSXAttribute::Attribute { ref name, ref value } => JSXAttribute::Attribute {
+                name: name.to_owned_ast(),
+                value: value.to_owned_ast(),
+            },
+            ast::expression::JSXAttribute::Spread { ref argument } => JSXAttribute::Spread {
+                argument: argument.to_owned_ast(),
+            },
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::expression::JSXChild<'ast> {
+    type Owned = JSXChild;
+
+    fn to_owned_ast(&self) -> JSXChild {
+        match *self {
+            ast::expression::JSXChild::Text(text)            => JSXChild::Text(text.to_owned()),
+            ast::expression::JSXChild::Expression(ref child) => JSXChild::Expression(child.to_owned_ast()),
+            ast::expression::JSXChild::Element(ref element)  => JSXChild::Element(element.to_owned_ast()),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::Expression<'ast> {
+    type Owned = Expression;
+
+    fn to_owned_ast(&self) -> Expression {
+        use ast::Expression::*;
+
+        match *self {
+            Void => Expression::Void,
+            This(this) => Expression::This(this),
+            Super(sup) => Expression::Super(sup),
+            Identifier(ident) => Expression::Identifier(ident.to_owned()),
+            Literal(ref literal) => Expression::Literal(literal.to_owned_ast()),
+            Sequence(ref expression) => Expression::Sequence(SequenceExpression {
+                body: expression.body.to_owned_ast(),
+            }),
+            Array(ref expression) => Expression::Array(ArrayExpression {
+                body: expression.body.to_owned_ast(),
+            }),
+            Member(ref expression) => Expression::Member(MemberExpression {
+                object: expression.object.to_owned_ast(),
+                property: expression.property.to_owned_ast(),
+            }),
+            ComputedMember(ref expression) => Expression::ComputedMember(ComputedMemberExpression {
+                object: expression.object.to_owned_ast(),
+                property: expression.property.to_owned_ast(),
+            }),
+            MetaProperty(ref expression) => Expression::MetaProperty(MetaPropertyExpression {
+                meta: expression.meta.to_owned_ast(),
+                property: expression.property.to_owned_ast(),
+            }),
+            Call(ref expression) => Expression::Call(CallExpression {
+                callee: expression.callee.to_owned_ast(),
+                arguments: expression.arguments.to_owned_ast(),
+            }),
+            Optional(ref expression) => Expression::Optional(OptionalExpression {
+                object: expression.object.to_owned_ast(),
+                link: expression.link.to_owned_ast(),
+            }),
+            Binary(ref expression) => Expression::Binary(BinaryExpression {
+                operator: expression.operator,
+                left: expression.left.to_owned_ast(),
+                right: expression.right.to_owned_ast(),
+            }),
+            Prefix(ref expression) => Expression::Prefix(PrefixExpression {
+                operator: expression.operator,
+                operand: expression.operand.to_owned_ast(),
+            }),
+            Yield(ref expression) => Expression::Yield(YieldExpression {
+                delegate: expression.delegate,
+                argument: expression.argument.to_owned_ast(),
+            }),
+            Await(ref expression) => Expression::Await(AwaitExpression {
+                argument: expression.argument.to_owned_ast(),
+            }),
+            Postfix(ref expression) => Expression::Postfix(PostfixExpression {
+                operator: expression.operator,
+                operand: expression.operand.to_owned_ast(),
+            }),
+            Conditional(ref expression) => Expression::Conditional(ConditionalExpression {
+                test: expression.test.to_owned_ast(),
+                consequent: expression.consequent.to_owned_ast(),
+                alternate: expression.alternate.to_owned_ast(),
+            }),
+            Template(ref expression) => Expression::Template(expression.to_owned_ast()),
+            TaggedTemplate(ref expression) => Expression::TaggedTemplate(TaggedTemplateExpression {
+                tag: expression.tag.to_owned_ast(),
+                quasi: expression.quasi.to_owned_ast(),
+            }),
+            Spread(ref expression) => Expression::Spread(SpreadExpression {
+                argument: expression.argument.to_owned_ast(),
+            }),
+            Arrow(ref expression) => Expression::Arrow(ArrowExpression {
+                params: expression.params.to_owned_ast(),
+                return_type: expression.return_type.to_owned_ast(),
+                body: expression.body.to_owned_ast(),
+            }),
+            Object(ref expression) => Expression::Object(ObjectExpression {
+                body: expression.body.to_owned_ast(),
+            }),
+            Function(ref function) => Expression::Function(function.to_owned_ast()),
+            Class(ref class) => Expression::Class(class.to_owned_ast()),
+            JSXElement(ref element) => Expression::JSXElement(self::JSXElement {
+                name: element.name.to_owned_ast(),
+                attributes: element.attributes.to_owned_ast(),
+                children: element.children.to_owned_ast(),
+                self_closing: element.self_closing,
+            }),
+            JSXFragment(ref fragment) => Expression::JSXFragment(self::JSXFragment {
+                children: fragment.children.to_owned_ast(),
+            }),
+            TypeCast(ref expression) => Expression::TypeCast(TypeCastExpression {
+                expression: expression.expression.to_owned_ast(),
+                kind: expression.kind,
+                annotation: expression.annotation.to_owned_ast(),
+            }),
+            Instantiation(ref expression) => Expression::Instantiation(InstantiationExpression {
+                expression: expression.expression.to_owned_ast(),
+                arguments: expression.arguments.to_owned_ast(),
+            }),
+            Parenthesized(ref expression) => Expression::Parenthesized(ParenthesizedExpression {
+                expression: expression.expression.to_owned_ast(),
+            }),
+        }
+    }
+}
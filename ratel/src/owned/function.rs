@@ -0,0 +1,123 @@
+use ast;
+use owned::{ToOwnedAst, Node, IdentifierNode, ExpressionNode, MethodKind};
+use owned::{BlockNode, Statement, PatternList, PropertyKey, TypeNode, TypeParameterList};
+
+pub use ast::EmptyName;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MandatoryName(pub IdentifierNode);
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct OptionalName(pub Option<IdentifierNode>);
+
+pub type Method = Function<EmptyName>;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Function<N> {
+    pub name: N,
+    pub generator: bool,
+    pub is_async: bool,
+    pub type_parameters: TypeParameterList,
+    pub params: PatternList,
+    pub return_type: Option<TypeNode>,
+    pub body: BlockNode<Statement>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ClassMember {
+    Error,
+    Method {
+        is_static: bool,
+        key: Node<PropertyKey>,
+        kind: MethodKind,
+        value: Node<Function<EmptyName>>,
+    },
+    Literal {
+        is_static: bool,
+        key: Node<PropertyKey>,
+        value: ExpressionNode,
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Class<N> {
+    pub name: N,
+    pub extends: Option<ExpressionNode>,
+    pub body: BlockNode<ClassMember>,
+}
+
+impl ToOwnedAst for EmptyName {
+    type Owned = EmptyName;
+
+    #[inline]
+    fn to_owned_ast(&self) -> EmptyName {
+        EmptyName
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::MandatoryName<'ast> {
+    type Owned = MandatoryName;
+
+    #[inline]
+    fn to_owned_ast(&self) -> MandatoryName {
+        MandatoryName(self.0.to_owned_ast())
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::OptionalName<'ast> {
+    type Owned = OptionalName;
+
+    #[inline]
+    fn to_owned_ast(&self) -> OptionalName {
+        OptionalName(self.0.to_owned_ast())
+    }
+}
+
+impl<'ast, N: ast::Name<'ast> + ToOwnedAst> ToOwnedAst for ast::Function<'ast, N> {
+    type Owned = Function<N::Owned>;
+
+    fn to_owned_ast(&self) -> Self::Owned {
+        Function {
+            name: self.name.to_owned_ast(),
+            generator: self.generator,
+            is_async: self.is_async,
+            type_parameters: self.type_parameters.to_owned_ast(),
+            params: self.params.to_owned_ast(),
+            return_type: self.return_type.to_owned_ast(),
+            body: self.body.to_owned_ast(),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::ClassMember<'ast> {
+    type Owned = ClassMember;
+
+    fn to_owned_ast(&self) -> ClassMember {
+        match *self {
+            ast::ClassMember::Error => ClassMember::Error,
+            ast::ClassMember::Method { is_static, ref key, kind, ref value } => ClassMember::Method {
+                is_static,
+                key: key.to_owned_ast(),
+                kind,
+                value: value.to_owned_ast(),
+            },
+            ast::ClassMember::Literal { is_static, ref key, ref value } => ClassMember::Literal {
+                is_static,
+                key: key.to_owned_ast(),
+                value: value.to_owned_ast(),
+            },
+        }
+    }
+}
+
+impl<'ast, N: ast::Name<'ast> + ToOwnedAst> ToOwnedAst for ast::Class<'ast, N> {
+    type Owned = Class<N::Owned>;
+
+    fn to_owned_ast(&self) -> Self::Owned {
+        Class {
+            name: self.name.to_owned_ast(),
+            extends: self.extends.to_owned_ast(),
+            body: self.body.to_owned_ast(),
+        }
+    }
+}
@@ -0,0 +1,307 @@
+//! Owned alternative to the AST in `ast`, using `Box`, `Vec` and `String`
+//! in place of references to the arena. Converting to it copies the whole
+//! tree, which makes it slower to work with, but the result no longer
+//! borrows from the `Module` it was made from: it can outlive the arena,
+//! be stored anywhere and sent across threads.
+//!
+//! Types mirror those of `ast`, with lifetimes removed. Types that don't
+//! borrow anything, such as `OperatorKind`, are shared with `ast`.
+
+mod expression;
+mod statement;
+mod function;
+mod types;
+
+use toolshed::list::List;
+use ast;
+use error::Error;
+use module::Module;
+use parser::{parse_with, ParseOptions};
+
+pub use ast::{Loc, OperatorKind, DeclarationKind, MethodKind, Primitive, RegexFlags};
+pub use ast::expression::TypeCastKind;
+pub use owned::expression::*;
+pub use owned::statement::*;
+pub use owned::function::*;
+pub use owned::types::*;
+
+// Owned counterparts of the aliases in `ast`
+pub type Identifier = String;
+pub type Node<T> = Box<Loc<T>>;
+pub type NodeList<T> = Vec<Loc<T>>;
+pub type BlockNode<T> = Node<Block<T>>;
+pub type PatternList = NodeList<Pattern>;
+pub type PropertyNode = Node<Property>;
+pub type ExpressionNode = Node<Expression>;
+pub type ExpressionList = NodeList<Expression>;
+pub type StatementNode = Node<Statement>;
+pub type StatementList = NodeList<Statement>;
+pub type IdentifierNode = Node<String>;
+pub type IdentifierList = NodeList<String>;
+pub type TypeNode = Node<Type>;
+pub type TypeList = NodeList<Type>;
+
+/// Deep copy of an arena allocated AST node into its owned counterpart.
+pub trait ToOwnedAst {
+    type Owned;
+
+    fn to_owned_ast(&self) -> Self::Owned;
+}
+
+/// Owned copy of a parsed `Module`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Program {
+    pub source: String,
+    pub body: StatementList,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Block<T> {
+    pub body: NodeList<T>
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Pattern {
+    /// Only used inside ArrayPattern
+    Void,
+    Identifier(Identifier),
+    ObjectPattern {
+        properties: NodeList<Property>
+    },
+    ArrayPattern {
+        elements: NodeList<Pattern>
+    },
+    RestElement {
+        argument: IdentifierNode
+    },
+    AssignmentPattern {
+        left: Node<Pattern>,
+        right: ExpressionNode,
+    },
+    /// TypeScript only, `x: number` in declarations, or `x?: number` in parameters
+    Annotated {
+        pattern: Node<Pattern>,
+        optional: bool,
+        annotation: Option<TypeNode>,
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Literal {
+    Undefined,
+    Null,
+    True,
+    False,
+    Number(String),
+    Binary(String),
+    String(String),
+    RegEx(String),
+}
+
+/// Parse `source` and copy the resulting AST out of the arena, which is
+/// dropped before returning.
+pub fn parse_owned(source: &str) -> Result<Program, Vec<Error>> {
+    parse_owned_with(source, ParseOptions::default())
+}
+
+/// Same as `parse_owned`, with syntax extensions enabled by `options`.
+pub fn parse_owned_with(source: &str, options: ParseOptions) -> Result<Program, Vec<Error>> {
+    parse_with(source, options).map(|module| module.to_owned_ast())
+}
+
+impl<'ast> ToOwnedAst for Module<'ast> {
+    type Owned = Program;
+
+    fn to_owned_ast(&self) -> Program {
+        Program {
+            source: self.source().to_owned(),
+            body: self.body().to_owned_ast(),
+        }
+    }
+}
+
+impl ToOwnedAst for &str {
+    type Owned = String;
+
+    #[inline]
+    fn to_owned_ast(&self) -> String {
+        (*self).to_owned()
+    }
+}
+
+impl<T: ToOwnedAst> ToOwnedAst for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    #[inline]
+    fn to_owned_ast(&self) -> Self::Owned {
+        self.as_ref().map(ToOwnedAst::to_owned_ast)
+    }
+}
+
+impl<T: ToOwnedAst> ToOwnedAst for ast::Loc<T> {
+    type Owned = Loc<T::Owned>;
+
+    #[inline]
+    fn to_owned_ast(&self) -> Self::Owned {
+        Loc::new(self.start, self.end, self.item.to_owned_ast())
+    }
+}
+
+impl<'ast, T: 'ast + ToOwnedAst> ToOwnedAst for ast::Node<'ast, T> {
+    type Owned = Node<T::Owned>;
+
+    #[inline]
+    fn to_owned_ast(&self) -> Self::Owned {
+        Box::new((**self).to_owned_ast())
+    }
+}
+
+impl<'ast, T: 'ast + ToOwnedAst> ToOwnedAst for List<'ast, ast::Node<'ast, T>> {
+    type Owned = NodeList<T::Owned>;
+
+    #[inline]
+    fn to_owned_ast(&self) -> Self::Owned {
+        self.iter().map(|node| (**node).to_owned_ast()).collect()
+    }
+}
+
+impl<'ast, T: ToOwnedAst> ToOwnedAst for ast::Block<'ast, T> {
+    type Owned = Block<T::Owned>;
+
+    #[inline]
+    fn to_owned_ast(&self) -> Self::Owned {
+        Block {
+            body: self.body.to_owned_ast(),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::Pattern<'ast> {
+    type Owned = Pattern;
+
+    fn to_owned_ast(&self) -> Pattern {
+        use ast::Pattern::*;
+
+        match *self {
+            Void => Pattern::Void,
+            Identifier(ident) => Pattern::Identifier(ident.to_owned()),
+            ObjectPattern { ref properties } => Pattern::ObjectPattern {
+                properties: properties.to_owned_ast(),
+            },
+            ArrayPattern { ref elements } => Pattern::ArrayPattern {
+                elements: elements.to_owned_ast(),
+            },
+            RestElement { ref argument } => Pattern::RestElement {
+                argument: argument.to_owned_ast(),
+            },
+            AssignmentPattern { ref left, ref right } => Pattern::AssignmentPattern {
+                left: left.to_owned_ast(),
+                right: right.to_owned_ast(),
+            },
+            Annotated { ref pattern, optional, ref annotation } => Pattern::Annotated {
+                pattern: pattern.to_owned_ast(),
+                optional,
+                annotation: annotation.to_owned_ast(),
+            },
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::Literal<'ast> {
+    type Owned = Literal;
+
+    fn to_owned_ast(&self) -> Literal {
+        use ast::Literal::*;
+
+        match *self {
+            Undefined     => Literal::Undefined,
+            Null          => Literal::Null,
+            True          => Literal::True,
+            False         => Literal::False,
+            Number(value) => Literal::Number(value.to_owned()),
+            Binary(value) => Literal::Binary(value.to_owned()),
+            String(value) => Literal::String(value.to_owned()),
+            RegEx(value)  => Literal::RegEx(value.to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::thread;
+    use parser::parse;
+
+    #[test]
+    fn outlives_arena() {
+        let program = {
+            let module = parse("let [a, b = 1] = c; function foo(d) { return d ** 2; }").unwrap();
+
+            module.to_owned_ast()
+        };
+
+        let expected = Program {
+            source: "let [a, b = 1] = c; function foo(d) { return d ** 2; }".to_owned(),
+            body: vec![
+                Loc::new(0, 19, Statement::Declaration(DeclarationStatement {
+                    kind: DeclarationKind::Let,
+                    declarators: vec![
+                        Loc::new(4, 18, Declarator {
+                            id: Box::new(Loc::new(4, 14, Pattern::ArrayPattern {
+                                elements: vec![
+                                    Loc::new(5, 6, Pattern::Identifier("a".to_owned())),
+                                    Loc::new(8, 13, Pattern::AssignmentPattern {
+                                        left: Box::new(Loc::new(8, 9, Pattern::Identifier("b".to_owned()))),
+                                        right: Box::new(Loc::new(12, 13, Expression::Literal(Literal::Number("1".to_owned())))),
+                                    }),
+                                ],
+                            })),
+                            init: Some(Box::new(Loc::new(17, 18, Expression::Identifier("c".to_owned())))),
+                        }),
+                    ],
+                })),
+                Loc::new(20, 54, Statement::Function(Function {
+                    name: MandatoryName(Box::new(Loc::new(29, 32, "foo".to_owned()))),
+                    generator: false,
+                    is_async: false,
+                    type_parameters: Vec::new(),
+                    params: vec![
+                        Loc::new(33, 34, Pattern::Identifier("d".to_owned())),
+                    ],
+                    return_type: None,
+                    body: Box::new(Loc::new(36, 54, Block {
+                        body: vec![
+                            Loc::new(38, 52, Statement::Return(ReturnStatement {
+                                value: Some(Box::new(Loc::new(45, 51, Expression::Binary(BinaryExpression {
+                                    operator: OperatorKind::Exponent,
+                                    left: Box::new(Loc::new(45, 46, Expression::Identifier("d".to_owned()))),
+                                    right: Box::new(Loc::new(50, 51, Expression::Literal(Literal::Number("2".to_owned())))),
+                                })))),
+                            })),
+                        ],
+                    })),
+                })),
+            ],
+        };
+
+        assert_eq!(program, expected);
+    }
+
+    #[test]
+    fn sent_across_threads() {
+        let program = parse_owned("foo(bar, `baz${qux}`);").unwrap();
+
+        let body = thread::spawn(move || program.body).join().unwrap();
+
+        match body[0].item {
+            Statement::Expression(ref expression) => match expression.item {
+                Expression::Call(CallExpression { ref callee, ref arguments }) => {
+                    assert_eq!(callee.item, Expression::Identifier("foo".to_owned()));
+                    assert_eq!(arguments.len(), 2);
+                },
+                _ => panic!("Expected a call expression"),
+            },
+            _ => panic!("Expected an expression statement"),
+        }
+    }
+}
@@ -0,0 +1,403 @@
+use ast;
+use owned::{ToOwnedAst, Node, NodeList, DeclarationKind, Function, Class, MandatoryName, OptionalName, IdentifierNode};
+use owned::{ExpressionNode, StatementNode, StatementList, Block, BlockNode, Pattern};
+use owned::{TypeNode, TypeList, TypeMember, TypeParameterList};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Declarator {
+    pub id: Node<Pattern>,
+    pub init: Option<ExpressionNode>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DeclarationStatement {
+    pub kind: DeclarationKind,
+    pub declarators: NodeList<Declarator>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ReturnStatement {
+    pub value: Option<ExpressionNode>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BreakStatement {
+    pub label: Option<IdentifierNode>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ContinueStatement {
+    pub label: Option<IdentifierNode>
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ThrowStatement {
+    pub value: ExpressionNode
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct IfStatement {
+    pub test: ExpressionNode,
+    pub consequent: StatementNode,
+    pub alternate: Option<StatementNode>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct WhileStatement {
+    pub test: ExpressionNode,
+    pub body: StatementNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DoStatement {
+    pub body: StatementNode,
+    pub test: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ForInit {
+    Declaration(DeclarationStatement),
+    Expression(ExpressionNode)
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ForStatement {
+    pub init: Option<Node<ForInit>>,
+    pub test: Option<ExpressionNode>,
+    pub update: Option<ExpressionNode>,
+    pub body: StatementNode
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ForInStatement {
+    pub left: Node<ForInit>,
+    pub right: ExpressionNode,
+    pub body: StatementNode
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ForOfStatement {
+    pub left: Node<ForInit>,
+    pub right: ExpressionNode,
+    pub body: StatementNode
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CatchClause {
+    pub param: Node<Pattern>,
+    pub body: BlockNode<Statement>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TryStatement {
+    pub block: BlockNode<Statement>,
+    pub handler: Option<Node<CatchClause>>,
+    pub finalizer: Option<BlockNode<Statement>>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LabeledStatement {
+    pub label: String,
+    pub body: StatementNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SwitchStatement {
+    pub discriminant: ExpressionNode,
+    pub cases: BlockNode<SwitchCase>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SwitchCase {
+    pub test: Option<ExpressionNode>,
+    pub consequent: StatementList,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImportDeclaration {
+    pub specifiers: NodeList<ForImportSpecifier>,
+    pub source: String,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImportSpecifier {
+    pub imported: IdentifierNode,
+    pub local: IdentifierNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImportDefaultSpecifier {
+    pub local: IdentifierNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImportNamespaceSpecifier {
+    pub local: IdentifierNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ForImportSpecifier {
+    ImportSpecifier(ImportSpecifier),
+    ImportDefaultSpecifier(ImportDefaultSpecifier),
+    ImportNamespaceSpecifier(ImportNamespaceSpecifier),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExportDeclaration {
+    pub declaration: StatementNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExportNamedDeclaration {
+    pub specifiers: NodeList<ExportSpecifier>,
+    pub source: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExportSpecifier {
+    pub local: IdentifierNode,
+    pub exported: IdentifierNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ExportDefaultDeclaration {
+    Function(Node<Function<OptionalName>>),
+    Class(Node<Class<OptionalName>>),
+    Expression(ExpressionNode),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypeAliasDeclaration {
+    pub name: IdentifierNode,
+    pub type_parameters: TypeParameterList,
+    pub value: TypeNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InterfaceDeclaration {
+    pub name: IdentifierNode,
+    pub type_parameters: TypeParameterList,
+    pub extends: TypeList,
+    pub body: NodeList<TypeMember>,
+}
+
+pub type BlockStatement = Block<Statement>;
+pub type FunctionStatement = Function<MandatoryName>;
+pub type ClassStatement = Class<MandatoryName>;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Statement {
+    Empty,
+    Debugger,
+    Expression(ExpressionNode),
+    Declaration(DeclarationStatement),
+    Return(ReturnStatement),
+    Break(BreakStatement),
+    Continue(ContinueStatement),
+    Throw(ThrowStatement),
+    If(IfStatement),
+    While(WhileStatement),
+    Do(DoStatement),
+    For(ForStatement),
+    ForIn(ForInStatement),
+    ForOf(ForOfStatement),
+    Try(TryStatement),
+    Block(BlockStatement),
+    Labeled(LabeledStatement),
+    Function(FunctionStatement),
+    Class(ClassStatement),
+    Switch(SwitchStatement),
+    Import(ImportDeclaration),
+    Export(ExportDeclaration),
+    ExportNamed(ExportNamedDeclaration),
+    ExportDefault(ExportDefaultDeclaration),
+    TypeAlias(TypeAliasDeclaration),
+    Interface(InterfaceDeclaration),
+}
+
+impl<'ast> ToOwnedAst for ast::Declarator<'ast> {
+    type Owned = Declarator;
+
+    fn to_owned_ast(&self) -> Declarator {
+        Declarator {
+            id: self.id.to_owned_ast(),
+            init: self.init.to_owned_ast(),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::statement::DeclarationStatement<'ast> {
+    type Owned = DeclarationStatement;
+
+    fn to_owned_ast(&self) -> DeclarationStatement {
+        DeclarationStatement {
+            kind: self.kind,
+            declarators: self.declarators.to_owned_ast(),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::statement::ForInit<'ast> {
+    type Owned = ForInit;
+
+    fn to_owned_ast(&self) -> ForInit {
+        match *self {
+            ast::statement::ForInit::Declaration(ref declaration) => ForInit::Declaration(declaration.to_owned_ast()),
+            ast::statement::ForInit::Expression(ref expression)   => ForInit::Expression(expression.to_owned_ast()),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::statement::CatchClause<'ast> {
+    type Owned = CatchClause;
+
+    fn to_owned_ast(&self) -> CatchClause {
+        CatchClause {
+            param: self.param.to_owned_ast(),
+            body: self.body.to_owned_ast(),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::statement::SwitchCase<'ast> {
+    type Owned = SwitchCase;
+
+    fn to_owned_ast(&self) -> SwitchCase {
+        SwitchCase {
+            test: self.test.to_owned_ast(),
+            consequent: self.consequent.to_owned_ast(),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::statement::ForImportSpecifier<'ast> {
+    type Owned = ForImportSpecifier;
+
+    fn to_owned_ast(&self) -> ForImportSpecifier {
+        use ast::statement::ForImportSpecifier::*;
+
+        match *self {
+            ImportSpecifier(ref specifier) => ForImportSpecifier::ImportSpecifier(self::ImportSpecifier {
+                imported: specifier.imported.to_owned_ast(),
+                local: specifier.local.to_owned_ast(),
+            }),
+            ImportDefaultSpecifier(ref specifier) => ForImportSpecifier::ImportDefaultSpecifier(self::ImportDefaultSpecifier {
+                local: specifier.local.to_owned_ast(),
+            }),
+            ImportNamespaceSpecifier(ref specifier) => ForImportSpecifier::ImportNamespaceSpecifier(self::ImportNamespaceSpecifier {
+                local: specifier.local.to_owned_ast(),
+            }),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::statement::ExportSpecifier<'ast> {
+    type Owned = ExportSpecifier;
+
+    fn to_owned_ast(&self) -> ExportSpecifier {
+        ExportSpecifier {
+            local: self.local.to_owned_ast(),
+            exported: self.exported.to_owned_ast(),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::Statement<'ast> {
+    type Owned = Statement;
+
+    fn to_owned_ast(&self) -> Statement {
+        use ast::Statement::*;
+        use ast::statement::ExportDefaultDeclaration as DefaultDeclaration;
+
+        match *self {
+            Empty => Statement::Empty,
+            Debugger => Statement::Debugger,
+            Expression(ref expression) => Statement::Expression(expression.to_owned_ast()),
+            Declaration(ref declaration) => Statement::Declaration(declaration.to_owned_ast()),
+            Return(ref statement) => Statement::Return(ReturnStatement {
+                value: statement.value.to_owned_ast(),
+            }),
+            Break(ref statement) => Statement::Break(BreakStatement {
+                label: statement.label.to_owned_ast(),
+            }),
+            Continue(ref statement) => Statement::Continue(ContinueStatement {
+                label: statement.label.to_owned_ast(),
+            }),
+            Throw(ref statement) => Statement::Throw(ThrowStatement {
+                value: statement.value.to_owned_ast(),
+            }),
+            If(ref statement) => Statement::If(IfStatement {
+                test: statement.test.to_owned_ast(),
+                consequent: statement.consequent.to_owned_ast(),
+                alternate: statement.alternate.to_owned_ast(),
+            }),
+            While(ref statement) => Statement::While(WhileStatement {
+                test: statement.test.to_owned_ast(),
+                body: statement.body.to_owned_ast(),
+            }),
+            Do(ref statement) => Statement::Do(DoStatement {
+                body: statement.body.to_owned_ast(),
+                test: statement.test.to_owned_ast(),
+            }),
+            For(ref statement) => Statement::For(ForStatement {
+                init: statement.init.to_owned_ast(),
+                test: statement.test.to_owned_ast(),
+                update: statement.update.to_owned_ast(),
+                body: statement.body.to_owned_ast(),
+            }),
+            ForIn(ref statement) => Statement::ForIn(ForInStatement {
+                left: statement.left.to_owned_ast(),
+                right: statement.right.to_owned_ast(),
+                body: statement.body.to_owned_ast(),
+            }),
+            ForOf(ref statement) => Statement::ForOf(ForOfStatement {
+                left: statement.left.to_owned_ast(),
+                right: statement.right.to_owned_ast(),
+                body: statement.body.to_owned_ast(),
+            }),
+            Try(ref statement) => Statement::Try(TryStatement {
+                block: statement.block.to_owned_ast(),
+                handler: statement.handler.to_owned_ast(),
+                finalizer: statement.finalizer.to_owned_ast(),
+            }),
+            Block(ref block) => Statement::Block(block.to_owned_ast()),
+            Labeled(ref statement) => Statement::Labeled(LabeledStatement {
+                label: statement.label.to_owned(),
+                body: statement.body.to_owned_ast(),
+            }),
+            Function(ref function) => Statement::Function(function.to_owned_ast()),
+            Class(ref class) => Statement::Class(class.to_owned_ast()),
+            Switch(ref statement) => Statement::Switch(SwitchStatement {
+                discriminant: statement.discriminant.to_owned_ast(),
+                cases: statement.cases.to_owned_ast(),
+            }),
+            Import(ref declaration) => Statement::Import(ImportDeclaration {
+                specifiers: declaration.specifiers.to_owned_ast(),
+                source: declaration.source.to_owned(),
+            }),
+            Export(ref declaration) => Statement::Export(ExportDeclaration {
+                declaration: declaration.declaration.to_owned_ast(),
+            }),
+            ExportNamed(ref declaration) => Statement::ExportNamed(ExportNamedDeclaration {
+                specifiers: declaration.specifiers.to_owned_ast(),
+                source: declaration.source.map(str::to_owned),
+            }),
+            ExportDefault(ref declaration) => Statement::ExportDefault(match *declaration {
+                DefaultDeclaration::Function(ref function)     => ExportDefaultDeclaration::Function(function.to_owned_ast()),
+                DefaultDeclaration::Class(ref class)           => ExportDefaultDeclaration::Class(class.to_owned_ast()),
+                DefaultDeclaration::Expression(ref expression) => ExportDefaultDeclaration::Expression(expression.to_owned_ast()),
+            }),
+            TypeAlias(ref declaration) => Statement::TypeAlias(TypeAliasDeclaration {
+                name: declaration.name.to_owned_ast(),
+                type_parameters: declaration.type_parameters.to_owned_ast(),
+                value: declaration.value.to_owned_ast(),
+            }),
+            Interface(ref declaration) => Statement::Interface(InterfaceDeclaration {
+                name: declaration.name.to_owned_ast(),
+                type_parameters: declaration.type_parameters.to_owned_ast(),
+                extends: declaration.extends.to_owned_ast(),
+                body: declaration.body.to_owned_ast(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use ast;
+use owned::{ToOwnedAst, NodeList, TypeNode, TypeList, PatternList, IdentifierNode, Literal, Primitive};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Type {
+    Any,
+    Primitive(Primitive),
+    Identifier(String),
+    Literal(Literal),
+    Union {
+        variants: TypeList,
+    },
+    Intersection {
+        variants: TypeList,
+    },
+    Generic {
+        ident: String,
+        subtypes: TypeList,
+    },
+    Array {
+        element: TypeNode,
+    },
+    Tuple {
+        elements: TypeList,
+    },
+    Function {
+        params: PatternList,
+        returns: TypeNode,
+    },
+    Object {
+        members: NodeList<TypeMember>,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypeMember {
+    pub key: IdentifierNode,
+    pub optional: bool,
+    pub value: TypeNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypeParameter {
+    pub name: String,
+    pub constraint: Option<TypeNode>,
+    pub default: Option<TypeNode>,
+}
+
+pub type TypeParameterList = NodeList<TypeParameter>;
+
+impl<'ast> ToOwnedAst for ast::Type<'ast> {
+    type Owned = Type;
+
+    fn to_owned_ast(&self) -> Type {
+        use ast::Type::*;
+
+        match *self {
+            Any => Type::Any,
+            Primitive(primitive) => Type::Primitive(primitive),
+            Identifier(ident) => Type::Identifier(ident.to_owned()),
+            Literal(ref literal) => Type::Literal(literal.to_owned_ast()),
+            Union { ref variants } => Type::Union {
+                variants: variants.to_owned_ast(),
+            },
+            Intersection { ref variants } => Type::Intersection {
+                variants: variants.to_owned_ast(),
+            },
+            Generic { ident, ref subtypes } => Type::Generic {
+                ident: ident.to_owned(),
+                subtypes: subtypes.to_owned_ast(),
+            },
+            Array { ref element } => Type::Array {
+                element: element.to_owned_ast(),
+            },
+            Tuple { ref elements } => Type::Tuple {
+                elements: elements.to_owned_ast(),
+            },
+            Function { ref params, ref returns } => Type::Function {
+                params: params.to_owned_ast(),
+                returns: returns.to_owned_ast(),
+            },
+            Object { ref members } => Type::Object {
+                members: members.to_owned_ast(),
+            },
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::TypeMember<'ast> {
+    type Owned = TypeMember;
+
+    fn to_owned_ast(&self) -> TypeMember {
+        TypeMember {
+            key: self.key.to_owned_ast(),
+            optional: self.optional,
+            value: self.value.to_owned_ast(),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::TypeParameter<'ast> {
+    type Owned = TypeParameter;
+
+    fn to_owned_ast(&self) -> TypeParameter {
+        TypeParameter {
+            name: self.name.to_owned(),
+            constraint: self.constraint.to_owned_ast(),
+            default: self.default.to_owned_ast(),
+        }
+    }
+}
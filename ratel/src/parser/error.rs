@@ -1,6 +1,6 @@
 use error::Error;
 
-use ast::{Node, Loc, NodeList, Pattern};
+use ast::{Node, Loc, NodeList, Pattern, Expression, Statement, Type};
 use ast::{Name, ClassMember, Property, PropertyKey, MandatoryName, Block};
 use parser::Parser;
 
@@ -89,6 +89,39 @@ impl<'ast> ToError for Node<'ast, Pattern<'ast>> {
     }
 }
 
+impl<'ast> ToError for Node<'ast, Expression<'ast>> {
+    #[inline]
+    fn to_error() -> Self {
+        Node::new(&Loc {
+            start: 0,
+            end: 0,
+            item: Expression::Void
+        })
+    }
+}
+
+impl<'ast> ToError for Node<'ast, Statement<'ast>> {
+    #[inline]
+    fn to_error() -> Self {
+        Node::new(&Loc {
+            start: 0,
+            end: 0,
+            item: Statement::Empty
+        })
+    }
+}
+
+impl<'ast> ToError for Node<'ast, Type<'ast>> {
+    #[inline]
+    fn to_error() -> Self {
+        Node::new(&Loc {
+            start: 0,
+            end: 0,
+            item: Type::Any
+        })
+    }
+}
+
 impl<'ast> ToError for Node<'ast, PropertyKey<'ast>> {
     #[inline]
     fn to_error() -> Self {
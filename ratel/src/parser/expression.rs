@@ -1,35 +1,38 @@
 use toolshed::list::ListBuilder;
-use parser::{Parser, Parse, BindingPower, ANY, B0, B15};
+use parser::{Parser, Parse, FunctionContext, BindingPower, ANY, B0, B15};
 use lexer::Token::*;
-use ast::{Node, NodeList, Expression, ExpressionNode, IdentifierNode, ExpressionList};
-use ast::{Property, PropertyKey, OperatorKind, Literal, Function, Class, StatementNode};
+use lexer::Asi;
+use error::ErrorCode;
+use span::Span;
+use ast::{Node, NodeList, Expression, ExpressionNode, IdentifierNode, ExpressionList, PatternList, TypeNode};
+use ast::{Property, PropertyKey, OperatorKind, Literal, Function, Class, StatementNode, MethodKind};
 use ast::expression::*;
 
 
 type ExpressionHandler = for<'ast> fn(&mut Parser<'ast>) -> ExpressionNode<'ast>;
 
-pub type Context = &'static [ExpressionHandler; 108];
+pub type Context = &'static [ExpressionHandler; 113];
 
 static DEF_CONTEXT: Context = &[
     ____, ____, ____, ____, PRN,  ____, ARR,  ____, OBJ,  ____, ____, NEW,
 //  EOF   ;     :     ,     (     )     [     ]     {     }     =>    NEW
 
-    OP,   OP,   OP,   OP,   OP,   OP,   OP,   ____, REG,  ____, ____, OP,
+    OP,   OP,   OP,   OP,   OP,   OP,   OP,   STAR, REG,  ____, ____, OP,
 //  ++    --    !     ~     TYPOF VOID  DELET *     /     %     **    +
 
-    OP,   ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
+    OP,   ____, ____, ____, JSX,  ____, ____, ____, ____, ____, ____, ____,
 //  -     <<    >>    >>>   <     <=    >     >=    INSOF IN    ===   !==
 
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
 //  ==    !=    &     ^     |     &&    ||    ?     =     +=    -=    **=
 
-    ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
+    ____, REG,  ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
 //  *=    /=    %=    <<=   >>=   >>>=  &=    ^=    |=    ...   VAR   LET
 
     ____, ____, ____, ____, ____, ____, ____, CLAS, ____, ____, ____, ____,
 //  CONST BREAK DO    CASE  ELSE  CATCH EXPRT CLASS EXTND RET   WHILE FINLY
 
-    ____, ____, ____, ____, ____, ____, ____, FUNC, THIS, ____, ____, ____,
+    SUPR, ____, ____, ____, ____, YILD, ____, FUNC, THIS, ____, ____, ____,
 //  SUPER WITH  CONT  FOR   SWTCH YIELD DBGGR FUNCT THIS  DEFLT IF    THROW
 
     ____, ____, ____, TRUE, FALS, NULL, UNDE, STR,  NUM,  BIN,  ____, ____,
@@ -37,32 +40,37 @@ static DEF_CONTEXT: Context = &[
 
     ____, ____, ____, ____, ____, ____, IDEN, ____, TPLE, TPLS, ____, ____,
 //  IMPL  PCKG  PROT  IFACE PRIV  PUBLI IDENT ACCSS TPL_O TPL_C ERR_T ERR_E
+
+    ____, ____, ____, ____, ____,
+//  ?.    ??    ??=   ||=   &&=
 ];
 
 // Adds handlers for VoidExpression and SpreadExpression
 pub static ARRAY_CONTEXT: Context = &[
     ____, ____, ____, VOID, PRN,  ____, ARR,  VOID, OBJ,  ____, ____, NEW,
-    OP,   OP,   OP,   OP,   OP,   OP,   OP,   ____, REG,  ____, ____, OP,
-    OP,   ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
+    OP,   OP,   OP,   OP,   OP,   OP,   OP,   STAR, REG,  ____, ____, OP,
+    OP,   ____, ____, ____, JSX,  ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
-    ____, ____, ____, ____, ____, ____, ____, ____, ____, SPRD, ____, ____,
+    ____, REG,  ____, ____, ____, ____, ____, ____, ____, SPRD, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, CLAS, ____, ____, ____, ____,
-    ____, ____, ____, ____, ____, ____, ____, FUNC, THIS, ____, ____, ____,
+    SUPR, ____, ____, ____, ____, YILD, ____, FUNC, THIS, ____, ____, ____,
     ____, ____, ____, TRUE, FALS, NULL, UNDE, STR,  NUM,  BIN,  ____, ____,
     ____, ____, ____, ____, ____, ____, IDEN, ____, TPLE, TPLS, ____, ____,
+    ____, ____, ____, ____, ____,
 ];
 
 // Adds handler for SpreadExpression
 pub static CALL_CONTEXT: Context = &[
     ____, ____, ____, ____, PRN,  ____, ARR,  ____, OBJ,  ____, ____, NEW,
-    OP,   OP,   OP,   OP,   OP,   OP,   OP,   ____, REG,  ____, ____, OP,
-    OP,   ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
+    OP,   OP,   OP,   OP,   OP,   OP,   OP,   STAR, REG,  ____, ____, OP,
+    OP,   ____, ____, ____, JSX,  ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
-    ____, ____, ____, ____, ____, ____, ____, ____, ____, SPRD, ____, ____,
+    ____, REG,  ____, ____, ____, ____, ____, ____, ____, SPRD, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, CLAS, ____, ____, ____, ____,
-    ____, ____, ____, ____, ____, ____, ____, FUNC, THIS, ____, ____, ____,
+    SUPR, ____, ____, ____, ____, YILD, ____, FUNC, THIS, ____, ____, ____,
     ____, ____, ____, TRUE, FALS, NULL, UNDE, STR,  NUM,  BIN,  ____, ____,
     ____, ____, ____, ____, ____, ____, IDEN, ____, TPLE, TPLS, ____, ____,
+    ____, ____, ____, ____, ____,
 ];
 
 macro_rules! create_handlers {
@@ -80,8 +88,9 @@ macro_rules! create_handlers {
             $(
                 #[allow(non_snake_case)]
                 pub fn $pname<'ast>($ppar: &mut Parser<'ast>) -> StatementNode<'ast> {
+                    let start = $ppar.lexer.start();
                     let expression = $pcode;
-                    $ppar.expression_statement(expression)
+                    $ppar.expression_statement_at(start, expression)
                 }
             )*
         }
@@ -112,6 +121,19 @@ create_handlers! {
 
     const IDEN = |par| {
         let ident = par.lexer.token_as_str();
+
+        if ident == "await" && par.context.await_expression {
+            let start = par.lexer.start_then_consume();
+
+            return par.await_expression(start);
+        }
+
+        if ident == "await" && par.context.await_reserved {
+            let span = par.lexer.span();
+
+            par.error_at::<()>(ErrorCode::UnexpectedToken, span);
+        }
+
         let expr = par.alloc_in_loc(ident);
 
         par.lexer.consume();
@@ -132,11 +154,25 @@ create_handlers! {
         expr
     };
 
+    pub const SUPR = |par| par.super_expression();
+
+    pub const YILD = |par| par.yield_expression();
+
+    pub const STAR = |par| par.generator_arrow_expression();
+
     pub const OP = |par| {
         let start = par.lexer.start();
         let op = OperatorKind::from_token(par.lexer.token).expect("Must be a prefix operator");
         par.lexer.consume();
         let expression = par.prefix_expression(op);
+
+        // `-a ** b` is ambiguous, the operand of a unary operator can only
+        // be raised when parenthesized as `(-a) ** b`
+        match op {
+            OperatorKind::Increment | OperatorKind::Decrement => {},
+            _ => par.exponent_base(),
+        }
+
         let end = par.lexer.end();
         par.alloc_at_loc(start, end, expression)
     };
@@ -154,6 +190,9 @@ create_handlers! {
             par.alloc_at_loc(start, end, expression)
         } else {
             let expression = par.prefix_expression(OperatorKind::New);
+
+            par.new_constructor(expression.operand);
+
             let end = par.lexer.end();
             par.alloc_at_loc(start, end, expression)
         }
@@ -232,17 +271,19 @@ create_handlers! {
     };
 
     pub const TPLE = |par| par.template_expression();
+
+    pub const JSX = |par| par.jsx_expression();
 }
 
 impl<'ast> Parser<'ast> {
     #[inline]
     fn bound_expression(&mut self) -> ExpressionNode<'ast> {
-        unsafe { (*(DEF_CONTEXT as *const ExpressionHandler).offset(self.lexer.token as isize))(self) }
+        self.allowing_in(|par| par.nested(|par| unsafe { (*(DEF_CONTEXT as *const ExpressionHandler).offset(par.lexer.token as isize))(par) }))
     }
 
     #[inline]
     fn context_bound_expression(&mut self, context: Context) -> ExpressionNode<'ast> {
-        unsafe { (*(context as *const ExpressionHandler).offset(self.lexer.token as isize))(self) }
+        self.allowing_in(|par| par.nested(|par| unsafe { (*(context as *const ExpressionHandler).offset(par.lexer.token as isize))(par) }))
     }
 
     #[inline]
@@ -269,17 +310,72 @@ impl<'ast> Parser<'ast> {
     pub fn arrow_function_expression(&mut self, params: ExpressionList<'ast>) -> ArrowExpression<'ast> {
         let params = self.params_from_expressions(params);
 
-        let body = match self.lexer.token {
-            BraceOpen => ArrowBody::Block(self.unchecked_block()),
-            _         => ArrowBody::Expression(self.expression::<B0>()),
+        self.arrow_function_body(params, None)
+    }
+
+    /// Parse the body of an arrow function following the `=>`.
+    #[inline]
+    pub fn arrow_function_body(&mut self, params: PatternList<'ast>, return_type: Option<TypeNode<'ast>>) -> ArrowExpression<'ast> {
+        self.unique_params(params);
+
+        if self.options.typescript {
+            self.ordered_params(params);
+        }
+
+        // Arrows share `super` and `new.target` with the function they are
+        // in, but can't `yield` or `await` on its behalf. Nor can they use
+        // `await` as a name within async functions.
+        let context = FunctionContext {
+            yield_expression: false,
+            await_expression: false,
+            return_statement: true,
+            ..self.context
         };
 
+        let recorded = self.recorded_block_functions();
+        let body = self.with_context(context, |par| match par.lexer.token {
+            BraceOpen => ArrowBody::Block(par.unchecked_block()),
+            _         => ArrowBody::Expression(par.expression::<B0>()),
+        });
+
+        if let ArrowBody::Block(block) = body {
+            self.strict_body_since(recorded, block.body);
+        }
+
         ArrowExpression {
             params,
+            return_type,
             body,
         }
     }
 
+    /// There is no such thing as a generator arrow, but `*() => {}` or
+    /// `*x => x` is parsed as one anyway so that the error can cover the
+    /// whole arrow, rather than only the unexpected `*`.
+    fn generator_arrow_expression(&mut self) -> ExpressionNode<'ast> {
+        let (start, end) = self.lexer.loc();
+
+        self.lexer.consume();
+
+        match self.lexer.token {
+            ParenOpen | Identifier => {},
+            _ => {
+                self.error_at::<()>(ErrorCode::UnexpectedToken, Span::new(start, end));
+
+                return self.alloc_at_loc(start, end, Expression::Void);
+            }
+        }
+
+        let expression = self.expression::<B0>();
+
+        match expression.item {
+            Expression::Arrow(_) => self.error_at::<()>(ErrorCode::GeneratorArrow, Span::new(start, expression.end)),
+            _                    => self.error_at::<()>(ErrorCode::UnexpectedToken, Span::new(start, end)),
+        }
+
+        expression
+    }
+
     #[inline]
     pub fn call_arguments(&mut self) -> ExpressionList<'ast> {
         if self.lexer.token == ParenClose {
@@ -315,20 +411,45 @@ impl<'ast> Parser<'ast> {
 
     #[inline]
     pub fn paren_expression(&mut self) -> ExpressionNode<'ast> {
-        let start = self.lexer.start_then_consume();
+        let start = self.lexer.start();
+
+        if self.options.typescript {
+            if let Some((params, return_type)) = self.typed_arrow_head() {
+                let expression = self.arrow_function_body(params, return_type);
+                let end = expression.body.end();
+
+                return self.alloc_at_loc(start, end, expression);
+            }
+        }
+
+        self.lexer.consume();
+
         match self.lexer.token {
             ParenClose => {
                 self.lexer.consume();
                 expect!(self, OperatorFatArrow);
                 let expression = self.arrow_function_expression(NodeList::empty());
-                let end = self.lexer.end();
+                let end = expression.body.end();
                 self.alloc_at_loc(start, end, expression)
             },
             _ => {
                 let expression = self.expression::<ANY>();
+                let end = self.lexer.end();
 
                 expect!(self, ParenClose);
 
+                // Parentheses end an optional chain, so `(a?.b).c` has to
+                // keep them around even when they aren't being preserved
+                if self.options.preserve_parens || expression.is_optional_chain() {
+                    return self.alloc_at_loc(start, end, ParenthesizedExpression {
+                        expression
+                    });
+                }
+
+                if let Expression::Binary(_) | Expression::Array(_) | Expression::Object(_) = expression.item {
+                    self.parenthesized.push(expression);
+                }
+
                 expression
             }
         }
@@ -344,6 +465,32 @@ impl<'ast> Parser<'ast> {
         }
     }
 
+    /// Reject an optional chain in the constructor of `new`, which is its
+    /// operand up to the first arguments, `a?.b` in `new a?.b().c`.
+    fn new_constructor(&mut self, operand: ExpressionNode<'ast>) {
+        let mut constructor = operand;
+        let mut object = operand;
+
+        loop {
+            object = match object.item {
+                Expression::Member(MemberExpression { object, .. })                 |
+                Expression::ComputedMember(ComputedMemberExpression { object, .. }) |
+                Expression::Optional(OptionalExpression { object, .. })             => object,
+
+                Expression::Call(CallExpression { callee, .. }) => {
+                    constructor = callee;
+                    callee
+                },
+
+                _ => break,
+            };
+        }
+
+        if constructor.is_optional_chain() {
+            self.error_at::<()>(ErrorCode::OptionalChainInNew, constructor.span());
+        }
+    }
+
     #[inline]
     pub fn object_expression(&mut self) -> ExpressionNode<'ast> {
         let start = self.lexer.start_then_consume();
@@ -355,6 +502,92 @@ impl<'ast> Parser<'ast> {
         })
     }
 
+    /// Parse `super`, which can only be called or have its properties
+    /// accessed, each in their own context.
+    #[inline]
+    pub fn super_expression(&mut self) -> ExpressionNode<'ast> {
+        let span = self.lexer.span();
+        let expression = self.alloc_in_loc(SuperExpression);
+
+        self.lexer.consume();
+
+        match self.lexer.token {
+            ParenOpen if !self.context.super_call => {
+                self.error_at::<()>(ErrorCode::InvalidSuperCall, span);
+            },
+            Accessor | BracketOpen if !self.context.super_property => {
+                self.error_at::<()>(ErrorCode::InvalidSuperProperty, span);
+            },
+            ParenOpen | Accessor | BracketOpen => {},
+            _ => return self.error(),
+        }
+
+        expression
+    }
+
+    /// Report a `**` following a unary operation, which can't be the base
+    /// of an exponentiation without parentheses.
+    #[inline]
+    pub fn exponent_base(&mut self) {
+        if self.lexer.token == OperatorExponent {
+            self.error::<()>();
+        }
+    }
+
+    /// Parse `yield`, `yield a` or `yield* a` within a generator. The argument
+    /// binds as loosely as an assignment, so `yield a + b` yields `a + b`.
+    #[inline]
+    pub fn yield_expression(&mut self) -> ExpressionNode<'ast> {
+        if !self.context.yield_expression {
+            return self.error();
+        }
+
+        let (start, mut end) = self.lexer.loc();
+
+        self.lexer.consume();
+
+        let delegate = self.lexer.token == OperatorMultiplication && self.asi() == Asi::NoSemicolon;
+
+        if delegate {
+            end = self.lexer.end_then_consume();
+        }
+
+        let asi = self.asi();
+
+        let argument = match self.lexer.token {
+            _ if delegate => Some(self.expression::<B0>()),
+
+            // Nothing to yield at the end of an expression or a line
+            ParenClose | BracketClose | BraceClose | Colon | Comma => None,
+            _ if asi != Asi::NoSemicolon                           => None,
+
+            _ => Some(self.expression::<B0>()),
+        };
+
+        if let Some(argument) = argument {
+            end = argument.end;
+        }
+
+        self.alloc_at_loc(start, end, YieldExpression {
+            delegate,
+            argument,
+        })
+    }
+
+    /// Parse the argument of `await` within an async function, the `await`
+    /// keyword starting at `start` has already been consumed. The argument
+    /// binds as tightly as the operand of a prefix operator.
+    #[inline]
+    pub fn await_expression(&mut self, start: u32) -> ExpressionNode<'ast> {
+        let argument = self.expression::<B15>();
+
+        self.exponent_base();
+
+        self.alloc_at_loc(start, argument.end, AwaitExpression {
+            argument
+        })
+    }
+
     #[inline]
     pub fn meta_property_expression(&mut self, meta: IdentifierNode<'ast>) -> MetaPropertyExpression<'ast> {
         let property = self.lexer.accessor_as_str();
@@ -362,6 +595,10 @@ impl<'ast> Parser<'ast> {
         // Only `NewTarget` is a valid MetaProperty.
         if property != "target" {
             self.error::<()>();
+        } else if !self.context.new_target {
+            let span = Span::new(meta.start, self.lexer.end());
+
+            self.error_at::<()>(ErrorCode::InvalidNewTarget, span);
         }
 
         let property = self.alloc_in_loc(property);
@@ -401,25 +638,50 @@ impl<'ast> Parser<'ast> {
     #[inline]
     pub fn property(&mut self) -> Node<'ast, Property<'ast>> {
         let start = self.lexer.start();
+        let mut is_async = false;
+        let mut kind = MethodKind::Method;
 
         let key = match self.lexer.token {
             _ if self.lexer.token.is_word() => {
-                let (start, end) = self.lexer.loc();
-                let label = self.lexer.token_as_str();
+                let (mut start, mut end) = self.lexer.loc();
+                let mut label = self.lexer.token_as_str();
 
                 self.lexer.consume();
 
+                // `get` or `set` followed by a name, or `async` followed by
+                // a name on the same line, is a modifier
+                if self.lexer.token.is_word() {
+                    match label {
+                        "get" => kind = MethodKind::Get,
+                        "set" => kind = MethodKind::Set,
+                        "async" if self.asi() == Asi::NoSemicolon => is_async = true,
+                        _ => return self.error(),
+                    }
+
+                    let (key_start, key_end) = self.lexer.loc();
+
+                    start = key_start;
+                    end = key_end;
+                    label = self.lexer.token_as_str();
+
+                    self.lexer.consume();
+                }
+
                 match self.lexer.token {
+                    Colon if kind != MethodKind::Method => return self.error(),
+
                     Colon | ParenOpen => self.alloc_at_loc(start, end, PropertyKey::Literal(label)),
 
+                    _ if is_async || kind != MethodKind::Method => return self.error(),
+
                     _ => return self.alloc_at_loc(start, end, Property::Shorthand(label)),
                 }
             },
             OperatorSpread => {
                 let start = self.lexer.start_then_consume();
                 let argument = self.expression::<B0>();
-                let end = self.lexer.end();
-                return self.alloc_at_loc(start, end, Property::Spread { argument });
+
+                return self.alloc_at_loc(start, argument.end, Property::Spread { argument });
             },
             LiteralString |
             LiteralNumber => {
@@ -451,7 +713,7 @@ impl<'ast> Parser<'ast> {
         };
 
         match self.lexer.token {
-            Colon => {
+            Colon if !is_async => {
                 self.lexer.consume();
 
                 let value = self.expression::<B0>();
@@ -462,10 +724,11 @@ impl<'ast> Parser<'ast> {
                 })
             },
             ParenOpen => {
-                let value = Node::parse(self);
+                let value = self.method(is_async, false);
 
                 self.alloc_at_loc(start, value.end, Property::Method {
                     key,
+                    kind,
                     value,
                 })
             },
@@ -609,7 +872,13 @@ impl<'ast> Parser<'ast> {
 
     #[inline]
     pub fn tagged_template_expression(&mut self, tag: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
-        let quasi = self.template_literal();
+        let quasi: Node<TemplateLiteral> = self.template_literal();
+
+        if tag.is_optional_chain() {
+            self.error_at::<()>(ErrorCode::TaggedTemplateInOptionalChain, quasi.span());
+
+            return tag;
+        }
 
         self.alloc_at_loc(tag.start, quasi.end, TaggedTemplateExpression {
             tag,
@@ -637,11 +906,15 @@ impl<'ast> Parser<'ast> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use ast::{OperatorKind, Literal, Statement, Function, Pattern, Class};
+    use ast::{OperatorKind, Literal, Statement, Function, Pattern, Class, EmptyName};
     use ast::expression::*;
     use ast::statement::*;
+    use ast::RegexFlags;
     use parser::parse;
+    use module::Module;
     use parser::mock::Mock;
+    use toolshed::Arena;
+    use span::Span;
 
     #[test]
     fn ident_expression() {
@@ -661,6 +934,46 @@ mod test {
         assert_expr!("true;", expected_c);
     }
 
+    #[test]
+    fn scientific_number_expression() {
+        assert_expr!("1e10;", Literal::Number("1e10"));
+        assert_expr!("1.5e-3;", Literal::Number("1.5e-3"));
+        assert_expr!("1E+2;", Literal::Number("1E+2"));
+    }
+
+    #[test]
+    fn dotted_float_expression() {
+        assert_expr!(".5;", Literal::Number(".5"));
+        assert_expr!("5.;", Literal::Number("5."));
+        assert_expr!("5.e3;", Literal::Number("5.e3"));
+        assert_expr!(".5e3;", Literal::Number(".5e3"));
+    }
+
+    #[test]
+    fn member_access_on_dotted_float() {
+        let mock = Mock::new();
+
+        let expected = MemberExpression {
+            object: mock.number("5."),
+            property: mock.ptr("a"),
+        };
+
+        assert_expr!("5..a;", expected);
+    }
+
+    #[test]
+    fn number_is_not_a_member() {
+        assert!(parse("a.5;").is_err());
+        assert!(parse("a.5e3;").is_err());
+    }
+
+    #[test]
+    fn malformed_number_expression() {
+        assert!(parse("1e;").is_err());
+        assert!(parse("1e+;").is_err());
+        assert!(parse("1.2.3;").is_err());
+    }
+
     #[test]
     fn template_expression() {
         let src = "`foobar`;";
@@ -724,6 +1037,90 @@ mod test {
         assert_expr!(src, expected);
     }
 
+    #[test]
+    fn nested_template_expression() {
+        let src = "`a${`b${c}`}d`;";
+        let mock = Mock::new();
+
+        let expected = TemplateLiteral {
+            expressions: mock.list([
+                TemplateLiteral {
+                    expressions: mock.list(["c"]),
+                    quasis: mock.list(["b", ""]),
+                },
+            ]),
+            quasis: mock.list(["a", "d"]),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn template_expression_with_escaped_backtick() {
+        let src = r"`foo\`bar${ 10 }\``;";
+        let mock = Mock::new();
+
+        let expected = TemplateLiteral {
+            expressions: mock.list([
+                Literal::Number("10"),
+            ]),
+            quasis: mock.list([r"foo\`bar", r"\`"]),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn template_expression_with_escaped_interpolation() {
+        let src = r"`foo\${bar}$baz${ 10 }`;";
+        let mock = Mock::new();
+
+        let expected = TemplateLiteral {
+            expressions: mock.list([
+                Literal::Number("10"),
+            ]),
+            quasis: mock.list([r"foo\${bar}$baz", ""]),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn division_after_call_expression() {
+        let src = "foo() / bar;";
+        let mock = Mock::new();
+
+        let expected = BinaryExpression {
+            operator: OperatorKind::Division,
+            left: mock.ptr(CallExpression {
+                callee: mock.ptr("foo"),
+                arguments: NodeList::empty(),
+            }),
+            right: mock.ptr("bar"),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn tagged_template_after_call_expression() {
+        let src = "foo()`bar`;";
+        let mock = Mock::new();
+
+        let expected = TaggedTemplateExpression {
+            tag: mock.ptr(CallExpression {
+                callee: mock.ptr("foo"),
+                arguments: NodeList::empty(),
+            }),
+            quasi: mock.ptr(TemplateLiteral {
+                expressions: NodeList::empty(),
+                quasis: mock.list(["bar"]),
+            })
+        };
+
+        assert_expr!(src, expected);
+    }
+
     #[test]
     fn sequence_expression() {
         let src = "foo, bar, baz;";
@@ -751,61 +1148,286 @@ mod test {
     }
 
     #[test]
-    fn parenthesized_binary_expression() {
-        let src = "(2 + 2);";
+    fn exponent_is_right_associative() {
+        let src = "a ** b ** c * d;";
         let mock = Mock::new();
 
         let expected = BinaryExpression {
-            operator: OperatorKind::Addition,
-            left: mock.number("2"),
-            right: mock.number("2"),
+            operator: OperatorKind::Multiplication,
+            left: mock.ptr(BinaryExpression {
+                operator: OperatorKind::Exponent,
+                left: mock.ptr("a"),
+                right: mock.ptr(BinaryExpression {
+                    operator: OperatorKind::Exponent,
+                    left: mock.ptr("b"),
+                    right: mock.ptr("c"),
+                }),
+            }),
+            right: mock.ptr("d"),
         };
 
         assert_expr!(src, expected);
     }
 
     #[test]
-    fn conditional_expression() {
-        let src = "true ? foo : bar";
+    fn exponent_of_unary_operation() {
+        assert!(parse("-a ** b;").is_err());
+        assert!(parse("typeof a.b ** c;").is_err());
+        assert!(parse("!a ** b;").is_err());
+        assert!(parse("(-a) ** b;").is_ok());
+        assert!(parse("-(a ** b);").is_ok());
+        assert!(parse("a ** -b;").is_ok());
+        assert!(parse("++a ** b; a-- ** b;").is_ok());
+    }
 
+    #[test]
+    fn in_expression() {
+        let src = "'foo' in bar === a + b in c;";
         let mock = Mock::new();
 
-        let expected = ConditionalExpression {
-            test: mock.ptr(Expression::Literal(Literal::True)),
-            consequent: mock.ptr("foo"),
-            alternate: mock.ptr("bar"),
+        let expected = BinaryExpression {
+            operator: OperatorKind::StrictEquality,
+            left: mock.ptr(BinaryExpression {
+                operator: OperatorKind::In,
+                left: mock.ptr(Literal::String("'foo'")),
+                right: mock.ptr("bar"),
+            }),
+            right: mock.ptr(BinaryExpression {
+                operator: OperatorKind::In,
+                left: mock.ptr(BinaryExpression {
+                    operator: OperatorKind::Addition,
+                    left: mock.ptr("a"),
+                    right: mock.ptr("b"),
+                }),
+                right: mock.ptr("c"),
+            }),
         };
 
         assert_expr!(src, expected);
     }
 
     #[test]
-    fn complex_conditional_expression() {
-        let src = "true ? foo = bar : baz";
-
+    fn instanceof_expression() {
+        let src = "a < b instanceof C && d;";
         let mock = Mock::new();
 
-        let expected = ConditionalExpression {
-            test: mock.ptr(Expression::Literal(Literal::True)),
-            consequent: mock.ptr(BinaryExpression {
-                operator: OperatorKind::Assign,
-                left: mock.ptr("foo"),
-                right: mock.ptr("bar"),
+        let expected = BinaryExpression {
+            operator: OperatorKind::LogicalAnd,
+            left: mock.ptr(BinaryExpression {
+                operator: OperatorKind::Instanceof,
+                left: mock.ptr(BinaryExpression {
+                    operator: OperatorKind::Lesser,
+                    left: mock.ptr("a"),
+                    right: mock.ptr("b"),
+                }),
+                right: mock.ptr("C"),
             }),
-            alternate: mock.ptr("baz"),
+            right: mock.ptr("d"),
         };
 
         assert_expr!(src, expected);
     }
 
     #[test]
-    fn postfix_expression() {
-        let src = "baz++;";
+    fn parenthesized_binary_expression() {
+        let src = "(2 + 2);";
         let mock = Mock::new();
 
-        let expected = PostfixExpression {
-            operator: OperatorKind::Increment,
-            operand: mock.ptr("baz"),
+        let expected = BinaryExpression {
+            operator: OperatorKind::Addition,
+            left: mock.number("2"),
+            right: mock.number("2"),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn preserved_parens() {
+        use parser::{parse_with, ParseOptions};
+        use error::ErrorCode;
+
+        let options = ParseOptions { preserve_parens: true, ..ParseOptions::default() };
+        let src = "(a + b) * c; ((a)); (x) = 1; ({}).a; (a, b) => a;";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            mock.ptr(BinaryExpression {
+                operator: OperatorKind::Multiplication,
+                left: mock.ptr(ParenthesizedExpression {
+                    expression: mock.ptr(BinaryExpression {
+                        operator: OperatorKind::Addition,
+                        left: mock.ptr("a"),
+                        right: mock.ptr("b"),
+                    })
+                }),
+                right: mock.ptr("c"),
+            }),
+            mock.ptr(ParenthesizedExpression {
+                expression: mock.ptr(ParenthesizedExpression {
+                    expression: mock.ptr("a")
+                })
+            }),
+            mock.ptr(BinaryExpression {
+                operator: OperatorKind::Assign,
+                left: mock.ptr(ParenthesizedExpression {
+                    expression: mock.ptr("x")
+                }),
+                right: mock.number("1"),
+            }),
+            mock.ptr(MemberExpression {
+                object: mock.ptr(ParenthesizedExpression {
+                    expression: mock.ptr(ObjectExpression {
+                        body: NodeList::empty()
+                    })
+                }),
+                property: mock.ptr("a"),
+            }),
+            mock.ptr(ArrowExpression {
+                params: mock.list([
+                    Pattern::Identifier("a"),
+                    Pattern::Identifier("b"),
+                ]),
+                return_type: None,
+                body: ArrowBody::Expression(mock.ptr("a"))
+            }),
+        ]);
+
+        assert_eq!(parse_with(src, options).unwrap().body(), expected);
+
+        let errors = parse_with("({ a }) = 1", options).unwrap_err();
+
+        assert_eq!(errors[0].code, ErrorCode::InvalidAssignmentTarget);
+        assert!(parse_with("((a)) => a", options).is_err());
+    }
+
+    #[test]
+    fn conditional_expression() {
+        let src = "true ? foo : bar";
+
+        let mock = Mock::new();
+
+        let expected = ConditionalExpression {
+            test: mock.ptr(Expression::Literal(Literal::True)),
+            consequent: mock.ptr("foo"),
+            alternate: mock.ptr("bar"),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn complex_conditional_expression() {
+        let src = "true ? foo = bar : baz";
+
+        let mock = Mock::new();
+
+        let expected = ConditionalExpression {
+            test: mock.ptr(Expression::Literal(Literal::True)),
+            consequent: mock.ptr(BinaryExpression {
+                operator: OperatorKind::Assign,
+                left: mock.ptr("foo"),
+                right: mock.ptr("bar"),
+            }),
+            alternate: mock.ptr("baz"),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn nested_conditional_in_alternate() {
+        let src = "a ? b : c ? d : e";
+        let mock = Mock::new();
+
+        let expected = ConditionalExpression {
+            test: mock.ptr("a"),
+            consequent: mock.ptr("b"),
+            alternate: mock.ptr(ConditionalExpression {
+                test: mock.ptr("c"),
+                consequent: mock.ptr("d"),
+                alternate: mock.ptr("e"),
+            }),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn nested_conditional_in_consequent() {
+        let src = "a ? b ? c : d : e";
+        let mock = Mock::new();
+
+        let expected = ConditionalExpression {
+            test: mock.ptr("a"),
+            consequent: mock.ptr(ConditionalExpression {
+                test: mock.ptr("b"),
+                consequent: mock.ptr("c"),
+                alternate: mock.ptr("d"),
+            }),
+            alternate: mock.ptr("e"),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn conditional_with_assignments_in_branches() {
+        let src = "x = a ? b = 1 : c = 2";
+        let mock = Mock::new();
+
+        let expected = BinaryExpression {
+            operator: OperatorKind::Assign,
+            left: mock.ptr("x"),
+            right: mock.ptr(ConditionalExpression {
+                test: mock.ptr("a"),
+                consequent: mock.ptr(BinaryExpression {
+                    operator: OperatorKind::Assign,
+                    left: mock.ptr("b"),
+                    right: mock.number("1"),
+                }),
+                alternate: mock.ptr(BinaryExpression {
+                    operator: OperatorKind::Assign,
+                    left: mock.ptr("c"),
+                    right: mock.number("2"),
+                }),
+            }),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn conditional_test_binds_tighter_than_sequence() {
+        let src = "a || b ? c : d, e";
+        let mock = Mock::new();
+
+        let expected = SequenceExpression {
+            body: mock.list([
+                Expression::from(ConditionalExpression {
+                    test: mock.ptr(BinaryExpression {
+                        operator: OperatorKind::LogicalOr,
+                        left: mock.ptr("a"),
+                        right: mock.ptr("b"),
+                    }),
+                    consequent: mock.ptr("c"),
+                    alternate: mock.ptr("d"),
+                }),
+                Expression::Identifier("e"),
+            ]),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn postfix_expression() {
+        let src = "baz++;";
+        let mock = Mock::new();
+
+        let expected = PostfixExpression {
+            operator: OperatorKind::Increment,
+            operand: mock.ptr("baz"),
         };
 
         assert_expr!(src, expected);
@@ -924,212 +1546,864 @@ mod test {
     }
 
     #[test]
-    fn meta_property_expression() {
-        let src = "new.target";
+    fn optional_member_expression() {
+        let src = "foo?.bar";
         let mock = Mock::new();
-        let expected = MetaPropertyExpression {
-            meta: mock.ptr("new"),
-            property: mock.ptr("target"),
-        };
-        assert_expr!(src, expected);
-    }
-
-    #[test]
-    fn meta_property_expression_throws() {
-        assert!(parse("new.callee").is_err());
-    }
-
-    #[test]
-    fn regular_expression() {
-        let src = r#"/^[A-Z]+\/[\d]+/g"#;
 
-        let expected = Literal::RegEx("/^[A-Z]+\\/[\\d]+/g");
+        let expected = OptionalExpression {
+            object: mock.ptr("foo"),
+            link: OptionalLink::Member(mock.ptr("bar")),
+        };
 
         assert_expr!(src, expected);
     }
 
     #[test]
-    fn array_expression() {
-        let src = "[0, 1, 2]";
+    fn optional_computed_member_expression() {
+        let src = "foo?.[10]";
         let mock = Mock::new();
 
-        let expected = ArrayExpression {
-            body: mock.list([
-                Literal::Number("0"),
-                Literal::Number("1"),
-                Literal::Number("2"),
-            ])
+        let expected = OptionalExpression {
+            object: mock.ptr("foo"),
+            link: OptionalLink::ComputedMember(mock.number("10")),
         };
 
         assert_expr!(src, expected);
     }
 
     #[test]
-    fn sparse_array_expression() {
-        let src = "[,,foo,bar,,]";
+    fn optional_call_expression() {
+        let src = "foo?.(bar)";
         let mock = Mock::new();
 
-        let expected = ArrayExpression {
-            body: mock.list([
-                Expression::Void,
-                Expression::Void,
-                Expression::Identifier("foo"),
-                Expression::Identifier("bar"),
-                Expression::Void,
-                Expression::Void,
-            ])
+        let expected = OptionalExpression {
+            object: mock.ptr("foo"),
+            link: OptionalLink::Call(mock.list(["bar"])),
         };
 
         assert_expr!(src, expected);
     }
 
     #[test]
-    fn spread_expression_in_array() {
-        let src = "[a, b, ...c]";
+    fn optional_chain_continues() {
+        let src = "foo?.bar.baz";
         let mock = Mock::new();
 
-        let expected = ArrayExpression {
-            body: mock.list([
-                Expression::Identifier("a"),
-                Expression::Identifier("b"),
-                Expression::Spread(SpreadExpression {
-                    argument: mock.ptr("c")
-                })
-            ])
+        let expected = MemberExpression {
+            object: mock.ptr(OptionalExpression {
+                object: mock.ptr("foo"),
+                link: OptionalLink::Member(mock.ptr("bar")),
+            }),
+            property: mock.ptr("baz"),
         };
 
         assert_expr!(src, expected);
     }
 
     #[test]
-    fn spread_expression_in_call() {
-        let src = "foo(a, b, ...c)";
+    fn optional_chain_on_this() {
+        let src = "this?.x";
         let mock = Mock::new();
 
-        let expected = CallExpression {
-            callee: mock.ptr("foo"),
-            arguments: mock.list([
-                Expression::Identifier("a"),
-                Expression::Identifier("b"),
-                Expression::Spread(SpreadExpression {
-                    argument: mock.ptr("c")
-                })
-            ])
+        let expected = OptionalExpression {
+            object: mock.ptr(ThisExpression),
+            link: OptionalLink::Member(mock.ptr("x")),
         };
 
         assert_expr!(src, expected);
+        assert!(parse("class A { m() { return this?.x; } }").is_ok());
     }
 
     #[test]
-    fn spread_expression_illegal_bare() {
-        assert!(parse("let foo = ...c;").is_err());
+    fn optional_chain_on_super() {
+        assert!(parse("class A extends B { m() { return super?.x; } }").is_err());
+        assert!(parse("class A extends B { m() { return super?.(); } }").is_err());
     }
 
     #[test]
-    fn function_expression() {
-        let src = "(function () {})";
+    fn conditional_expression_with_float() {
+        let src = "foo?.5:1";
         let mock = Mock::new();
 
-        let expected = Function {
-            name: None.into(),
-            generator: false,
-            params: NodeList::empty(),
-            body: mock.empty_block()
+        let expected = ConditionalExpression {
+            test: mock.ptr("foo"),
+            consequent: mock.number(".5"),
+            alternate: mock.number("1"),
         };
 
         assert_expr!(src, expected);
     }
 
     #[test]
-    fn named_function_expression() {
-        let src = "(function foo () {})";
-        let mock = Mock::new();
+    fn template_after_optional_chain() {
+        let arena = Arena::new();
+        let mut parser = Parser::new("foo?.bar`baz`; a?.b.c`${ d }`; qux;", &arena);
 
-        let expected = Function {
-            name: mock.name("foo"),
-            generator: false,
-            params: NodeList::empty(),
-            body: mock.empty_block()
-        };
+        parser.parse();
 
-        assert_expr!(src, expected);
+        assert_eq!(parser.errors.len(), 2);
+        assert_eq!(parser.errors[0].code, ErrorCode::TaggedTemplateInOptionalChain);
+        assert_eq!(parser.errors[0].span, Span::new(8, 13));
+        assert_eq!(parser.errors[1].code, ErrorCode::TaggedTemplateInOptionalChain);
+        assert_eq!(parser.body.iter().count(), 3);
     }
 
     #[test]
-    fn arrow_function_expression() {
-        let src = "() => bar";
-        let mock = Mock::new();
+    fn template_after_optional_chain_single_error() {
+        let errors = parse("foo?.bar`baz`; qux;").unwrap_err();
 
-        let expected = ArrowExpression {
-            params: NodeList::empty(),
-            body: ArrowBody::Expression(mock.ptr("bar")),
-        };
-        assert_expr!(src, expected);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, ErrorCode::TaggedTemplateInOptionalChain);
     }
 
     #[test]
-    fn arrow_function_shorthand() {
-        let src = "n => n * n";
-        let mock = Mock::new();
+    fn optional_chain_in_new() {
+        for src in ["new a?.b", "new a?.b()", "new a?.()", "new a?.[b].c()"].iter() {
+            let errors = parse(src).unwrap_err();
 
-        let expected = ArrowExpression {
-            params: mock.list([
-                Pattern::Identifier("n")
-            ]),
+            assert_eq!(errors.len(), 1, "{}", src);
+            assert_eq!(errors[0].code, ErrorCode::OptionalChainInNew, "{}", src);
+        }
 
-            body: ArrowBody::Expression(mock.ptr(BinaryExpression {
-                operator: OperatorKind::Multiplication,
-                left: mock.ptr("n"),
-                right: mock.ptr("n"),
-            }))
+        let errors = parse("new a?.b().c").unwrap_err();
 
-        };
-        assert_expr!(src, expected);
+        assert_eq!(errors[0].span, Span::new(4, 8));
+
+        assert!(parse("new (a?.b)()").is_ok());
+        assert!(parse("new a.b()?.c").is_ok());
+        assert!(parse("new a()?.()").is_ok());
     }
 
     #[test]
-    fn arrow_function_with_params() {
-        let src = "(a, b, c) => bar";
-        let mock = Mock::new();
+    fn globals_are_assignable() {
+        for src in ["undefined = 1", "(undefined) = 1", "undefined++", "[undefined] = a", "NaN = 1", "Infinity -= 1"].iter() {
+            assert!(parse(src).is_ok(), "{}", src);
+        }
 
-        let expected = ArrowExpression {
-            params: mock.list([
-                Pattern::Identifier("a"),
-                Pattern::Identifier("b"),
-                Pattern::Identifier("c")
-            ]),
-            body: ArrowBody::Expression(mock.ptr("bar"))
-        };
-        assert_expr!(src, expected);
+        assert!(parse("null = 1").is_err());
+        assert!(parse("true = 1").is_err());
     }
 
     #[test]
-    fn arrow_function_invalid_params_throws() {
-        assert!(parse("(a, b, c * 2) => bar").is_err());
-    }
+    fn optional_chain_is_not_assignable() {
+        for src in ["a?.b = 1", "a?.[b] = 1", "a?.b.c = 1", "a?.b[c] = 1", "a?.b += 1", "a?.b++", "[a?.b] = c"].iter() {
+            let errors = parse(src).unwrap_err();
 
-    #[test]
-    fn arrow_function_with_default_params() {
-        let src = "(a, b, c = 2) => bar";
-        let mock = Mock::new();
+            assert_eq!(errors[0].code, ErrorCode::InvalidAssignmentTarget, "{}", src);
+        }
 
-        let expected = ArrowExpression {
-            params: mock.list([
-                Pattern::Identifier("a"),
-                Pattern::Identifier("b"),
-                Pattern::AssignmentPattern {
-                    left: mock.ptr(Pattern::Identifier("c")),
-                    right: mock.number("2")
-                }
-            ]),
-            body: ArrowBody::Expression(mock.ptr("bar"))
-        };
-        assert_expr!(src, expected);
+        assert!(parse("a.b = 1").is_ok());
+        assert!(parse("a[b] = 1").is_ok());
     }
 
     #[test]
-    fn class_expression() {
-        let src = "(class {})";
+    fn parentheses_end_optional_chain() {
+        let src = "(a?.b).c = 1";
+        let mock = Mock::new();
+        let expected = BinaryExpression {
+            operator: OperatorKind::Assign,
+            left: mock.ptr(MemberExpression {
+                object: mock.ptr(ParenthesizedExpression {
+                    expression: mock.ptr(OptionalExpression {
+                        object: mock.ptr("a"),
+                        link: OptionalLink::Member(mock.ptr("b")),
+                    }),
+                }),
+                property: mock.ptr("c"),
+            }),
+            right: mock.number("1"),
+        };
+
+        assert_expr!(src, expected);
+        assert!(parse("(a?.b)`c`").is_ok());
+    }
+
+    #[test]
+    fn optional_chain_links() {
+        fn assert_chain<'ast>(src: &str, expected: Option<OptionalChain<'ast>>) {
+            let module = parse(src).unwrap();
+
+            match module.body().iter().next().map(|statement| statement.item) {
+                Some(Statement::Expression(expression)) => assert_eq!(expression.optional_chain(), expected, "{}", src),
+                _ => panic!("Expected an expression statement"),
+            }
+        }
+
+        let mock = Mock::new();
+
+        // `.b.c` are both skipped when `a` is nullish
+        assert_chain("a?.b.c", Some(OptionalChain {
+            object: mock.ptr("a"),
+            links: vec![
+                ChainLink { link: OptionalLink::Member(mock.ptr("b")), optional: true },
+                ChainLink { link: OptionalLink::Member(mock.ptr("c")), optional: false },
+            ],
+        }));
+
+        assert_chain("a?.b?.c", Some(OptionalChain {
+            object: mock.ptr("a"),
+            links: vec![
+                ChainLink { link: OptionalLink::Member(mock.ptr("b")), optional: true },
+                ChainLink { link: OptionalLink::Member(mock.ptr("c")), optional: true },
+            ],
+        }));
+
+        assert_chain("a.b?.(c)[d]", Some(OptionalChain {
+            object: mock.ptr("a"),
+            links: vec![
+                ChainLink { link: OptionalLink::Member(mock.ptr("b")), optional: false },
+                ChainLink { link: OptionalLink::Call(mock.list(["c"])), optional: true },
+                ChainLink { link: OptionalLink::ComputedMember(mock.ptr("d")), optional: false },
+            ],
+        }));
+
+        assert_chain("a.b.c", None);
+        assert_chain("(a?.b).c", None);
+    }
+
+    #[test]
+    fn meta_property_expression() {
+        let src = "function f() { new.target }";
+        let mock = Mock::new();
+        let target: ExpressionNode = mock.ptr(MetaPropertyExpression {
+            meta: mock.ptr("new"),
+            property: mock.ptr("target"),
+        });
+        let expected = mock.list([
+            Function {
+                name: mock.name("f"),
+                generator: false,
+                is_async: false,
+                type_parameters: NodeList::empty(),
+                params: NodeList::empty(),
+                return_type: None,
+                body: mock.block([
+                    Statement::from(target)
+                ]),
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn meta_property_expression_throws() {
+        assert!(parse("function f() { new.callee }").is_err());
+    }
+
+    #[test]
+    fn new_target_context() {
+        assert!(parse("function f() { () => new.target; }").is_ok());
+        assert!(parse("(function (a = new.target) {})").is_ok());
+        assert!(parse("class A { m() { new.target; } a = new.target; }").is_ok());
+        assert!(parse("({ m() { return new.target; } })").is_ok());
+
+        let errors = parse("new.target").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, ErrorCode::InvalidNewTarget);
+        assert_eq!(errors[0].span, Span::new(0, 10));
+
+        let errors = parse("() => new.target").unwrap_err();
+
+        assert_eq!(errors[0].code, ErrorCode::InvalidNewTarget);
+    }
+
+    #[test]
+    fn super_call_context() {
+        assert!(parse("class A extends B { constructor() { super(); } }").is_ok());
+        assert!(parse("class A extends B { constructor() { () => super(); } }").is_ok());
+        assert!(parse("(class extends B { constructor(a = super()) {} })").is_ok());
+
+        let invalid = [
+            "super()",
+            "function f() { super(); }",
+            "class A { constructor() { super(); } }",
+            "class A extends B { m() { super(); } }",
+            "class A extends B { static constructor() { super(); } }",
+            "class A extends B { a = super(); }",
+            "class A extends B { constructor() { function f() { super(); } } }",
+            "class A extends B { constructor() { class C { constructor() { super(); } } } }",
+            "({ constructor() { super(); } })",
+        ];
+
+        for src in invalid.iter() {
+            let errors = parse(src).unwrap_err();
+
+            assert_eq!(errors.len(), 1, "{}", src);
+            assert_eq!(errors[0].code, ErrorCode::InvalidSuperCall, "{}", src);
+        }
+
+        assert_eq!(parse("super()").unwrap_err()[0].span, Span::new(0, 5));
+    }
+
+    #[test]
+    fn super_property_context() {
+        assert!(parse("class A { m() { super.m(); super[m]; } }").is_ok());
+        assert!(parse("class A { static get a() { return () => super.a; } }").is_ok());
+        assert!(parse("class A extends B { a = super.a; }").is_ok());
+        assert!(parse("({ m() { return super.m; } })").is_ok());
+
+        let invalid = [
+            "super.a",
+            "super[a]",
+            "function f() { super.a; }",
+            "class A { m() { function f() { super.a; } } }",
+            "({ a: function () { super.a; } })",
+        ];
+
+        for src in invalid.iter() {
+            let errors = parse(src).unwrap_err();
+
+            assert_eq!(errors.len(), 1, "{}", src);
+            assert_eq!(errors[0].code, ErrorCode::InvalidSuperProperty, "{}", src);
+        }
+    }
+
+    #[test]
+    fn bare_super() {
+        let errors = parse("class A extends B { m() { return super; } }").unwrap_err();
+
+        assert_eq!(errors[0].code, ErrorCode::UnexpectedToken);
+        assert_eq!(errors[0].span, Span::new(38, 39));
+    }
+
+    #[test]
+    fn yield_expression() {
+        let src = "function* f() { yield; yield a; yield* b; }";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Function {
+                name: mock.name("f"),
+                generator: true,
+                is_async: false,
+                type_parameters: NodeList::empty(),
+                params: NodeList::empty(),
+                return_type: None,
+                body: mock.block([
+                    mock.ptr(YieldExpression { delegate: false, argument: None }),
+                    mock.ptr(YieldExpression { delegate: false, argument: Some(mock.ptr("a")) }),
+                    mock.ptr(YieldExpression { delegate: true, argument: Some(mock.ptr("b")) }),
+                ]),
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn await_expression() {
+        let src = "async function f() { await a; return await b; }";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Function {
+                name: mock.name("f"),
+                generator: false,
+                is_async: true,
+                type_parameters: NodeList::empty(),
+                params: NodeList::empty(),
+                return_type: None,
+                body: mock.block([
+                    Statement::from(mock.ptr(AwaitExpression { argument: mock.ptr("a") })),
+                    Statement::from(ReturnStatement {
+                        value: Some(mock.ptr(AwaitExpression { argument: mock.ptr("b") }))
+                    }),
+                ]),
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn yield_and_await_precedence() {
+        let pairs = [
+            ("function* f() { yield a + b; }", "function* f() { yield (a + b); }"),
+            ("function* f() { yield a = b, c; }", "function* f() { (yield (a = b)), c; }"),
+            ("function* f() { yield* a ? b : c; }", "function* f() { yield* (a ? b : c); }"),
+            ("function* f() { x = yield a; }", "function* f() { x = (yield a); }"),
+            ("function* f() { foo(yield, yield a); }", "function* f() { foo((yield), (yield a)); }"),
+            ("async function f() { await a + b; }", "async function f() { (await a) + b; }"),
+            ("async function f() { await a.b(); }", "async function f() { await (a.b()); }"),
+            ("async function f() { -await a; }", "async function f() { -(await a); }"),
+        ];
+
+        for &(src, grouped) in pairs.iter() {
+            assert_eq!(parse(src).unwrap().body(), parse(grouped).unwrap().body(), "{:?}", src);
+        }
+    }
+
+    #[test]
+    fn nested_yield_and_await() {
+        let src = "async function* f() { await (yield x); yield await x; }";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Function {
+                name: mock.name("f"),
+                generator: true,
+                is_async: true,
+                type_parameters: NodeList::empty(),
+                params: NodeList::empty(),
+                return_type: None,
+                body: mock.block([
+                    mock.ptr(AwaitExpression {
+                        argument: mock.ptr(YieldExpression { delegate: false, argument: Some(mock.ptr("x")) }),
+                    }),
+                    mock.ptr(YieldExpression {
+                        delegate: false,
+                        argument: Some(mock.ptr(AwaitExpression { argument: mock.ptr("x") })),
+                    }),
+                ]),
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn yield_and_await_context() {
+        assert!(parse("function f() { var await = 1; return await; }").is_ok());
+        assert!(parse("async function f() { () => await; }").is_err());
+        assert!(parse("async function f() { () => { await; }; }").is_err());
+        assert!(parse("async function f() { function g() { return await; } }").is_ok());
+        assert!(parse("async function f() { await a ** b; }").is_err());
+        assert!(parse("async function f() { (await a) ** b; await (a ** b); }").is_ok());
+        assert!(parse("function* f() { function g() { yield a; } }").is_err());
+        assert!(parse("function* f() { () => yield a; }").is_err());
+        assert!(parse("yield a").is_err());
+        assert!(parse("async function f() { function g() { await a; } }").is_err());
+        assert!(parse("({ async f() { await a; } })").is_ok());
+        assert!(parse("class A { async f() { await a; } }").is_ok());
+    }
+
+    #[test]
+    fn object_literal_keys() {
+        let src = r#"({ "a-b": 1, 2: 3 });"#;
+        let mock = Mock::new();
+
+        let expected = ObjectExpression {
+            body: mock.list([
+                Property::Literal {
+                    key: mock.ptr(PropertyKey::Literal(r#""a-b""#)),
+                    value: mock.number("1"),
+                },
+                Property::Literal {
+                    key: mock.ptr(PropertyKey::Literal("2")),
+                    value: mock.number("3"),
+                },
+            ]),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn regular_expression() {
+        let src = r#"/^[A-Z]+\/[\d]+/g"#;
+
+        let expected = Literal::RegEx("/^[A-Z]+\\/[\\d]+/g");
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn regular_expression_flags() {
+        let module = parse("/x/giu").unwrap();
+
+        let flags = match module.body().only_element().map(|statement| statement.item) {
+            Some(Statement::Expression(expression)) => match expression.item {
+                Expression::Literal(literal) => literal.regex_flags(),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        assert_eq!(flags, Some(RegexFlags {
+            global: true,
+            ignore_case: true,
+            unicode: true,
+            ..RegexFlags::default()
+        }));
+    }
+
+    #[test]
+    fn regular_expression_leading_program() {
+        let src = "/foo/.test(x);";
+        let mock = Mock::new();
+
+        let expected = CallExpression {
+            callee: mock.ptr(MemberExpression {
+                object: mock.ptr(Literal::RegEx("/foo/")),
+                property: mock.ptr("test"),
+            }),
+            arguments: mock.list(["x"]),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn regular_expression_starting_with_equals() {
+        let mock = Mock::new();
+
+        assert_expr!("/=/;", Literal::RegEx("/=/"));
+        assert_expr!("/=foo/g;", Literal::RegEx("/=foo/g"));
+        assert_expr!("[/=a/, /=b/];", ArrayExpression {
+            body: mock.list([
+                Literal::RegEx("/=a/"),
+                Literal::RegEx("/=b/"),
+            ])
+        });
+
+        // Divide-assign without a left-hand side to apply to
+        assert!(parse("/=;").is_err());
+        assert!(parse("a /= 2;").is_ok());
+    }
+
+    #[test]
+    fn regular_expression_character_class() {
+        let mock = Mock::new();
+
+        assert_expr!(r"/[/]/;", Literal::RegEx(r"/[/]/"));
+        assert_expr!(r"/[\]/]/;", Literal::RegEx(r"/[\]/]/"));
+        assert_expr!(r"/[[/]]/g;", Literal::RegEx(r"/[[/]]/g"));
+        assert_expr!(r"/[/]/ / 2;", BinaryExpression {
+            operator: OperatorKind::Division,
+            left: mock.ptr(Literal::RegEx(r"/[/]/")),
+            right: mock.number("2"),
+        });
+        assert_expr!(r"/a\/b/s;", Literal::RegEx(r"/a\/b/s"));
+
+        assert!(parse("/[/;").is_err());
+        assert!(parse("/a\\\nb/;").is_err());
+    }
+
+    #[test]
+    fn array_expression() {
+        let src = "[0, 1, 2]";
+        let mock = Mock::new();
+
+        let expected = ArrayExpression {
+            body: mock.list([
+                Literal::Number("0"),
+                Literal::Number("1"),
+                Literal::Number("2"),
+            ])
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn sparse_array_expression() {
+        let src = "[,,foo,bar,,]";
+        let mock = Mock::new();
+
+        let expected = ArrayExpression {
+            body: mock.list([
+                Expression::Void,
+                Expression::Void,
+                Expression::Identifier("foo"),
+                Expression::Identifier("bar"),
+                Expression::Void,
+                Expression::Void,
+            ])
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn array_holes_distinct_from_undefined() {
+        // Elements borrow from the module, which has to outlive them
+        fn elements<'ast>(module: &'ast Module<'ast>) -> Vec<Option<Expression<'ast>>> {
+            match module.body().only_element().map(|statement| statement.item) {
+                Some(Statement::Expression(expression)) => match expression.item {
+                    Expression::Array(array) => array.elements().map(|element| element.map(|node| node.item)).collect(),
+                    _ => panic!("Expected an array"),
+                },
+                _ => panic!("Expected an expression statement"),
+            }
+        }
+
+        let module = parse("[, 1]").unwrap();
+        assert_eq!(elements(&module), [None, Some(Expression::Literal(Literal::Number("1")))]);
+
+        let module = parse("[undefined, 1]").unwrap();
+        assert_eq!(elements(&module), [
+            Some(Expression::Literal(Literal::Undefined)),
+            Some(Expression::Literal(Literal::Number("1"))),
+        ]);
+
+        let module = parse("[]").unwrap();
+        assert!(elements(&module).is_empty());
+
+        let module = parse("[,]").unwrap();
+        assert_eq!(elements(&module), vec![None]);
+
+        let module = parse("[1,]").unwrap();
+        assert_eq!(elements(&module).len(), 1);
+
+        let module = parse("[1,,]").unwrap();
+        assert_eq!(elements(&module).len(), 2);
+    }
+
+    #[test]
+    fn spread_expression_in_array() {
+        let src = "[a, b, ...c]";
+        let mock = Mock::new();
+
+        let expected = ArrayExpression {
+            body: mock.list([
+                Expression::Identifier("a"),
+                Expression::Identifier("b"),
+                Expression::Spread(SpreadExpression {
+                    argument: mock.ptr("c")
+                })
+            ])
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn spread_expression_in_array_with_trailing_comma() {
+        let src = "[...a,]";
+        let mock = Mock::new();
+
+        let expected = ArrayExpression {
+            body: mock.list([
+                Expression::Spread(SpreadExpression {
+                    argument: mock.ptr("a")
+                }),
+                Expression::Void,
+            ])
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn spread_property_with_trailing_comma() {
+        let src = "({...a,})";
+        let mock = Mock::new();
+
+        let expected = ObjectExpression {
+            body: mock.list([
+                Property::Spread {
+                    argument: mock.ptr("a")
+                },
+            ])
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn spread_expression_in_call() {
+        let src = "foo(a, b, ...c)";
+        let mock = Mock::new();
+
+        let expected = CallExpression {
+            callee: mock.ptr("foo"),
+            arguments: mock.list([
+                Expression::Identifier("a"),
+                Expression::Identifier("b"),
+                Expression::Spread(SpreadExpression {
+                    argument: mock.ptr("c")
+                })
+            ])
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn spread_expression_illegal_bare() {
+        assert!(parse("let foo = ...c;").is_err());
+    }
+
+    #[test]
+    fn function_expression() {
+        let src = "(function () {})";
+        let mock = Mock::new();
+
+        let expected = Function {
+            name: None.into(),
+            generator: false,
+            is_async: false,
+            type_parameters: NodeList::empty(),
+            params: NodeList::empty(),
+            return_type: None,
+            body: mock.empty_block()
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn named_function_expression() {
+        let src = "(function foo () {})";
+        let mock = Mock::new();
+
+        let expected = Function {
+            name: mock.name("foo"),
+            generator: false,
+            is_async: false,
+            type_parameters: NodeList::empty(),
+            params: NodeList::empty(),
+            return_type: None,
+            body: mock.empty_block()
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn arrow_function_expression() {
+        let src = "() => bar";
+        let mock = Mock::new();
+
+        let expected = ArrowExpression {
+            params: NodeList::empty(),
+            return_type: None,
+            body: ArrowBody::Expression(mock.ptr("bar")),
+        };
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn arrow_function_shorthand() {
+        let src = "n => n * n";
+        let mock = Mock::new();
+
+        let expected = ArrowExpression {
+            params: mock.list([
+                Pattern::Identifier("n")
+            ]),
+
+            return_type: None,
+            body: ArrowBody::Expression(mock.ptr(BinaryExpression {
+                operator: OperatorKind::Multiplication,
+                left: mock.ptr("n"),
+                right: mock.ptr("n"),
+            }))
+
+        };
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn arrow_function_with_params() {
+        let src = "(a, b, c) => bar";
+        let mock = Mock::new();
+
+        let expected = ArrowExpression {
+            params: mock.list([
+                Pattern::Identifier("a"),
+                Pattern::Identifier("b"),
+                Pattern::Identifier("c")
+            ]),
+            return_type: None,
+            body: ArrowBody::Expression(mock.ptr("bar"))
+        };
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn arrow_function_invalid_params_throws() {
+        assert!(parse("(a, b, c * 2) => bar").is_err());
+    }
+
+    #[test]
+    fn arrow_function_with_default_params() {
+        let src = "(a, b, c = 2) => bar";
+        let mock = Mock::new();
+
+        let expected = ArrowExpression {
+            params: mock.list([
+                Pattern::Identifier("a"),
+                Pattern::Identifier("b"),
+                Pattern::AssignmentPattern {
+                    left: mock.ptr(Pattern::Identifier("c")),
+                    right: mock.number("2")
+                }
+            ]),
+            return_type: None,
+            body: ArrowBody::Expression(mock.ptr("bar"))
+        };
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn arrow_function_with_object_pattern() {
+        let src = "({ a }) => a";
+        let mock = Mock::new();
+
+        let expected = ArrowExpression {
+            params: mock.list([
+                Pattern::ObjectPattern {
+                    properties: mock.list([
+                        Property::Shorthand("a")
+                    ])
+                }
+            ]),
+            return_type: None,
+            body: ArrowBody::Expression(mock.ptr("a"))
+        };
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn arrow_function_with_array_pattern() {
+        let src = "([x, , y = 1]) => x";
+        let mock = Mock::new();
+
+        let expected = ArrowExpression {
+            params: mock.list([
+                Pattern::ArrayPattern {
+                    elements: mock.list([
+                        Pattern::Identifier("x"),
+                        Pattern::Void,
+                        Pattern::AssignmentPattern {
+                            left: mock.ptr(Pattern::Identifier("y")),
+                            right: mock.number("1")
+                        }
+                    ])
+                }
+            ]),
+            return_type: None,
+            body: ArrowBody::Expression(mock.ptr("x"))
+        };
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn arrow_function_with_mixed_patterns() {
+        let src = "(a, { b }) => b";
+        let mock = Mock::new();
+
+        let expected = ArrowExpression {
+            params: mock.list([
+                Pattern::Identifier("a"),
+                Pattern::ObjectPattern {
+                    properties: mock.list([
+                        Property::Shorthand("b")
+                    ])
+                }
+            ]),
+            return_type: None,
+            body: ArrowBody::Expression(mock.ptr("b"))
+        };
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn class_expression() {
+        let src = "(class {})";
         let mock = Mock::new();
 
         let expected = Class {
@@ -1142,119 +2416,430 @@ mod test {
     }
 
     #[test]
-    fn named_class_expression() {
-        let src = "(class Foo {})";
+    fn named_class_expression() {
+        let src = "(class Foo {})";
+        let mock = Mock::new();
+
+        let expected = Class {
+            name: mock.name("Foo"),
+            extends: None,
+            body: mock.empty_block()
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn named_child_class_expression() {
+        let src = "(class Foo extends Bar {})";
+        let mock = Mock::new();
+
+        let expected = Class {
+            name: mock.name("Foo"),
+            extends: Some(mock.ptr("Bar")),
+            body: mock.empty_block()
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn regression_operator_precedence() {
+        let src = "true === true && false === false";
+        let mock = Mock::new();
+
+        let expected = BinaryExpression {
+            operator: OperatorKind::LogicalAnd,
+            left: mock.ptr(BinaryExpression {
+                operator: OperatorKind::StrictEquality,
+                left: mock.ptr(Literal::True),
+                right: mock.ptr(Literal::True),
+            }),
+            right: mock.ptr(BinaryExpression {
+                operator: OperatorKind::StrictEquality,
+                left: mock.ptr(Literal::False),
+                right: mock.ptr(Literal::False),
+            }),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn arrow_function_in_sequence() {
+        let src = "(() => {}, foo)";
+        let mock = Mock::new();
+
+        let expected = SequenceExpression {
+            body: mock.list([
+                Expression::Arrow(ArrowExpression {
+                    params: NodeList::empty(),
+                    return_type: None,
+                    body: ArrowBody::Block(mock.ptr(BlockStatement {
+                        body: NodeList::empty()
+                    }))
+                }),
+                Expression::Identifier("foo"),
+            ])
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn arrow_function_with_async_param() {
+        // Without parameters following it, `async` is just the name of one
+        let src = "async => async";
+        let mock = Mock::new();
+
+        let expected = ArrowExpression {
+            params: mock.list([
+                Pattern::Identifier("async")
+            ]),
+            return_type: None,
+            body: ArrowBody::Expression(mock.ptr("async")),
+        };
+
+        assert_expr!(src, expected);
+        assert!(parse("(async) => async").is_ok());
+        assert!(parse("async function* f() { yield await x; }").is_ok());
+    }
+
+    #[test]
+    fn generator_arrow_function() {
+        fn errors(src: &str) -> Vec<(ErrorCode, Span)> {
+            parse(src).unwrap_err().iter().map(|err| (err.code, err.span)).collect()
+        }
+
+        assert_eq!(errors("*() => {}"), [(ErrorCode::GeneratorArrow, Span::new(0, 9))]);
+        assert_eq!(errors("a = *x => x;"), [(ErrorCode::GeneratorArrow, Span::new(4, 11))]);
+        assert_eq!(errors("f(*(a, b) => a);"), [(ErrorCode::GeneratorArrow, Span::new(2, 14))]);
+
+        // Anything else following the `*` is reported at the `*` itself
+        assert_eq!(errors("*a;"), [(ErrorCode::UnexpectedToken, Span::new(0, 1))]);
+        assert_eq!(errors("a = *(b);"), [(ErrorCode::UnexpectedToken, Span::new(4, 5))]);
+    }
+
+    #[test]
+    fn regression_increments() {
+        let src = "x++ + ++y";
+        let mock = Mock::new();
+
+        let expected = BinaryExpression {
+            operator: OperatorKind::Addition,
+            left: mock.ptr(PostfixExpression {
+                operator: OperatorKind::Increment,
+                operand: mock.ptr("x"),
+            }),
+            right: mock.ptr(PrefixExpression {
+                operator: OperatorKind::Increment,
+                operand: mock.ptr("y"),
+            })
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn regression_decrements() {
+        let src = "x-- - --y";
+        let mock = Mock::new();
+
+        let expected = BinaryExpression {
+            operator: OperatorKind::Subtraction,
+            left: mock.ptr(PostfixExpression {
+                operator: OperatorKind::Decrement,
+                operand: mock.ptr("x"),
+            }),
+            right: mock.ptr(PrefixExpression {
+                operator: OperatorKind::Decrement,
+                operand: mock.ptr("y"),
+            })
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn assignment_to_lvalue() {
+        assert!(parse("(x++)++").is_err());
+        assert!(parse("x+++++y").is_err());
+    }
+
+    #[test]
+    fn chained_assignment() {
+        let src = "a = b = c";
         let mock = Mock::new();
 
-        let expected = Class {
-            name: mock.name("Foo"),
-            extends: None,
-            body: mock.empty_block()
+        let expected = BinaryExpression {
+            operator: OperatorKind::Assign,
+            left: mock.ptr("a"),
+            right: mock.ptr(BinaryExpression {
+                operator: OperatorKind::Assign,
+                left: mock.ptr("b"),
+                right: mock.ptr("c"),
+            }),
         };
 
         assert_expr!(src, expected);
     }
 
     #[test]
-    fn named_child_class_expression() {
-        let src = "(class Foo extends Bar {})";
+    fn chained_compound_assignment() {
+        let src = "a += b -= c = d";
         let mock = Mock::new();
 
-        let expected = Class {
-            name: mock.name("Foo"),
-            extends: Some(mock.ptr("Bar")),
-            body: mock.empty_block()
+        let expected = BinaryExpression {
+            operator: OperatorKind::AddAssign,
+            left: mock.ptr("a"),
+            right: mock.ptr(BinaryExpression {
+                operator: OperatorKind::SubtractAssign,
+                left: mock.ptr("b"),
+                right: mock.ptr(BinaryExpression {
+                    operator: OperatorKind::Assign,
+                    left: mock.ptr("c"),
+                    right: mock.ptr("d"),
+                }),
+            }),
         };
 
         assert_expr!(src, expected);
+        assert!(parse("a = b + c = d").is_err());
     }
 
     #[test]
-    fn regression_operator_precedence() {
-        let src = "true === true && false === false";
+    fn nullish_coalescing() {
+        let src = "a ?? b ?? c";
         let mock = Mock::new();
 
         let expected = BinaryExpression {
-            operator: OperatorKind::LogicalAnd,
+            operator: OperatorKind::Nullish,
             left: mock.ptr(BinaryExpression {
-                operator: OperatorKind::StrictEquality,
-                left: mock.ptr(Literal::True),
-                right: mock.ptr(Literal::True),
+                operator: OperatorKind::Nullish,
+                left: mock.ptr("a"),
+                right: mock.ptr("b"),
             }),
-            right: mock.ptr(BinaryExpression {
-                operator: OperatorKind::StrictEquality,
-                left: mock.ptr(Literal::False),
-                right: mock.ptr(Literal::False),
+            right: mock.ptr("c"),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn nullish_coalescing_mixed_with_logical() {
+        for src in ["a ?? b || c", "a || b ?? c", "a ?? b && c", "a && b ?? c", "(a) ?? b || c"].iter() {
+            let errors = parse(src).unwrap_err();
+
+            assert_eq!(errors[0].code, ErrorCode::MixedNullish, "{}", src);
+        }
+
+        let errors = parse("a ?? b || c").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].span, Span::new(0, 6));
+
+        assert!(parse("(a ?? b) || c").is_ok());
+        assert!(parse("a || (b ?? c)").is_ok());
+        assert!(parse("a ?? (b && c)").is_ok());
+        assert!(parse("(a && b) ?? c").is_ok());
+        assert!(parse("(a && b) ?? (c || d)").is_ok());
+        assert!(parse("a || b && c").is_ok());
+    }
+
+    #[test]
+    fn nullish_assignment_to_member() {
+        let src = "a.b ??= c";
+        let mock = Mock::new();
+
+        let expected = BinaryExpression {
+            operator: OperatorKind::NullishAssign,
+            left: mock.ptr(MemberExpression {
+                object: mock.ptr("a"),
+                property: mock.ptr("b"),
             }),
+            right: mock.ptr("c"),
         };
 
         assert_expr!(src, expected);
     }
 
     #[test]
-    fn arrow_function_in_sequence() {
-        let src = "(() => {}, foo)";
+    fn logical_assignment_to_computed_member() {
+        let src = "a[x] ||= y &&= z";
         let mock = Mock::new();
 
-        let expected = SequenceExpression {
-            body: mock.list([
-                Expression::Arrow(ArrowExpression {
-                    params: NodeList::empty(),
-                    body: ArrowBody::Block(mock.ptr(BlockStatement {
-                        body: NodeList::empty()
-                    }))
-                }),
-                Expression::Identifier("foo"),
-            ])
+        let expected = BinaryExpression {
+            operator: OperatorKind::LogicalOrAssign,
+            left: mock.ptr(ComputedMemberExpression {
+                object: mock.ptr("a"),
+                property: mock.ptr("x"),
+            }),
+            right: mock.ptr(BinaryExpression {
+                operator: OperatorKind::LogicalAndAssign,
+                left: mock.ptr("y"),
+                right: mock.ptr("z"),
+            }),
         };
 
         assert_expr!(src, expected);
     }
 
     #[test]
-    fn regression_increments() {
-        let src = "x++ + ++y";
+    fn logical_assignment_invalid_target() {
+        assert!(parse("a() ??= b").is_err());
+        assert!(parse("[a] ||= b").is_err());
+        assert!(parse("({ a } &&= b)").is_err());
+    }
+
+    #[test]
+    fn destructuring_assignment_to_members() {
+        let src = "[a.b, c[0]] = x";
         let mock = Mock::new();
 
         let expected = BinaryExpression {
-            operator: OperatorKind::Addition,
-            left: mock.ptr(PostfixExpression {
-                operator: OperatorKind::Increment,
-                operand: mock.ptr("x"),
+            operator: OperatorKind::Assign,
+            left: mock.ptr(ArrayExpression {
+                body: mock.list([
+                    Expression::Member(MemberExpression {
+                        object: mock.ptr("a"),
+                        property: mock.ptr("b"),
+                    }),
+                    Expression::ComputedMember(ComputedMemberExpression {
+                        object: mock.ptr("c"),
+                        property: mock.number("0"),
+                    }),
+                ]),
             }),
-            right: mock.ptr(PrefixExpression {
-                operator: OperatorKind::Increment,
-                operand: mock.ptr("y"),
-            })
+            right: mock.ptr("x"),
         };
 
         assert_expr!(src, expected);
     }
 
     #[test]
-    fn regression_decrements() {
-        let src = "x-- - --y";
+    fn destructuring_assignment_to_object_members() {
+        let src = "({ k: a.b } = x)";
         let mock = Mock::new();
 
         let expected = BinaryExpression {
-            operator: OperatorKind::Subtraction,
-            left: mock.ptr(PostfixExpression {
-                operator: OperatorKind::Decrement,
-                operand: mock.ptr("x"),
+            operator: OperatorKind::Assign,
+            left: mock.ptr(ObjectExpression {
+                body: mock.list([
+                    Property::Literal {
+                        key: mock.ptr(PropertyKey::Literal("k")),
+                        value: mock.ptr(MemberExpression {
+                            object: mock.ptr("a"),
+                            property: mock.ptr("b"),
+                        }),
+                    },
+                ]),
             }),
-            right: mock.ptr(PrefixExpression {
-                operator: OperatorKind::Decrement,
-                operand: mock.ptr("y"),
-            })
+            right: mock.ptr("x"),
         };
 
         assert_expr!(src, expected);
     }
 
     #[test]
-    fn assignment_to_lvalue() {
-        assert!(parse("(x++)++").is_err());
-        assert!(parse("x+++++y").is_err());
+    fn destructuring_assignment_to_nested_targets() {
+        assert!(parse("[a.b = 1, ...c.d] = x").is_ok());
+        assert!(parse("[, [a.b], { c: d[0] }] = x").is_ok());
+        assert!(parse("({ ...a.b } = x)").is_ok());
+    }
+
+    #[test]
+    fn destructuring_assignment_to_invalid_target() {
+        let errors = parse("[1] = x").unwrap_err();
+
+        assert_eq!(errors[0].code, ErrorCode::InvalidAssignmentTarget);
+        assert_eq!(errors[0].span, Span::new(1, 2));
+
+        assert!(parse("[a()] = x").is_err());
+        assert!(parse("({ k: 1 } = x)").is_err());
+        assert!(parse("[[a + b]] = x").is_err());
+        assert!(parse("({ m() {} } = x)").is_err());
+        assert!(parse("[1] == x").is_ok());
+    }
+
+    #[test]
+    fn destructuring_assignment_rest_not_last() {
+        for src in ["[...a, b] = c", "[...a,] = c", "[[...a, b]] = c", "[a, ...b, ,] = c"].iter() {
+            let errors = parse(src).unwrap_err();
+
+            assert_eq!(errors[0].code, ErrorCode::RestElementNotLast, "{}", src);
+        }
+
+        let errors = parse("[a, ...b, c] = d").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].span, Span::new(4, 8));
+
+        assert!(parse("[...a, b]").is_ok());
+        assert!(parse("[...a,]").is_ok());
+        assert!(parse("[a, ...b] = c").is_ok());
+    }
+
+    #[test]
+    fn destructuring_assignment_to_parenthesized_target() {
+        for src in ["[(a = 1)] = b", "([a]) = 1", "({ a }) = 1", "[([a])] = b", "({ a: ({ b }) } = c)", "[...(a = 1)] = b", "[(a + b)] = c"].iter() {
+            let errors = parse(src).unwrap_err();
+
+            assert_eq!(errors[0].code, ErrorCode::InvalidAssignmentTarget, "{}", src);
+        }
+
+        let errors = parse("[(a = 1), (b)] = c").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].span, Span::new(2, 7));
+
+        for src in ["[(a)] = b", "[(a.b), (c[0])] = d", "({ a: (b) } = c)", "[(a)= 1] = b", "[a = (b, c)] = d", "x = ([a] = b)"].iter() {
+            assert!(parse(src).is_ok(), "{}", src);
+        }
+    }
+
+    #[test]
+    fn destructuring_assignment_statements() {
+        let src = "[a, b] = arr; ({ a } = obj);";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            mock.ptr(BinaryExpression {
+                operator: OperatorKind::Assign,
+                left: mock.ptr(ArrayExpression {
+                    body: mock.list(["a", "b"]),
+                }),
+                right: mock.ptr("arr"),
+            }),
+            mock.ptr(BinaryExpression {
+                operator: OperatorKind::Assign,
+                left: mock.ptr(ObjectExpression {
+                    body: mock.list([
+                        Property::Shorthand("a"),
+                    ]),
+                }),
+                right: mock.ptr("obj"),
+            }),
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn destructuring_assignment_requires_parens_for_objects() {
+        // `{ a }` is a block at the start of a statement
+        assert!(parse("{ a } = obj;").is_err());
+        assert!(parse("({ a } = obj);").is_ok());
+    }
+
+    #[test]
+    fn destructuring_compound_assignment() {
+        let errors = parse("[a] += x").unwrap_err();
+
+        assert_eq!(errors[0].code, ErrorCode::InvalidAssignmentTarget);
+        assert_eq!(errors[0].span, Span::new(0, 3));
+
+        assert!(parse("({ a } |= x)").is_err());
+        assert!(parse("[a] = x").is_ok());
     }
 
     #[test]
@@ -1308,6 +2893,36 @@ mod test {
         assert_eq!(parse(src).unwrap().body(), expected);
     }
 
+    #[test]
+    fn asi_across_unicode_line_terminators() {
+        let mock = Mock::new();
+
+        for src in ["x\u{2028}++\u{2028}y", "x\u{2029}++y", "x /* \u{2028} */ ++y", "x // \u{2028}++y"].iter() {
+            let expected = mock.list([
+                mock.ptr(Expression::Identifier("x")),
+                mock.ptr(PrefixExpression {
+                    operator: OperatorKind::Increment,
+                    operand: mock.ptr("y"),
+                }),
+            ]);
+
+            assert_eq!(parse(src).unwrap().body(), expected, "{:?}", src);
+        }
+    }
+
+    #[test]
+    fn unicode_line_terminators_in_strings() {
+        let src = "'a\u{2028}b'\u{2029}'c\u{2029}'";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            mock.ptr(Literal::String("'a\u{2028}b'")),
+            mock.ptr(Literal::String("'c\u{2029}'")),
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
     #[test]
     fn regression_asi_safe() {
         let src = r#"foo
@@ -1321,4 +2936,194 @@ mod test {
 
         assert_expr!(src, expected);
     }
+
+    #[test]
+    fn typeof_member_expression() {
+        let src = "typeof a.b";
+        let mock = Mock::new();
+
+        let expected = PrefixExpression {
+            operator: OperatorKind::Typeof,
+            operand: mock.ptr(MemberExpression {
+                object: mock.ptr("a"),
+                property: mock.ptr("b"),
+            }),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn void_member_expression() {
+        let mock = Mock::new();
+
+        assert_expr!("void 0", PrefixExpression {
+            operator: OperatorKind::Void,
+            operand: mock.number("0"),
+        });
+
+        assert_expr!("void a.b", PrefixExpression {
+            operator: OperatorKind::Void,
+            operand: mock.ptr(MemberExpression {
+                object: mock.ptr("a"),
+                property: mock.ptr("b"),
+            }),
+        });
+    }
+
+    #[test]
+    fn delete_member_expression() {
+        let mock = Mock::new();
+
+        assert_expr!("delete obj.x", PrefixExpression {
+            operator: OperatorKind::Delete,
+            operand: mock.ptr(MemberExpression {
+                object: mock.ptr("obj"),
+                property: mock.ptr("x"),
+            }),
+        });
+
+        assert_expr!("delete 1", PrefixExpression {
+            operator: OperatorKind::Delete,
+            operand: mock.number("1"),
+        });
+    }
+
+    #[test]
+    fn typeof_binds_tighter_than_binary() {
+        let src = "typeof a + b";
+        let mock = Mock::new();
+
+        let expected = BinaryExpression {
+            operator: OperatorKind::Addition,
+            left: mock.ptr(PrefixExpression {
+                operator: OperatorKind::Typeof,
+                operand: mock.ptr("a"),
+            }),
+            right: mock.ptr("b"),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn unary_keywords_as_object_keys() {
+        let src = "({ typeof: 1, void: 2, delete: 3 })";
+        let mock = Mock::new();
+
+        let expected = ObjectExpression {
+            body: mock.list([
+                Property::Literal {
+                    key: mock.ptr(PropertyKey::Literal("typeof")),
+                    value: mock.number("1"),
+                },
+                Property::Literal {
+                    key: mock.ptr(PropertyKey::Literal("void")),
+                    value: mock.number("2"),
+                },
+                Property::Literal {
+                    key: mock.ptr(PropertyKey::Literal("delete")),
+                    value: mock.number("3"),
+                },
+            ]),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn modifier_keywords_as_object_keys() {
+        let src = "({ async: 1, get: 2, set: 3, static: 4, async() {}, get, set })";
+        let mock = Mock::new();
+
+        let literal = |key, value| Property::Literal {
+            key: mock.ptr(PropertyKey::Literal(key)),
+            value: mock.number(value),
+        };
+
+        let expected = ObjectExpression {
+            body: mock.list([
+                literal("async", "1"),
+                literal("get", "2"),
+                literal("set", "3"),
+                literal("static", "4"),
+                Property::Method {
+                    key: mock.ptr(PropertyKey::Literal("async")),
+                    kind: MethodKind::Method,
+                    value: mock.ptr(Function {
+                        name: EmptyName,
+                        generator: false,
+                        is_async: false,
+                        type_parameters: NodeList::empty(),
+                        params: NodeList::empty(),
+                        return_type: None,
+                        body: mock.empty_block(),
+                    }),
+                },
+                Property::Shorthand("get"),
+                Property::Shorthand("set"),
+            ]),
+        };
+
+        assert_expr!(src, expected);
+    }
+
+    #[test]
+    fn async_object_method() {
+        let src = "({ async foo() {} })";
+        let mock = Mock::new();
+
+        let expected = ObjectExpression {
+            body: mock.list([
+                Property::Method {
+                    key: mock.ptr(PropertyKey::Literal("foo")),
+                    kind: MethodKind::Method,
+                    value: mock.ptr(Function {
+                        name: EmptyName,
+                        generator: false,
+                        is_async: true,
+                        type_parameters: NodeList::empty(),
+                        params: NodeList::empty(),
+                        return_type: None,
+                        body: mock.empty_block(),
+                    }),
+                },
+            ]),
+        };
+
+        assert_expr!(src, expected);
+        assert!(parse("({ async foo: 1 })").is_err());
+        assert!(parse("({ async\n foo() {} })").is_err());
+    }
+
+    #[test]
+    fn object_accessors() {
+        let src = "({ get foo() {}, set foo(v) {} })";
+        let mock = Mock::new();
+
+        let accessor = |kind, params| Property::Method {
+            key: mock.ptr(PropertyKey::Literal("foo")),
+            kind,
+            value: mock.ptr(Function {
+                name: EmptyName,
+                generator: false,
+                is_async: false,
+                type_parameters: NodeList::empty(),
+                params,
+                return_type: None,
+                body: mock.empty_block(),
+            }),
+        };
+
+        let expected = ObjectExpression {
+            body: mock.list([
+                accessor(MethodKind::Get, NodeList::empty()),
+                accessor(MethodKind::Set, mock.list([Pattern::Identifier("v")])),
+            ]),
+        };
+
+        assert_expr!(src, expected);
+        assert!(parse("({ get foo: 1 })").is_err());
+        assert!(parse("({ set foo })").is_err());
+    }
 }
@@ -1,8 +1,10 @@
-use toolshed::list::ListBuilder;
-use parser::{Parser, Parse, ANY, B0};
+use toolshed::list::{ListBuilder, GrowableList};
+use parser::{Parser, Parse, FunctionContext, ANY, B0};
+use error::ErrorCode;
 use lexer::Token::*;
-use ast::{Node, NodeList, EmptyName, OptionalName, MandatoryName, Name};
-use ast::{MethodKind, Pattern, Function, Class, ClassMember, PropertyKey};
+use lexer::Asi;
+use ast::{Node, NodeList, Block, BlockNode, EmptyName, OptionalName, MandatoryName, Name};
+use ast::{MethodKind, Pattern, PatternList, Function, Class, ClassMember, PropertyKey};
 
 impl<'ast> Parse<'ast> for EmptyName {
     type Output = Self;
@@ -66,21 +68,7 @@ impl<'ast, N> Parse<'ast> for Function<'ast, N> where
 
     #[inline]
     fn parse(par: &mut Parser<'ast>) -> Self::Output {
-        let generator: bool = if par.lexer.token == OperatorMultiplication {
-            par.lexer.consume();
-            true
-        } else {
-            false
-        };
-
-        let name = N::parse(par);
-
-        Function {
-            name,
-            generator,
-            params: par.params(),
-            body: par.block(),
-        }
+        par.function_in(false, FunctionContext::function())
     }
 }
 
@@ -105,42 +93,54 @@ impl<'ast> Parse<'ast> for ClassMember<'ast> {
     fn parse(par: &mut Parser<'ast>) -> Self::Output {
         let start = par.lexer.start();
 
-        let is_static = match par.lexer.token {
-            Static => {
-                par.lexer.consume();
-                true
-            }
-            _ => false
-        };
+        let mut is_static = false;
+        let mut static_end = start;
+
+        if par.lexer.token == Static {
+            is_static = true;
+            static_end = par.lexer.end_then_consume();
+        }
 
         let mut kind = MethodKind::Method;
+        let mut is_async = false;
 
-        let token_start = par.lexer.start();
+        let mut token_start = par.lexer.start();
         let token_end;
 
         let key = match par.lexer.token {
+            // `static` on its own is the name of a method or a field
+            ParenOpen | OperatorAssign if is_static => {
+                is_static = false;
+                token_start = start;
+                token_end = static_end;
+
+                PropertyKey::Literal("static")
+            },
             _ if par.lexer.token.is_word() => {
                 let mut label = par.lexer.token_as_str();
-                token_end = par.lexer.end_then_consume();
+                let mut end = par.lexer.end_then_consume();
 
                 if par.lexer.token.is_word() {
-                    kind = match label {
-                        "get" => MethodKind::Get,
-                        "set" => MethodKind::Set,
-                        _     => return par.error()
-                    };
+                    match label {
+                        "get" => kind = MethodKind::Get,
+                        "set" => kind = MethodKind::Set,
+                        "async" if par.asi() == Asi::NoSemicolon => is_async = true,
+                        _ => return par.error()
+                    }
                     label = par.lexer.token_as_str();
-                    par.lexer.consume();
-                } else if !is_static && label == "constructor" {
-                    kind = MethodKind::Constructor;
+                    token_start = par.lexer.start();
+                    end = par.lexer.end_then_consume();
                 }
 
+                token_end = end;
+
                 PropertyKey::Literal(label)
             },
+            LiteralString |
             LiteralNumber => {
-                let num = par.lexer.token_as_str();
+                let literal = par.lexer.token_as_str();
                 token_end = par.lexer.end_then_consume();
-                PropertyKey::Literal(num)
+                PropertyKey::Literal(literal)
             },
             LiteralBinary => {
                 let num = par.lexer.token_as_str();
@@ -160,11 +160,26 @@ impl<'ast> Parse<'ast> for ClassMember<'ast> {
             _ => return par.error()
         };
 
-        let key = par.alloc_at_loc(token_start, token_end, key);
+        let is_constructor = matches!(
+            key,
+            PropertyKey::Literal("constructor")   |
+            PropertyKey::Literal("'constructor'") |
+            PropertyKey::Literal("\"constructor\"")
+        );
+
+        let key: Node<PropertyKey> = par.alloc_at_loc(token_start, token_end, key);
         let end;
         let member = match par.lexer.token {
             ParenOpen => {
-                let value = Node::parse(par);
+                // Static methods can be called `constructor`, fields can't
+                if is_constructor && !is_static {
+                    match kind {
+                        MethodKind::Method if !is_async => kind = MethodKind::Constructor,
+                        _ => par.error_at::<()>(ErrorCode::InvalidConstructor, key.span()),
+                    }
+                }
+
+                let value = par.method(is_async, kind == MethodKind::Constructor);
 
                 end = value.end;
 
@@ -176,12 +191,25 @@ impl<'ast> Parse<'ast> for ClassMember<'ast> {
                 }
             },
             OperatorAssign => {
+                // Only methods can be accessors or async
+                if kind != MethodKind::Method || is_async {
+                    return par.error();
+                }
+
+                if is_constructor {
+                    par.error_at::<()>(ErrorCode::InvalidConstructor, key.span());
+                }
+
                 par.lexer.consume();
 
-                let expression = par.expression::<B0>();
+                let context = par.context.method(false);
+                let expression = par.with_context(context, |par| par.expression::<B0>());
 
                 end = expression.end;
 
+                // Fields need a semicolon, or a line break before the next member
+                par.expect_semicolon();
+
                 ClassMember::Literal {
                     is_static,
                     key,
@@ -191,10 +219,6 @@ impl<'ast> Parse<'ast> for ClassMember<'ast> {
             _ => return par.error(),
         };
 
-        if par.lexer.token == Semicolon {
-            par.lexer.consume();
-        }
-
         par.alloc_at_loc(start, end, member)
     }
 }
@@ -206,6 +230,8 @@ impl<'ast, N> Parse<'ast> for Class<'ast, N> where
 
     #[inline]
     fn parse(par: &mut Parser<'ast>) -> Self::Output {
+        // All parts of a class are strict code
+        let recorded = par.recorded_block_functions();
         let name = N::parse(par);
 
         let super_class = match par.lexer.token {
@@ -217,15 +243,134 @@ impl<'ast, N> Parse<'ast> for Class<'ast, N> where
             _ => None
         };
 
+        let mut context = par.context;
+
+        context.derived_class = super_class.is_some();
+
+        let body = par.with_context(context, |par| par.class_body());
+
+        par.unique_constructor(body);
+        par.strict_since(recorded);
+
         Class {
             name,
             extends: super_class,
-            body: par.block(),
+            body,
         }
     }
 }
 
 impl<'ast> Parser<'ast> {
+    /// Parse the rest of a function after the `function` keyword, with its
+    /// parameters and body in the given context.
+    fn function_in<N>(&mut self, is_async: bool, context: FunctionContext) -> Function<'ast, N> where
+        N: Name<'ast> + Parse<'ast, Output = N>,
+    {
+        let generator: bool = if self.lexer.token == OperatorMultiplication {
+            self.lexer.consume();
+            true
+        } else {
+            false
+        };
+
+        let name = N::parse(self);
+        let type_parameters = self.type_parameters();
+
+        let (params, return_type) = self.with_context(context, |par| {
+            let params = par.params();
+            let return_type = par.type_annotation();
+
+            // Duplicate names are only allowed in simple parameter lists
+            if params.iter().any(|param| !param.is_identifier()) {
+                par.unique_params(params);
+            }
+
+            if par.options.typescript {
+                par.ordered_params(params);
+            }
+
+            (params, return_type)
+        });
+
+        // `yield` and `await` are only allowed in the body, not in parameters
+        let context = FunctionContext {
+            yield_expression: generator,
+            await_expression: is_async,
+            await_reserved: is_async,
+            ..context
+        };
+
+        let recorded = self.recorded_block_functions();
+        let body = self.with_context(context, |par| par.block());
+
+        self.strict_body_since(recorded, body.body);
+
+        Function {
+            name,
+            generator,
+            is_async,
+            type_parameters,
+            params,
+            return_type,
+            body,
+        }
+    }
+
+    /// Parse the rest of an async function after the `function` keyword.
+    pub fn async_function<N>(&mut self) -> Function<'ast, N> where
+        N: Name<'ast> + Parse<'ast, Output = N>,
+    {
+        self.function_in(true, FunctionContext::function())
+    }
+
+    /// Parse the parameters and body of a class or object method.
+    pub fn method(&mut self, is_async: bool, constructor: bool) -> Node<'ast, Function<'ast, EmptyName>> {
+        let start = self.lexer.start();
+        let context = self.context.method(constructor);
+        let function: Function<'ast, EmptyName> = self.function_in(is_async, context);
+
+        self.unique_params(function.params);
+        self.alloc_at_loc(start, function.body.end, function)
+    }
+
+    /// Same as `block`, but skips any `;` between the class members.
+    fn class_body(&mut self) -> BlockNode<'ast, ClassMember<'ast>> {
+        let start = self.lexer.start();
+
+        expect!(self, BraceOpen);
+
+        let builder = GrowableList::new();
+
+        loop {
+            match self.lexer.token {
+                Semicolon                   => self.lexer.consume(),
+                BraceClose | EndOfProgram   => break,
+                _                           => builder.push(self.arena, ClassMember::parse(self)),
+            }
+        }
+
+        let end = self.close_block();
+
+        self.alloc_at_loc(start, end, Block {
+            body: builder.as_list()
+        })
+    }
+
+    /// Report a `DuplicateConstructor` error for every constructor in the
+    /// class body but the first.
+    fn unique_constructor(&mut self, body: BlockNode<'ast, ClassMember<'ast>>) {
+        let mut constructors = body.body.iter().filter_map(|member| match member.item {
+            ClassMember::Method { kind: MethodKind::Constructor, key, .. } => Some(key.span()),
+            _ => None
+        });
+
+        constructors.next();
+
+        for span in constructors {
+            self.error_at::<()>(ErrorCode::DuplicateConstructor, span);
+        }
+    }
+
     #[inline]
     fn pattern_void(&mut self) -> Node<'ast, Pattern<'ast>> {
         let loc = self.lexer.start();
@@ -245,7 +390,7 @@ impl<'ast> Parser<'ast> {
     #[inline]
     fn pattern_array(&mut self) -> Node<'ast, Pattern<'ast>> {
         let start = self.lexer.start_then_consume();
-        let elements = self.array_elements(Parser::pattern_array_element);
+        let elements = self.nested(Parser::pattern_array_elements);
         let end = self.lexer.end_then_consume();
 
         self.alloc_at_loc(start, end, Pattern::ArrayPattern {
@@ -253,12 +398,43 @@ impl<'ast> Parser<'ast> {
         })
     }
 
+    /// Elements of an array pattern, holes become `Pattern::Void`. Unlike in
+    /// array literals, a trailing comma doesn't add a hole, `[a, b,]` has
+    /// two elements while `[a, b, ,]` has three.
+    fn pattern_array_elements(&mut self) -> PatternList<'ast> {
+        let builder = match self.lexer.token {
+            BracketClose => return NodeList::empty(),
+            _            => ListBuilder::new(self.arena, self.pattern_array_element()),
+        };
+
+        loop {
+            match self.lexer.token {
+                Comma        => self.lexer.consume(),
+                BracketClose => break,
+                _            => {
+                    self.error::<()>();
+                    break;
+                }
+            }
+
+            if self.lexer.token == BracketClose {
+                break;
+            }
+
+            builder.push(self.arena, self.pattern_array_element());
+        }
+
+        builder.as_list()
+    }
+
     #[inline]
     fn pattern_object(&mut self) -> Node<'ast, Pattern<'ast>> {
         let start = self.lexer.start_then_consume();
         let properties = self.property_list();
         let end = self.lexer.end_then_consume();
 
+        self.rest_last(properties);
+
         self.alloc_at_loc(start, end, Pattern::ObjectPattern {
             properties,
         })
@@ -303,6 +479,8 @@ impl<'ast> Parser<'ast> {
             _                    => self.error()
         };
 
+        let left = self.pattern_annotation(left, true);
+
         self.pattern_assign(left)
     }
 
@@ -321,15 +499,18 @@ impl<'ast> Parser<'ast> {
             _ => self.error()
         };
 
+        let rest = self.alloc_at_loc(start, argument.end, Pattern::RestElement {
+            argument
+        });
+        let rest = self.pattern_annotation(rest, false);
+
         expect!(self, ParenClose);
 
-        self.alloc_at_loc(start, argument.end, Pattern::RestElement {
-            argument
-        })
+        rest
     }
 
     #[inline]
-    fn params(&mut self) -> NodeList<'ast, Pattern<'ast>> {
+    pub fn params(&mut self) -> NodeList<'ast, Pattern<'ast>> {
         expect!(self, ParenOpen);
 
         let item = match self.lexer.token {
@@ -390,6 +571,8 @@ mod test {
     use ast::{NodeList, Literal, Expression, Function, Class};
     use ast::{ClassMember, Pattern};
     use ast::statement::*;
+    use error::ErrorCode;
+    use span::Span;
 
     #[test]
     fn function_empty() {
@@ -400,7 +583,10 @@ mod test {
             Function {
                 name: mock.name("foo"),
                 generator: false,
+                is_async: false,
+                type_parameters: NodeList::empty(),
                 params: NodeList::empty(),
+                return_type: None,
                 body: mock.empty_block(),
             }
         ]);
@@ -418,7 +604,10 @@ mod test {
                 Function {
                     name: mock.name("foo"),
                     generator: true,
+                    is_async: false,
+                    type_parameters: NodeList::empty(),
                     params: NodeList::empty(),
+                    return_type: None,
                     body: mock.empty_block(),
                 }
             ]);
@@ -434,7 +623,10 @@ mod test {
                 Function {
                     name: mock.name("foo"),
                     generator: true,
+                    is_async: false,
+                    type_parameters: NodeList::empty(),
                     params: NodeList::empty(),
+                    return_type: None,
                     body: mock.empty_block(),
                 }
             ]);
@@ -450,7 +642,10 @@ mod test {
                 Function {
                     name: mock.name("foo"),
                     generator: true,
+                    is_async: false,
+                    type_parameters: NodeList::empty(),
                     params: NodeList::empty(),
+                    return_type: None,
                     body: mock.empty_block(),
                 }
             ]);
@@ -459,6 +654,13 @@ mod test {
         }
     }
 
+    #[test]
+    fn generator_yield_in_param_default() {
+        assert!(parse("function* g(a = yield) {}").is_err());
+        assert!(parse("function* g(a = yield 1) {}").is_err());
+        assert!(parse("function* g(a = [yield]) {}").is_err());
+    }
+
     #[test]
     fn function_params() {
         let src = "function foo(bar, baz) {}";
@@ -468,10 +670,13 @@ mod test {
             Function {
                 name: mock.name("foo"),
                 generator: false,
+                is_async: false,
+                type_parameters: NodeList::empty(),
                 params: mock.list([
                     Pattern::Identifier("bar"),
                     Pattern::Identifier("baz"),
                 ]),
+                return_type: None,
                 body: mock.empty_block(),
             }
         ]);
@@ -488,7 +693,10 @@ mod test {
             Function {
                 name: mock.name("foo"),
                 generator: false,
+                is_async: false,
+                type_parameters: NodeList::empty(),
                 params: NodeList::empty(),
+                return_type: None,
                 body: mock.block([
                     mock.ptr("bar"),
                     mock.ptr("baz"),
@@ -508,6 +716,8 @@ mod test {
             Function {
                 name: mock.name("foo"),
                 generator: false,
+                is_async: false,
+                type_parameters: NodeList::empty(),
                 params: mock.list([
                     Pattern::AssignmentPattern {
                         left: mock.ptr(Pattern::Identifier("a")),
@@ -522,6 +732,7 @@ mod test {
                         right: mock.number("2")
                     }
                 ]),
+                return_type: None,
                 body: mock.block([
                     ReturnStatement {
                         value: Some(mock.number("2"))
@@ -541,6 +752,8 @@ mod test {
             Function {
                 name: mock.name("foo"),
                 generator: false,
+                is_async: false,
+                type_parameters: NodeList::empty(),
                 params: mock.list([
                     Pattern::Identifier("a"),
                     Pattern::Identifier("b"),
@@ -550,6 +763,7 @@ mod test {
                     },
                     Pattern::Identifier("d")
                 ]),
+                return_type: None,
                 body: mock.block([
                     ReturnStatement {
                         value: Some(mock.number("2"))
@@ -569,11 +783,14 @@ mod test {
             Function {
                 name: mock.name("foo"),
                 generator: false,
+                is_async: false,
+                type_parameters: NodeList::empty(),
                 params: mock.list([
                     Pattern::RestElement {
                         argument: mock.ptr("rest"),
                     }
                 ]),
+                return_type: None,
                 body: mock.empty_block()
             }
         ]);
@@ -589,6 +806,8 @@ mod test {
             Function {
                 name: mock.name("foo"),
                 generator: false,
+                is_async: false,
+                type_parameters: NodeList::empty(),
                 params: mock.list([
                     Pattern::Identifier("a"),
                     Pattern::AssignmentPattern {
@@ -599,6 +818,7 @@ mod test {
                         argument: mock.ptr("rest"),
                     }
                 ]),
+                return_type: None,
                 body: mock.empty_block()
             }
         ]);
@@ -626,6 +846,89 @@ mod test {
         assert_eq!(parse(src).unwrap().body(), expected);
     }
 
+    #[test]
+    fn class_semicolons_only() {
+        let src = "class Foo { ; ; ; }";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Class {
+                name: mock.name("Foo"),
+                extends: None,
+                body: mock.empty_block(),
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn class_semicolons_between_members() {
+        let src = "class Foo { ; bar() {} ;; baz() {}; }";
+
+        assert_eq!(parse(src).unwrap().body(), parse("class Foo { bar() {} baz() {} }").unwrap().body());
+    }
+
+    #[test]
+    fn class_fields_separated_by_newlines() {
+        let src = "class C { x = 1\n y = 2\n static z = 3\n m() {} }";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Class {
+                name: mock.name("C"),
+                extends: None,
+                body: mock.block([
+                    ClassMember::Literal {
+                        is_static: false,
+                        key: mock.ptr(PropertyKey::Literal("x")),
+                        value: mock.number("1")
+                    },
+                    ClassMember::Literal {
+                        is_static: false,
+                        key: mock.ptr(PropertyKey::Literal("y")),
+                        value: mock.number("2")
+                    },
+                    ClassMember::Literal {
+                        is_static: true,
+                        key: mock.ptr(PropertyKey::Literal("z")),
+                        value: mock.number("3")
+                    },
+                    ClassMember::Method {
+                        is_static: false,
+                        key: mock.ptr(PropertyKey::Literal("m")),
+                        kind: MethodKind::Method,
+                        value: mock.ptr(Function {
+                            name: EmptyName,
+                            generator: false,
+                            is_async: false,
+                            type_parameters: NodeList::empty(),
+                            params: NodeList::empty(),
+                            return_type: None,
+                            body: mock.empty_block()
+                        })
+                    },
+                ])
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn class_fields_with_redundant_semicolons() {
+        let src = "class C { ;; x = 1;; y = 2; ; m() {};; }";
+
+        assert_eq!(parse(src).unwrap().body(), parse("class C { x = 1\n y = 2\n m() {} }").unwrap().body());
+        assert_eq!(parse("class C { x = 1 }").unwrap().body(), parse("class C { x = 1; }").unwrap().body());
+    }
+
+    #[test]
+    fn class_fields_without_separator() {
+        assert!(parse("class C { x = 1 y = 2 }").is_err());
+        assert!(parse("class C { x = 1 m() {} }").is_err());
+    }
+
     #[test]
     fn child_class_empty() {
         let src = "class Foo extends Bar {}";
@@ -667,10 +970,13 @@ mod test {
                         value: mock.ptr(Function {
                             name: EmptyName,
                             generator: false,
+                            is_async: false,
+                            type_parameters: NodeList::empty(),
                             params: mock.list([
                                 Pattern::Identifier("bar"),
                                 Pattern::Identifier("baz")
                             ]),
+                            return_type: None,
                             body: mock.block([
                                 mock.ptr("debug")
                             ])
@@ -716,10 +1022,13 @@ mod test {
                         value: mock.ptr(Function {
                             name: EmptyName,
                             generator: false,
+                            is_async: false,
+                            type_parameters: NodeList::empty(),
                             params: mock.list([
                                 Pattern::Identifier("bar"),
                                 Pattern::Identifier("baz")
                             ]),
+                            return_type: None,
                             body: mock.block([
                                 mock.ptr("debug")
                             ])
@@ -732,9 +1041,12 @@ mod test {
                         value: mock.ptr(Function {
                             name: EmptyName,
                             generator: false,
+                            is_async: false,
+                            type_parameters: NodeList::empty(),
                             params: mock.list([
                                 Pattern::Identifier("moon")
                             ]),
+                            return_type: None,
                             body: mock.block([
                                 mock.ptr("debug")
                             ])
@@ -747,7 +1059,10 @@ mod test {
                         value: mock.ptr(Function {
                             name: EmptyName,
                             generator: false,
+                            is_async: false,
+                            type_parameters: NodeList::empty(),
                             params: NodeList::empty(),
+                            return_type: None,
                             body: mock.empty_block()
                         })
                     },
@@ -758,7 +1073,10 @@ mod test {
                         value: mock.ptr(Function {
                             name: EmptyName,
                             generator: false,
+                            is_async: false,
+                            type_parameters: NodeList::empty(),
                             params: NodeList::empty(),
+                            return_type: None,
                             body: mock.empty_block()
                         })
                     },
@@ -769,7 +1087,10 @@ mod test {
                         value: mock.ptr(Function {
                             name: EmptyName,
                             generator: false,
+                            is_async: false,
+                            type_parameters: NodeList::empty(),
                             params: NodeList::empty(),
+                            return_type: None,
                             body: mock.empty_block()
                         })
                     },
@@ -826,6 +1147,41 @@ mod test {
         assert_eq!(parse(src).unwrap().body(), expected);
     }
 
+    #[test]
+    fn class_literal_keys() {
+        let src = r#"class Foo { "foo bar"() {} 42 = 10; }"#;
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Class {
+                name: mock.name("Foo"),
+                extends: None,
+                body: mock.block([
+                    ClassMember::Method {
+                        is_static: false,
+                        key: mock.ptr(PropertyKey::Literal(r#""foo bar""#)),
+                        kind: MethodKind::Method,
+                        value: mock.ptr(Function {
+                            name: EmptyName,
+                            generator: false,
+                            is_async: false,
+                            type_parameters: NodeList::empty(),
+                            params: NodeList::empty(),
+                            return_type: None,
+                            body: mock.empty_block()
+                        })
+                    },
+                    ClassMember::Literal {
+                        is_static: false,
+                        key: mock.ptr(PropertyKey::Literal("42")),
+                        value: mock.number("10")
+                    },
+                ])
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
 
     #[test]
     fn class_extends_null() {
@@ -867,9 +1223,12 @@ mod test {
                         value: mock.ptr(Function {
                             name: EmptyName,
                             generator: false,
+                            is_async: false,
+                            type_parameters: NodeList::empty(),
                             params: mock.list([
                                 Pattern::Identifier("foo")
                             ]),
+                            return_type: None,
                             body: mock.empty_block()
                         })
                     },
@@ -880,9 +1239,12 @@ mod test {
                         value: mock.ptr(Function {
                             name: EmptyName,
                             generator: false,
+                            is_async: false,
+                            type_parameters: NodeList::empty(),
                             params: mock.list([
                                 Pattern::Identifier("bar")
                             ]),
+                            return_type: None,
                             body: mock.empty_block()
                         })
                     },
@@ -892,4 +1254,268 @@ mod test {
 
         assert_eq!(parse(src).unwrap().body(), expected);
     }
+
+    #[test]
+    fn function_default_param_order() {
+        // Parameters with and without defaults can come in any order
+        assert!(parse("function f(a, b = 1) {}").is_ok());
+        assert!(parse("function f(a = 1, b) {}").is_ok());
+        assert!(parse("function f(a = 1, ...b) {}").is_ok());
+    }
+
+    #[test]
+    fn function_rest_element_with_default() {
+        let errors = parse("function f(...r = []) {}").unwrap_err();
+
+        assert_eq!(errors[0].code, ErrorCode::UnexpectedToken);
+        assert_eq!(errors[0].span, Span::new(16, 17));
+    }
+
+    #[test]
+    fn function_optional_param_order() {
+        use parser::{parse_with, ParseOptions};
+
+        let ts = ParseOptions { typescript: true, ..ParseOptions::default() };
+
+        assert!(parse_with("function f(a: T, b?: T, c = 1, ...d) {}", ts).is_ok());
+        assert!(parse_with("function f(a?: T, b?: T) {}", ts).is_ok());
+
+        let errors = parse_with("function f(a?: T, b: T) {}", ts).unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, ErrorCode::RequiredParamAfterOptional);
+        assert_eq!(errors[0].span, Span::new(18, 22));
+
+        let errors = parse_with("(a?, b) => a", ts).unwrap_err();
+
+        assert_eq!(errors[0].code, ErrorCode::RequiredParamAfterOptional);
+        assert_eq!(errors[0].span, Span::new(5, 6));
+    }
+
+    #[test]
+    fn class_duplicate_constructor() {
+        let errors = parse("class Foo { constructor() {} bar() {} constructor() {} }").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, ErrorCode::DuplicateConstructor);
+        assert_eq!(errors[0].span, Span::new(38, 49));
+    }
+
+    #[test]
+    fn class_duplicate_string_constructor() {
+        let errors = parse("class Foo { constructor() {} 'constructor'() {} }").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, ErrorCode::DuplicateConstructor);
+    }
+
+    #[test]
+    fn class_accessor_constructor() {
+        let errors = parse("class Foo { get constructor() {} }").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, ErrorCode::InvalidConstructor);
+        assert_eq!(errors[0].span, Span::new(16, 27));
+
+        let errors = parse("class Foo { set constructor(bar) {} }").unwrap_err();
+
+        assert_eq!(errors[0].code, ErrorCode::InvalidConstructor);
+    }
+
+    #[test]
+    fn class_field_constructor() {
+        let errors = parse("class Foo { constructor = 1; }").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, ErrorCode::InvalidConstructor);
+    }
+
+    #[test]
+    fn class_static_constructor() {
+        assert!(parse("class Foo { constructor() {} static constructor() {} }").is_ok());
+        assert!(parse("class Foo { static get constructor() {} }").is_ok());
+    }
+
+    #[test]
+    fn class_static_field_constructor() {
+        let errors = parse("class A { static constructor = 1 }").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, ErrorCode::InvalidConstructor);
+        assert_eq!(errors[0].span, Span::new(17, 28));
+    }
+
+    #[test]
+    fn class_unary_keyword_keys() {
+        let src = "class Foo { typeof() {} static void() {} delete = 1; }";
+        let mock = Mock::new();
+
+        let method = || mock.ptr(Function {
+            name: EmptyName,
+            generator: false,
+            is_async: false,
+            type_parameters: NodeList::empty(),
+            params: NodeList::empty(),
+            return_type: None,
+            body: mock.empty_block(),
+        });
+
+        let expected = mock.list([
+            Class {
+                name: mock.name("Foo"),
+                extends: None,
+                body: mock.block([
+                    ClassMember::Method {
+                        is_static: false,
+                        key: mock.ptr(PropertyKey::Literal("typeof")),
+                        kind: MethodKind::Method,
+                        value: method(),
+                    },
+                    ClassMember::Method {
+                        is_static: true,
+                        key: mock.ptr(PropertyKey::Literal("void")),
+                        kind: MethodKind::Method,
+                        value: method(),
+                    },
+                    ClassMember::Literal {
+                        is_static: false,
+                        key: mock.ptr(PropertyKey::Literal("delete")),
+                        value: mock.number("1"),
+                    },
+                ])
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn class_modifier_keywords_as_names() {
+        let src = "class Foo { static() {} async() {} get() {} set() {} static = 1; }";
+        let mock = Mock::new();
+
+        let method = |key| ClassMember::Method {
+            is_static: false,
+            key: mock.ptr(PropertyKey::Literal(key)),
+            kind: MethodKind::Method,
+            value: mock.ptr(Function {
+                name: EmptyName,
+                generator: false,
+                is_async: false,
+                type_parameters: NodeList::empty(),
+                params: NodeList::empty(),
+                return_type: None,
+                body: mock.empty_block(),
+            }),
+        };
+
+        let expected = mock.list([
+            Class {
+                name: mock.name("Foo"),
+                extends: None,
+                body: mock.block([
+                    method("static"),
+                    method("async"),
+                    method("get"),
+                    method("set"),
+                    ClassMember::Literal {
+                        is_static: false,
+                        key: mock.ptr(PropertyKey::Literal("static")),
+                        value: mock.number("1"),
+                    },
+                ])
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn class_modifier_keywords() {
+        let src = "class Foo { static static() {} async get() {} static async set() {} get async() {} }";
+        let mock = Mock::new();
+
+        let method = |is_static, key, kind, is_async| ClassMember::Method {
+            is_static,
+            key: mock.ptr(PropertyKey::Literal(key)),
+            kind,
+            value: mock.ptr(Function {
+                name: EmptyName,
+                generator: false,
+                is_async,
+                type_parameters: NodeList::empty(),
+                params: NodeList::empty(),
+                return_type: None,
+                body: mock.empty_block(),
+            }),
+        };
+
+        let expected = mock.list([
+            Class {
+                name: mock.name("Foo"),
+                extends: None,
+                body: mock.block([
+                    method(true, "static", MethodKind::Method, false),
+                    method(false, "get", MethodKind::Method, true),
+                    method(true, "set", MethodKind::Method, true),
+                    method(false, "async", MethodKind::Get, false),
+                ])
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn class_invalid_async_members() {
+        assert!(parse("class Foo { async foo = 1; }").is_err());
+        assert!(parse("class Foo { get foo = 1; }").is_err());
+        assert!(parse("class Foo { async\n foo() {} }").is_err());
+
+        let errors = parse("class Foo { async constructor() {} }").unwrap_err();
+
+        assert_eq!(errors[0].code, ErrorCode::InvalidConstructor);
+    }
+
+    #[test]
+    fn parameter_names() {
+        fn params(src: &str) -> Vec<(String, usize, bool, bool)> {
+            let module = parse(src).unwrap();
+
+            let function = match module.body().only_element().map(|statement| statement.item) {
+                Some(Statement::Function(function)) => function,
+                _ => panic!("Expected a function statement"),
+            };
+
+            function.parameter_names()
+                .into_iter()
+                .map(|param| (param.name.to_owned(), param.index, param.default, param.rest))
+                .collect()
+        }
+
+        let owned = |list: &[(&str, usize, bool, bool)]| -> Vec<(String, usize, bool, bool)> {
+            list.iter().map(|&(name, index, default, rest)| (name.to_owned(), index, default, rest)).collect()
+        };
+
+        assert_eq!(params("function f(a, b) {}"), owned(&[
+            ("a", 0, false, false),
+            ("b", 1, false, false),
+        ]));
+        assert_eq!(params("function f(a, b = 1) {}"), owned(&[
+            ("a", 0, false, false),
+            ("b", 1, true, false),
+        ]));
+        assert_eq!(params("function f({ a, b: c } = {}, ...rest) {}"), owned(&[
+            ("a", 0, true, false),
+            ("c", 0, true, false),
+            ("rest", 1, false, true),
+        ]));
+        assert_eq!(params("function f([a, , { b: [c = 1] }], { d: { e }, ...f }) {}"), owned(&[
+            ("a", 0, false, false),
+            ("c", 0, false, false),
+            ("e", 1, false, false),
+            ("f", 1, false, false),
+        ]));
+        assert_eq!(params("function f() {}"), owned(&[]));
+    }
 }
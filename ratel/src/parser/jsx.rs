@@ -0,0 +1,463 @@
+use toolshed::list::GrowableList;
+use parser::{Parser, ANY, B0};
+use error::ErrorCode;
+use lexer::Token::*;
+use ast::{NodeList, Expression, ExpressionNode, Literal};
+use ast::expression::{MemberExpression, JSXElement, JSXFragment, JSXAttribute, JSXChild};
+
+impl<'ast> Parser<'ast> {
+    /// Parse a JSX element or fragment starting at the current `<` token.
+    /// Outside of the JSX mode this is an unexpected token.
+    pub fn jsx_expression(&mut self) -> ExpressionNode<'ast> {
+        if !self.options.jsx {
+            let loc = self.lexer.start();
+            self.error::<()>();
+            return self.alloc_at_loc(loc, loc, Expression::Void);
+        }
+
+        let start = self.lexer.start_then_consume();
+
+        self.jsx_element(start, false)
+    }
+
+    /// Parse the remainder of an element or a fragment past its opening `<`.
+    /// A `nested` element is followed by another child of its parent, rather
+    /// than regular tokens.
+    fn jsx_element(&mut self, start: u32, nested: bool) -> ExpressionNode<'ast> {
+        if self.lexer.token == OperatorGreater {
+            let children = self.jsx_children();
+
+            if self.lexer.token != OperatorGreater {
+                self.error::<()>();
+            }
+
+            let end = self.jsx_close(nested);
+
+            return self.alloc_at_loc(start, end, JSXFragment { children });
+        }
+
+        let name = self.jsx_name();
+        let attributes = self.jsx_attributes();
+
+        if self.lexer.token == OperatorDivision {
+            self.lexer.consume();
+
+            let end = self.jsx_close(nested);
+
+            return self.alloc_at_loc(start, end, JSXElement {
+                name,
+                attributes,
+                children: NodeList::empty(),
+                self_closing: true,
+            });
+        }
+
+        let children = self.jsx_children();
+        let closing = self.jsx_name();
+
+        if closing.item != name.item {
+            self.error_at::<()>(ErrorCode::MismatchedClosingTag, closing.span());
+        }
+
+        let end = self.jsx_close(nested);
+
+        self.alloc_at_loc(start, end, JSXElement {
+            name,
+            attributes,
+            children,
+            self_closing: false,
+        })
+    }
+
+    /// Tag names are identifiers that can contain dashes, or member
+    /// expressions made of such, as in `<a.b />`.
+    fn jsx_name(&mut self) -> ExpressionNode<'ast> {
+        if !self.lexer.token.is_word() {
+            let loc = self.lexer.start();
+            self.error::<()>();
+            return self.alloc_at_loc(loc, loc, Expression::Void);
+        }
+
+        self.lexer.extend_jsx_name();
+
+        let ident = self.lexer.token_as_str();
+        let mut name = self.alloc_in_loc(ident);
+
+        self.lexer.consume();
+
+        while self.lexer.token == Accessor {
+            let member = self.lexer.accessor_as_str();
+            let property = self.alloc_in_loc(member);
+
+            self.lexer.consume();
+
+            name = self.alloc_at_loc(name.start, property.end, MemberExpression {
+                object: name,
+                property,
+            });
+        }
+
+        name
+    }
+
+    fn jsx_attributes(&mut self) -> NodeList<'ast, JSXAttribute<'ast>> {
+        let builder = GrowableList::new();
+
+        loop {
+            let attribute = match self.lexer.token {
+                BraceOpen => {
+                    let start = self.lexer.start_then_consume();
+
+                    expect!(self, OperatorSpread);
+
+                    let argument = self.expression::<B0>();
+                    let end = self.lexer.end();
+
+                    expect!(self, BraceClose);
+
+                    self.alloc_at_loc(start, end, JSXAttribute::Spread { argument })
+                },
+                _ if self.lexer.token.is_word() => {
+                    self.lexer.extend_jsx_name();
+
+                    let name = self.lexer.token_as_str();
+                    let name = self.alloc_in_loc(name);
+
+                    self.lexer.consume();
+
+                    let value = match self.lexer.token {
+                        OperatorAssign => {
+                            self.lexer.consume();
+                            self.lexer.extend_jsx_string();
+
+                            Some(self.jsx_attribute_value())
+                        },
+                        _ => None,
+                    };
+
+                    let end = value.map(|value| value.end).unwrap_or(name.end);
+
+                    self.alloc_at_loc(name.start, end, JSXAttribute::Attribute { name, value })
+                },
+                _ => break,
+            };
+
+            builder.push(self.arena, attribute);
+        }
+
+        builder.as_list()
+    }
+
+    fn jsx_attribute_value(&mut self) -> ExpressionNode<'ast> {
+        match self.lexer.token {
+            LiteralString => {
+                let value = self.lexer.token_as_str();
+                let value = self.alloc_in_loc(Literal::String(value));
+
+                self.lexer.consume();
+
+                value
+            },
+            BraceOpen => {
+                self.lexer.consume();
+
+                let value = self.expression::<B0>();
+
+                expect!(self, BraceClose);
+
+                value
+            },
+            OperatorLesser => self.jsx_expression(),
+            _ => {
+                let loc = self.lexer.start();
+                self.error::<()>();
+                self.alloc_at_loc(loc, loc, Expression::Void)
+            }
+        }
+    }
+
+    /// Parse children following the `>` of an opening tag, up to and
+    /// including the `</` of the closing tag.
+    fn jsx_children(&mut self) -> NodeList<'ast, JSXChild<'ast>> {
+        let builder = GrowableList::new();
+
+        if !self.lexer.token_as_str().starts_with('>') {
+            self.error::<()>();
+            return builder.as_list();
+        }
+
+        let index = self.lexer.start() + 1;
+
+        self.lexer.read_jsx_child(index);
+
+        loop {
+            let child = match self.lexer.token {
+                LiteralString => {
+                    let text = self.lexer.token_as_str();
+                    let child = self.alloc_in_loc(JSXChild::Text(text));
+
+                    self.lexer.read_jsx_child(child.end);
+
+                    child
+                },
+                BraceOpen => {
+                    let start = self.lexer.start_then_consume();
+
+                    let expression = match self.lexer.token {
+                        BraceClose => {
+                            let loc = self.lexer.start();
+                            self.alloc_at_loc(loc, loc, Expression::Void)
+                        },
+                        _ => self.expression::<ANY>(),
+                    };
+
+                    if self.lexer.token != BraceClose {
+                        self.error::<()>();
+                        break;
+                    }
+
+                    let end = self.lexer.end();
+
+                    self.lexer.read_jsx_child(end);
+
+                    self.alloc_at_loc(start, end, JSXChild::Expression(expression))
+                },
+                OperatorLesser => {
+                    let start = self.lexer.start_then_consume();
+
+                    if self.lexer.token == OperatorDivision {
+                        self.lexer.consume();
+                        break;
+                    }
+
+                    let element = self.nested(|par| par.jsx_element(start, true));
+
+                    self.alloc_at_loc(element.start, element.end, JSXChild::Element(element))
+                },
+                _ => {
+                    self.error::<()>();
+                    break;
+                }
+            };
+
+            builder.push(self.arena, child);
+        }
+
+        builder.as_list()
+    }
+
+    /// Finish a tag at the current token, which might be a longer operator
+    /// such as `>>` or `>=` starting with its closing `>`. Returns the end
+    /// of the tag.
+    fn jsx_close(&mut self, nested: bool) -> u32 {
+        if !self.lexer.token_as_str().starts_with('>') {
+            let end = self.lexer.end();
+            self.error::<()>();
+            return end;
+        }
+
+        let end = self.lexer.start() + 1;
+
+        if nested {
+            self.lexer.read_jsx_child(end);
+        } else {
+            self.lexer.consume_at(end);
+        }
+
+        end
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use parser::{parse, parse_with, ParseOptions};
+    use parser::mock::Mock;
+    use ast::Statement;
+    use span::Span;
+
+    fn jsx() -> ParseOptions {
+        ParseOptions { jsx: true, ..ParseOptions::default() }
+    }
+
+    fn element<'ast>(
+        mock: &'ast Mock,
+        name: ExpressionNode<'ast>,
+        attributes: &[JSXAttribute<'ast>],
+        children: &[JSXChild<'ast>],
+    ) -> ExpressionNode<'ast> {
+        mock.ptr(JSXElement {
+            name,
+            attributes: mock.list(attributes),
+            children: mock.list(children),
+            self_closing: false,
+        })
+    }
+
+    #[test]
+    fn nested_element_with_attributes() {
+        let src = r#"<div id="main" a={x} hidden><span>{child}</span> text</div>;"#;
+        let module = parse_with(src, jsx()).unwrap();
+
+        let mock = Mock::new();
+
+        let span = element(&mock, mock.ptr("span"), &[], &[
+            JSXChild::Expression(mock.ptr("child")),
+        ]);
+
+        let expected = mock.list([
+            element(&mock, mock.ptr("div"), &[
+                JSXAttribute::Attribute {
+                    name: mock.ptr("id"),
+                    value: Some(mock.ptr(Literal::String("\"main\""))),
+                },
+                JSXAttribute::Attribute {
+                    name: mock.ptr("a"),
+                    value: Some(mock.ptr("x")),
+                },
+                JSXAttribute::Attribute {
+                    name: mock.ptr("hidden"),
+                    value: None,
+                },
+            ], &[
+                JSXChild::Element(span),
+                JSXChild::Text(" text"),
+            ])
+        ]);
+
+        assert_eq!(module.body(), expected);
+    }
+
+    #[test]
+    fn self_closing_element() {
+        let module = parse_with("<data-grid rows={10} />", jsx()).unwrap();
+
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            mock.ptr(JSXElement {
+                name: mock.ptr("data-grid"),
+                attributes: mock.list([
+                    JSXAttribute::Attribute {
+                        name: mock.ptr("rows"),
+                        value: Some(mock.number("10")),
+                    }
+                ]),
+                children: NodeList::empty(),
+                self_closing: true,
+            })
+        ]);
+
+        assert_eq!(module.body(), expected);
+    }
+
+    #[test]
+    fn fragment() {
+        let module = parse_with("<><a.b /> {}</>", jsx()).unwrap();
+
+        let mock = Mock::new();
+
+        let member = mock.ptr(JSXElement {
+            name: mock.ptr(MemberExpression {
+                object: mock.ptr("a"),
+                property: mock.ptr("b"),
+            }),
+            attributes: NodeList::empty(),
+            children: NodeList::empty(),
+            self_closing: true,
+        });
+
+        let expected = mock.list([
+            mock.ptr(JSXFragment {
+                children: mock.list([
+                    JSXChild::Element(member),
+                    JSXChild::Text(" "),
+                    JSXChild::Expression(mock.ptr(Expression::Void)),
+                ])
+            })
+        ]);
+
+        assert_eq!(module.body(), expected);
+    }
+
+    #[test]
+    fn spread_attribute() {
+        let module = parse_with("x = <div {...props} key={k}></div>", jsx()).unwrap();
+
+        let mock = Mock::new();
+
+        let div = element(&mock, mock.ptr("div"), &[
+            JSXAttribute::Spread {
+                argument: mock.ptr("props"),
+            },
+            JSXAttribute::Attribute {
+                name: mock.ptr("key"),
+                value: Some(mock.ptr("k")),
+            },
+        ], &[]);
+
+        let expected = mock.list([
+            mock.ptr(::ast::expression::BinaryExpression {
+                operator: ::ast::OperatorKind::Assign,
+                left: mock.ptr("x"),
+                right: div,
+            })
+        ]);
+
+        assert_eq!(module.body(), expected);
+    }
+
+    #[test]
+    fn element_followed_by_operators() {
+        let module = parse_with("<a/>>b; <a></a>>=c", jsx()).unwrap();
+
+        match module.body().iter().map(|statement| statement.item).collect::<Vec<_>>()[..] {
+            [Statement::Expression(first), Statement::Expression(second)] => {
+                assert_eq!(first.span(), Span::new(0, 6));
+                assert_eq!(second.span(), Span::new(8, 18));
+            },
+            ref body => panic!("Unexpected body {:?}", body),
+        }
+    }
+
+    #[test]
+    fn attribute_string_spanning_lines() {
+        let src = "<a b=\"c\nd\" e='f\u{2028}'/>";
+        let module = parse_with(src, jsx()).unwrap();
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            mock.ptr(JSXElement {
+                name: mock.ptr("a"),
+                attributes: mock.list([
+                    JSXAttribute::Attribute {
+                        name: mock.ptr("b"),
+                        value: Some(mock.ptr(Literal::String("\"c\nd\""))),
+                    },
+                    JSXAttribute::Attribute {
+                        name: mock.ptr("e"),
+                        value: Some(mock.ptr(Literal::String("'f\u{2028}'"))),
+                    },
+                ]),
+                children: NodeList::empty(),
+                self_closing: true,
+            })
+        ]);
+
+        assert_eq!(module.body(), expected);
+        assert!(parse("a = 'b\nc'").is_err());
+    }
+
+    #[test]
+    fn rejected_by_default() {
+        assert!(parse("<div />").is_err());
+    }
+
+    #[test]
+    fn mismatched_closing_tag() {
+        let errors = parse_with("<a><b></a></b>", jsx()).unwrap_err();
+
+        assert_eq!(errors[0].code, ErrorCode::MismatchedClosingTag);
+        assert_eq!(errors[0].span, Span::new(8, 9));
+    }
+}
@@ -15,10 +15,10 @@ macro_rules! allow {
 /// Return an error if the next token doesn't match $p.
 #[macro_export]
 macro_rules! expect {
-    ($parser:ident, $p:pat) => {
+    ($parser:ident, $p:ident) => {
         match $parser.lexer.token {
             $p => $parser.lexer.consume(),
-            _  => $parser.error()
+            _  => $parser.error_expected($p)
         }
     }
 }
@@ -5,20 +5,26 @@ mod expression;
 mod statement;
 mod function;
 mod nested;
+mod jsx;
+mod types;
 
-use toolshed::list::ListBuilder;
+use std::mem;
+use std::ptr;
+use toolshed::list::{ListBuilder, GrowableList, UnsafeList};
 use toolshed::Arena;
-use error::Error;
+use error::{Error, ErrorCode};
 use module::Module;
+use span::Span;
 
 use self::error::ToError;
 use self::nested::*;
 
-use ast::{Loc, Node, Statement, NodeList, Block, BlockNode};
+use ast::{Loc, Node, Statement, StatementNode, StatementList, NodeList, Block, BlockNode};
+use ast::statement::is_strict;
 use ast::{Expression, ExpressionNode, ExpressionList, IdentifierNode};
-use ast::{OperatorKind, Pattern};
-use ast::expression::BinaryExpression;
-use lexer::{Lexer, Asi};
+use ast::{OperatorKind, Pattern, PatternList, Property};
+use ast::expression::{BinaryExpression, ArrayExpression, ObjectExpression, SpreadExpression};
+use lexer::{Lexer, LexToken, Comment, Asi, Token};
 use lexer::Token::*;
 
 pub trait Parse<'ast> {
@@ -27,37 +33,567 @@ pub trait Parse<'ast> {
     fn parse(&mut Parser<'ast>) -> Self::Output;
 }
 
+/// Default maximum nesting of expressions, statements, patterns and types,
+/// safe for the stack of a spawned thread.
+const MAX_DEPTH: u32 = 1024;
+
+/// Options enabling syntax extensions that are not a part of the language.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct ParseOptions {
+    /// Parse JSX elements and fragments, such as `<div>{child}</div>`.
+    pub jsx: bool,
+
+    /// Parse TypeScript type annotations, interfaces, type aliases, generics
+    /// and `as`/`satisfies` expressions.
+    pub typescript: bool,
+
+    /// Keep explicit parentheses in the tree as `Expression::Parenthesized`,
+    /// instead of dropping them once precedence is settled.
+    pub preserve_parens: bool,
+
+    /// Maximum nesting of expressions, statements, patterns and types,
+    /// past which parsing fails with `ErrorCode::NestingTooDeep` instead of
+    /// overflowing the stack. Defaults to 1024 levels when `None`.
+    pub max_depth: Option<u32>,
+
+    /// Allow `return` outside of functions, as in CommonJS modules, which
+    /// node wraps in a function before running them.
+    pub allow_return_outside_function: bool,
+
+    /// Collect all tokens of the source in order, comments and whitespace
+    /// aside, into `Parsed::tokens`. See `parse_collecting`.
+    pub tokens: bool,
+
+    /// Collect all comments of the source in order into `Parsed::comments`.
+    /// See `comments::find_markers` to pick out the ones marked with `TODO`.
+    pub comments: bool,
+
+    /// Collect the offsets of all tokens before which a semicolon was inserted
+    /// automatically into `Parsed::semicolons`.
+    pub semicolons: bool,
+
+    /// Collect function declarations nested in blocks into
+    /// `Parsed::block_functions`.
+    pub block_functions: bool,
+}
+
+/// A module along with everything collected while parsing it, as requested
+/// by the options given to `parse_collecting`. Collections that weren't
+/// requested are left empty.
+pub struct Parsed<'ast> {
+    pub module: Module<'ast>,
+
+    /// All tokens of the source in order, comments and whitespace aside.
+    pub tokens: Vec<LexToken>,
+
+    /// All comments of the source in order, delimiters included.
+    pub comments: Vec<Comment>,
+
+    /// Offsets of all tokens before which a semicolon was inserted
+    /// automatically, in order. Statements ending at the end of the program
+    /// are recorded with the length of the source. Code generated from the
+    /// module always has explicit semicolons, so this is only needed to tell
+    /// which ones were missing from the source.
+    pub semicolons: Vec<u32>,
+
+    /// Function declarations nested in blocks in order, along with the
+    /// strictness of the code they are in. See `BlockFunction`.
+    pub block_functions: Vec<BlockFunction>,
+}
+
+/// What the function being parsed allows, arrow functions share
+/// the context of the function they are in.
+#[derive(Debug, Default, Clone, Copy)]
+struct FunctionContext {
+    /// `new.target`, within any function
+    new_target: bool,
+
+    /// `super.a` and `super[a]`, within methods
+    super_property: bool,
+
+    /// `super()`, within constructors of derived classes
+    super_call: bool,
+
+    /// Set within the body of a class with an `extends` clause
+    derived_class: bool,
+
+    /// `yield`, within the body of generators
+    yield_expression: bool,
+
+    /// `await`, within the body of async functions
+    await_expression: bool,
+
+    /// `await` can't be a name within async functions, arrows included
+    await_reserved: bool,
+
+    /// `return`, within any function, arrows included
+    return_statement: bool,
+}
+
+impl FunctionContext {
+    #[inline]
+    fn top_level(options: ParseOptions) -> Self {
+        FunctionContext {
+            return_statement: options.allow_return_outside_function,
+            ..FunctionContext::default()
+        }
+    }
+
+    #[inline]
+    fn function() -> Self {
+        FunctionContext {
+            new_target: true,
+            return_statement: true,
+            ..FunctionContext::default()
+        }
+    }
+
+    #[inline]
+    fn method(self, constructor: bool) -> Self {
+        FunctionContext {
+            new_target: true,
+            super_property: true,
+            super_call: constructor && self.derived_class,
+            derived_class: false,
+            yield_expression: false,
+            await_expression: false,
+            await_reserved: false,
+            return_statement: true,
+        }
+    }
+}
+
+/// Function declaration nested in a block, a `switch` case or an `if`
+/// statement, rather than directly in a function body or the program, as
+/// collected with `ParseOptions::block_functions`.
+///
+/// In strict code these are scoped to their block like a `let` would be.
+/// Otherwise plain functions, neither async nor generators, are also hoisted
+/// to the enclosing function as a `var`, per Annex B of the specification.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BlockFunction {
+    /// Span of the `Statement::Function` declaring it
+    pub span: Span,
+
+    /// Whether the declaration is within strict code, either a class or
+    /// a function or program with a `"use strict"` directive. Programs
+    /// loaded as ES modules are strict regardless.
+    pub strict: bool,
+}
+
+/// Label of a statement, see `Parser::labeled_or_expression_statement`.
+struct Label<'ast> {
+    name: &'ast str,
+
+    /// Labels of all `continue` statements targeting this one, only valid
+    /// if it turns out to label a loop
+    continues: Vec<Span>,
+}
+
 pub struct Parser<'ast> {
     arena: &'ast Arena,
 
+    /// Syntax extensions enabled for this parser
+    options: ParseOptions,
+
     /// Lexer will produce tokens from the source
     lexer: Lexer<'ast>,
 
     /// Errors occurred during parsing
     errors: Vec<Error>,
 
+    /// Set when an unexpected token threw the parser off, cleared once
+    /// the parser has resynchronized at a statement boundary
+    desync: bool,
+
+    /// Current nesting level, see `Parser::nested`
+    depth: u32,
+
+    /// Nesting level past which the parser gives up
+    max_depth: u32,
+
+    /// Where `super` and `new.target` are allowed
+    context: FunctionContext,
+
+    /// Labels enclosing the statement being parsed, within the current function
+    labels: Vec<Label<'ast>>,
+
+    /// Offsets of tokens before which a semicolon was inserted automatically,
+    /// if recording. See `ParseOptions::semicolons`.
+    semicolons: Option<Vec<u32>>,
+
+    /// Nesting of blocks within the current function, function
+    /// declarations past zero are recorded as a `BlockFunction`
+    block_depth: u32,
+
+    /// Function declarations within blocks, if recording. See `ParseOptions::block_functions`.
+    block_functions: Option<Vec<BlockFunction>>,
+
+    /// Binary expressions, arrays and objects wrapped in parentheses, which
+    /// are dropped from the tree unless `preserve_parens` is set, but still
+    /// matter to what can be mixed or assigned to. See `Parser::is_parenthesized`.
+    parenthesized: Vec<ExpressionNode<'ast>>,
+
+    /// Whether `in` is kept from being read as an operator, which is the case
+    /// within the init of a `for` statement. See `Parser::allowing_in`.
+    in_excluded: bool,
+
+    /// Whether arrow functions are read without return types, which is the
+    /// case when reading the consequent of a conditional again, as in
+    /// `a ? (b) : c => d` with `typescript` set.
+    untyped_arrows: bool,
+
     /// AST under construction
     body: NodeList<'ast, Statement<'ast>>,
 }
 
 impl<'ast> Parser<'ast> {
+    #[cfg(test)]
     pub fn new(source: &str, arena: &'ast Arena) -> Self {
+        Parser::at_offset(alloc_source(arena, source), 0, arena, ParseOptions::default())
+    }
+
+    /// Create a parser that starts at byte `offset` of a source previously
+    /// allocated with `alloc_source`.
+    fn at_offset(source: &'ast str, offset: usize, arena: &'ast Arena, options: ParseOptions) -> Self {
         Parser {
             arena,
-            lexer: Lexer::new(arena, source),
+            options,
+            lexer: unsafe { Lexer::from_ptr_at(source.as_ptr(), offset) },
             errors: Vec::new(),
+            desync: false,
+            depth: 0,
+            max_depth: options.max_depth.unwrap_or(MAX_DEPTH),
+            context: FunctionContext::top_level(options),
+            labels: Vec::new(),
+            semicolons: None,
+            block_depth: 0,
+            block_functions: None,
+            parenthesized: Vec::new(),
+            in_excluded: false,
+            untyped_arrows: false,
             body: NodeList::empty(),
         }
     }
 
+    /// Create a parser allocating all trees it produces on `arena`, to be
+    /// given a source to parse with `reset`. Reusing a single parser for
+    /// many sources, such as all files in a directory, saves allocating
+    /// a new arena for each one of them.
+    pub fn with_arena(arena: &'ast Arena, options: ParseOptions) -> Self {
+        Parser::at_offset(alloc_source(arena, ""), 0, arena, options)
+    }
+
+    /// Create a parser for `source` that takes its tokens from `tokens`,
+    /// such as the ones collected by `parse_collecting` for the same source,
+    /// rather than reading them again. Tokens that don't fit the source,
+    /// being out of order or past its end, are ignored and the source is
+    /// read instead.
+    pub fn from_tokens(arena: &'ast Arena, source: &str, tokens: &[LexToken], options: ParseOptions) -> Self {
+        let source = alloc_source(arena, source);
+        let mut parser = Parser::at_offset(source, 0, arena, options);
+        let mut end = 0;
+
+        let valid = tokens.iter().all(|token| {
+            let span = token.span;
+            let fits = span.start >= end && span.start < span.end
+                && source.is_char_boundary(span.start as usize)
+                && source.is_char_boundary(span.end as usize);
+
+            end = span.end;
+            fits
+        });
+
+        if valid {
+            parser.lexer.replay_tokens(tokens.into());
+        }
+
+        parser
+    }
+
+    /// Start over on a new source, discarding all errors and state left
+    /// over from the previous one. Trees parsed earlier remain valid, as
+    /// they live on the arena along with their source.
+    pub fn reset(&mut self, source: &str) {
+        let source = alloc_source(self.arena, source);
+
+        self.lexer = unsafe { Lexer::from_ptr_at(source.as_ptr(), 0) };
+        self.errors.clear();
+        self.desync = false;
+        self.depth = 0;
+        self.context = FunctionContext::top_level(self.options);
+        self.labels.clear();
+        self.semicolons = None;
+        self.block_depth = 0;
+        self.block_functions = None;
+        self.body = NodeList::empty();
+    }
+
+    /// Parse the whole program from the current source, see `reset`.
+    pub fn parse_program(&mut self) -> Result<NodeList<'ast, Statement<'ast>>, Vec<Error>> {
+        self.parse();
+
+        match self.errors.len() {
+            0 => Ok(self.body),
+            _ => Err(self.errors.drain(..).collect()),
+        }
+    }
+
+    /// Get the kind of the token the parser will consume next, without consuming it.
+    #[inline]
+    pub fn peek_kind(&self) -> Token {
+        self.lexer.token
+    }
+
     fn error<T: ToError>(&mut self) -> T {
         let err = self.lexer.invalid_token();
 
         self.errors.push(err);
+        self.desync = true;
 
         T::to_error()
     }
 
+    /// Same as `error`, but also records the token that was expected instead.
+    fn error_expected<T: ToError>(&mut self, expected: Token) -> T {
+        let mut err = self.lexer.invalid_token();
+
+        err.expected = Some(expected);
+        self.errors.push(err);
+        self.desync = true;
+
+        T::to_error()
+    }
+
+    /// Parse with `f` one nesting level deeper. Past `max_depth` levels the
+    /// current token is rejected instead, so that no input can overflow the stack.
+    #[inline]
+    fn nested<T, F>(&mut self, f: F) -> T where
+        T: ToError,
+        F: FnOnce(&mut Self) -> T,
+    {
+        if self.depth >= self.max_depth {
+            let mut err = self.lexer.invalid_token();
+
+            err.code = ErrorCode::NestingTooDeep;
+            self.errors.push(err);
+            self.desync = true;
+
+            return T::to_error();
+        }
+
+        self.depth += 1;
+
+        let result = f(self);
+
+        self.depth -= 1;
+
+        result
+    }
+
+    /// Parse with `f` in the given function context, restoring the
+    /// current one, along with the labels in scope, afterwards.
+    #[inline]
+    fn with_context<T, F>(&mut self, context: FunctionContext, f: F) -> T where
+        F: FnOnce(&mut Self) -> T,
+    {
+        let outer = self.context;
+        let block_depth = mem::replace(&mut self.block_depth, 0);
+
+        // Labels don't reach into functions either
+        let labels = mem::take(&mut self.labels);
+
+        self.context = context;
+
+        let result = f(self);
+
+        self.context = outer;
+        self.block_depth = block_depth;
+        self.labels = labels;
+
+        result
+    }
+
+    /// Parse with `f` nested in a block, where function declarations are
+    /// recorded as a `BlockFunction`.
+    #[inline]
+    fn in_block<T, F>(&mut self, f: F) -> T where
+        F: FnOnce(&mut Self) -> T,
+    {
+        self.block_depth += 1;
+
+        let result = f(self);
+
+        self.block_depth -= 1;
+
+        result
+    }
+
+    /// Record the function declaration spanning `span` if it's within a
+    /// block, see `BlockFunction`.
+    #[inline]
+    fn block_function(&mut self, span: Span) {
+        if self.block_depth == 0 {
+            return;
+        }
+
+        if let Some(ref mut functions) = self.block_functions {
+            functions.push(BlockFunction {
+                span,
+                strict: false,
+            });
+        }
+    }
+
+    /// Count of block functions recorded so far, to be passed to `strict_since`.
+    #[inline]
+    fn recorded_block_functions(&self) -> usize {
+        self.block_functions.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Mark all block functions recorded past the first `from` as strict,
+    /// once the body they are in turns out to be strict code.
+    fn strict_since(&mut self, from: usize) {
+        if let Some(ref mut functions) = self.block_functions {
+            for function in functions.iter_mut().skip(from) {
+                function.strict = true;
+            }
+        }
+    }
+
+    /// Same as `strict_since`, if `body` opens with a `"use strict"` directive.
+    #[inline]
+    fn strict_body_since(&mut self, from: usize, body: StatementList<'ast>) {
+        if self.block_functions.is_some() && is_strict(body) {
+            self.strict_since(from);
+        }
+    }
+
+    /// Parse with `f` where `in` is an operator again, such as within brackets
+    /// inside the init of a `for` statement.
+    #[inline]
+    fn allowing_in<T, F>(&mut self, f: F) -> T where
+        F: FnOnce(&mut Self) -> T,
+    {
+        if !self.in_excluded {
+            return f(self);
+        }
+
+        self.in_excluded = false;
+
+        let result = f(self);
+
+        self.in_excluded = true;
+
+        result
+    }
+
+    /// Parse with `f` where `in` is not an operator, so that the init of
+    /// a `for` statement stops before it, `for (a in b)`.
+    #[inline]
+    fn excluding_in<T, F>(&mut self, f: F) -> T where
+        F: FnOnce(&mut Self) -> T,
+    {
+        let excluded = mem::replace(&mut self.in_excluded, true);
+
+        let result = f(self);
+
+        self.in_excluded = excluded;
+
+        result
+    }
+
+    /// Check whether a binary expression, an array or an object was wrapped
+    /// in parentheses. Always `false` for anything else.
+    #[inline]
+    fn is_parenthesized(&self, expression: ExpressionNode<'ast>) -> bool {
+        match expression.item {
+            Expression::Parenthesized(_) => true,
+            _ => self.parenthesized.iter().rev().any(|parenthesized| ptr::eq(&**parenthesized, &*expression)),
+        }
+    }
+
+    /// Record an error with a specific code spanning `span`. Unlike `error`,
+    /// this doesn't consume the current token, so parsing can carry on as normal.
+    fn error_at<T: ToError>(&mut self, code: ErrorCode, span: Span) -> T {
+        let err = self.lexer.error_at(code, span);
+
+        self.errors.push(err);
+
+        T::to_error()
+    }
+
+    /// Report a `DuplicateParam` error for every name that is bound more than
+    /// once within the parameter list.
+    fn unique_params(&mut self, params: PatternList<'ast>) {
+        let mut names = Vec::new();
+
+        for param in params.iter() {
+            collect_bound_names(param, &mut names);
+        }
+
+        for (index, &(name, span)) in names.iter().enumerate() {
+            if names[..index].iter().any(|&(other, _)| other == name) {
+                self.error_at::<()>(ErrorCode::DuplicateParam, span);
+            }
+        }
+    }
+
+    /// Attempt to parse with `f`, rewinding the lexer if it returns `None`
+    /// or runs into an error, in which case nothing is reported.
+    fn speculate<T, F>(&mut self, f: F) -> Option<T> where
+        F: FnOnce(&mut Self) -> Option<T>,
+    {
+        let lexer = self.lexer.clone();
+        let errors = self.errors.len();
+        let desync = self.desync;
+        let semicolons = self.semicolons.as_ref().map(Vec::len);
+        let block_functions = self.block_functions.as_ref().map(Vec::len);
+
+        match f(self) {
+            Some(result) if self.errors.len() == errors => Some(result),
+            _ => {
+                self.lexer = lexer;
+                self.errors.truncate(errors);
+                self.desync = desync;
+
+                if let (Some(recorded), Some(len)) = (self.semicolons.as_mut(), semicolons) {
+                    recorded.truncate(len);
+                }
+
+                if let (Some(recorded), Some(len)) = (self.block_functions.as_mut(), block_functions) {
+                    recorded.truncate(len);
+                }
+
+                None
+            }
+        }
+    }
+
+    /// Report a `RequiredParamAfterOptional` error for every parameter without
+    /// a default value that follows a parameter marked optional with `?`.
+    /// Parameters with defaults may come in any order.
+    fn ordered_params(&mut self, params: PatternList<'ast>) {
+        let mut optional = false;
+
+        for param in params.iter() {
+            let pattern = match param.item {
+                Pattern::Annotated { optional: true, .. } => {
+                    optional = true;
+                    continue;
+                },
+                Pattern::Annotated { pattern, .. } => pattern.item,
+                pattern => pattern,
+            };
+
+            match pattern {
+                Pattern::AssignmentPattern { .. } | Pattern::RestElement { .. } => {},
+                _ if optional => self.error_at::<()>(ErrorCode::RequiredParamAfterOptional, param.span()),
+                _ => {},
+            }
+        }
+    }
+
     #[inline]
     fn asi(&mut self) -> Asi {
         self.lexer.asi()
@@ -105,16 +641,49 @@ impl<'ast> Parser<'ast> {
             return;
         }
 
-        let statement = self.statement();
+        let statement = self.recoverable_statement();
         let builder = ListBuilder::new(self.arena, statement);
 
         while self.lexer.token != EndOfProgram {
-            builder.push(self.arena, self.statement());
+            builder.push(self.arena, self.recoverable_statement());
         }
 
         self.body = builder.as_list()
     }
 
+    /// Parse a statement, resynchronizing the lexer at the next statement
+    /// boundary if an unexpected token occurred while parsing it.
+    #[inline]
+    fn recoverable_statement(&mut self) -> Node<'ast, Statement<'ast>> {
+        let statement = self.statement();
+
+        if self.desync {
+            self.synchronize();
+        }
+
+        statement
+    }
+
+    /// Skip tokens until a likely statement boundary: past a `;`, or up to
+    /// a `}` or a keyword that starts a statement.
+    fn synchronize(&mut self) {
+        self.desync = false;
+
+        loop {
+            match self.lexer.token {
+                Semicolon => {
+                    self.lexer.consume();
+                    return;
+                },
+                EndOfProgram | BraceClose | DeclarationVar | DeclarationLet |
+                DeclarationConst | Function | Class | If | For | While | Do |
+                Return | Try | Switch | Throw | Break | Continue | Import |
+                Export => return,
+                _ => self.lexer.consume(),
+            }
+        }
+    }
+
     #[inline]
     fn block<I>(&mut self) -> BlockNode<'ast, I> where
         I: Parse<'ast, Output = Node<'ast, I>> + Copy
@@ -127,7 +696,7 @@ impl<'ast> Parser<'ast> {
         }
 
         let block = self.raw_block();
-        let end   = self.lexer.end_then_consume();
+        let end   = self.close_block();
 
         self.alloc_at_loc(start, end, block)
     }
@@ -139,7 +708,7 @@ impl<'ast> Parser<'ast> {
     {
         let start = self.lexer.start_then_consume();
         let block = self.raw_block();
-        let end   = self.lexer.end_then_consume();
+        let end   = self.close_block();
 
         self.alloc_at_loc(start, end, block)
     }
@@ -162,6 +731,23 @@ impl<'ast> Parser<'ast> {
         Block { body: builder.as_list() }
     }
 
+    /// Consume the `}` closing a block, returning its end. A program ending
+    /// before the block is closed is reported, the block is then closed at
+    /// the end of the program so that the statements within it are kept.
+    #[inline]
+    fn close_block(&mut self) -> u32 {
+        match self.lexer.token {
+            EndOfProgram => {
+                let end = self.lexer.end();
+
+                self.error_expected::<()>(BraceClose);
+
+                end
+            },
+            _ => self.lexer.end_then_consume(),
+        }
+    }
+
     #[inline]
     fn identifier(&mut self) -> IdentifierNode<'ast> {
         match self.lexer.token {
@@ -191,6 +777,19 @@ impl<'ast> Parser<'ast> {
             Expression::Identifier(ident) => {
                 Pattern::Identifier(ident)
             },
+            Expression::Array(ArrayExpression { body }) => {
+                Pattern::ArrayPattern {
+                    elements: self.elements_from_expressions(body)
+                }
+            },
+            Expression::Object(ObjectExpression { body }) => {
+                self.rest_last(body);
+
+                Pattern::ObjectPattern {
+                    properties: body
+                }
+            },
+            Expression::Void => Pattern::Void,
             _ => self.error()
         };
 
@@ -216,26 +815,388 @@ impl<'ast> Parser<'ast> {
 
         builder.as_list()
     }
+
+    /// Array literals keep a trailing comma as a hole at the end of their
+    /// body, which isn't an element of the array pattern made from them.
+    fn elements_from_expressions(&mut self, expressions: ExpressionList<'ast>) -> NodeList<'ast, Pattern<'ast>> {
+        let mut count = expressions.iter().count();
+
+        if let Some(&Expression::Void) = expressions.iter().last().map(|node| &node.item) {
+            count -= 1;
+        }
+
+        let mut expressions = expressions.iter().take(count);
+
+        let builder = match expressions.next() {
+            Some(&expression) => {
+                let element = self.pattern_from_expression(expression);
+
+                ListBuilder::new(self.arena, element)
+            },
+            None => return NodeList::empty()
+        };
+
+        for &expression in expressions {
+            builder.push(self.arena, self.pattern_from_expression(expression));
+        }
+
+        builder.as_list()
+    }
+
+    /// Check the elements of an array or object literal being assigned to,
+    /// such as `[a.b, c] = arr`. Each must be an assignment target itself.
+    fn validate_destructuring(&mut self, target: ExpressionNode<'ast>) {
+        match target.item {
+            Expression::Array(ArrayExpression { body }) => {
+                let mut elements = body.iter().peekable();
+
+                while let Some(&element) = elements.next() {
+                    // A trailing comma is kept as a hole, so `[...a,] = b`
+                    // is rejected here as well
+                    if let Expression::Spread(_) = element.item {
+                        if elements.peek().is_some() {
+                            self.error_at::<()>(ErrorCode::RestElementNotLast, element.span());
+                        }
+                    }
+
+                    self.validate_destructuring_element(element);
+                }
+            },
+            Expression::Object(ObjectExpression { body }) => {
+                self.rest_last(body);
+
+                for property in body.iter() {
+                    match property.item {
+                        Property::Shorthand(_) => {},
+                        Property::Literal { value, .. } => self.validate_destructuring_element(value),
+                        Property::Spread { argument } => self.validate_destructuring_element(argument),
+                        Property::Method { .. } => self.error_at(ErrorCode::InvalidAssignmentTarget, property.span()),
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    /// Report a `RestElementNotLast` error for every rest element of an
+    /// object pattern that has properties following it.
+    fn rest_last(&mut self, properties: NodeList<'ast, Property<'ast>>) {
+        let mut properties = properties.iter().peekable();
+
+        while let Some(property) = properties.next() {
+            if let Property::Spread { .. } = property.item {
+                if properties.peek().is_some() {
+                    self.error_at::<()>(ErrorCode::RestElementNotLast, property.span());
+                }
+            }
+        }
+    }
+
+    fn validate_destructuring_element(&mut self, element: ExpressionNode<'ast>) {
+        match element.item {
+            // Holes, as in `[, a] = arr`
+            Expression::Void => {},
+            // `[(a)] = b` is fine, but `[(a = 1)] = b` and `[([a])] = b` are not
+            Expression::Binary(_) |
+            Expression::Array(_)  |
+            Expression::Object(_) if self.is_parenthesized(element) => {
+                self.error_at(ErrorCode::InvalidAssignmentTarget, element.span())
+            },
+            Expression::Binary(BinaryExpression {
+                operator: OperatorKind::Assign,
+                left,
+                ..
+            }) => self.validate_destructuring_element(left),
+            Expression::Spread(SpreadExpression { argument }) => self.validate_destructuring_element(argument),
+            Expression::Array(_) |
+            Expression::Object(_) => self.validate_destructuring(element),
+            _ if element.is_lvalue() => {},
+            _ => self.error_at(ErrorCode::InvalidAssignmentTarget, element.span()),
+        }
+    }
+}
+
+/// Collect all names bound by a pattern, along with their positions.
+fn collect_bound_names<'ast>(pattern: &Node<'ast, Pattern<'ast>>, names: &mut Vec<(&'ast str, Span)>) {
+    match pattern.item {
+        Pattern::Identifier(name) => names.push((name, pattern.span())),
+        Pattern::RestElement { argument } => names.push((argument.item, argument.span())),
+        Pattern::AssignmentPattern { left, .. } => collect_bound_names(&left, names),
+        Pattern::Annotated { pattern, .. } => collect_bound_names(&pattern, names),
+        Pattern::ArrayPattern { elements } => {
+            for element in elements.iter() {
+                collect_bound_names(element, names);
+            }
+        },
+        Pattern::ObjectPattern { properties } => {
+            for property in properties.iter() {
+                match property.item {
+                    Property::Shorthand(name) => names.push((name, property.span())),
+                    Property::Spread { argument } => if let Expression::Identifier(name) = argument.item {
+                        names.push((name, argument.span()));
+                    },
+                    Property::Literal { value, .. } => match value.item {
+                        Expression::Identifier(name) => names.push((name, value.span())),
+                        Expression::Binary(BinaryExpression {
+                            operator: OperatorKind::Assign,
+                            left,
+                            ..
+                        }) => if let Expression::Identifier(name) = left.item {
+                            names.push((name, left.span()));
+                        },
+                        _ => {}
+                    },
+                    _ => {}
+                }
+            }
+        },
+        Pattern::Void => {},
+    }
+}
+
+/// Copy the source onto the arena, terminated with a 0 byte as the `Lexer` requires.
+fn alloc_source<'ast>(arena: &'ast Arena, source: &str) -> &'ast str {
+    use std::slice::from_raw_parts;
+    use std::str::from_utf8_unchecked;
+
+    unsafe { from_utf8_unchecked(from_raw_parts(arena.alloc_str_with_nul(source), source.len())) }
 }
 
 /// Parse the JavaScript source `&str` and produce an Abstract Syntax Tree `Module`.
+///
+/// Malformed source of any kind is reported as errors, and never panics or
+/// overflows the stack, making this safe to call with arbitrary input.
 pub fn parse<'src, 'ast>(source: &'src str) -> Result<Module<'ast>, Vec<Error>> {
+    parse_with(source, ParseOptions::default())
+}
+
+/// Same as `parse`, with syntax extensions enabled by `options`.
+pub fn parse_with<'ast>(source: &str, options: ParseOptions) -> Result<Module<'ast>, Vec<Error>> {
+    let (module, errors) = parse_recovering(source, options);
+
+    match errors.len() {
+        0 => Ok(module),
+        _ => Err(errors)
+    }
+}
+
+/// Same as `parse_with`, but doesn't give up on malformed source, which is
+/// the usual state of source being typed in an editor. Along with all errors
+/// a best-effort tree is returned: blocks left open at the end of the program
+/// are closed there, and statements that failed to parse are skipped up to
+/// the next statement boundary.
+pub fn parse_recovering<'ast>(source: &str, options: ParseOptions) -> (Module<'ast>, Vec<Error>) {
+    let (parsed, errors) = collect(source, options);
+
+    (parsed.module, errors)
+}
+
+/// Same as `parse_with`, but also returns whatever `options` ask to be
+/// collected in the same pass, such as tokens or comments, which can be
+/// combined with each other and with any of the syntax extensions.
+pub fn parse_collecting<'ast>(source: &str, options: ParseOptions) -> Result<Parsed<'ast>, Vec<Error>> {
+    let (parsed, errors) = collect(source, options);
+
+    match errors.len() {
+        0 => Ok(parsed),
+        _ => Err(errors)
+    }
+}
+
+fn collect<'ast>(source: &str, options: ParseOptions) -> (Parsed<'ast>, Vec<Error>) {
     let arena = Arena::new();
 
-    let (body, errors) = {
-        let mut parser = Parser::new(source, &arena);
+    let (body, source, errors, tokens, comments, semicolons, block_functions) = {
+        let source = alloc_source(&arena, source);
+        let mut parser = Parser::at_offset(source, 0, &arena, options);
+
+        // Reading comments goes back to the start of the source, so it has
+        // to come before tokens are recorded
+        if options.comments {
+            parser.lexer.record_comments_from(0);
+        }
+        if options.tokens {
+            parser.lexer.record_tokens();
+        }
+        if options.semicolons {
+            parser.semicolons = Some(Vec::new());
+        }
+        if options.block_functions {
+            parser.block_functions = Some(Vec::new());
+        }
 
         parser.parse();
 
-        (parser.body.into_unsafe(), parser.errors)
+        let body = parser.body;
+
+        parser.strict_body_since(0, body);
+
+        // Nested functions are recorded before the ones they are in
+        let mut block_functions = parser.block_functions.take().unwrap_or_default();
+
+        block_functions.sort_by_key(|function| function.span.start);
+
+        let tokens = parser.lexer.take_tokens();
+        let comments = parser.lexer.take_comments();
+        let semicolons = parser.semicolons.take().unwrap_or_default();
+
+        (parser.body.into_unsafe(), source as *const str, parser.errors, tokens, comments, semicolons, block_functions)
     };
 
-    match errors.len() {
-        0 => Ok(Module::new(body, arena)),
-        _ => Err(errors)
+    let parsed = Parsed {
+        module: Module::new(body, source, options, arena),
+        tokens,
+        comments,
+        semicolons,
+        block_functions,
+    };
+
+    (parsed, errors)
+}
+
+/// Iterator parsing one top-level statement at a time. See `Module::statements`.
+pub struct Statements<'ast> {
+    parser: Parser<'ast>,
+}
+
+impl<'ast> Statements<'ast> {
+    pub(crate) fn new(arena: &'ast Arena, source: &str, options: ParseOptions) -> Self {
+        Statements {
+            parser: Parser::at_offset(alloc_source(arena, source), 0, arena, options),
+        }
+    }
+
+    /// Get the kind of the token starting the next statement, `EndOfProgram`
+    /// once all statements were parsed.
+    #[inline]
+    pub fn peek_kind(&self) -> Token {
+        self.parser.peek_kind()
+    }
+
+    /// Errors encountered in the statements parsed so far.
+    #[inline]
+    pub fn errors(&self) -> &[Error] {
+        &self.parser.errors
+    }
+
+    /// Get a reference to the `Arena` on which the statements are allocated.
+    #[inline]
+    pub fn arena(&self) -> &'ast Arena {
+        self.parser.arena
     }
 }
 
+impl<'ast> Iterator for Statements<'ast> {
+    type Item = StatementNode<'ast>;
+
+    #[inline]
+    fn next(&mut self) -> Option<StatementNode<'ast>> {
+        match self.peek_kind() {
+            EndOfProgram => None,
+            _            => Some(self.parser.recoverable_statement()),
+        }
+    }
+}
+
+/// Replace the `edit` range of `source` with `text`.
+pub(crate) fn apply_edit(source: &str, edit: Span, text: &str) -> String {
+    let (edit_start, edit_end) = (edit.start as usize, edit.end as usize);
+    let mut changed = String::with_capacity(source.len() - (edit_end - edit_start) + text.len());
+
+    changed.push_str(&source[..edit_start]);
+    changed.push_str(text);
+    changed.push_str(&source[edit_end..]);
+
+    changed
+}
+
+/// Replace the `edit` range of `source` with `text`, and parse again only
+/// the statements of `body` affected by it. The whole changed source is
+/// copied onto `arena`, see `Module::reparse`.
+pub(crate) fn reparse(
+    arena: &Arena,
+    body: UnsafeList,
+    source: &str,
+    options: ParseOptions,
+    edit: Span,
+    text: &str,
+) -> Result<(UnsafeList, *const str), Vec<Error>> {
+    let old: Vec<StatementNode> = unsafe { body.into_list::<StatementNode>() }.iter().cloned().collect();
+    let (edit_start, edit_end) = (edit.start as usize, edit.end as usize);
+
+    let changed = alloc_source(arena, &apply_edit(source, edit, text));
+
+    // Statements ending before the edit are kept, unless the edited text
+    // could continue the last of them (`foo` followed by `.bar`).
+    let mut first = old.iter().take_while(|statement| statement.end < edit.start).count();
+
+    let start = match first.checked_sub(1).map(|index| old[index]) {
+        None => 0,
+        Some(previous) => match previous.item {
+            Statement::Function(_) |
+            Statement::Class(_)    |
+            Statement::Block(_)    |
+            Statement::Empty       => previous.end as usize,
+            _ => {
+                let gap = source[previous.end as usize..edit_start].trim_start();
+
+                if gap.starts_with(';') {
+                    edit_start - gap.len() + 1
+                } else {
+                    first -= 1;
+                    previous.start as usize
+                }
+            }
+        }
+    };
+
+    // Statements starting after the edit are kept once the parser lands
+    // exactly on one of them, which requires their positions to be unchanged.
+    let same_length = text.len() == edit_end - edit_start;
+    let mut next = old.iter().position(|statement| statement.start > edit.end).unwrap_or(old.len());
+
+    let (reparsed, errors) = {
+        let mut parser = Parser::at_offset(changed, start, arena, options);
+        let reparsed = GrowableList::new();
+
+        while parser.lexer.token != EndOfProgram {
+            if same_length {
+                let position = parser.lexer.start();
+
+                while next < old.len() && old[next].start < position {
+                    next += 1;
+                }
+
+                if next < old.len() && old[next].start == position {
+                    break;
+                }
+            }
+
+            reparsed.push(arena, parser.recoverable_statement());
+        }
+
+        if parser.lexer.token == EndOfProgram {
+            next = old.len();
+        }
+
+        (reparsed.as_list(), parser.errors)
+    };
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let body = GrowableList::new();
+
+    for &statement in old[..first].iter().chain(reparsed.iter()).chain(old[next..].iter()) {
+        body.push(arena, statement);
+    }
+
+    let body: StatementList = body.as_list();
+
+    Ok((body.into_unsafe(), changed as *const str))
+}
+
 #[cfg(test)]
 mod mock {
     use super::*;
@@ -297,12 +1258,194 @@ mod mock {
 mod test {
     use super::*;
     use parser::mock::Mock;
+    use ast::expression::CallExpression;
+    use ast::statement::IfStatement;
 
     #[test]
     fn empty_parse() {
         assert_eq!(parse("").unwrap().body(), NodeList::empty());
     }
 
+    #[test]
+    fn tokens_along_with_ast() {
+        use lexer::Token::*;
+
+        let source = "let a = `x${b}y` / 2; // c\nc.d(/re/g) /* e */ >= f";
+        let Parsed { module, tokens, .. } = parse_collecting(source, ParseOptions { tokens: true, ..ParseOptions::default() }).unwrap();
+
+        let expected = parse(source).unwrap();
+
+        assert_eq!(module.body(), expected.body());
+        assert_eq!(tokens.len(), 16);
+
+        let kinds: Vec<Token> = tokens.iter().map(|token| token.token).collect();
+
+        assert_eq!(kinds, [
+            DeclarationLet, Identifier, OperatorAssign, TemplateOpen, Identifier,
+            TemplateClosed, OperatorDivision, LiteralNumber, Semicolon, Identifier,
+            Accessor, ParenOpen, LiteralRegEx, ParenClose, OperatorGreaterEquals, Identifier,
+        ]);
+
+        let slices: Vec<&str> = tokens.iter().map(|token| &source[token.span.start as usize..token.span.end as usize]).collect();
+
+        assert_eq!(slices, [
+            "let", "a", "=", "`x${", "b", "}y`", "/", "2", ";", "c",
+            ".d", "(", "/re/g", ")", ">=", "f",
+        ]);
+    }
+
+    #[test]
+    fn parse_from_tokens() {
+        let sources = [
+            "let a = `x${b}y` / 2; // c\nc.d(/re/g) /* e */ >= f",
+            "a\n++b\nfunction f() { return\n1 } /=/.test(x) ? { a, b: [c] } : 0x1f",
+            "class A extends B { get x() { return super.x?.[0] } } label: for (;;) { break label }",
+        ];
+
+        for source in sources.iter() {
+            let tokens = parse_collecting(source, ParseOptions { tokens: true, ..ParseOptions::default() }).unwrap().tokens;
+            let arena = Arena::new();
+            let mut parser = Parser::from_tokens(&arena, source, &tokens, ParseOptions::default());
+
+            assert_eq!(parser.parse_program().unwrap(), parse(source).unwrap().body());
+        }
+
+        let arena = Arena::new();
+        let tokens = parse_collecting("foo(bar);", ParseOptions { tokens: true, ..ParseOptions::default() }).unwrap().tokens;
+        let mut parser = Parser::from_tokens(&arena, "foo;", &tokens, ParseOptions::default());
+
+        assert_eq!(parser.parse_program().unwrap(), parse("foo;").unwrap().body());
+    }
+
+    #[test]
+    fn semicolons_inserted_automatically() {
+        let source = "let a = 1\nfoo()\nfunction f() { return\nb }\nthrow c;d";
+        let options = ParseOptions { semicolons: true, ..ParseOptions::default() };
+        let Parsed { module, semicolons, .. } = parse_collecting(source, options).unwrap();
+
+        assert_eq!(module.body(), parse(source).unwrap().body());
+        assert_eq!(semicolons, [10, 16, 38, 40, 51]);
+
+        assert!(parse_collecting("a;\nb;", options).unwrap().semicolons.is_empty());
+    }
+
+    #[test]
+    fn collecting_combined_with_extensions() {
+        let source = "// a\nx = <b />\n{ function f() {} }";
+        let options = ParseOptions {
+            jsx: true,
+            tokens: true,
+            comments: true,
+            semicolons: true,
+            block_functions: true,
+            ..ParseOptions::default()
+        };
+        let parsed = parse_collecting(source, options).unwrap();
+
+        assert_eq!(parsed.module.body(), parse_with(source, ParseOptions { jsx: true, ..ParseOptions::default() }).unwrap().body());
+        assert_eq!(parsed.tokens.first().map(|token| token.span), Some(Span::new(5, 6)));
+        assert_eq!(parsed.comments.len(), 1);
+        assert_eq!(parsed.semicolons, [15]);
+        assert_eq!(parsed.block_functions.len(), 1);
+
+        let parsed = parse_collecting(source, ParseOptions { jsx: true, ..ParseOptions::default() }).unwrap();
+
+        assert!(parsed.tokens.is_empty());
+        assert!(parsed.comments.is_empty());
+        assert!(parsed.semicolons.is_empty());
+        assert!(parsed.block_functions.is_empty());
+    }
+
+    #[test]
+    fn reused_parser() {
+        let arena = Arena::new();
+        let mock = Mock::new();
+        let mut parser = Parser::with_arena(&arena, ParseOptions::default());
+
+        parser.reset("foo;");
+        let first = parser.parse_program().unwrap();
+
+        parser.reset("let = ;");
+        assert!(parser.parse_program().is_err());
+
+        parser.reset("bar(); baz");
+        let second = parser.parse_program().unwrap();
+
+        parser.reset("");
+        let empty = parser.parse_program().unwrap();
+
+        assert_eq!(first, mock.list([mock.ptr("foo")]));
+        assert_eq!(second, mock.list([
+            mock.ptr(CallExpression {
+                callee: mock.ptr("bar"),
+                arguments: NodeList::empty(),
+            }),
+            mock.ptr("baz"),
+        ]));
+        assert_eq!(empty, NodeList::empty());
+    }
+
+    #[test]
+    fn recovering_unclosed_function_body() {
+        let source = "foo();\nfunction bar(a) {\n  let b = a;\n  b++;";
+        let (module, errors) = parse_recovering(source, ParseOptions::default());
+        let body: Vec<_> = module.body().iter().cloned().collect();
+
+        assert_eq!(body.len(), 2);
+
+        match body[1].item {
+            Statement::Function(function) => {
+                assert_eq!(function.body.body.iter().count(), 2);
+                assert_eq!(function.body.end, source.len() as u32);
+            },
+            _ => panic!("Expected a function, got {:?}", body[1]),
+        }
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, ErrorCode::UnexpectedEndOfProgram);
+        assert_eq!(errors[0].expected, Some(BraceClose));
+
+        assert!(parse(source).is_err());
+    }
+
+    #[test]
+    fn recovering_unterminated_block() {
+        let source = "let a = 1; { a = 2; if (a) { a++";
+        let (module, errors) = parse_recovering(source, ParseOptions::default());
+        let body: Vec<_> = module.body().iter().cloned().collect();
+
+        assert_eq!(body.len(), 2);
+
+        match body[1].item {
+            Statement::Block(block) => {
+                let inner: Vec<_> = block.body.iter().cloned().collect();
+
+                assert_eq!(inner.len(), 2);
+
+                match inner[1].item {
+                    Statement::If(IfStatement { consequent, .. }) => match consequent.item {
+                        Statement::Block(block) => assert_eq!(block.body.iter().count(), 1),
+                        _ => panic!("Expected a block, got {:?}", consequent),
+                    },
+                    _ => panic!("Expected an if statement, got {:?}", inner[1]),
+                }
+            },
+            _ => panic!("Expected a block, got {:?}", body[1]),
+        }
+
+        // One for each of the blocks left open
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|error| error.code == ErrorCode::UnexpectedEndOfProgram));
+    }
+
+    #[test]
+    fn recovering_valid_source() {
+        let (module, errors) = parse_recovering("foo; { bar }", ParseOptions::default());
+
+        assert!(errors.is_empty());
+        assert_eq!(module.body(), parse("foo; { bar }").unwrap().body());
+    }
+
     #[test]
     fn empty_statements() {
         let mock = Mock::new();
@@ -315,4 +1458,229 @@ mod test {
 
         assert_eq!(parse(";;;").unwrap().body(), expected);
     }
+
+    #[test]
+    fn crlf_line_endings() {
+        let source = "let a = 1\r\n// comment\r\nlet b = a /* block\r\ncomment */\r\n++b\r\nfoo(`x\r\n${a}`, 'y\\\r\nz')\r\nc = a\r\n/* one */ bar()\r\n";
+        let module = parse(source).unwrap();
+        let body: Vec<_> = module.body().iter().cloned().collect();
+
+        let lf = parse(&source.replace("\r\n", "\n")).unwrap();
+        let lf: Vec<_> = lf.body().iter().cloned().collect();
+
+        assert_eq!(body.len(), 6);
+        assert_eq!(lf.len(), 6);
+
+        // Template and string contents keep their line breaks as written
+        for (index, (crlf, lf)) in body.iter().zip(lf.iter()).enumerate() {
+            if index != 3 {
+                assert_eq!(crlf, lf);
+            }
+        }
+
+        match body[3].item {
+            Statement::Expression(expression) => match expression.item {
+                Expression::Call(call) => assert_eq!(call.arguments.iter().count(), 2),
+                _ => panic!("Expected a call, got {:?}", expression),
+            },
+            _ => panic!("Expected an expression, got {:?}", body[3]),
+        }
+
+        assert_eq!(module.line_column(body[2].start), (5, 1));
+        assert_eq!(module.line_column(body[4].start), (9, 1));
+        assert_eq!(module.line_column(body[5].start), (10, 11));
+    }
+
+    #[test]
+    fn lone_carriage_returns() {
+        assert_eq!(parse("a\rb").unwrap().body().iter().count(), 2);
+        assert_eq!(parse("a // c\rb").unwrap().body().iter().count(), 2);
+        assert_eq!(parse("a /* c\r */ b").unwrap().body().iter().count(), 2);
+        assert_eq!(parse("a\n/* c */ b").unwrap().body().iter().count(), 2);
+        assert!(parse("a /* c */ b").is_err());
+    }
+
+    #[test]
+    fn statements_are_parsed_lazily() {
+        // Any statement past the first two would report an error
+        let source = format!("foo; bar;{}", " baz +;".repeat(10000));
+        let arena = Arena::new();
+        let mut statements = Statements::new(&arena, &source, ParseOptions::default());
+
+        let expected = parse("foo; bar;").unwrap();
+        let first: Vec<_> = statements.by_ref().take(2).collect();
+
+        assert_eq!(first, expected.body().iter().cloned().collect::<Vec<_>>());
+        assert!(statements.errors().is_empty());
+
+        assert!(statements.next().is_some());
+        assert!(!statements.errors().is_empty());
+    }
+
+    #[test]
+    fn statements_end_with_the_source() {
+        let arena = Arena::new();
+        let statements = Statements::new(&arena, "foo; bar; baz", ParseOptions::default());
+
+        assert_eq!(statements.count(), 3);
+        assert_eq!(Statements::new(&arena, "", ParseOptions::default()).count(), 0);
+    }
+
+    #[test]
+    fn statements_outlive_the_iterator() {
+        let arena = Arena::new();
+        let first = {
+            let mut statements = Module::statements(&arena, "foo; bar;", ParseOptions::default());
+
+            statements.next().unwrap()
+        };
+
+        assert_eq!(Some(&first), parse("foo;").unwrap().body().iter().next());
+    }
+
+    #[test]
+    fn peek_kind_does_not_consume() {
+        let arena = Arena::new();
+        let mut parser = Parser::new("foo + 1", &arena);
+
+        assert_eq!(parser.peek_kind(), Identifier);
+        assert_eq!(parser.peek_kind(), Identifier);
+
+        parser.lexer.consume();
+
+        assert_eq!(parser.peek_kind(), OperatorAddition);
+    }
+
+    #[test]
+    fn peek_kind_between_statements() {
+        let arena = Arena::new();
+        let mut statements = Statements::new(&arena, "let a; if (a) {}", ParseOptions::default());
+
+        assert_eq!(statements.peek_kind(), DeclarationLet);
+        assert_eq!(statements.peek_kind(), DeclarationLet);
+
+        match statements.next().unwrap().item {
+            Statement::Declaration(_) => {},
+            _ => panic!("Expected a declaration"),
+        }
+
+        assert_eq!(statements.peek_kind(), If);
+
+        match statements.next().unwrap().item {
+            Statement::If(_) => {},
+            _ => panic!("Expected an if statement"),
+        }
+
+        assert_eq!(statements.peek_kind(), EndOfProgram);
+    }
+
+    #[test]
+    fn synchronize_after_error() {
+        let arena = Arena::new();
+        let mut parser = Parser::new("foo; bar baz qux; quux;", &arena);
+        let mock = Mock::new();
+
+        parser.parse();
+
+        let expected = mock.list([
+            mock.ptr("foo"),
+            mock.ptr("bar"),
+            mock.ptr("quux"),
+        ]);
+
+        assert_eq!(parser.errors.len(), 1);
+        assert_eq!(parser.body, expected);
+    }
+
+    #[test]
+    fn synchronize_at_statement_keyword() {
+        let arena = Arena::new();
+        let mut parser = Parser::new("foo bar baz\nvar a; quux;", &arena);
+
+        parser.parse();
+
+        assert_eq!(parser.errors.len(), 1);
+        assert_eq!(parser.body.iter().count(), 3);
+    }
+
+    #[test]
+    fn deep_nesting() {
+        let sources = [
+            "(".repeat(100000),
+            "[".repeat(100000),
+            "{".repeat(100000),
+            "if (a) ".repeat(100000),
+            "a = ".repeat(100000),
+            format!("let {}", "[".repeat(100000)),
+        ];
+
+        for source in sources.iter() {
+            let errors = parse(source).unwrap_err();
+
+            assert!(errors.iter().any(|err| err.code == ErrorCode::NestingTooDeep));
+        }
+    }
+
+    #[test]
+    fn configured_max_depth() {
+        let options = ParseOptions { max_depth: Some(16), ..ParseOptions::default() };
+        let deep = format!("{}a{}", "[".repeat(20), "]".repeat(20));
+        let shallow = format!("{}a{}", "[".repeat(4), "]".repeat(4));
+
+        let errors = parse_with(&deep, options).unwrap_err();
+
+        assert_eq!(errors[0].code, ErrorCode::NestingTooDeep);
+        assert!(parse_with(&shallow, options).is_ok());
+        assert!(parse(&deep).is_ok());
+    }
+
+    #[test]
+    fn shallow_nesting() {
+        let source = format!("{}a{}", "(".repeat(200), ")".repeat(200));
+
+        assert!(parse(&source).is_ok());
+    }
+
+    #[test]
+    fn arbitrary_input_does_not_panic() {
+        const PIECES: &[&str] = &[
+            "(", ")", "{", "}", "[", "]", "`", "${", "/", "'", "<", ">", "/>",
+            "=>", "?.", "...", ".", ",", ";", ":", "?", "=", "+", "\\", "\n",
+            "a", "1", "1e", "0x", "function", "class", "let", "switch", "case",
+            "import", "export", "async", "type", "as", "é", "—", "🎉",
+        ];
+
+        let options = [
+            ParseOptions::default(),
+            ParseOptions { jsx: true, ..ParseOptions::default() },
+            ParseOptions { typescript: true, preserve_parens: true, ..ParseOptions::default() },
+        ];
+
+        // xorshift, so that failures can be reproduced
+        let mut seed = 0x2545F4914F6CDD1Du64;
+        let mut random = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed as usize
+        };
+
+        for _ in 0..2000 {
+            let len = random() % 24;
+            let source: String = (0..len).map(|_| PIECES[random() % PIECES.len()]).collect();
+
+            for options in options.iter() {
+                let _ = parse_with(&source, *options);
+            }
+        }
+
+        // Accessors at the end of the source, with nothing left to read
+        for source in ["a.", ".", "a. ", "a.\n", "a?."].iter() {
+            for options in options.iter() {
+                let errors = parse_with(source, *options).unwrap_err();
+
+                assert_eq!(errors[0].code, ErrorCode::UnexpectedEndOfProgram, "{:?}", source);
+            }
+        }
+    }
 }
@@ -2,12 +2,14 @@ use toolshed::list::ListBuilder;
 use parser::Parser;
 use lexer::Token;
 use lexer::Token::*;
-use ast::{NodeList, Expression, ExpressionNode};
+use ast::{Node, NodeList, Expression, ExpressionNode, OperatorKind};
 use ast::expression::*;
 use ast::OperatorKind::*;
 use lexer::Asi;
+use error::ErrorCode;
+use std::mem;
 
-const TOTAL_TOKENS: usize = 108;
+const TOTAL_TOKENS: usize = 113;
 
 type NestedHandler = Option<for<'ast> fn(&mut Parser<'ast>, ExpressionNode<'ast>) -> ExpressionNode<'ast>>;
 
@@ -66,6 +68,9 @@ bp!(ANY, [
 
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
 //  IMPL  PCKG  PROT  IFACE PRIV  PUBLI IDENT ACCSS TPL_O TPL_C ERR_T ERR_E
+
+    OPTC, NLSH, NLSA, LORA, LANA,
+//  ?.    ??    ??=   ||=   &&=
 ]);
 
 bp!(B0, [
@@ -78,6 +83,7 @@ bp!(B0, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    OPTC, NLSH, NLSA, LORA, LANA,
 ]);
 
 bp!(B1, [
@@ -90,6 +96,7 @@ bp!(B1, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    OPTC, NLSH, NLSA, LORA, LANA,
 ]);
 
 bp!(B5, [
@@ -102,6 +109,7 @@ bp!(B5, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    OPTC, ____, ____, ____, ____,
 ]);
 
 bp!(B6, [
@@ -114,6 +122,7 @@ bp!(B6, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    OPTC, ____, ____, ____, ____,
 ]);
 
 bp!(B7, [
@@ -126,6 +135,7 @@ bp!(B7, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    OPTC, ____, ____, ____, ____,
 ]);
 
 bp!(B8, [
@@ -138,6 +148,7 @@ bp!(B8, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    OPTC, ____, ____, ____, ____,
 ]);
 
 bp!(B9, [
@@ -150,6 +161,7 @@ bp!(B9, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    OPTC, ____, ____, ____, ____,
 ]);
 
 bp!(B10, [
@@ -162,6 +174,7 @@ bp!(B10, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    OPTC, ____, ____, ____, ____,
 ]);
 
 bp!(B11, [
@@ -174,6 +187,7 @@ bp!(B11, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    OPTC, ____, ____, ____, ____,
 ]);
 
 bp!(B12, [
@@ -186,6 +200,7 @@ bp!(B12, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    OPTC, ____, ____, ____, ____,
 ]);
 
 bp!(B13, [
@@ -198,6 +213,7 @@ bp!(B13, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    OPTC, ____, ____, ____, ____,
 ]);
 
 bp!(B14, [
@@ -210,6 +226,7 @@ bp!(B14, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    OPTC, ____, ____, ____, ____,
 ]);
 
 bp!(B15, [
@@ -222,6 +239,7 @@ bp!(B15, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    OPTC, ____, ____, ____, ____,
 ]);
 
 const ____: NestedHandler = None;
@@ -246,7 +264,10 @@ const SEQ: NestedHandler = Some(|par, left| {
 const COND: NestedHandler = Some(|par, left| {
     par.lexer.consume();
 
-    let consequent = par.expression::<B1>();
+    let consequent = match par.options.typescript {
+        true  => par.typed_consequent(),
+        false => par.allowing_in(|par| par.expression::<B1>()),
+    };
     expect!(par, Colon);
     let alternate = par.expression::<B1>();
 
@@ -260,22 +281,30 @@ const COND: NestedHandler = Some(|par, left| {
 const ARRW: NestedHandler = Some(|par, left| {
     par.lexer.consume();
 
-    let params = match left.item {
+    // Parentheses preserved around the parameters belong to the arrow
+    let inner = match left.item {
+        Expression::Parenthesized(ParenthesizedExpression { expression }) => expression,
+        _ => left
+    };
+
+    let params = match inner.item {
         Expression::Sequence(SequenceExpression { body }) => body,
-        _ => NodeList::from(par.arena, left)
+        _ => NodeList::from(par.arena, inner)
     };
 
     let expression = par.arrow_function_expression(params);
     let start = left.start;
-    let end = par.lexer.end();
+    let end = expression.body.end();
     par.alloc_at_loc(start, end, expression)
 });
 
 const ACCS: NestedHandler = Some(|par, left| {
     let member = par.lexer.accessor_as_str();
+    let end = par.lexer.end();
+    let start = end - member.len() as u32;
     par.lexer.consume();
 
-    let right = par.alloc_in_loc(member);
+    let right = par.alloc_at_loc(start, end, member);
 
     par.alloc_at_loc(left.start, right.end, MemberExpression {
         object: left,
@@ -283,12 +312,51 @@ const ACCS: NestedHandler = Some(|par, left| {
     })
 });
 
+const OPTC: NestedHandler = Some(|par, left| {
+    par.lexer.consume();
+
+    let (link, end) = match par.lexer.token {
+        ParenOpen => {
+            par.lexer.consume();
+            let arguments = par.call_arguments();
+            let end = par.lexer.end_then_consume();
+
+            (OptionalLink::Call(arguments), end)
+        },
+        BracketOpen => {
+            par.lexer.consume();
+            let property = par.expression::<ANY>();
+            let end = par.lexer.end();
+            expect!(par, BracketClose);
+
+            (OptionalLink::ComputedMember(property), end)
+        },
+        _ if par.lexer.token.is_word() => {
+            let property = par.lexer.token_as_str();
+            let property = par.alloc_in_loc(property);
+            par.lexer.consume();
+
+            (OptionalLink::Member(property), property.end)
+        },
+        _ => {
+            par.error::<()>();
+
+            return left;
+        }
+    };
+
+    par.alloc_at_loc(left.start, end, OptionalExpression {
+        object: left,
+        link,
+    })
+});
+
 const CALL: NestedHandler = Some(|par, left| {
-    let start = par.lexer.start_then_consume();
+    par.lexer.consume();
     let arguments = par.call_arguments();
     let end = par.lexer.end_then_consume();
 
-    par.alloc_at_loc(start, end, CallExpression {
+    par.alloc_at_loc(left.start, end, CallExpression {
         callee: left,
         arguments,
     })
@@ -297,9 +365,9 @@ const CALL: NestedHandler = Some(|par, left| {
 const CMEM: NestedHandler = Some(|par, left| {
     par.lexer.consume();
     let property = par.expression::<ANY>();
+    let end = par.lexer.end();
 
     expect!(par, BracketClose);
-    let end = par.lexer.end();
 
     par.alloc_at_loc(left.start, end, ComputedMemberExpression {
         object: left,
@@ -308,7 +376,13 @@ const CMEM: NestedHandler = Some(|par, left| {
 });
 
 const TPLS: NestedHandler = Some(|par, left| {
-    let quasi = par.template_string();
+    let quasi: Node<TemplateLiteral> = par.template_string();
+
+    if left.is_optional_chain() {
+        par.error_at::<()>(ErrorCode::TaggedTemplateInOptionalChain, quasi.span());
+
+        return left;
+    }
 
     par.alloc_at_loc(left.start, quasi.end, TaggedTemplateExpression {
         tag: left,
@@ -328,7 +402,7 @@ macro_rules! postfix {
                 par.lexer.consume();
 
                 if !left.is_lvalue() {
-                    par.error::<()>();
+                    par.error_at::<()>(ErrorCode::InvalidAssignmentTarget, left.span());
                 }
 
                 par.alloc_at_loc(left.start, end, PostfixExpression {
@@ -348,8 +422,16 @@ macro_rules! assign {
             fn handler<'ast>(par: &mut Parser<'ast>, left: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
                 par.lexer.consume();
 
-                if !left.is_lvalue() {
-                    par.error::<()>();
+                // Only a plain `=` can destructure, `[a] += b` is an error
+                let destructuring = match left.item {
+                    Expression::Array(_) | Expression::Object(_) => true,
+                    _ => false,
+                };
+
+                if !left.is_lvalue() || (destructuring && ($op != Assign || par.is_parenthesized(left))) {
+                    par.error_at::<()>(ErrorCode::InvalidAssignmentTarget, left.span());
+                } else if $op == Assign {
+                    par.validate_destructuring(left);
                 }
 
                 let right = par.expression::<B1>();
@@ -386,6 +468,30 @@ macro_rules! binary {
     }
 }
 
+// `??` can't be mixed with `||` and `&&` without parentheses
+macro_rules! logical {
+    ($name:ident, $bp:ident => $op:ident) => {
+        const $name: NestedHandler = {
+            fn handler<'ast>(par: &mut Parser<'ast>, left: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
+                par.lexer.consume();
+                par.logical_operand($op, left);
+
+                let right = par.expression::<$bp>();
+
+                par.logical_operand($op, right);
+
+                par.alloc_at_loc(left.start, right.end, BinaryExpression {
+                    operator: $op,
+                    left,
+                    right,
+                })
+            }
+
+            Some(handler)
+        };
+    }
+}
+
 postfix!(INC => Increment);
 postfix!(DEC => Decrement);
 
@@ -402,9 +508,13 @@ assign!(UBSA => UBSRAssign);
 assign!(BWAA => BitAndAssign);
 assign!(XORA => BitXorAssign);
 assign!(BORA => BitOrAssign);
+assign!(NLSA => NullishAssign);
+assign!(LORA => LogicalOrAssign);
+assign!(LANA => LogicalAndAssign);
 
-binary!(OR   , B5  => LogicalOr);
-binary!(AND  , B6  => LogicalAnd);
+logical!(NLSH , B5  => Nullish);
+logical!(OR   , B5  => LogicalOr);
+logical!(AND  , B6  => LogicalAnd);
 binary!(BWOR , B7  => BitwiseOr);
 binary!(BWXO , B8  => BitwiseXor);
 binary!(BWAN , B9  => BitwiseAnd);
@@ -426,7 +536,8 @@ binary!(SUB  , B13 => Subtraction);
 binary!(MUL  , B14 => Multiplication);
 binary!(DIV  , B14 => Division);
 binary!(REM  , B14 => Remainder);
-binary!(EXPN , B15 => Exponent);
+// Right associative, `a ** b ** c` is `a ** (b ** c)`
+binary!(EXPN , B14 => Exponent);
 
 
 impl<'ast> Parser<'ast> {
@@ -435,10 +546,67 @@ impl<'ast> Parser<'ast> {
     where
         B: BindingPower
     {
-        while let Some(handler) = B::handler(self.asi(), self.lexer.token) {
-            left = handler(self, left);
+        loop {
+            if self.options.typescript {
+                if let Some(nested) = self.typescript_nested_expression::<B>(left) {
+                    left = nested;
+                    continue;
+                }
+            }
+
+            match B::handler(self.asi(), self.lexer.token) {
+                Some(_) if self.in_excluded && self.lexer.token == OperatorIn => break,
+                Some(handler) => left = match self.lexer.token {
+                    // `in` is an operator again within brackets
+                    ParenOpen | BracketOpen | TemplateOpen | OperatorOptionalChaining => {
+                        self.allowing_in(|par| par.nested(|par| handler(par, left)))
+                    },
+                    _ => self.nested(|par| handler(par, left)),
+                },
+                None          => break,
+            }
         }
 
         left
     }
+
+    /// With `typescript` set, `a ? (b) : c => d` starts off as an arrow
+    /// with a return type, leaving the conditional without its `:`. The
+    /// consequent is then read again, this time without return types.
+    fn typed_consequent(&mut self) -> ExpressionNode<'ast> {
+        let consequent = self.speculate(|par| {
+            let consequent = par.allowing_in(|par| par.expression::<B1>());
+
+            match par.lexer.token {
+                Colon => Some(consequent),
+                _     => None,
+            }
+        });
+
+        if let Some(consequent) = consequent {
+            return consequent;
+        }
+
+        let untyped = mem::replace(&mut self.untyped_arrows, true);
+        let consequent = self.allowing_in(|par| par.expression::<B1>());
+
+        self.untyped_arrows = untyped;
+
+        consequent
+    }
+
+    fn logical_operand(&mut self, operator: OperatorKind, operand: ExpressionNode<'ast>) {
+        if let Expression::Binary(BinaryExpression { operator: nested, .. }) = operand.item {
+            let mixed = matches!((operator, nested),
+                (Nullish, LogicalOr)  |
+                (Nullish, LogicalAnd) |
+                (LogicalOr, Nullish)  |
+                (LogicalAnd, Nullish)
+            );
+
+            if mixed && !self.is_parenthesized(operand) {
+                self.error_at::<()>(ErrorCode::MixedNullish, operand.span());
+            }
+        }
+    }
 }
@@ -1,47 +1,52 @@
 use toolshed::list::{ListBuilder, GrowableList};
-use parser::{Parser, Parse, ANY, B0};
+use parser::{Parser, Parse, Label, ANY, B0, B15};
+use error::ErrorCode;
+use span::Span;
 use lexer::Token::*;
-use lexer::Asi;
-use ast::{Node, NodeList, Declarator, DeclarationKind};
-use ast::{Statement, StatementNode, Expression, ExpressionNode, Class, Function, Pattern};
-use ast::expression::BinaryExpression;
+use lexer::{Asi, Token};
+use ast::{Node, NodeList, Declarator, DeclarationKind, IdentifierNode};
+use ast::{Statement, StatementNode, ExpressionNode, Class, Function, OptionalName, Pattern};
+use ast::expression::AwaitExpression;
 use ast::statement::{ThrowStatement, ContinueStatement, BreakStatement, ReturnStatement};
 use ast::statement::{TryStatement, CatchClause, IfStatement, WhileStatement, DoStatement};
 use ast::statement::{DeclarationStatement, ForStatement, ForInStatement, ForOfStatement};
 use ast::statement::{SwitchStatement, SwitchCase, LabeledStatement, ForInit};
 use ast::statement::{ImportDeclaration, ImportDefaultSpecifier, ImportNamespaceSpecifier, ImportSpecifier, ForImportSpecifier};
-use ast::OperatorKind::*;
+use ast::statement::{ExportDeclaration, ExportDefaultDeclaration, ExportNamedDeclaration, ExportSpecifier};
 
 
 type StatementHandler = for<'ast> fn(&mut Parser<'ast>) -> StatementNode<'ast>;
 
-static STMT_HANDLERS: [StatementHandler; 108] = [
-    ____, EMPT, ____, ____, PRN,  ____, ARR,  ____, BLCK, ____, ____, NEW,
+static STMT_HANDLERS: [StatementHandler; 113] = [
+    ____, EMPT, ____, ____, PRN,  UNMT, ARR,  UNMT, BLCK, UNMT, ____, NEW,
 //  EOF   ;     :     ,     (     )     [     ]     {     }     =>    NEW
 
-    OP,   OP,   OP,   OP,   OP,   OP,   OP,   ____, REG,  ____, ____, OP,
+    OP,   OP,   OP,   OP,   OP,   OP,   OP,   STAR, REG,  ____, ____, OP,
 //  ++    --    !     ~     TYPOF VOID  DELET *     /     %     **    +
 
-    OP,   ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
+    OP,   ____, ____, ____, JSX,  ____, ____, ____, ____, ____, ____, ____,
 //  -     <<    >>    >>>   <     <=    >     >=    INSOF IN    ===   !==
 
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
 //  ==    !=    &     ^     |     &&    ||    ?     =     +=    -=    **=
 
-    ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, VAR,  LET,
+    ____, REG,  ____, ____, ____, ____, ____, ____, ____, ____, VAR,  LET,
 //  *=    /=    %=    <<=   >>=   >>>=  &=    ^=    |=    ...   VAR   LET
 
-    CONS, BRK,  DO,   ____, ____, ____, ____, CLAS, ____, RET,  WHL,  ____,
+    CONS, BRK,  DO,   ____, ____, ____, EXPT, CLAS, ____, RET,  WHL,  ____,
 //  CONST BREAK DO    CASE  ELSE  CATCH EXPRT CLASS EXTND RET   WHILE FINLY
 
-    ____, ____, CONT, FOR,  SWCH, ____, DBGGR, FUNC, THIS, ____, IF,   THRW,
+    SUPR, ____, CONT, FOR,  SWCH, YILD, DBGGR, FUNC, THIS, ____, IF,   THRW,
 //  SUPER WITH  CONT  FOR   SWTCH YIELD DBGGR FUNCT THIS  DEFLT IF    THROW
 
     IMPT, TRY,  ____, TRUE, FALS, NULL, UNDE, STR,  NUM,  BIN,  ____, ____,
 //  IMPRT TRY   STATI TRUE  FALSE NULL  UNDEF STR   NUM   BIN   REGEX ENUM
 
-    ____, ____, ____, ____, ____, ____, LABL, ____, TPLE, TPLS, ____, ____,
+    ____, ____, ____, IFCE, ____, ____, LABL, ____, TPLE, TPLS, ____, ____,
 //  IMPL  PCKG  PROT  IFACE PRIV  PUBLI IDENT ACCSS TPL_O TPL_C ERR_T ERR_E
+
+    ____, ____, ____, ____, ____,
+//  ?.    ??    ??=   ||=   &&=
 ];
 
 
@@ -58,7 +63,7 @@ macro_rules! create_handlers {
 
 /// Shared expression handlers that produce StatementNode<'ast>
 use parser::expression::handlers::{
-    PRN, ARR, OP, NEW, REG, THIS, TRUE, FALS, NULL, UNDE, STR, NUM, BIN, TPLS, TPLE
+    PRN, ARR, OP, NEW, REG, THIS, SUPR, YILD, STAR, TRUE, FALS, NULL, UNDE, STR, NUM, BIN, TPLS, TPLE, JSX
 };
 
 create_handlers! {
@@ -97,6 +102,9 @@ create_handlers! {
     const SWCH = |par| par.switch_statement();
     const LABL = |par| par.labeled_or_expression_statement();
     const IMPT = |par| par.import_declaration();
+    const EXPT = |par| par.export_declaration();
+    const IFCE = |par| par.interface_declaration();
+    const UNMT = |par| par.unmatched_closing_bracket();
 }
 
 impl<'ast> Parse<'ast> for Statement<'ast> {
@@ -139,7 +147,7 @@ impl<'ast> Parse<'ast> for SwitchCase<'ast> {
 
         loop {
             match par.lexer.token {
-                Case | Default | BraceClose => break,
+                Case | Default | BraceClose | EndOfProgram => break,
                 _ => {
                     let statement = par.statement();
                     end = statement.end;
@@ -158,34 +166,65 @@ impl<'ast> Parse<'ast> for SwitchCase<'ast> {
 impl<'ast> Parser<'ast> {
     #[inline]
     pub fn statement(&mut self) -> StatementNode<'ast> {
-        unsafe { (*(&STMT_HANDLERS as *const StatementHandler).offset(self.lexer.token as isize))(self) }
+        self.nested(|par| unsafe { (*(&STMT_HANDLERS as *const StatementHandler).offset(par.lexer.token as isize))(par) })
     }
 
     /// Expect a semicolon to terminate a statement. Will assume a semicolon
     /// following the ASI rules.
     #[inline]
-    fn expect_semicolon(&mut self) {
+    pub fn expect_semicolon(&mut self) {
         match self.asi() {
             Asi::ExplicitSemicolon => self.lexer.consume(),
-            Asi::ImplicitSemicolon => {},
+            Asi::ImplicitSemicolon => self.implicit_semicolon(),
             Asi::NoSemicolon       => self.error(),
         }
     }
 
+    /// Record a semicolon inserted before the current token, see `ParseOptions::semicolons`.
+    #[inline]
+    fn implicit_semicolon(&mut self) {
+        let start = self.lexer.start();
+
+        if let Some(ref mut semicolons) = self.semicolons {
+            semicolons.push(start);
+        }
+    }
+
+    /// Report a closing bracket that doesn't close anything and skip past it,
+    /// so that parsing can carry on with the next statement.
+    fn unmatched_closing_bracket(&mut self) -> StatementNode<'ast> {
+        let span = self.lexer.span();
+
+        self.error_at::<()>(ErrorCode::UnmatchedClosingBracket, span);
+        self.lexer.consume();
+
+        self.alloc_at_loc(span.start, span.end, Statement::Empty)
+    }
+
     #[inline]
     pub fn block_statement(&mut self) -> StatementNode<'ast> {
         let start = self.lexer.start_then_consume();
-        let block = self.raw_block();
-        let end   = self.lexer.end_then_consume();
+        let block = self.in_block(|par| par.raw_block());
+        let end   = self.close_block();
 
         self.alloc_at_loc(start, end, block)
     }
 
     #[inline]
     pub fn expression_statement(&mut self, expression: ExpressionNode<'ast>) -> StatementNode<'ast> {
+        let start = expression.start;
+
+        self.expression_statement_at(start, expression)
+    }
+
+    /// Statement starts where its first token does, which is before the
+    /// expression when the latter opens with parentheses, as in `(a);`.
+    #[inline]
+    pub fn expression_statement_at(&mut self, start: u32, expression: ExpressionNode<'ast>) -> StatementNode<'ast> {
         let expression = self.nested_expression::<ANY>(expression);
 
-        self.wrap_expression(expression)
+        self.expect_semicolon();
+        self.alloc_at_loc(start, expression.end, expression)
     }
 
     #[inline]
@@ -204,15 +243,74 @@ impl<'ast> Parser<'ast> {
         if self.lexer.token == Colon {
             self.lexer.consume();
 
+            self.labels.push(Label {
+                name: label,
+                continues: Vec::new(),
+            });
+
             let body = self.statement();
 
+            if let Some(Label { continues, .. }) = self.labels.pop() {
+                if !labels_loop(&body) {
+                    for span in continues {
+                        self.error_at::<()>(ErrorCode::InvalidContinueLabel, span);
+                    }
+                }
+            }
+
             return self.alloc_at_loc(start, body.end, LabeledStatement {
                 label,
                 body,
             });
         }
 
-        let expression = self.alloc_at_loc(start, end, label);
+        if label == "async" && self.lexer.token == Token::Function && self.asi() == Asi::NoSemicolon {
+            return self.async_function_statement(start);
+        }
+
+        if label == "type" && self.options.typescript && self.lexer.token == Identifier && self.asi() == Asi::NoSemicolon {
+            return self.type_alias_declaration(start);
+        }
+
+        if label == "using" && self.lexer.token == Identifier && self.asi() == Asi::NoSemicolon {
+            return self.using_declaration(start, DeclarationKind::Using);
+        }
+
+        let expression = if label == "await" && self.lexer.token == Identifier && self.lexer.token_as_str() == "using" && self.asi() == Asi::NoSemicolon {
+            let (using_start, using_end) = self.lexer.loc();
+
+            self.lexer.consume();
+
+            if self.lexer.token == Identifier && self.asi() == Asi::NoSemicolon {
+                if !self.context.await_expression {
+                    self.error_at::<()>(ErrorCode::InvalidAwaitUsing, Span::new(start, using_end));
+                }
+
+                return self.using_declaration(start, DeclarationKind::AwaitUsing);
+            }
+
+            // Otherwise it's `await` of a reference to `using`
+            if self.context.await_expression {
+                let using = self.alloc_at_loc(using_start, using_end, "using");
+                let argument = self.nested_expression::<B15>(using);
+
+                self.exponent_base();
+
+                self.alloc_at_loc(start, argument.end, AwaitExpression {
+                    argument
+                })
+            } else {
+                self.error_at::<()>(ErrorCode::UnexpectedToken, Span::new(using_start, using_end));
+                self.alloc_at_loc(start, end, label)
+            }
+        } else if label == "await" && self.context.await_expression {
+            self.await_expression(start)
+        } else if label == "await" && self.context.await_reserved {
+            self.error_at::<()>(ErrorCode::UnexpectedToken, Span::new(start, end));
+            self.alloc_at_loc(start, end, label)
+        } else {
+            self.alloc_at_loc(start, end, label)
+        };
         let expression = self.nested_expression::<ANY>(expression);
 
         self.expect_semicolon();
@@ -225,6 +323,18 @@ impl<'ast> Parser<'ast> {
         let start = self.lexer.start_then_consume();
         let function = Function::parse(self);
 
+        self.block_function(Span::new(start, function.body.end));
+        self.alloc_at_loc(start, function.body.end, function)
+    }
+
+    /// Parse an `async function` declaration, the `async` keyword starting at `start`
+    /// has already been consumed.
+    #[inline]
+    fn async_function_statement(&mut self, start: u32) -> StatementNode<'ast> {
+        self.lexer.consume();
+        let function = self.async_function();
+
+        self.block_function(Span::new(start, function.body.end));
         self.alloc_at_loc(start, function.body.end, function)
     }
 
@@ -251,9 +361,43 @@ impl<'ast> Parser<'ast> {
         declaration
     }
 
+    /// Parse `using` or `await using` declarations following the keywords.
+    /// Unlike with `const`, every declarator must be initialized and can't
+    /// be destructured. Only a name directly following the keywords makes
+    /// for a declaration, `using [a] = b` assigns to a computed member.
+    fn using_declaration(&mut self, start: u32, kind: DeclarationKind) -> StatementNode<'ast> {
+        let declarators = self.variable_declarators();
+        let end = self.lexer.end();
+
+        for declarator in declarators.iter() {
+            let id = match declarator.id.item {
+                Pattern::Annotated { pattern, .. } => pattern,
+                _                                  => declarator.id,
+            };
+
+            match id.item {
+                Pattern::Identifier(_) if declarator.init.is_none() => {
+                    self.error_at::<()>(ErrorCode::MissingInitializer, id.span());
+                },
+                Pattern::Identifier(_) => {},
+                _ => self.error_at::<()>(ErrorCode::InvalidUsingBinding, id.span()),
+            }
+        }
+
+        let declaration = self.alloc_at_loc(start, end, DeclarationStatement {
+            kind,
+            declarators
+        });
+
+        self.expect_semicolon();
+
+        declaration
+    }
+
     #[inline]
     pub fn variable_declarator(&mut self) -> Node<'ast, Declarator<'ast>> {
-        let id = Pattern::parse(self);
+        let id = self.allowing_in(Pattern::parse);
+        let id = self.pattern_annotation(id, false);
 
         let (init, end) = match self.lexer.token {
             OperatorAssign => {
@@ -293,6 +437,11 @@ impl<'ast> Parser<'ast> {
     #[inline]
     pub fn return_statement(&mut self) -> StatementNode<'ast> {
         let (start, mut end) = self.lexer.loc();
+
+        if !self.context.return_statement {
+            self.error_at::<()>(ErrorCode::InvalidReturn, Span::new(start, end));
+        }
+
         self.lexer.consume();
 
         let value = match self.asi() {
@@ -305,7 +454,10 @@ impl<'ast> Parser<'ast> {
                 Some(expression)
             }
 
-            Asi::ImplicitSemicolon => None,
+            Asi::ImplicitSemicolon => {
+                self.implicit_semicolon();
+                None
+            },
             Asi::ExplicitSemicolon => {
                 self.lexer.consume();
 
@@ -326,7 +478,10 @@ impl<'ast> Parser<'ast> {
                 self.lexer.consume();
                 None
             },
-            Asi::ImplicitSemicolon => None,
+            Asi::ImplicitSemicolon => {
+                self.implicit_semicolon();
+                None
+            },
             Asi::NoSemicolon => {
                 let label = self.identifier();
                 end = label.end;
@@ -350,11 +505,19 @@ impl<'ast> Parser<'ast> {
                 self.lexer.consume();
                 None
             },
-            Asi::ImplicitSemicolon => None,
+            Asi::ImplicitSemicolon => {
+                self.implicit_semicolon();
+                None
+            },
             Asi::NoSemicolon => {
                 let label = self.identifier();
                 end = label.end;
 
+                match self.labels.iter_mut().rev().find(|target| target.name == label.item) {
+                    Some(target) => target.continues.push(label.span()),
+                    None         => self.error_at(ErrorCode::UndefinedLabel, label.span()),
+                }
+
                 self.expect_semicolon();
 
                 Some(label)
@@ -429,12 +592,13 @@ impl<'ast> Parser<'ast> {
         let test = self.expression::<ANY>();
         expect!(self, ParenClose);
 
-        let consequent = self.statement();
+        // Functions are allowed as the body in sloppy mode, as if in a block
+        let consequent = self.in_block(|par| par.statement());
 
         let (alternate, end) = match self.lexer.token {
             Else => {
                 self.lexer.consume();
-                let alternate = self.statement();
+                let alternate = self.in_block(|par| par.statement());
                 (Some(alternate), alternate.end)
             },
             _ => (None, consequent.end)
@@ -472,6 +636,12 @@ impl<'ast> Parser<'ast> {
         let end = self.lexer.end();
         expect!(self, ParenClose);
 
+        // A semicolon is inserted after `do-while` even without a line break,
+        // so an explicit one is optional.
+        if self.lexer.token == Semicolon {
+            self.lexer.consume();
+        }
+
         self.alloc_at_loc(start, end, DoStatement {
             body,
             test,
@@ -481,7 +651,7 @@ impl<'ast> Parser<'ast> {
     #[inline]
     fn for_init(&mut self, kind: DeclarationKind) -> Node<'ast, ForInit<'ast>> {
         let start = self.lexer.start_then_consume();
-        let declarators = self.variable_declarators();
+        let declarators = self.excluding_in(Parser::variable_declarators);
         let end = self.lexer.end();
         
         self.alloc_at_loc(start, end, DeclarationStatement {
@@ -504,18 +674,9 @@ impl<'ast> Parser<'ast> {
             DeclarationLet   => Some(self.for_init(DeclarationKind::Let)),
             DeclarationConst => Some(self.for_init(DeclarationKind::Const)),
             _ => {
-                let init = self.expression::<ANY>();
-
-                if let Expression::Binary(BinaryExpression {
-                    operator: In,
-                    left,
-                    right,
-                    ..
-                }) = init.item {
-                    let left = self.alloc_at_loc(left.start, left.end, left);
-
-                    return self.for_in_statement_from_parts(start, left, right);
-                }
+                // `in` following the init makes this a for-in, unless it's
+                // within parentheses, as in `for ((a in b);;)`
+                let init = self.excluding_in(|par| par.expression::<ANY>());
 
                 Some(self.alloc_at_loc(init.start, init.end, init))
             },
@@ -571,18 +732,6 @@ impl<'ast> Parser<'ast> {
         })
     }
 
-    fn for_in_statement_from_parts(&mut self, start: u32, left: Node<'ast, ForInit<'ast>>, right: ExpressionNode<'ast>) -> StatementNode<'ast> {
-        expect!(self, ParenClose);
-
-        let body = self.statement();
-
-        self.alloc_at_loc(start, body.end, ForInStatement {
-            left,
-            right,
-            body,
-        })
-    }
-
     fn for_in_statement(&mut self, start: u32, left: Node<'ast, ForInit<'ast>>) -> StatementNode<'ast> {
         let right = self.expression::<ANY>();
 
@@ -619,7 +768,7 @@ impl<'ast> Parser<'ast> {
 
         expect!(self, ParenClose);
 
-        let cases = self.block();
+        let cases = self.in_block(|par| par.block());
 
         self.alloc_at_loc(start, cases.end, SwitchStatement {
             discriminant,
@@ -665,6 +814,8 @@ impl<'ast> Parser<'ast> {
                 let source = &str_ltr[1..str_ltr.len() - 1];
                 let end = self.lexer.end_then_consume();
 
+                self.expect_semicolon();
+
                 return self.alloc_at_loc(start, end, ImportDeclaration {
                     specifiers: NodeList::empty(),
                     source,
@@ -679,9 +830,14 @@ impl<'ast> Parser<'ast> {
                     self.lexer.consume();
                     loop {
                         match self.lexer.token {
-                            Identifier => {
-                                let start = self.lexer.start();
-                                let name = self.identifier();
+                            // Imported names can be any word or string, `{ default as x }`
+                            // or `{ "a b" as x }`, but only identifiers can be used as bindings
+                            _ if self.lexer.token.is_word() || self.lexer.token == LiteralString => {
+                                let reserved = self.lexer.token != Identifier;
+                                let name = self.lexer.token_as_str();
+                                let name = self.alloc_in_loc(name);
+                                let start = name.start;
+                                self.lexer.consume();
                                 let mut import = ImportSpecifier {
                                     imported: name,
                                     local: name,
@@ -690,6 +846,8 @@ impl<'ast> Parser<'ast> {
                                     self.lexer.consume();
                                     let local = self.identifier();
                                     import.local = local;
+                                } else if reserved {
+                                    self.error_at::<()>(ErrorCode::UnexpectedToken, name.span());
                                 }
                                 let end = self.lexer.end();
                                 builder.push(self.arena, self.alloc_at_loc(start, end, import));
@@ -750,22 +908,221 @@ impl<'ast> Parser<'ast> {
             }
         };
         let end = self.lexer.end_then_consume();
+
+        self.expect_semicolon();
+
         self.alloc_at_loc(start, end, ImportDeclaration {
             specifiers: builder.as_list(),
             source,
         })
     }
+
+    fn export_declaration(&mut self) -> StatementNode<'ast> {
+        let start = self.lexer.start_then_consume();
+
+        if self.lexer.token == Default {
+            self.lexer.consume();
+
+            return self.export_default_declaration(start);
+        }
+
+        if self.lexer.token == BraceOpen {
+            return self.export_named_declaration(start);
+        }
+
+        let declaration = self.statement();
+
+        match declaration.item {
+            Statement::Declaration(_) |
+            Statement::Function(_)    |
+            Statement::Class(_)       => {},
+            _ => self.error_at::<()>(ErrorCode::UnexpectedToken, declaration.span()),
+        }
+
+        self.alloc_at_loc(start, declaration.end, ExportDeclaration {
+            declaration,
+        })
+    }
+
+    /// Parse `export { a, b as c }`, optionally followed by `from 'm'`.
+    fn export_named_declaration(&mut self, start: u32) -> StatementNode<'ast> {
+        self.lexer.consume();
+
+        let builder = GrowableList::new();
+        let mut reserved = Vec::new();
+
+        loop {
+            match self.lexer.token {
+                BraceClose => break,
+                // Like imported names, exported names can be any word or string,
+                // `{ a as default }` or `{ a as "a b" }`
+                _ if self.lexer.token.is_word() || self.lexer.token == LiteralString => {
+                    let local = self.lexer.token_as_str();
+                    let local: IdentifierNode<'ast> = self.alloc_in_loc(local);
+
+                    if self.lexer.token != Identifier {
+                        reserved.push(local.span());
+                    }
+
+                    self.lexer.consume();
+
+                    let exported = if self.lexer.token == Identifier && self.lexer.token_as_str() == "as" {
+                        self.lexer.consume();
+
+                        if !self.lexer.token.is_word() && self.lexer.token != LiteralString {
+                            self.error::<()>();
+                            break;
+                        }
+
+                        let exported = self.lexer.token_as_str();
+                        let exported = self.alloc_in_loc(exported);
+                        self.lexer.consume();
+
+                        exported
+                    } else {
+                        local
+                    };
+
+                    builder.push(self.arena, self.alloc_at_loc(local.start, exported.end, ExportSpecifier {
+                        local,
+                        exported,
+                    }));
+
+                    match self.lexer.token {
+                        Comma      => self.lexer.consume(),
+                        BraceClose => break,
+                        _          => {
+                            self.error::<()>();
+                            break;
+                        }
+                    }
+                },
+                _ => {
+                    self.error::<()>();
+                    break;
+                }
+            }
+        }
+
+        let mut end = self.lexer.end();
+        expect!(self, BraceClose);
+
+        let source = if self.lexer.token == Identifier && self.lexer.token_as_str() == "from" {
+            self.lexer.consume();
+
+            match self.lexer.token {
+                LiteralString => {
+                    let source = self.lexer.token_as_str();
+                    end = self.lexer.end_then_consume();
+
+                    Some(&source[1..source.len() - 1])
+                },
+                _ => {
+                    self.error::<()>();
+
+                    None
+                }
+            }
+        } else {
+            // Without `from`, local names refer to bindings of this module, so
+            // `export { default }` or `export { "a" }` are errors while
+            // `export { default } from 'm'` isn't
+            for span in reserved {
+                self.error_at::<()>(ErrorCode::UnexpectedToken, span);
+            }
+
+            None
+        };
+
+        self.expect_semicolon();
+
+        self.alloc_at_loc(start, end, ExportNamedDeclaration {
+            specifiers: builder.as_list(),
+            source,
+        })
+    }
+
+    /// Parse whatever follows `export default`. Unlike in statements, function
+    /// and class declarations here may omit their names.
+    fn export_default_declaration(&mut self, start: u32) -> StatementNode<'ast> {
+        let declaration = match self.lexer.token {
+            Token::Function => {
+                let function_start = self.lexer.start_then_consume();
+                let function: Function<OptionalName> = Function::parse(self);
+
+                ExportDefaultDeclaration::Function(self.alloc_at_loc(function_start, function.body.end, function))
+            },
+            Token::Class => {
+                let class_start = self.lexer.start_then_consume();
+                let class: Class<OptionalName> = Class::parse(self);
+
+                ExportDefaultDeclaration::Class(self.alloc_at_loc(class_start, class.body.end, class))
+            },
+            Identifier if self.lexer.token_as_str() == "async" => {
+                let (async_start, async_end) = self.lexer.loc();
+
+                self.lexer.consume();
+
+                if self.lexer.token == Token::Function && self.asi() == Asi::NoSemicolon {
+                    self.lexer.consume();
+
+                    let function: Function<OptionalName> = self.async_function();
+
+                    ExportDefaultDeclaration::Function(self.alloc_at_loc(async_start, function.body.end, function))
+                } else {
+                    let expression = self.alloc_at_loc(async_start, async_end, "async");
+                    let value = self.nested_expression::<B0>(expression);
+
+                    self.expect_semicolon();
+
+                    ExportDefaultDeclaration::Expression(value)
+                }
+            },
+            _ => {
+                let value = self.expression::<B0>();
+
+                self.expect_semicolon();
+
+                ExportDefaultDeclaration::Expression(value)
+            }
+        };
+
+        let end = match declaration {
+            ExportDefaultDeclaration::Function(function) => function.end,
+            ExportDefaultDeclaration::Class(class)       => class.end,
+            ExportDefaultDeclaration::Expression(value)  => value.end,
+        };
+
+        self.alloc_at_loc(start, end, declaration)
+    }
+}
+
+/// Check whether a label with the given body labels a loop, directly or
+/// through other labels, as in `a: b: while (x) {}`.
+fn labels_loop(body: &Statement) -> bool {
+    match *body {
+        Statement::While(_) |
+        Statement::Do(_)    |
+        Statement::For(_)   |
+        Statement::ForIn(_) |
+        Statement::ForOf(_) => true,
+
+        Statement::Labeled(LabeledStatement { body, .. }) => labels_loop(&body),
+
+        _ => false,
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use parser::parse;
+    use parser::{parse, parse_with, ParseOptions};
     use parser::mock::Mock;
     use toolshed::list::List;
-    use ast::{NodeList, Literal, Function, Class, OperatorKind, BlockStatement};
+    use ast::{NodeList, Literal, Function, Class, OperatorKind, BlockStatement, OptionalName};
     use ast::expression::*;
-    
+    use error::ErrorCode;
+    use span::Span;
 
     #[test]
     fn import_declaration() {
@@ -836,15 +1193,19 @@ mod test {
     }
 
     #[test]
-    fn block_statement() {
-        let src = "{ true }";
+    fn async_function_statement() {
+        let src = "async function foo() {}";
         let mock = Mock::new();
 
         let expected = mock.list([
-            BlockStatement {
-                body: mock.list([
-                    mock.ptr(Literal::True)
-                ])
+            Function {
+                name: mock.name("foo"),
+                generator: false,
+                is_async: true,
+                type_parameters: NodeList::empty(),
+                params: NodeList::empty(),
+                return_type: None,
+                body: mock.empty_block(),
             }
         ]);
 
@@ -852,34 +1213,42 @@ mod test {
     }
 
     #[test]
-    fn labeled_block_statement() {
-        let src = "foobar: { true }";
+    fn async_identifier_followed_by_newline() {
+        let src = "async\nfunction foo() {}";
         let mock = Mock::new();
 
         let expected = mock.list([
-            LabeledStatement {
-                label: "foobar",
-                body: mock.ptr(BlockStatement {
-                    body: mock.list([
-                        mock.ptr(Literal::True)
-                    ])
-                }),
-            }
+            Statement::from(mock.ptr("async")),
+            Statement::from(Function {
+                name: mock.name("foo"),
+                generator: false,
+                is_async: false,
+                type_parameters: NodeList::empty(),
+                params: NodeList::empty(),
+                return_type: None,
+                body: mock.empty_block(),
+            }),
         ]);
 
         assert_eq!(parse(src).unwrap().body(), expected);
     }
 
     #[test]
-    fn if_statement() {
-        let src = "if (true) foo;";
+    fn export_generator_function_declaration() {
+        let src = "export function* foo() {}";
         let mock = Mock::new();
 
         let expected = mock.list([
-            IfStatement {
-                test: mock.ptr(Literal::True),
-                consequent: mock.ptr(mock.ptr("foo")),
-                alternate: None,
+            ExportDeclaration {
+                declaration: mock.ptr(Function {
+                    name: mock.name("foo"),
+                    generator: true,
+                    is_async: false,
+                    type_parameters: NodeList::empty(),
+                    params: NodeList::empty(),
+                    return_type: None,
+                    body: mock.empty_block(),
+                }),
             }
         ]);
 
@@ -887,19 +1256,21 @@ mod test {
     }
 
     #[test]
-    fn if_else_statement() {
-        let src = "if (true) foo; else { bar; }";
+    fn export_async_function_declaration() {
+        let src = "export async function foo() {}";
         let mock = Mock::new();
 
         let expected = mock.list([
-            IfStatement {
-                test: mock.ptr(Literal::True),
-                consequent: mock.ptr(mock.ptr("foo")),
-                alternate: Some(mock.ptr(BlockStatement {
-                    body: mock.list([
-                        mock.ptr("bar")
-                    ])
-                }))
+            ExportDeclaration {
+                declaration: mock.ptr(Function {
+                    name: mock.name("foo"),
+                    generator: false,
+                    is_async: true,
+                    type_parameters: NodeList::empty(),
+                    params: NodeList::empty(),
+                    return_type: None,
+                    body: mock.empty_block(),
+                }),
             }
         ]);
 
@@ -907,41 +1278,480 @@ mod test {
     }
 
     #[test]
-    fn while_statement() {
-        let src = "while (true) foo;";
+    fn export_default_sequence_expression() {
+        let src = "export default (a, b);";
         let mock = Mock::new();
 
         let expected = mock.list([
-            WhileStatement {
-                test: mock.ptr(Literal::True),
-                body: mock.ptr(mock.ptr("foo"))
-            }
+            ExportDefaultDeclaration::Expression(mock.ptr(SequenceExpression {
+                body: mock.list(["a", "b"]),
+            })),
         ]);
 
         assert_eq!(parse(src).unwrap().body(), expected);
     }
 
     #[test]
-    fn while_statement_block() {
-        let src = "while (true) { foo; }";
+    fn export_default_assignment_expression() {
+        let src = "export default a = 1\nfoo";
         let mock = Mock::new();
 
         let expected = mock.list([
-            WhileStatement {
-                test: mock.ptr(Literal::True),
-                body: mock.ptr(BlockStatement {
-                    body: mock.list([
-                        mock.ptr("foo")
-                    ])
-                })
-            }
+            Statement::from(ExportDefaultDeclaration::Expression(mock.ptr(BinaryExpression {
+                operator: OperatorKind::Assign,
+                left: mock.ptr("a"),
+                right: mock.number("1"),
+            }))),
+            Statement::from(mock.ptr("foo")),
         ]);
 
         assert_eq!(parse(src).unwrap().body(), expected);
     }
 
     #[test]
-    fn do_statement() {
+    fn export_default_function() {
+        let src = "export default function foo() {} export default function () {}";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            ExportDefaultDeclaration::Function(mock.ptr(Function {
+                name: mock.name::<OptionalName>("foo"),
+                generator: false,
+                is_async: false,
+                type_parameters: NodeList::empty(),
+                params: NodeList::empty(),
+                return_type: None,
+                body: mock.empty_block(),
+            })),
+            ExportDefaultDeclaration::Function(mock.ptr(Function {
+                name: OptionalName(None),
+                generator: false,
+                is_async: false,
+                type_parameters: NodeList::empty(),
+                params: NodeList::empty(),
+                return_type: None,
+                body: mock.empty_block(),
+            })),
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn export_default_async_generator_function() {
+        let src = "export default async function* () {}";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            ExportDefaultDeclaration::Function(mock.ptr(Function {
+                name: OptionalName(None),
+                generator: true,
+                is_async: true,
+                type_parameters: NodeList::empty(),
+                params: NodeList::empty(),
+                return_type: None,
+                body: mock.empty_block(),
+            })),
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn export_default_class() {
+        let src = "export default class Foo {} export default class extends Bar {}";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            ExportDefaultDeclaration::Class(mock.ptr(Class {
+                name: mock.name::<OptionalName>("Foo"),
+                extends: None,
+                body: mock.empty_block(),
+            })),
+            ExportDefaultDeclaration::Class(mock.ptr(Class {
+                name: OptionalName(None),
+                extends: Some(mock.ptr("Bar")),
+                body: mock.empty_block(),
+            })),
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn export_default_declaration_is_not_an_expression() {
+        let src = "export default function () {}\n(foo)";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Statement::from(ExportDefaultDeclaration::Function(mock.ptr(Function {
+                name: OptionalName(None),
+                generator: false,
+                is_async: false,
+                type_parameters: NodeList::empty(),
+                params: NodeList::empty(),
+                return_type: None,
+                body: mock.empty_block(),
+            }))),
+            Statement::from(mock.ptr("foo")),
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+
+        // Only declarations may drop their names
+        assert!(parse("function () {}").is_err());
+        assert!(parse("export function () {}").is_err());
+        assert!(parse("export class {}").is_err());
+    }
+
+    #[test]
+    fn export_named_declaration() {
+        let src = "export { a, b as c, x as default }";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            ExportNamedDeclaration {
+                specifiers: mock.list([
+                    ExportSpecifier { local: mock.ptr("a"), exported: mock.ptr("a") },
+                    ExportSpecifier { local: mock.ptr("b"), exported: mock.ptr("c") },
+                    ExportSpecifier { local: mock.ptr("x"), exported: mock.ptr("default") },
+                ]),
+                source: None,
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+        assert_eq!(parse("export {};").unwrap().body(), mock.list([
+            ExportNamedDeclaration {
+                specifiers: NodeList::empty(),
+                source: None,
+            }
+        ]));
+    }
+
+    #[test]
+    fn export_named_declaration_from() {
+        let src = "export { default } from 'm'; export { default as y, z } from \"n\"";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            ExportNamedDeclaration {
+                specifiers: mock.list([
+                    ExportSpecifier { local: mock.ptr("default"), exported: mock.ptr("default") },
+                ]),
+                source: Some("m"),
+            },
+            ExportNamedDeclaration {
+                specifiers: mock.list([
+                    ExportSpecifier { local: mock.ptr("default"), exported: mock.ptr("y") },
+                    ExportSpecifier { local: mock.ptr("z"), exported: mock.ptr("z") },
+                ]),
+                source: Some("n"),
+            },
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn export_named_declaration_spans() {
+        let module = parse("export { x as default } from 'm';").unwrap();
+        let statement = module.body().only_element().unwrap();
+
+        assert_eq!(statement.span(), Span::new(0, 32));
+
+        let specifier = match statement.item {
+            Statement::ExportNamed(export) => export.specifiers.only_element().unwrap(),
+            _ => panic!("Unexpected statement {:?}", statement),
+        };
+
+        assert_eq!(specifier.span(), Span::new(9, 21));
+        assert_eq!(specifier.exported.span(), Span::new(14, 21));
+    }
+
+    #[test]
+    fn export_named_declaration_invalid() {
+        // Reserved words can only be re-exported from another module
+        let errors = parse("export { default }").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, ErrorCode::UnexpectedToken);
+        assert_eq!(errors[0].span, Span::new(9, 16));
+
+        assert!(parse("export { a as }").is_err());
+        assert!(parse("export { a b }").is_err());
+        assert!(parse("export { a } from b").is_err());
+    }
+
+    #[test]
+    fn export_string_names() {
+        let src = "export { x as \"string name\" }; export { 'a b' as c, 'd' } from 'm'";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            ExportNamedDeclaration {
+                specifiers: mock.list([
+                    ExportSpecifier { local: mock.ptr("x"), exported: mock.ptr("\"string name\"") },
+                ]),
+                source: None,
+            },
+            ExportNamedDeclaration {
+                specifiers: mock.list([
+                    ExportSpecifier { local: mock.ptr("'a b'"), exported: mock.ptr("c") },
+                    ExportSpecifier { local: mock.ptr("'d'"), exported: mock.ptr("'d'") },
+                ]),
+                source: Some("m"),
+            },
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+
+        // Local bindings can't be strings
+        assert!(parse("export { 'a' as b }").is_err());
+    }
+
+    #[test]
+    fn import_string_names() {
+        let src = "import { \"string name\" as y, 'z' as z } from 'm'";
+        let mock = Mock::new();
+
+        let specifiers: List<Node<ForImportSpecifier>> = mock.list([
+            ImportSpecifier {
+                imported: mock.ptr("\"string name\""),
+                local: mock.ptr("y"),
+            },
+            ImportSpecifier {
+                imported: mock.ptr("'z'"),
+                local: mock.ptr("z"),
+            },
+        ]);
+        let expected = mock.list([
+            ImportDeclaration {
+                specifiers,
+                source: "m",
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+
+        // Strings have to be renamed to a binding
+        assert!(parse("import { 'a' } from 'm'").is_err());
+        assert!(parse("import { a as 'b' } from 'm'").is_err());
+    }
+
+    #[test]
+    fn export_default_async_expressions() {
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Statement::from(ExportDefaultDeclaration::Expression(mock.ptr("async"))),
+            Statement::from(ExportDefaultDeclaration::Expression(mock.ptr(CallExpression {
+                callee: mock.ptr("async"),
+                arguments: mock.list(["a"]),
+            }))),
+        ]);
+
+        assert_eq!(parse("export default async\nexport default async(a)").unwrap().body(), expected);
+
+        // `async` followed by a line break isn't a part of a function
+        assert!(parse("export default async\nfunction () {}").is_err());
+    }
+
+    #[test]
+    fn export_default_requires_expression() {
+        assert!(parse("export default;").is_err());
+        assert!(parse("export default a, b;").is_err());
+        assert!(parse("export default a b;").is_err());
+    }
+
+    #[test]
+    fn export_requires_declaration() {
+        assert!(parse("export foo;").is_err());
+        assert!(parse("export async;").is_err());
+        assert!(parse("export const foo = 1;").is_ok());
+        assert!(parse("export class Foo {}").is_ok());
+    }
+
+    #[test]
+    fn block_statement() {
+        let src = "{ true }";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            BlockStatement {
+                body: mock.list([
+                    mock.ptr(Literal::True)
+                ])
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn parenthesized_at_statement_start() {
+        let src = "({}).x; (() => {})(); {}";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Statement::from(mock.ptr(MemberExpression {
+                object: mock.ptr(ObjectExpression {
+                    body: NodeList::empty(),
+                }),
+                property: mock.ptr("x"),
+            })),
+            Statement::from(mock.ptr(CallExpression {
+                callee: mock.ptr(ArrowExpression {
+                    params: NodeList::empty(),
+                    return_type: None,
+                    body: ArrowBody::Block(mock.ptr(BlockStatement {
+                        body: NodeList::empty(),
+                    })),
+                }),
+                arguments: NodeList::empty(),
+            })),
+            Statement::from(BlockStatement {
+                body: NodeList::empty(),
+            }),
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+
+        // Without the parentheses it's a block followed by an error
+        assert!(parse("{}.x;").is_err());
+    }
+
+    #[test]
+    fn labeled_block_statement() {
+        let src = "foobar: { true }";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            LabeledStatement {
+                label: "foobar",
+                body: mock.ptr(BlockStatement {
+                    body: mock.list([
+                        mock.ptr(Literal::True)
+                    ])
+                }),
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn if_statement() {
+        let src = "if (true) foo;";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            IfStatement {
+                test: mock.ptr(Literal::True),
+                consequent: mock.ptr(mock.ptr("foo")),
+                alternate: None,
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn if_else_statement() {
+        let src = "if (true) foo; else { bar; }";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            IfStatement {
+                test: mock.ptr(Literal::True),
+                consequent: mock.ptr(mock.ptr("foo")),
+                alternate: Some(mock.ptr(BlockStatement {
+                    body: mock.list([
+                        mock.ptr("bar")
+                    ])
+                }))
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn while_statement() {
+        let src = "while (true) foo;";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            WhileStatement {
+                test: mock.ptr(Literal::True),
+                body: mock.ptr(mock.ptr("foo"))
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn while_statement_empty_body() {
+        let src = "while (x); foo;";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Statement::from(WhileStatement {
+                test: mock.ptr("x"),
+                body: mock.ptr(Statement::Empty),
+            }),
+            Statement::from(mock.ptr("foo")),
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn empty_statements() {
+        let src = ";;; foo; { ; }";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Statement::Empty,
+            Statement::Empty,
+            Statement::Empty,
+            Statement::from(mock.ptr("foo")),
+            Statement::from(BlockStatement {
+                body: mock.list([
+                    Statement::Empty,
+                ])
+            }),
+        ]);
+
+        let module = parse(src).unwrap();
+        let spans: Vec<_> = module.body().iter().take(3).map(|statement| statement.span()).collect();
+
+        assert_eq!(module.body(), expected);
+        assert_eq!(spans, [Span::new(0, 1), Span::new(1, 2), Span::new(2, 3)]);
+    }
+
+    #[test]
+    fn while_statement_block() {
+        let src = "while (true) { foo; }";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            WhileStatement {
+                test: mock.ptr(Literal::True),
+                body: mock.ptr(BlockStatement {
+                    body: mock.list([
+                        mock.ptr("foo")
+                    ])
+                })
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn do_statement() {
         let src = "do foo; while (true)";
         let mock = Mock::new();
 
@@ -955,6 +1765,43 @@ mod test {
         assert_eq!(parse(src).unwrap().body(), expected);
     }
 
+    #[test]
+    fn do_statement_with_semicolon() {
+        let src = "do foo; while (true);";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            DoStatement {
+                body: mock.ptr(mock.ptr("foo")),
+                test: mock.ptr(Literal::True)
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn do_statement_followed_by_statement() {
+        let src = "do a(); while (x) b();";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Statement::from(DoStatement {
+                body: mock.ptr(mock.ptr(CallExpression {
+                    callee: mock.ptr("a"),
+                    arguments: NodeList::empty(),
+                })),
+                test: mock.ptr("x")
+            }),
+            Statement::from(mock.ptr(CallExpression {
+                callee: mock.ptr("b"),
+                arguments: NodeList::empty(),
+            })),
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
     #[test]
     fn break_statement() {
         let src = "break;";
@@ -983,6 +1830,35 @@ mod test {
         assert_eq!(parse(src).unwrap().body(), expected);
     }
 
+    #[test]
+    fn continue_statement_label_across_loops() {
+        let src = "outer: for (;;) { inner: while (x) { if (y) continue outer; continue inner; } }";
+
+        assert!(parse(src).is_ok());
+        assert!(parse("a: b: do { continue a; } while (x)").is_ok());
+        assert!(parse("a: for (;;) { b: for (;;) continue a; continue a; }").is_ok());
+    }
+
+    #[test]
+    fn continue_statement_invalid_label() {
+        fn codes(src: &str) -> Vec<ErrorCode> {
+            parse(src).unwrap_err().iter().map(|err| err.code).collect()
+        }
+
+        let errors = parse("a: { while (x) { continue a; } }").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, ErrorCode::InvalidContinueLabel);
+        assert_eq!(errors[0].span, Span::new(26, 27));
+
+        assert_eq!(codes("while (x) { continue a; }"), [ErrorCode::UndefinedLabel]);
+
+        // Labels are scoped to their statement, and don't reach into functions
+        assert_eq!(codes("a: for (;;) {} for (;;) { continue a; }"), [ErrorCode::UndefinedLabel]);
+        assert_eq!(codes("a: for (;;) { (function () { for (;;) continue a; }); }"), [ErrorCode::UndefinedLabel]);
+        assert_eq!(codes("a: for (;;) { () => { for (;;) continue a; }; }"), [ErrorCode::UndefinedLabel]);
+    }
+
     #[test]
     fn throw_statement() {
         let src = "throw '3'";
@@ -1095,24 +1971,245 @@ mod test {
     }
 
     #[test]
-    fn variable_declaration_statement_spread() {
-        let src = "const a = {...foo}";
+    fn variable_declaration_statement_spread() {
+        let src = "const a = {...foo}";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            DeclarationStatement {
+                kind: DeclarationKind::Const,
+                declarators: mock.list([
+                    Declarator {
+                        id: mock.ptr(Pattern::Identifier("a")),
+                        init: Some(mock.ptr(ObjectExpression {
+                            body: mock.list([
+                                Property::Spread {
+                                    argument: mock.ptr("foo")
+                                },
+                            ])
+                        }))
+                    }
+                ])
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn variable_declaration_statement_destructuring_array() {
+        let src = "let [x, y] = [1, 2];";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            DeclarationStatement {
+                kind: DeclarationKind::Let,
+                declarators: mock.list([
+                    Declarator {
+                        id: mock.ptr(Pattern::ArrayPattern {
+                            elements: mock.list([
+                                Pattern::Identifier("x"),
+                                Pattern::Identifier("y")
+                            ])
+                        }),
+                        init: Some(mock.ptr(ArrayExpression {
+                            body: mock.list([
+                                Expression::Literal(Literal::Number("1")),
+                                Expression::Literal(Literal::Number("2")),
+                            ])
+                        })),
+                    },
+                ])
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn variable_declaration_statement_destructuring_array_sparse() {
+        let src = "let [, foo] = bar;";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            DeclarationStatement {
+                kind: DeclarationKind::Let,
+                declarators: mock.list([
+                    Declarator {
+                        id: mock.ptr(Pattern::ArrayPattern {
+                            elements: mock.list([
+                                Pattern::Void,
+                                Pattern::Identifier("foo")
+                            ])
+                        }),
+                        init: Some(mock.ptr("bar")),
+                    },
+                ])
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn variable_declaration_statement_destructuring_array_hole() {
+        let src = "let [a, , b] = c;";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            DeclarationStatement {
+                kind: DeclarationKind::Let,
+                declarators: mock.list([
+                    Declarator {
+                        id: mock.ptr(Pattern::ArrayPattern {
+                            elements: mock.list([
+                                Pattern::Identifier("a"),
+                                Pattern::Void,
+                                Pattern::Identifier("b"),
+                            ])
+                        }),
+                        init: Some(mock.ptr("c")),
+                    },
+                ])
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn variable_declaration_statement_destructuring_array_trailing_comma() {
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            DeclarationStatement {
+                kind: DeclarationKind::Let,
+                declarators: mock.list([
+                    Declarator {
+                        id: mock.ptr(Pattern::ArrayPattern {
+                            elements: mock.list([
+                                Pattern::Identifier("a"),
+                                Pattern::Identifier("b"),
+                            ])
+                        }),
+                        init: Some(mock.ptr("c")),
+                    },
+                ])
+            }
+        ]);
+
+        assert_eq!(parse("let [a, b,] = c;").unwrap().body(), expected);
+
+        let expected = mock.list([
+            DeclarationStatement {
+                kind: DeclarationKind::Let,
+                declarators: mock.list([
+                    Declarator {
+                        id: mock.ptr(Pattern::ArrayPattern {
+                            elements: mock.list([
+                                Pattern::Identifier("a"),
+                                Pattern::Void,
+                            ])
+                        }),
+                        init: Some(mock.ptr("c")),
+                    },
+                ])
+            }
+        ]);
+
+        assert_eq!(parse("let [a, ,] = c;").unwrap().body(), expected);
+    }
+
+    #[test]
+    fn variable_declaration_statement_destructuring_object_trailing_comma() {
+        let src = "let { a, b, } = c;";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            DeclarationStatement {
+                kind: DeclarationKind::Let,
+                declarators: mock.list([
+                    Declarator {
+                        id: mock.ptr(Pattern::ObjectPattern {
+                            properties: mock.list([
+                                Property::Shorthand("a"),
+                                Property::Shorthand("b"),
+                            ])
+                        }),
+                        init: Some(mock.ptr("c")),
+                    },
+                ])
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn variable_declaration_statement_destructuring_object() {
+        let src = "const { x, y } = { a, b };";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            DeclarationStatement {
+                kind: DeclarationKind::Const,
+                declarators: mock.list([
+                    Declarator {
+                        id: mock.ptr(Pattern::ObjectPattern {
+                            properties: mock.list([
+                                Property::Shorthand("x"),
+                                Property::Shorthand("y"),
+                            ])
+                        }),
+                        init: Some(mock.ptr(ObjectExpression {
+                            body: mock.list([
+                                Property::Shorthand("a"),
+                                Property::Shorthand("b"),
+                            ])
+                        })),
+                    },
+                ])
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn variable_declaration_statement_destructuring_computed_key() {
+        let src = "let { [k]: v, [a + b]: w = 1, ...rest } = obj;";
         let mock = Mock::new();
 
         let expected = mock.list([
             DeclarationStatement {
-                kind: DeclarationKind::Const,
+                kind: DeclarationKind::Let,
                 declarators: mock.list([
                     Declarator {
-                        id: mock.ptr(Pattern::Identifier("a")),
-                        init: Some(mock.ptr(ObjectExpression {
-                            body: mock.list([
+                        id: mock.ptr(Pattern::ObjectPattern {
+                            properties: mock.list([
+                                Property::Literal {
+                                    key: mock.ptr(PropertyKey::Computed(mock.ptr("k"))),
+                                    value: mock.ptr("v"),
+                                },
+                                Property::Literal {
+                                    key: mock.ptr(PropertyKey::Computed(mock.ptr(BinaryExpression {
+                                        operator: OperatorKind::Addition,
+                                        left: mock.ptr("a"),
+                                        right: mock.ptr("b"),
+                                    }))),
+                                    value: mock.ptr(BinaryExpression {
+                                        operator: OperatorKind::Assign,
+                                        left: mock.ptr("w"),
+                                        right: mock.number("1"),
+                                    }),
+                                },
                                 Property::Spread {
-                                    argument: mock.ptr("foo")
+                                    argument: mock.ptr("rest"),
                                 },
                             ])
-                        }))
-                    }
+                        }),
+                        init: Some(mock.ptr("obj")),
+                    },
                 ])
             }
         ]);
@@ -1121,82 +2218,117 @@ mod test {
     }
 
     #[test]
-    fn variable_declaration_statement_destructuring_array() {
-        let src = "let [x, y] = [1, 2];";
+    fn destructuring_rest_must_be_last() {
+        fn errors(src: &str) -> Vec<(ErrorCode, Span)> {
+            parse(src).unwrap_err().iter().map(|err| (err.code, err.span)).collect()
+        }
+
+        assert!(parse("let { [k]: v, ...rest } = obj;").is_ok());
+        assert!(parse("({ [k]: v, ...rest } = obj);").is_ok());
+        assert!(parse("({ [k]: v, ...rest }) => rest;").is_ok());
+
+        assert_eq!(errors("let { ...rest, [k]: v } = obj;"), [(ErrorCode::RestElementNotLast, Span::new(6, 13))]);
+        assert_eq!(errors("({ ...rest, [k]: v } = obj);"), [(ErrorCode::RestElementNotLast, Span::new(3, 10))]);
+        assert_eq!(errors("function f({ ...rest, a }) {}"), [(ErrorCode::RestElementNotLast, Span::new(13, 20))]);
+        assert_eq!(errors("({ ...rest, a }) => a;"), [(ErrorCode::RestElementNotLast, Span::new(3, 10))]);
+
+        // Rest elements bind names like any other
+        assert_eq!(errors("function f(a, { ...a }) {}"), [(ErrorCode::DuplicateParam, Span::new(19, 20))]);
+    }
+
+    #[test]
+    fn using_declaration() {
+        let src = "using a = b(), c = d;";
         let mock = Mock::new();
 
         let expected = mock.list([
             DeclarationStatement {
-                kind: DeclarationKind::Let,
+                kind: DeclarationKind::Using,
                 declarators: mock.list([
                     Declarator {
-                        id: mock.ptr(Pattern::ArrayPattern {
-                            elements: mock.list([
-                                Pattern::Identifier("x"),
-                                Pattern::Identifier("y")
-                            ])
-                        }),
-                        init: Some(mock.ptr(ArrayExpression {
-                            body: mock.list([
-                                Expression::Literal(Literal::Number("1")),
-                                Expression::Literal(Literal::Number("2")),
-                            ])
+                        id: mock.ptr(Pattern::Identifier("a")),
+                        init: Some(mock.ptr(CallExpression {
+                            callee: mock.ptr("b"),
+                            arguments: NodeList::empty(),
                         })),
                     },
+                    Declarator {
+                        id: mock.ptr(Pattern::Identifier("c")),
+                        init: Some(mock.ptr("d")),
+                    },
                 ])
             }
         ]);
 
         assert_eq!(parse(src).unwrap().body(), expected);
+
+        // Still a plain name anywhere else
+        assert!(parse("using; using = 1; using(a); using.a; using [a] = b; using\na = b;").is_ok());
     }
 
     #[test]
-    fn variable_declaration_statement_destructuring_array_sparse() {
-        let src = "let [, foo] = bar;";
+    fn await_using_declaration() {
+        let module = parse("async function f() { await using a = b(); await using; await using.c; }").unwrap();
+
+        let body = match module.body().iter().next().map(|statement| statement.item) {
+            Some(Statement::Function(function)) => function.body.body,
+            _ => panic!("Expected a function"),
+        };
+
+        let kinds: Vec<_> = body.iter().map(|statement| match statement.item {
+            Statement::Declaration(DeclarationStatement { kind, .. }) => Some(kind),
+            _ => None,
+        }).collect();
+
+        assert_eq!(kinds, [Some(DeclarationKind::AwaitUsing), None, None]);
+    }
+
+    #[test]
+    fn using_declaration_invalid() {
+        fn errors(src: &str) -> Vec<(ErrorCode, Span)> {
+            parse(src).unwrap_err().iter().map(|err| (err.code, err.span)).collect()
+        }
+
+        assert_eq!(errors("using a;"), [(ErrorCode::MissingInitializer, Span::new(6, 7))]);
+        assert_eq!(errors("using a = b, c;"), [(ErrorCode::MissingInitializer, Span::new(13, 14))]);
+        assert_eq!(errors("using a = b, { c } = d;"), [(ErrorCode::InvalidUsingBinding, Span::new(13, 18))]);
+        assert_eq!(errors("await using a = b;"), [(ErrorCode::InvalidAwaitUsing, Span::new(0, 11))]);
+        assert_eq!(errors("function f() { await using a = b; }"), [(ErrorCode::InvalidAwaitUsing, Span::new(15, 26))]);
+        assert_eq!(errors("async function f() { () => { await using a = b; }; }"), [(ErrorCode::InvalidAwaitUsing, Span::new(29, 40))]);
+    }
+
+    #[test]
+    fn variable_declaration_statement_mixed_declarators() {
+        let src = "let a = 1, [b] = arr, { c } = obj, d;";
         let mock = Mock::new();
 
         let expected = mock.list([
             DeclarationStatement {
                 kind: DeclarationKind::Let,
                 declarators: mock.list([
+                    Declarator {
+                        id: mock.ptr(Pattern::Identifier("a")),
+                        init: Some(mock.number("1")),
+                    },
                     Declarator {
                         id: mock.ptr(Pattern::ArrayPattern {
                             elements: mock.list([
-                                Pattern::Void,
-                                Pattern::Identifier("foo")
+                                Pattern::Identifier("b"),
                             ])
                         }),
-                        init: Some(mock.ptr("bar")),
+                        init: Some(mock.ptr("arr")),
                     },
-                ])
-            }
-        ]);
-
-        assert_eq!(parse(src).unwrap().body(), expected);
-    }
-
-    #[test]
-    fn variable_declaration_statement_destructuring_object() {
-        let src = "const { x, y } = { a, b };";
-        let mock = Mock::new();
-
-        let expected = mock.list([
-            DeclarationStatement {
-                kind: DeclarationKind::Const,
-                declarators: mock.list([
                     Declarator {
                         id: mock.ptr(Pattern::ObjectPattern {
                             properties: mock.list([
-                                Property::Shorthand("x"),
-                                Property::Shorthand("y"),
+                                Property::Shorthand("c"),
                             ])
                         }),
-                        init: Some(mock.ptr(ObjectExpression {
-                            body: mock.list([
-                                Property::Shorthand("a"),
-                                Property::Shorthand("b"),
-                            ])
-                        })),
+                        init: Some(mock.ptr("obj")),
+                    },
+                    Declarator {
+                        id: mock.ptr(Pattern::Identifier("d")),
+                        init: None,
                     },
                 ])
             }
@@ -1239,6 +2371,138 @@ mod test {
         assert_eq!(parse(src).unwrap().body(), expected);
     }
 
+    #[test]
+    fn for_in_statement_with_expression() {
+        let src = "for (a in b) {}";
+        let mock = Mock::new();
+        let left: ExpressionNode = mock.ptr("a");
+
+        let expected = mock.list([
+            ForInStatement {
+                left: mock.ptr(left),
+                right: mock.ptr("b"),
+                body: mock.ptr(BlockStatement {
+                    body: NodeList::empty()
+                })
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn for_statement_with_parenthesized_in() {
+        let src = "for ((a in b);;) {}";
+        let mock = Mock::new();
+        let init: ExpressionNode = mock.ptr(BinaryExpression {
+            operator: OperatorKind::In,
+            left: mock.ptr("a"),
+            right: mock.ptr("b"),
+        });
+
+        let expected = mock.list([
+            ForStatement {
+                init: Some(mock.ptr(init)),
+                test: None,
+                update: None,
+                body: mock.ptr(BlockStatement {
+                    body: NodeList::empty()
+                })
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+        assert!(parse("for (a in b;;) {}").is_err());
+        assert!(parse("for ((a).b in c;;) {}").is_err());
+        assert!(parse("for (var a = b in c;;) {}").is_err());
+        assert!(parse("for (a = b in c;;) {}").is_err());
+        assert!(parse("for ((a) in b) {}").is_ok());
+        assert!(parse("for ((a).b in c) {}").is_ok());
+        assert!(parse("for (a in b, c) {}").is_ok());
+        assert!(parse("for (var a = (b in c);;) {}").is_ok());
+        assert!(parse("for (var [a = b in c] = d;;) {}").is_ok());
+        assert!(parse("for (f(a in b), [a in b], a ? b in c : d;;) {}").is_ok());
+        assert!(parse("for (a;;) { b in c }").is_ok());
+    }
+
+    #[test]
+    fn for_statement_header_declaration_kinds() {
+        let module = parse("for (let i of a) {} for (const k in b) {} for (var j = 0;;) {} for (let m = 0;;) {}").unwrap();
+
+        let kinds: Vec<_> = module.body().iter().map(|statement| {
+            let init = match statement.item {
+                Statement::ForOf(ForOfStatement { left, .. }) |
+                Statement::ForIn(ForInStatement { left, .. }) => Some(left),
+                Statement::For(ForStatement { init, .. }) => init,
+                _ => None,
+            };
+
+            match init.map(|init| init.item) {
+                Some(ForInit::Declaration(declaration)) => Some(declaration.kind),
+                _ => None,
+            }
+        }).collect();
+
+        assert_eq!(kinds, vec![
+            Some(DeclarationKind::Let),
+            Some(DeclarationKind::Const),
+            Some(DeclarationKind::Var),
+            Some(DeclarationKind::Let),
+        ]);
+    }
+
+    #[test]
+    fn block_scoped_declaration_statements() {
+        let src = "{ const a = 1; let b; } switch (x) { case 1: const c = 2; }";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Statement::from(BlockStatement {
+                body: mock.list([
+                    DeclarationStatement {
+                        kind: DeclarationKind::Const,
+                        declarators: mock.list([
+                            Declarator {
+                                id: mock.ptr(Pattern::Identifier("a")),
+                                init: Some(mock.number("1")),
+                            }
+                        ]),
+                    },
+                    DeclarationStatement {
+                        kind: DeclarationKind::Let,
+                        declarators: mock.list([
+                            Declarator {
+                                id: mock.ptr(Pattern::Identifier("b")),
+                                init: None,
+                            }
+                        ]),
+                    },
+                ])
+            }),
+            Statement::from(SwitchStatement {
+                discriminant: mock.ptr("x"),
+                cases: mock.block([
+                    SwitchCase {
+                        test: Some(mock.number("1")),
+                        consequent: mock.list([
+                            DeclarationStatement {
+                                kind: DeclarationKind::Const,
+                                declarators: mock.list([
+                                    Declarator {
+                                        id: mock.ptr(Pattern::Identifier("c")),
+                                        init: Some(mock.number("2")),
+                                    }
+                                ]),
+                            }
+                        ])
+                    },
+                ])
+            }),
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
     #[test]
     fn empty_for_statement() {
         let src = "for (;;) {}";
@@ -1258,6 +2522,24 @@ mod test {
         assert_eq!(parse(src).unwrap().body(), expected);
     }
 
+    #[test]
+    fn empty_for_statement_empty_body() {
+        let src = "for (;;); foo;";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Statement::from(ForStatement {
+                init: None,
+                test: None,
+                update: None,
+                body: mock.ptr(Statement::Empty),
+            }),
+            Statement::from(mock.ptr("foo")),
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
 
     #[test]
     fn for_statement_continue() {
@@ -1365,7 +2647,10 @@ mod test {
             Function {
                 name: mock.name("foo"),
                 generator: false,
+                is_async: false,
+                type_parameters: NodeList::empty(),
                 params: NodeList::empty(),
+                return_type: None,
                 body: mock.empty_block(),
             }
         ]);
@@ -1400,6 +2685,29 @@ mod test {
         parse("class {}").unwrap();
     }
 
+    #[test]
+    fn return_outside_function() {
+        let options = ParseOptions { allow_return_outside_function: true, ..ParseOptions::default() };
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            ReturnStatement { value: None },
+            ReturnStatement { value: Some(mock.ptr("module")) },
+        ]);
+
+        assert_eq!(parse_with("return; return module;", options).unwrap().body(), expected);
+        assert!(parse_with("if (a) { return; } for (;;) return 1;", options).is_ok());
+
+        let errors = parse("a; return b;").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, ErrorCode::InvalidReturn);
+        assert_eq!(errors[0].span, Span::new(3, 9));
+
+        assert!(parse("{ return; }").is_err());
+        assert!(parse("function f() { return; } () => { return; }; ({ m() { return; } })").is_ok());
+    }
+
     #[test]
     fn switch_statement() {
         let src = r#"
@@ -1413,6 +2721,7 @@ mod test {
                 return false;
         }
         "#;
+        let options = ParseOptions { allow_return_outside_function: true, ..ParseOptions::default() };
         let mock = Mock::new();
 
         let expected = mock.list([
@@ -1444,6 +2753,194 @@ mod test {
                 ])
             }
         ]);
+        assert_eq!(parse_with(src, options).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn switch_statement_unterminated() {
+        // Cases running into the end of the program must not wait for a `}` forever
+        let _ = parse("switch (a) { case 1:");
+        let _ = parse("switch (a) { default: foo();");
+
+        assert!(parse("switch (a) { case 1: foo(").is_err());
+    }
+
+    #[test]
+    fn switch_cases_share_lexical_scope() {
+        let module = parse("switch (x) { case 1: let a; var b; case 2: let a; class C {} }").unwrap();
+
+        let switch = match module.body().only_element().map(|statement| statement.item) {
+            Some(Statement::Switch(switch)) => switch,
+            body => panic!("Unexpected body {:?}", body),
+        };
+
+        let mut names = Vec::new();
+
+        for statement in switch.lexical_declarations() {
+            match statement.item {
+                Statement::Declaration(declaration) => {
+                    for declarator in declaration.declarators.iter() {
+                        if let Pattern::Identifier(name) = declarator.id.item {
+                            names.push(name);
+                        }
+                    }
+                },
+                Statement::Class(class) => names.push(class.name.0.item),
+                _ => {}
+            }
+        }
+
+        assert_eq!(names, ["a", "a", "C"]);
+
+        // A later pass can flag the second `a` as a redeclaration
+        let redeclared = names.iter().enumerate().any(|(index, name)| names[..index].contains(name));
+
+        assert!(redeclared);
+    }
+
+    #[test]
+    fn import_reserved_word_binding() {
+        let errors = parse("import default from 'm'").unwrap_err();
+
+        assert_eq!(errors[0].code, ErrorCode::UnexpectedToken);
+        assert_eq!(errors[0].span, Span::new(7, 14));
+
+        let errors = parse("import { default } from 'm'").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].span, Span::new(9, 16));
+    }
+
+    #[test]
+    fn import_reserved_word_aliased() {
+        let src = "import { default as x } from 'm'";
+        let mock = Mock::new();
+        let specifiers: List<Node<ForImportSpecifier>> = mock.list([
+            ImportSpecifier{
+                imported: mock.ptr("default"),
+                local: mock.ptr("x"),
+            },
+        ]);
+        let expected = mock.list([
+            ImportDeclaration {
+                specifiers,
+                source: "m",
+            }
+        ]);
         assert_eq!(parse(src).unwrap().body(), expected);
     }
+
+    #[test]
+    fn unmatched_closing_brackets() {
+        for (src, span) in [("a; }", Span::new(3, 4)), ("a; )", Span::new(3, 4)), ("a;\n] b;", Span::new(3, 4))].iter() {
+            let errors = parse(src).unwrap_err();
+
+            assert_eq!(errors.len(), 1, "{}", src);
+            assert_eq!(errors[0].code, ErrorCode::UnmatchedClosingBracket);
+            assert_eq!(errors[0].span, *span);
+        }
+    }
+
+    #[test]
+    fn unmatched_closing_bracket_recovery() {
+        // Skipping the bracket leaves following statements intact
+        let errors = parse("} let a = 1; ) a + b; ]").unwrap_err();
+
+        let spans: Vec<_> = errors.iter().map(|error| error.span).collect();
+
+        assert!(errors.iter().all(|error| error.code == ErrorCode::UnmatchedClosingBracket));
+        assert_eq!(spans, [Span::new(0, 1), Span::new(13, 14), Span::new(22, 23)]);
+    }
+
+    #[test]
+    fn directive_prologue() {
+        use ast::statement::{directives, is_strict};
+
+        let module = parse("'use strict'\n\"use\\x20strict\"; foo(); \"bar\";").unwrap();
+        let prologue: Vec<_> = directives(module.body()).collect();
+
+        assert_eq!(prologue, ["'use strict'", r#""use\x20strict""#]);
+        assert!(is_strict(module.body()));
+    }
+
+    #[test]
+    fn directive_use_strict_escaped() {
+        use ast::statement::is_strict;
+
+        // Evaluates to "use strict", but escapes disqualify a directive
+        assert!(!is_strict(parse(r#""use\x20strict";"#).unwrap().body()));
+        assert!(!is_strict(parse("'use \\\nstrict';").unwrap().body()));
+        assert!(!is_strict(parse("('use strict');").unwrap().body()));
+    }
+
+    #[test]
+    fn directive_outside_prologue() {
+        use ast::statement::is_strict;
+
+        assert!(!is_strict(parse("foo(); 'use strict';").unwrap().body()));
+        assert!(!is_strict(parse("'use strict'\n+ 1;").unwrap().body()));
+        assert!(!is_strict(parse("{ 'use strict'; }").unwrap().body()));
+    }
+
+    #[test]
+    fn directive_in_function_body() {
+        use ast::statement::is_strict;
+
+        let module = parse("function f() { 'foo'; \"use strict\"; }").unwrap();
+
+        let function = match module.body().only_element().map(|statement| statement.item) {
+            Some(Statement::Function(function)) => function,
+            body => panic!("Unexpected body {:?}", body),
+        };
+
+        assert!(is_strict(function.body.body));
+        assert!(!is_strict(module.body()));
+    }
+
+    #[test]
+    fn function_declaration_in_block() {
+        let module = parse("{ function f() {} }").unwrap();
+
+        let block = match module.body().only_element().map(|statement| statement.item) {
+            Some(Statement::Block(block)) => block,
+            body => panic!("Unexpected body {:?}", body),
+        };
+
+        match block.body.only_element().map(|statement| statement.item) {
+            Some(Statement::Function(function)) => assert_eq!(function.name.0.item, "f"),
+            body => panic!("Unexpected block body {:?}", body),
+        }
+    }
+
+    #[test]
+    fn block_functions_strictness() {
+        use parser::parse_collecting;
+
+        fn functions(src: &str) -> Vec<(&str, bool)> {
+            let options = ParseOptions { block_functions: true, ..ParseOptions::default() };
+            let functions = parse_collecting(src, options).unwrap().block_functions;
+
+            functions.iter().map(|function| (&src[function.span.start as usize..function.span.end as usize], function.strict)).collect()
+        }
+
+        assert_eq!(functions("function a() {} { function b() {} } if (x) function c() {} else { async function d() {} }"), [
+            ("function b() {}", false),
+            ("function c() {}", false),
+            ("async function d() {}", false),
+        ]);
+        assert_eq!(functions("switch (x) { case 1: function a() {} } function b() { function c() {} { function d() {} } }"), [
+            ("function a() {}", false),
+            ("function d() {}", false),
+        ]);
+        assert_eq!(functions("function a() { 'use strict'; { function b() { { function c() {} } } } } { function d() {} }"), [
+            ("function b() { { function c() {} } }", true),
+            ("function c() {}", true),
+            ("function d() {}", false),
+        ]);
+        assert_eq!(functions("class A { m() { { function b() {} } } } () => { 'use strict'; if (x) { function c() {} } };"), [
+            ("function b() {}", true),
+            ("function c() {}", true),
+        ]);
+        assert_eq!(functions("'use strict'; { function a() {} }"), [("function a() {}", true)]);
+    }
 }
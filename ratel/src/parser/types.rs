@@ -0,0 +1,819 @@
+use toolshed::list::GrowableList;
+use parser::{Parser, BindingPower};
+use lexer::Token::*;
+use lexer::Asi;
+use ast::{Node, NodeList, Pattern, PatternList, Literal, Statement, StatementNode};
+use ast::{Type, Primitive, TypeNode, TypeList, TypeMember, TypeParameter, TypeParameterList};
+use ast::ExpressionNode;
+use ast::expression::{TypeCastKind, TypeCastExpression, InstantiationExpression};
+use ast::statement::{TypeAliasDeclaration, InterfaceDeclaration};
+
+impl<'ast> Parser<'ast> {
+    /// Parse an optional `: T` annotation in TypeScript mode.
+    pub fn type_annotation(&mut self) -> Option<TypeNode<'ast>> {
+        match self.lexer.token {
+            Colon if self.options.typescript => {
+                self.lexer.consume();
+
+                Some(self.type_node())
+            },
+            _ => None,
+        }
+    }
+
+    /// Wrap the pattern with its type annotation, if any. Only parameters
+    /// can be marked as optional with `?`.
+    pub fn pattern_annotation(&mut self, pattern: Node<'ast, Pattern<'ast>>, param: bool) -> Node<'ast, Pattern<'ast>> {
+        if !self.options.typescript {
+            return pattern;
+        }
+
+        let mut end = pattern.end;
+
+        let optional = match self.lexer.token {
+            OperatorConditional if param => {
+                end = self.lexer.end_then_consume();
+                true
+            },
+            _ => false,
+        };
+
+        let annotation = self.type_annotation();
+
+        if let Some(annotation) = annotation {
+            end = annotation.end;
+        } else if !optional {
+            return pattern;
+        }
+
+        self.alloc_at_loc(pattern.start, end, Pattern::Annotated {
+            pattern,
+            optional,
+            annotation,
+        })
+    }
+
+    /// Parse `<T, U extends V = W>` if present in TypeScript mode.
+    pub fn type_parameters(&mut self) -> TypeParameterList<'ast> {
+        if !self.options.typescript || self.lexer.token != OperatorLesser {
+            return NodeList::empty();
+        }
+
+        self.lexer.consume();
+
+        let builder = GrowableList::new();
+
+        loop {
+            let start = self.lexer.start();
+            let name = match self.lexer.token {
+                Identifier => self.lexer.token_as_str(),
+                _          => {
+                    self.error::<()>();
+                    break;
+                }
+            };
+            let mut end = self.lexer.end_then_consume();
+
+            let constraint = match self.lexer.token {
+                Extends => {
+                    self.lexer.consume();
+
+                    let constraint = self.type_node();
+
+                    end = constraint.end;
+                    Some(constraint)
+                },
+                _ => None,
+            };
+
+            let default = match self.lexer.token {
+                OperatorAssign => {
+                    self.lexer.consume();
+
+                    let default = self.type_node();
+
+                    end = default.end;
+                    Some(default)
+                },
+                _ => None,
+            };
+
+            builder.push(self.arena, self.alloc_at_loc(start, end, TypeParameter {
+                name,
+                constraint,
+                default,
+            }));
+
+            match self.lexer.token {
+                Comma => self.lexer.consume(),
+                _     => break,
+            }
+        }
+
+        self.type_closing_angle();
+
+        builder.as_list()
+    }
+
+    /// Parse `<T, U>` starting at the current `<` token, returns the types
+    /// along with the end of the closing `>`.
+    fn type_arguments(&mut self) -> (TypeList<'ast>, u32) {
+        self.lexer.consume();
+
+        let builder = GrowableList::new();
+
+        loop {
+            builder.push(self.arena, self.type_node());
+
+            match self.lexer.token {
+                Comma => self.lexer.consume(),
+                _     => break,
+            }
+        }
+
+        let end = self.type_closing_angle();
+
+        (builder.as_list(), end)
+    }
+
+    /// Consume the `>` closing a list of type arguments or parameters. The
+    /// lexer reads `>>` in `A<B<C>>` as a single token, so it is split here.
+    fn type_closing_angle(&mut self) -> u32 {
+        let end = self.lexer.start() + 1;
+
+        match self.lexer.token {
+            OperatorGreater => self.lexer.consume(),
+            _ if self.lexer.token_as_str().starts_with('>') => self.lexer.consume_at(end),
+            _ => self.error_expected(OperatorGreater),
+        }
+
+        end
+    }
+
+    /// Parse a type, including unions and intersections.
+    pub fn type_node(&mut self) -> TypeNode<'ast> {
+        if self.lexer.token == OperatorBitwiseOr {
+            self.lexer.consume();
+        }
+
+        let first = self.nested(Parser::type_intersection);
+
+        self.type_list_of(first, OperatorBitwiseOr, |variants| Type::Union { variants })
+    }
+
+    fn type_intersection(&mut self) -> TypeNode<'ast> {
+        if self.lexer.token == OperatorBitwiseAnd {
+            self.lexer.consume();
+        }
+
+        let first = self.type_postfix();
+
+        self.type_list_of(first, OperatorBitwiseAnd, |variants| Type::Intersection { variants })
+    }
+
+    #[inline]
+    fn type_list_of<F>(&mut self, first: TypeNode<'ast>, separator: ::lexer::Token, build: F) -> TypeNode<'ast>
+    where
+        F: FnOnce(TypeList<'ast>) -> Type<'ast>,
+    {
+        if self.lexer.token != separator {
+            return first;
+        }
+
+        let builder = GrowableList::new();
+
+        builder.push(self.arena, first);
+
+        while self.lexer.token == separator {
+            self.lexer.consume();
+
+            let next = if separator == OperatorBitwiseOr {
+                self.type_intersection()
+            } else {
+                self.type_postfix()
+            };
+
+            builder.push(self.arena, next);
+        }
+
+        let variants = builder.as_list();
+        let end = variants.iter().last().map(|last| last.end).unwrap_or(first.end);
+
+        self.alloc_at_loc(first.start, end, build(variants))
+    }
+
+    fn type_postfix(&mut self) -> TypeNode<'ast> {
+        let mut element = self.type_primary();
+
+        while self.lexer.token == BracketOpen && self.asi() == Asi::NoSemicolon {
+            self.lexer.consume();
+
+            let end = self.lexer.end();
+
+            expect!(self, BracketClose);
+
+            element = self.alloc_at_loc(element.start, end, Type::Array { element });
+        }
+
+        element
+    }
+
+    fn type_primary(&mut self) -> TypeNode<'ast> {
+        let (start, end) = self.lexer.loc();
+
+        match self.lexer.token {
+            LiteralString | LiteralNumber => {
+                let value = self.lexer.token_as_str();
+                let literal = match self.lexer.token {
+                    LiteralString => Literal::String(value),
+                    _             => Literal::Number(value),
+                };
+
+                self.lexer.consume();
+                self.alloc_at_loc(start, end, literal)
+            },
+            BracketOpen => {
+                self.lexer.consume();
+
+                let builder = GrowableList::new();
+
+                while self.lexer.token != BracketClose {
+                    builder.push(self.arena, self.type_node());
+
+                    match self.lexer.token {
+                        Comma => self.lexer.consume(),
+                        _     => break,
+                    }
+                }
+
+                let end = self.lexer.end();
+
+                expect!(self, BracketClose);
+
+                self.alloc_at_loc(start, end, Type::Tuple { elements: builder.as_list() })
+            },
+            BraceOpen => {
+                let (members, end) = self.type_members();
+
+                self.alloc_at_loc(start, end, Type::Object { members })
+            },
+            ParenOpen => {
+                if let Some(params) = self.speculate(|par| {
+                    let params = par.params();
+
+                    match par.lexer.token {
+                        OperatorFatArrow => Some(params),
+                        _                => None,
+                    }
+                }) {
+                    self.lexer.consume();
+
+                    let returns = self.type_node();
+
+                    return self.alloc_at_loc(start, returns.end, Type::Function { params, returns });
+                }
+
+                self.lexer.consume();
+
+                let inner = self.type_node();
+
+                expect!(self, ParenClose);
+
+                inner
+            },
+            _ if self.lexer.token.is_word() => {
+                let name = self.lexer.token_as_str();
+
+                self.lexer.consume();
+
+                let item = match name {
+                    "any"       => Type::Any,
+                    "number"    => Type::Primitive(Primitive::Number),
+                    "string"    => Type::Primitive(Primitive::String),
+                    "boolean"   => Type::Primitive(Primitive::Boolean),
+                    "null"      => Type::Primitive(Primitive::Null),
+                    "undefined" => Type::Primitive(Primitive::Undefined),
+                    "void"      => Type::Primitive(Primitive::Void),
+                    "never"     => Type::Primitive(Primitive::Never),
+                    "unknown"   => Type::Primitive(Primitive::Unknown),
+                    "true"      => Type::Literal(Literal::True),
+                    "false"     => Type::Literal(Literal::False),
+                    _ if self.lexer.token == OperatorLesser => {
+                        let (subtypes, end) = self.type_arguments();
+
+                        return self.alloc_at_loc(start, end, Type::Generic {
+                            ident: name,
+                            subtypes,
+                        });
+                    },
+                    _ => Type::Identifier(name),
+                };
+
+                self.alloc_at_loc(start, end, item)
+            },
+            _ => {
+                self.error::<()>();
+                self.alloc_at_loc(start, start, Type::Any)
+            }
+        }
+    }
+
+    /// Parse members of an object type or an interface body, returns the
+    /// members along with the end of the closing brace.
+    fn type_members(&mut self) -> (NodeList<'ast, TypeMember<'ast>>, u32) {
+        expect!(self, BraceOpen);
+
+        let builder = GrowableList::new();
+
+        while self.lexer.token != BraceClose {
+            if !self.lexer.token.is_word() {
+                self.error::<()>();
+                break;
+            }
+
+            let name = self.lexer.token_as_str();
+            let key = self.alloc_in_loc(name);
+
+            self.lexer.consume();
+
+            let optional = match self.lexer.token {
+                OperatorConditional => {
+                    self.lexer.consume();
+                    true
+                },
+                _ => false,
+            };
+
+            let value = match self.lexer.token {
+                ParenOpen => {
+                    let start = self.lexer.start();
+                    let params = self.params();
+
+                    expect!(self, Colon);
+
+                    let returns = self.type_node();
+
+                    self.alloc_at_loc(start, returns.end, Type::Function { params, returns })
+                },
+                _ => {
+                    expect!(self, Colon);
+
+                    self.type_node()
+                }
+            };
+
+            builder.push(self.arena, self.alloc_at_loc(key.start, value.end, TypeMember {
+                key,
+                optional,
+                value,
+            }));
+
+            match self.lexer.token {
+                Semicolon | Comma => self.lexer.consume(),
+                BraceClose        => break,
+                _ if self.lexer.asi() == Asi::ImplicitSemicolon => {},
+                _ => {
+                    self.error::<()>();
+                    break;
+                }
+            }
+        }
+
+        let end = self.lexer.end();
+
+        expect!(self, BraceClose);
+
+        (builder.as_list(), end)
+    }
+
+    /// Parse `type Name<T> = ...;` with `type` already consumed.
+    pub fn type_alias_declaration(&mut self, start: u32) -> StatementNode<'ast> {
+        let name = self.lexer.token_as_str();
+        let name = self.alloc_in_loc(name);
+
+        self.lexer.consume();
+
+        let type_parameters = self.type_parameters();
+
+        expect!(self, OperatorAssign);
+
+        let value = self.type_node();
+
+        self.expect_semicolon();
+
+        self.alloc_at_loc(start, value.end, TypeAliasDeclaration {
+            name,
+            type_parameters,
+            value,
+        })
+    }
+
+    /// Parse `interface Name<T> extends Base { ... }`.
+    pub fn interface_declaration(&mut self) -> StatementNode<'ast> {
+        let start = self.lexer.start();
+
+        if !self.options.typescript {
+            self.error::<()>();
+            return self.alloc_at_loc(start, start, Statement::Empty);
+        }
+
+        self.lexer.consume();
+
+        let name = match self.lexer.token {
+            Identifier => {
+                let name = self.lexer.token_as_str();
+                let name = self.alloc_in_loc(name);
+
+                self.lexer.consume();
+                name
+            },
+            _ => self.error(),
+        };
+
+        let type_parameters = self.type_parameters();
+
+        let extends = match self.lexer.token {
+            Extends => {
+                self.lexer.consume();
+
+                let builder = GrowableList::new();
+
+                loop {
+                    builder.push(self.arena, self.type_postfix());
+
+                    match self.lexer.token {
+                        Comma => self.lexer.consume(),
+                        _     => break,
+                    }
+                }
+
+                builder.as_list()
+            },
+            _ => NodeList::empty(),
+        };
+
+        let (body, end) = self.type_members();
+
+        self.alloc_at_loc(start, end, InterfaceDeclaration {
+            name,
+            type_parameters,
+            extends,
+            body,
+        })
+    }
+
+    /// Continue an expression with TypeScript specific syntax: explicit type
+    /// arguments of a call such as `foo<T>(x)`, or an `as`/`satisfies` cast
+    /// where binding power `B` permits relational operators.
+    pub fn typescript_nested_expression<B>(&mut self, expression: ExpressionNode<'ast>) -> Option<ExpressionNode<'ast>>
+    where
+        B: BindingPower
+    {
+        match self.lexer.token {
+            OperatorLesser => {
+                let (arguments, end) = self.speculate(|par| {
+                    let arguments = par.type_arguments();
+
+                    match par.lexer.token {
+                        ParenOpen => Some(arguments),
+                        _         => None,
+                    }
+                })?;
+
+                Some(self.alloc_at_loc(expression.start, end, InstantiationExpression {
+                    expression,
+                    arguments,
+                }))
+            },
+            Identifier if self.lexer.asi() == Asi::NoSemicolon && B::handler(Asi::NoSemicolon, OperatorLesser).is_some() => {
+                let kind = match self.lexer.token_as_str() {
+                    "as"        => TypeCastKind::As,
+                    "satisfies" => TypeCastKind::Satisfies,
+                    _           => return None,
+                };
+
+                self.lexer.consume();
+
+                let annotation = self.type_node();
+
+                Some(self.alloc_at_loc(expression.start, annotation.end, TypeCastExpression {
+                    expression,
+                    kind,
+                    annotation,
+                }))
+            },
+            _ => None,
+        }
+    }
+
+    /// In TypeScript mode, attempt to read the parameters of an arrow function
+    /// along with an optional return type, up to and including the `=>`.
+    pub fn typed_arrow_head(&mut self) -> Option<(PatternList<'ast>, Option<TypeNode<'ast>>)> {
+        self.speculate(|par| {
+            let params = par.params();
+            let return_type = match par.untyped_arrows {
+                true  => None,
+                false => par.type_annotation(),
+            };
+
+            match par.lexer.token {
+                OperatorFatArrow if par.lexer.asi() == Asi::NoSemicolon => {
+                    par.lexer.consume();
+
+                    Some((params, return_type))
+                },
+                _ => None,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use parser::{parse, parse_with, ParseOptions};
+    use parser::mock::Mock;
+    use ast::{Function, Statement};
+    use ast::expression::{ArrowExpression, ArrowBody, CallExpression, MemberExpression};
+    use ast::statement::ReturnStatement;
+
+    fn ts() -> ParseOptions {
+        ParseOptions { typescript: true, ..ParseOptions::default() }
+    }
+
+    #[test]
+    fn annotated_function() {
+        let src = "function pick<T extends object>(a: T, key?: string): T[] { return a; }";
+        let module = parse_with(src, ts()).unwrap();
+
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Function {
+                name: mock.name("pick"),
+                generator: false,
+                is_async: false,
+                type_parameters: mock.list([
+                    TypeParameter {
+                        name: "T",
+                        constraint: Some(mock.ptr("object")),
+                        default: None,
+                    }
+                ]),
+                params: mock.list([
+                    Pattern::Annotated {
+                        pattern: mock.ptr(Pattern::Identifier("a")),
+                        optional: false,
+                        annotation: Some(mock.ptr("T")),
+                    },
+                    Pattern::Annotated {
+                        pattern: mock.ptr(Pattern::Identifier("key")),
+                        optional: true,
+                        annotation: Some(mock.ptr(Primitive::String)),
+                    },
+                ]),
+                return_type: Some(mock.ptr(Type::Array {
+                    element: mock.ptr("T"),
+                })),
+                body: mock.block([
+                    ReturnStatement {
+                        value: Some(mock.ptr("a")),
+                    }
+                ]),
+            }
+        ]);
+
+        assert_eq!(module.body(), expected);
+    }
+
+    #[test]
+    fn annotated_arrow_function() {
+        let module = parse_with("(n: number): string => n.toString()", ts()).unwrap();
+
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            mock.ptr(ArrowExpression {
+                params: mock.list([
+                    Pattern::Annotated {
+                        pattern: mock.ptr(Pattern::Identifier("n")),
+                        optional: false,
+                        annotation: Some(mock.ptr(Primitive::Number)),
+                    }
+                ]),
+                return_type: Some(mock.ptr(Primitive::String)),
+                body: ArrowBody::Expression(mock.ptr(CallExpression {
+                    callee: mock.ptr(MemberExpression {
+                        object: mock.ptr("n"),
+                        property: mock.ptr("toString"),
+                    }),
+                    arguments: NodeList::empty(),
+                })),
+            })
+        ]);
+
+        assert_eq!(module.body(), expected);
+    }
+
+    #[test]
+    fn type_alias() {
+        let src = "type Pair<T = any> = [T, T] | { first: T; second?: T } & Base;";
+        let module = parse_with(src, ts()).unwrap();
+
+        let mock = Mock::new();
+
+        let object = mock.ptr(Type::Object {
+            members: mock.list([
+                TypeMember {
+                    key: mock.ptr("first"),
+                    optional: false,
+                    value: mock.ptr("T"),
+                },
+                TypeMember {
+                    key: mock.ptr("second"),
+                    optional: true,
+                    value: mock.ptr("T"),
+                },
+            ]),
+        });
+
+        let expected = mock.list([
+            TypeAliasDeclaration {
+                name: mock.ptr("Pair"),
+                type_parameters: mock.list([
+                    TypeParameter {
+                        name: "T",
+                        constraint: None,
+                        default: Some(mock.ptr(Type::Any)),
+                    }
+                ]),
+                value: mock.ptr(Type::Union {
+                    variants: mock.list([
+                        Type::Tuple {
+                            elements: mock.list(["T", "T"]),
+                        },
+                        Type::Intersection {
+                            variants: mock.list([object.item, Type::Identifier("Base")]),
+                        },
+                    ]),
+                }),
+            }
+        ]);
+
+        assert_eq!(module.body(), expected);
+    }
+
+    #[test]
+    fn interface() {
+        let src = "interface Point extends Base { x: number, move(by: number): void }";
+        let module = parse_with(src, ts()).unwrap();
+
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            InterfaceDeclaration {
+                name: mock.ptr("Point"),
+                type_parameters: NodeList::empty(),
+                extends: mock.list(["Base"]),
+                body: mock.list([
+                    TypeMember {
+                        key: mock.ptr("x"),
+                        optional: false,
+                        value: mock.ptr(Primitive::Number),
+                    },
+                    TypeMember {
+                        key: mock.ptr("move"),
+                        optional: false,
+                        value: mock.ptr(Type::Function {
+                            params: mock.list([
+                                Pattern::Annotated {
+                                    pattern: mock.ptr(Pattern::Identifier("by")),
+                                    optional: false,
+                                    annotation: Some(mock.ptr(Primitive::Number)),
+                                }
+                            ]),
+                            returns: mock.ptr(Primitive::Void),
+                        }),
+                    },
+                ]),
+            }
+        ]);
+
+        assert_eq!(module.body(), expected);
+    }
+
+    #[test]
+    fn generic_call() {
+        let module = parse_with("identity<Map<string, number[]>>(1) < 2", ts()).unwrap();
+
+        let mock = Mock::new();
+
+        let map = Type::Generic {
+            ident: "Map",
+            subtypes: mock.list([
+                Type::Primitive(Primitive::String),
+                Type::Array { element: mock.ptr(Primitive::Number) },
+            ]),
+        };
+
+        let expected = mock.list([
+            mock.ptr(::ast::expression::BinaryExpression {
+                operator: ::ast::OperatorKind::Lesser,
+                left: mock.ptr(CallExpression {
+                    callee: mock.ptr(InstantiationExpression {
+                        expression: mock.ptr("identity"),
+                        arguments: mock.list([map]),
+                    }),
+                    arguments: mock.list([Literal::Number("1")]),
+                }),
+                right: mock.number("2"),
+            })
+        ]);
+
+        assert_eq!(module.body(), expected);
+    }
+
+    #[test]
+    fn casts_bind_as_relational_operators() {
+        let module = parse_with("a + b as T satisfies U", ts()).unwrap();
+
+        let mock = Mock::new();
+
+        let sum = mock.ptr(::ast::expression::BinaryExpression {
+            operator: ::ast::OperatorKind::Addition,
+            left: mock.ptr("a"),
+            right: mock.ptr("b"),
+        });
+
+        let expected = mock.list([
+            mock.ptr(TypeCastExpression {
+                expression: mock.ptr(TypeCastExpression {
+                    expression: sum,
+                    kind: TypeCastKind::As,
+                    annotation: mock.ptr("T"),
+                }),
+                kind: TypeCastKind::Satisfies,
+                annotation: mock.ptr("U"),
+            })
+        ]);
+
+        assert_eq!(module.body(), expected);
+    }
+
+    #[test]
+    fn conditional_with_parenthesized_consequent() {
+        let module = parse_with("x = a ? (b) : c => d", ts()).unwrap();
+
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            mock.ptr(::ast::expression::BinaryExpression {
+                operator: ::ast::OperatorKind::Assign,
+                left: mock.ptr("x"),
+                right: mock.ptr(::ast::expression::ConditionalExpression {
+                    test: mock.ptr("a"),
+                    consequent: mock.ptr("b"),
+                    alternate: mock.ptr(ArrowExpression {
+                        params: mock.list([
+                            Pattern::Identifier("c"),
+                        ]),
+                        return_type: None,
+                        body: ArrowBody::Expression(mock.ptr("d")),
+                    }),
+                }),
+            })
+        ]);
+
+        assert_eq!(module.body(), expected);
+
+        assert!(parse_with("a ? (b): T => c : d", ts()).is_ok());
+        assert!(parse_with("a ? (b: T) => c : (d) : U => e", ts()).is_ok());
+        assert!(parse_with("a ? b ? (c) : d => e : f", ts()).is_ok());
+    }
+
+    #[test]
+    fn not_parsed_by_default() {
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            mock.ptr(::ast::expression::BinaryExpression {
+                operator: ::ast::OperatorKind::Greater,
+                left: mock.ptr(::ast::expression::BinaryExpression {
+                    operator: ::ast::OperatorKind::Lesser,
+                    left: mock.ptr("a"),
+                    right: mock.ptr("b"),
+                }),
+                right: mock.ptr("c"),
+            })
+        ]);
+
+        assert_eq!(parse("a < b > c").unwrap().body(), expected);
+        assert!(parse("function f(a: number) {}").is_err());
+        assert!(parse("interface A {}").is_err());
+
+        match parse("type\nA = 1").unwrap().body().iter().next().map(|statement| statement.item) {
+            Some(Statement::Expression(_)) => {},
+            other => panic!("Unexpected statement {:?}", other),
+        }
+    }
+}
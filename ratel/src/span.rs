@@ -0,0 +1,115 @@
+/// A range of byte offsets in the source, `end` being exclusive.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct Span {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Span {
+    #[inline]
+    pub fn new(start: u32, end: u32) -> Self {
+        Span {
+            start,
+            end,
+        }
+    }
+
+    /// Number of bytes covered by the span.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        self.end - self.start
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Check whether the byte at `offset` is within the span.
+    #[inline]
+    pub fn contains(&self, offset: u32) -> bool {
+        self.start <= offset && offset < self.end
+    }
+
+    /// Create the smallest span covering both `self` and `other`.
+    #[inline]
+    pub fn merge(&self, other: Span) -> Span {
+        Span {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+}
+
+/// Check whether `ch` ends a line, same as in `line_column`.
+pub(crate) fn is_line_terminator(ch: char) -> bool {
+    matches!(ch, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+/// Get the line and column of the character at byte `offset` of `source`,
+/// both counting from 1. Columns count characters rather than bytes. Lines
+/// are terminated by `\n`, `\r\n`, `\r`, `U+2028` or `U+2029`.
+pub(crate) fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let mut line = 1;
+    let mut line_start = 0;
+    let mut chars = before.char_indices().peekable();
+
+    while let Some((index, ch)) = chars.next() {
+        match ch {
+            '\r' if chars.peek().map(|&(_, ch)| ch) == Some('\n') => continue,
+            '\r' | '\n' | '\u{2028}' | '\u{2029}' => {
+                line += 1;
+                line_start = index + ch.len_utf8();
+            },
+            _ => {}
+        }
+    }
+
+    (line, before[line_start..].chars().count() + 1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn len_and_empty() {
+        assert_eq!(Span::new(3, 7).len(), 4);
+        assert!(Span::new(5, 5).is_empty());
+        assert!(!Span::new(5, 6).is_empty());
+    }
+
+    #[test]
+    fn contains() {
+        let span = Span::new(2, 5);
+
+        assert!(!span.contains(1));
+        assert!(span.contains(2));
+        assert!(span.contains(4));
+        assert!(!span.contains(5));
+    }
+
+    #[test]
+    fn line_columns() {
+        let source = "ab\ncd\r\nef\rgh\u{2028}ĳk";
+
+        assert_eq!(line_column(source, 0), (1, 1));
+        assert_eq!(line_column(source, 2), (1, 3));
+        assert_eq!(line_column(source, 3), (2, 1));
+        assert_eq!(line_column(source, 5), (2, 3));
+        assert_eq!(line_column(source, 7), (3, 1));
+        assert_eq!(line_column(source, 10), (4, 1));
+        assert_eq!(line_column(source, 15), (5, 1));
+        assert_eq!(line_column(source, 17), (5, 2));
+        assert_eq!(line_column(source, 100), (5, 3));
+    }
+
+    #[test]
+    fn merge() {
+        assert_eq!(Span::new(2, 5).merge(Span::new(4, 9)), Span::new(2, 9));
+        assert_eq!(Span::new(4, 9).merge(Span::new(2, 5)), Span::new(2, 9));
+        assert_eq!(Span::new(0, 10).merge(Span::new(3, 4)), Span::new(0, 10));
+        assert_eq!(Span::new(0, 1).merge(Span::new(8, 9)), Span::new(0, 9));
+    }
+}
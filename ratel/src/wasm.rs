@@ -0,0 +1,37 @@
+use parser::parse;
+
+/// Parse the source and serialize the resulting AST to ESTree JSON.
+///
+/// Errors are returned as text, one per line, so that the result can be
+/// handed over to JavaScript as is. Nothing in here panics on bad input.
+pub fn parse_to_json(source: &str) -> Result<String, String> {
+    let module = parse(source).map_err(|errors| {
+        errors
+            .iter()
+            .map(|error| format!("{} at {}:{}", error.message(), error.span.start, error.span.end))
+            .collect::<Vec<_>>()
+            .join("\n")
+    })?;
+
+    ::serde_json::to_string(&module).map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn valid_source_to_json() {
+        let json = parse_to_json("let a = 1;").unwrap();
+        let value: Value = ::serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["type"], "Program");
+        assert_eq!(value["body"][0]["type"], "VariableDeclaration");
+    }
+
+    #[test]
+    fn invalid_source_to_error() {
+        assert_eq!(parse_to_json("let a = ;").unwrap_err(), "Unexpected token `;` at 8:9");
+    }
+}